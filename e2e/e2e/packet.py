@@ -78,7 +78,7 @@ class TxPacketRecv(Cmd[TxPacketRecvRes]):
         return [self.dst_chain_id, self.src_chain_id, self.src_port, self.src_channel]
 
     def process(self, result: Any) -> TxPacketRecvRes:
-        entry = find_entry(result, 'WriteAcknowledgement')
+        entry = find_entry(result['events'], 'WriteAcknowledgement')
         return from_dict(TxPacketRecvRes, entry)
 
 # -----------------------------------------------------------------------------
@@ -102,7 +102,7 @@ class TxPacketTimeout(Cmd[TxPacketTimeoutRes]):
         return [self.dst_chain_id, self.src_chain_id, self.src_port, self.src_channel]
 
     def process(self, result: Any) -> TxPacketTimeoutRes:
-        entry = find_entry(result, 'TimeoutPacket')
+        entry = find_entry(result['events'], 'TimeoutPacket')
         return from_dict(TxPacketTimeoutRes, entry)
 
 
@@ -127,7 +127,7 @@ class TxPacketAck(Cmd[TxPacketAckRes]):
         return [self.dst_chain_id, self.src_chain_id, self.src_port, self.src_channel]
 
     def process(self, result: Any) -> TxPacketAckRes:
-        entry = find_entry(result, 'AcknowledgePacket')
+        entry = find_entry(result['events'], 'AcknowledgePacket')
         return from_dict(TxPacketAckRes, entry)
 
 
@@ -1,5 +1,59 @@
-use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use sha2::{Digest, Sha256};
+use subtle_encoding::{Encoding, Hex};
 
-/// Captures all the dependencies which the ICS20 module requires to be able to dispatch and
-/// process IBC messages.
-pub trait Ics20Context: ChannelReader + ChannelKeeper + Clone {}
+use crate::application::ics20_fungible_token_transfer::denom::DenomTrace;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::VERSION;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+
+/// Captures the operations on the account balances of the host chain which the ICS20 module
+/// requires to escrow, unescrow, mint and burn tokens.
+pub trait BankKeeper {
+    /// Returns the balance of `account` in the denomination `denom`.
+    fn balance(&self, account: &Signer, denom: &str) -> u64;
+
+    /// Transfers `amount` tokens of denomination `denom` from the account `from` to `to`.
+    fn send_coins(
+        &mut self,
+        from: &Signer,
+        to: &Signer,
+        denom: &str,
+        amount: u64,
+    ) -> Result<(), Error>;
+
+    /// Creates `amount` new tokens of denomination `denom` in `account`.
+    fn mint_coins(&mut self, account: &Signer, denom: &str, amount: u64) -> Result<(), Error>;
+
+    /// Destroys `amount` tokens of denomination `denom` from `account`.
+    fn burn_coins(&mut self, account: &Signer, denom: &str, amount: u64) -> Result<(), Error>;
+}
+
+/// Captures the state which the ICS20 module acts upon: the account balances of the host chain,
+/// and the traces of the vouchers it minted.
+pub trait Ics20Context: BankKeeper {
+    /// Returns the denomination trace whose hash is `hash`, as stored when vouchers for that
+    /// trace were first minted on the host chain.
+    fn get_denom_trace(&self, hash: &str) -> Option<DenomTrace>;
+
+    /// Stores the given denomination trace, under its hash.
+    fn store_denom_trace(&mut self, denom_trace: DenomTrace) -> Result<(), Error>;
+
+    /// Returns the account which escrows the native tokens sent through the channel
+    /// `channel_id` of the port `port_id`.
+    fn get_channel_escrow_address(&self, port_id: &PortId, channel_id: &ChannelId) -> Signer {
+        escrow_address(port_id, channel_id)
+    }
+}
+
+/// Derives the escrow account of the channel `channel_id` of the port `port_id` like the Cosmos
+/// SDK does: the first 20 bytes of the SHA256 hash of the protocol version and of the channel,
+/// here rendered as an upper case hex string.
+pub fn escrow_address(port_id: &PortId, channel_id: &ChannelId) -> Signer {
+    let preimage = format!("{}\0{}/{}", VERSION, port_id, channel_id);
+    let digest = Sha256::digest(preimage.as_bytes());
+    Hex::upper_case()
+        .encode_to_string(&digest[..20])
+        .unwrap()
+        .into()
+}
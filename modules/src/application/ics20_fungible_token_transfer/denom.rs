@@ -0,0 +1,191 @@
+//! Tracing of the denominations of the tokens transferred over ICS20 channels.
+
+use std::fmt;
+
+use sha2::{Digest, Sha256};
+use subtle_encoding::{Encoding, Hex};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::apps::transfer::v1::DenomTrace as RawDenomTrace;
+
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+
+/// Prefix of the denominations of the vouchers minted for tokens received over IBC.
+pub const IBC_DENOM_PREFIX: &str = "ibc/";
+
+/// The trace of a token denomination: the path of `{port}/{channel}` pairs through which the
+/// tokens were transferred, the latest hop first, and their denomination on the chain where
+/// they originate. See:
+/// <https://github.com/cosmos/ibc/tree/master/spec/app/ics-020-fungible-token-transfer>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DenomTrace {
+    pub path: String,
+    pub base_denom: String,
+}
+
+impl DenomTrace {
+    /// Parses a full denomination path of the form `{port}/{channel}/.../{base_denom}`.
+    pub fn parse(full_denom: &str) -> Result<Self, Error> {
+        let (path, base_denom) = match full_denom.rfind('/') {
+            Some(idx) => (&full_denom[..idx], &full_denom[idx + 1..]),
+            None => ("", full_denom),
+        };
+
+        let hops: Vec<&str> = path.split('/').filter(|hop| !hop.is_empty()).collect();
+        let well_formed = !base_denom.is_empty()
+            && (path.is_empty() || hops.len() == path.split('/').count())
+            && hops.len() % 2 == 0;
+        if !well_formed {
+            return Err(Kind::InvalidDenom(full_denom.to_string()).into());
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            base_denom: base_denom.to_string(),
+        })
+    }
+
+    /// Returns the full denomination path, i.e., the path followed by the base denomination.
+    pub fn full_path(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}/{}", self.path, self.base_denom)
+        }
+    }
+
+    /// The hash of the full denomination path, as an upper case hex string.
+    pub fn hash(&self) -> String {
+        let digest = Sha256::digest(self.full_path().as_bytes());
+        Hex::upper_case().encode_to_string(digest).unwrap()
+    }
+
+    /// Returns the denomination of the tokens on the host chain: the base denomination for
+    /// native tokens, and `ibc/{hash}` for vouchers.
+    pub fn ibc_denom(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}{}", IBC_DENOM_PREFIX, self.hash())
+        }
+    }
+
+    /// Returns true if the tokens were last transferred through the channel `channel_id` of the
+    /// port `port_id`.
+    pub fn has_prefix(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        let prefix = format!("{}/{}", port_id, channel_id);
+        self.path == prefix || self.path.starts_with(&format!("{}/", prefix))
+    }
+
+    /// Returns the trace of the tokens after a transfer to the channel `channel_id` of the
+    /// port `port_id`.
+    pub fn add_prefix(&self, port_id: &PortId, channel_id: &ChannelId) -> Self {
+        let prefix = format!("{}/{}", port_id, channel_id);
+        let path = if self.path.is_empty() {
+            prefix
+        } else {
+            format!("{}/{}", prefix, self.path)
+        };
+
+        Self {
+            path,
+            base_denom: self.base_denom.clone(),
+        }
+    }
+
+    /// Returns the trace of the tokens before their transfer to the channel `channel_id` of the
+    /// port `port_id`. The trace is returned unchanged if it does not start with that channel.
+    pub fn remove_prefix(&self, port_id: &PortId, channel_id: &ChannelId) -> Self {
+        if !self.has_prefix(port_id, channel_id) {
+            return self.clone();
+        }
+
+        let prefix = format!("{}/{}", port_id, channel_id);
+        let path = self.path[prefix.len()..].trim_start_matches('/');
+
+        Self {
+            path: path.to_string(),
+            base_denom: self.base_denom.clone(),
+        }
+    }
+}
+
+impl fmt::Display for DenomTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.full_path())
+    }
+}
+
+impl Protobuf<RawDenomTrace> for DenomTrace {}
+
+impl From<RawDenomTrace> for DenomTrace {
+    fn from(raw: RawDenomTrace) -> Self {
+        Self {
+            path: raw.path,
+            base_denom: raw.base_denom,
+        }
+    }
+}
+
+impl From<DenomTrace> for RawDenomTrace {
+    fn from(trace: DenomTrace) -> Self {
+        Self {
+            path: trace.path,
+            base_denom: trace.base_denom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use super::DenomTrace;
+    use crate::application::ics20_fungible_token_transfer::PORT_ID_STR;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+
+    #[test]
+    fn parse_denom_trace() {
+        let trace = DenomTrace::parse("transfer/channel-0/uatom").unwrap();
+        assert_eq!(trace.path, "transfer/channel-0");
+        assert_eq!(trace.base_denom, "uatom");
+        assert_eq!(trace.full_path(), "transfer/channel-0/uatom");
+
+        let native = DenomTrace::parse("uatom").unwrap();
+        assert!(native.path.is_empty());
+        assert_eq!(native.ibc_denom(), "uatom");
+
+        for invalid in &[
+            "",
+            "transfer/uatom",
+            "transfer/channel-0/",
+            "transfer//uatom",
+        ] {
+            assert!(
+                DenomTrace::parse(invalid).is_err(),
+                "{} should be invalid",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn prefix_and_hash() {
+        let port_id: PortId = PORT_ID_STR.parse().unwrap();
+        let channel_id = ChannelId::new(0);
+
+        let native = DenomTrace::parse("uatom").unwrap();
+        let voucher = native.add_prefix(&port_id, &channel_id);
+        assert_eq!(voucher.full_path(), "transfer/channel-0/uatom");
+        assert!(voucher.has_prefix(&port_id, &channel_id));
+        assert!(!voucher.has_prefix(&port_id, &ChannelId::new(1)));
+        assert_eq!(voucher.remove_prefix(&port_id, &channel_id), native);
+
+        // The hash of the denomination trace matches the one computed by the Cosmos SDK.
+        assert_eq!(
+            voucher.ibc_denom(),
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+    }
+}
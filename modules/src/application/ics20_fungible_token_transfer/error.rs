@@ -1,7 +1,9 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics04_channel::channel::Order;
 use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
 
 pub type Error = anomaly::Error<Kind>;
 
@@ -35,6 +37,33 @@ pub enum Kind {
 
     #[error("invalid packet timeout timestamp value")]
     InvalidPacketTimeoutTimestamp(u64),
+
+    #[error("missing token in the transfer message")]
+    MissingToken,
+
+    #[error("invalid token amount {0}, expected a positive integer")]
+    InvalidTokenAmount(String),
+
+    #[error("invalid denomination {0}")]
+    InvalidDenom(String),
+
+    #[error("no denomination trace found for hash {0}")]
+    DenomTraceNotFound(String),
+
+    #[error("account {0} has insufficient funds: {1}{2} needed, {3}{2} available")]
+    InsufficientFunds(Signer, u64, String, u64),
+
+    #[error("invalid fungible token packet data")]
+    InvalidPacketData,
+
+    #[error("invalid acknowledgement for a fungible token packet")]
+    InvalidAcknowledgement,
+
+    #[error("transfer channels must be unordered, got {0} ordering")]
+    InvalidChannelOrder(Order),
+
+    #[error("invalid transfer channel version {0}, expected {1}")]
+    InvalidVersion(String, String),
 }
 
 impl Kind {
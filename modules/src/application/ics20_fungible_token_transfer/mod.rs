@@ -1,5 +1,18 @@
 //! ICS 20: IBC Transfer implementation
 pub mod context;
+pub mod denom;
 pub mod error;
+pub mod module;
 pub mod msgs;
+pub mod packet;
 pub mod relay_application_logic;
+
+/// The port to which the transfer module is conventionally bound.
+pub const PORT_ID_STR: &str = "transfer";
+
+/// Identifier under which the transfer module (see `module::TransferModule`) is conventionally
+/// registered with the router of the host chain.
+pub const MODULE_ID_STR: &str = "transfer";
+
+/// The version of the ICS20 protocol negotiated on transfer channels.
+pub const VERSION: &str = "ics20-1";
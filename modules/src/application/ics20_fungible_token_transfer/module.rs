@@ -0,0 +1,199 @@
+//! The ICS20 application, as a module registered with the ICS26 router of the host chain.
+
+use std::fmt::Debug;
+
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::error::Error as Ics20Error;
+use crate::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::channel_handshake;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_ack_packet::on_acknowledgement_packet;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_recv_packet::on_recv_packet;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_timeout_packet::on_timeout_packet;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::send_transfer::send_transfer;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::{Counterparty, Order};
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics04_channel::packet::{Packet, PacketResult};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::ics26_routing::context::{Module, ModuleOutput};
+use crate::signer::Signer;
+
+/// The transfer module. It holds the `keeper` of the balances and denomination traces it acts
+/// upon, and processes the channel handshakes and packets of the ports bound to it.
+#[derive(Clone, Debug)]
+pub struct TransferModule<K> {
+    keeper: K,
+}
+
+impl<K> TransferModule<K> {
+    pub fn new(keeper: K) -> Self {
+        Self { keeper }
+    }
+
+    pub fn keeper(&self) -> &K {
+        &self.keeper
+    }
+
+    pub fn keeper_mut(&mut self) -> &mut K {
+        &mut self.keeper
+    }
+
+    /// Processes a transfer message submitted to the host chain `ctx`, with the keeper of this
+    /// module and the capability `channel_cap` it claimed for the source channel. See
+    /// `send_transfer`.
+    pub fn send_transfer<Ctx>(
+        &mut self,
+        ctx: &Ctx,
+        channel_cap: &Capability,
+        msg: MsgTransfer,
+    ) -> Result<HandlerOutput<PacketResult>, Ics20Error>
+    where
+        Ctx: ChannelReader,
+        K: Ics20Context,
+    {
+        send_transfer(ctx, &mut self.keeper, channel_cap, msg)
+    }
+}
+
+impl<K> Module for TransferModule<K>
+where
+    K: Ics20Context + Clone + Debug + Send + Sync + 'static,
+{
+    fn on_chan_open_init(
+        &mut self,
+        _output: &mut ModuleOutput,
+        order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        version: &str,
+    ) -> Result<(), Error> {
+        channel_handshake::on_chan_open_init(order, version)
+            .map_err(|e| Kind::AppModule.context(e).into())
+    }
+
+    fn on_chan_open_try(
+        &mut self,
+        _output: &mut ModuleOutput,
+        order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        version: &str,
+        counterparty_version: &str,
+    ) -> Result<(), Error> {
+        channel_handshake::on_chan_open_try(order, version, counterparty_version)
+            .map_err(|e| Kind::AppModule.context(e).into())
+    }
+
+    fn on_chan_open_ack(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty_version: &str,
+    ) -> Result<(), Error> {
+        channel_handshake::on_chan_open_ack(counterparty_version)
+            .map_err(|e| Kind::AppModule.context(e).into())
+    }
+
+    /// Received packets are always acknowledged synchronously.
+    fn on_recv_packet(
+        &mut self,
+        _output: &mut ModuleOutput,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        Ok(Some(on_recv_packet(&mut self.keeper, packet).to_bytes()))
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        _output: &mut ModuleOutput,
+        packet: &Packet,
+        acknowledgement: &[u8],
+        _relayer: &Signer,
+    ) -> Result<(), Error> {
+        on_acknowledgement_packet(&mut self.keeper, packet, acknowledgement)
+            .map_err(|e| Kind::AppModule.context(e).into())
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        _output: &mut ModuleOutput,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), Error> {
+        on_timeout_packet(&mut self.keeper, packet).map_err(|e| Kind::AppModule.context(e).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use test_env_log::test;
+
+    use crate::application::ics20_fungible_token_transfer::module::TransferModule;
+    use crate::application::ics20_fungible_token_transfer::VERSION;
+    use crate::ics03_connection::connection::{ConnectionEnd, State as ConnectionState};
+    use crate::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::Order;
+    use crate::ics04_channel::context::ChannelReader;
+    use crate::ics04_channel::msgs::chan_open_init::test_util::get_dummy_raw_msg_chan_open_init;
+    use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::ics04_channel::msgs::ChannelMsg;
+    use crate::ics24_host::identifier::ConnectionId;
+    use crate::ics26_routing::context::ModuleId;
+    use crate::ics26_routing::handler::dispatch;
+    use crate::ics26_routing::msgs::Ics26Envelope;
+    use crate::mock::bank::MockBank;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn handshake_through_router() {
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            msg_conn_init.client_id().clone(),
+            msg_conn_init.counterparty().clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+        let msg_chan_init =
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init()).unwrap();
+
+        // The module is registered like any other, here under an arbitrary identifier.
+        let ctx = MockContext::default()
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_module(
+                msg_chan_init.port_id().clone(),
+                ModuleId::new("bank-transfer"),
+                TransferModule::new(MockBank::default()),
+            );
+
+        let open_channel = |order: Order, version: &str| {
+            let mut ctx = ctx.clone();
+            let mut msg = msg_chan_init.clone();
+            msg.channel.ordering = order;
+            msg.channel.version = version.to_string();
+
+            let res = dispatch(
+                &mut ctx,
+                Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg)),
+            );
+            (res.is_ok(), ChannelReader::channel_counter(&ctx))
+        };
+
+        assert_eq!(open_channel(Order::Unordered, VERSION), (true, 1));
+        assert_eq!(open_channel(Order::Ordered, VERSION), (false, 0));
+        assert_eq!(open_channel(Order::Unordered, "ics20"), (false, 0));
+    }
+}
@@ -0,0 +1,164 @@
+//! The data carried by ICS20 packets, and their acknowledgements.
+
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use subtle_encoding::base64;
+
+use ibc_proto::ibc::apps::transfer::v1::FungibleTokenPacketData as RawFungibleTokenPacketData;
+
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::signer::Signer;
+
+/// The data of a fungible token transfer packet. On the wire, the data is encoded as JSON with
+/// sorted keys, and the amount as a string, to be compatible with the Cosmos SDK implementation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FungibleTokenPacketData {
+    /// The amount of tokens to be transferred.
+    #[serde(serialize_with = "ser_amount", deserialize_with = "de_amount")]
+    pub amount: u64,
+    /// The full denomination path (see `DenomTrace`) of the tokens, on the sending chain.
+    pub denom: String,
+    /// The recipient address on the receiving chain.
+    pub receiver: Signer,
+    /// The sender address on the sending chain.
+    pub sender: Signer,
+}
+
+impl FungibleTokenPacketData {
+    /// Encodes the packet data as the bytes to be set in the `data` field of a packet.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+}
+
+impl TryFrom<&[u8]> for FungibleTokenPacketData {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let data: Self =
+            serde_json::from_slice(bytes).map_err(|e| Kind::InvalidPacketData.context(e))?;
+
+        if data.amount == 0 {
+            return Err(Kind::InvalidTokenAmount(data.amount.to_string()).into());
+        }
+
+        Ok(data)
+    }
+}
+
+impl From<RawFungibleTokenPacketData> for FungibleTokenPacketData {
+    fn from(raw: RawFungibleTokenPacketData) -> Self {
+        Self {
+            amount: raw.amount,
+            denom: raw.denom,
+            receiver: raw.receiver.into(),
+            sender: raw.sender.into(),
+        }
+    }
+}
+
+impl From<FungibleTokenPacketData> for RawFungibleTokenPacketData {
+    fn from(data: FungibleTokenPacketData) -> Self {
+        Self {
+            denom: data.denom,
+            amount: data.amount,
+            sender: data.sender.to_string(),
+            receiver: data.receiver.to_string(),
+        }
+    }
+}
+
+fn ser_amount<S: Serializer>(amount: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    amount.to_string().serialize(serializer)
+}
+
+fn de_amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+/// The acknowledgement of a fungible token transfer packet, which the receiving chain writes
+/// once it processed the packet. Encoded as JSON, like in the Cosmos SDK implementation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Acknowledgement {
+    /// The tokens were transferred; holds the base64 encoding of the result bytes.
+    Result(String),
+    /// The transfer failed, and the tokens are to be refunded to the sender.
+    Error(String),
+}
+
+impl Acknowledgement {
+    /// The acknowledgement of a successful transfer.
+    pub fn success() -> Self {
+        Self::Result(String::from_utf8(base64::encode([1u8])).unwrap())
+    }
+
+    /// The acknowledgement of a failed transfer.
+    pub fn error(err: impl ToString) -> Self {
+        Self::Error(err.to_string())
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Result(_))
+    }
+
+    /// Encodes the acknowledgement as the bytes to be written for the packet.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+}
+
+impl TryFrom<&[u8]> for Acknowledgement {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(bytes).map_err(|e| Kind::InvalidAcknowledgement.context(e).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use test_env_log::test;
+
+    use super::{Acknowledgement, FungibleTokenPacketData};
+
+    #[test]
+    fn packet_data_json_encoding() {
+        let data = FungibleTokenPacketData {
+            amount: 100,
+            denom: "transfer/channel-0/uatom".to_string(),
+            receiver: "cosmos1receiver".parse().unwrap(),
+            sender: "cosmos1sender".parse().unwrap(),
+        };
+
+        let bytes = data.to_bytes();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            r#"{"amount":"100","denom":"transfer/channel-0/uatom","receiver":"cosmos1receiver","sender":"cosmos1sender"}"#
+        );
+        assert_eq!(
+            FungibleTokenPacketData::try_from(bytes.as_slice()).unwrap(),
+            data
+        );
+
+        assert!(FungibleTokenPacketData::try_from(&b"not json"[..]).is_err());
+        let zero = r#"{"amount":"0","denom":"uatom","receiver":"a","sender":"b"}"#;
+        assert!(FungibleTokenPacketData::try_from(zero.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn acknowledgement_json_encoding() {
+        let success = Acknowledgement::success();
+        assert_eq!(success.to_bytes(), br#"{"result":"AQ=="}"#.to_vec());
+        assert!(success.is_success());
+
+        let error = Acknowledgement::try_from(&br#"{"error":"insufficient funds"}"#[..]).unwrap();
+        assert_eq!(error, Acknowledgement::error("insufficient funds"));
+        assert!(!error.is_success());
+    }
+}
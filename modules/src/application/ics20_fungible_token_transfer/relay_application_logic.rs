@@ -1,3 +1,245 @@
 //! This module implements the processing logic for ICS20 (token transfer) message.
 
+use std::convert::TryFrom;
+
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::DenomTrace;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+use crate::ics04_channel::packet::Packet;
+
+pub mod channel_handshake;
+pub mod on_ack_packet;
+pub mod on_recv_packet;
+pub mod on_timeout_packet;
 pub mod send_transfer;
+
+/// Gives the tokens of a packet that was not delivered back to their sender: the tokens are
+/// unescrowed if they are native to the host chain, and the burnt vouchers are minted again
+/// otherwise.
+pub(crate) fn refund_packet_token<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    let data = FungibleTokenPacketData::try_from(packet.data.as_slice())?;
+    let trace = DenomTrace::parse(&data.denom)?;
+    let denom = trace.ibc_denom();
+
+    if trace.has_prefix(&packet.source_port, &packet.source_channel) {
+        // The tokens were vouchers returning to their source chain, which were burnt when sent.
+        ctx.mint_coins(&data.sender, &denom, data.amount)
+    } else {
+        let escrow = ctx.get_channel_escrow_address(&packet.source_port, &packet.source_channel);
+        ctx.send_coins(&escrow, &data.sender, &denom, data.amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
+    use crate::application::ics20_fungible_token_transfer::context::{
+        escrow_address, BankKeeper, Ics20Context,
+    };
+    use crate::application::ics20_fungible_token_transfer::denom::DenomTrace;
+    use crate::application::ics20_fungible_token_transfer::error::Error;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
+    use crate::application::ics20_fungible_token_transfer::packet::{
+        Acknowledgement, FungibleTokenPacketData,
+    };
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_ack_packet::on_acknowledgement_packet;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_recv_packet::on_recv_packet;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_timeout_packet::on_timeout_packet;
+    use crate::application::ics20_fungible_token_transfer::{PORT_ID_STR, VERSION};
+    use crate::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::context::ChannelReader;
+    use crate::ics04_channel::packet::{Packet, PacketResult};
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::mock::bank::MockBank;
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::Height;
+
+    fn transfer_port() -> PortId {
+        PORT_ID_STR.parse().unwrap()
+    }
+
+    /// Returns a mock chain with an open transfer channel `channel_id`, whose counterparty is the
+    /// channel `counterparty_channel_id`.
+    fn transfer_chain(channel_id: &ChannelId, counterparty_channel_id: &ChannelId) -> MockContext {
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(transfer_port(), Some(counterparty_channel_id.clone())),
+            vec![ConnectionId::default()],
+            VERSION.to_string(),
+        );
+
+        MockContext::default()
+            .with_client(&ClientId::default(), Height::new(0, 5))
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_transfer_port(transfer_port())
+            .with_channel(transfer_port(), channel_id.clone(), channel_end)
+            .with_send_sequence(transfer_port(), channel_id.clone(), 1.into())
+    }
+
+    /// The bank of the transfer module of `ctx`.
+    fn bank(ctx: &mut MockContext) -> &mut MockBank {
+        ctx.transfer_module_mut().keeper_mut()
+    }
+
+    fn balance(ctx: &MockContext, account: &Signer, denom: &str) -> u64 {
+        ctx.transfer_module().keeper().balance(account, denom)
+    }
+
+    /// Sends `amount` tokens of denomination `denom` from `sender` through the transfer channel
+    /// `channel_id` of `ctx`, and returns the packet to relay to the counterparty chain.
+    fn transfer(
+        ctx: &mut MockContext,
+        channel_id: &ChannelId,
+        denom: &str,
+        amount: u64,
+        sender: &Signer,
+        receiver: &Signer,
+    ) -> Result<Packet, Error> {
+        let msg = MsgTransfer {
+            source_port: transfer_port(),
+            source_channel: channel_id.clone(),
+            token: Some(Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }),
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            timeout_height: Height::zero(),
+            timeout_timestamp: Timestamp::none(),
+        };
+
+        let output = ctx.send_transfer(msg)?;
+
+        let channel_end = ctx
+            .channel_end(&(transfer_port(), channel_id.clone()))
+            .unwrap();
+        match output.result {
+            PacketResult::Send(res) => Ok(Packet {
+                sequence: res.seq,
+                source_port: res.port_id,
+                source_channel: res.channel_id,
+                destination_port: channel_end.counterparty().port_id().clone(),
+                destination_channel: channel_end.counterparty().channel_id().unwrap().clone(),
+                data: res.data,
+                timeout_height: res.timeout_height,
+                timeout_timestamp: res.timeout_timestamp,
+            }),
+            res => panic!("unexpected packet result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn round_trip_between_two_chains() {
+        let (alice, bob): (Signer, Signer) = ("alice".parse().unwrap(), "bob".parse().unwrap());
+        let (chan_a, chan_b) = (ChannelId::new(0), ChannelId::new(1));
+        let mut ctx_a = transfer_chain(&chan_a, &chan_b).with_balance(alice.clone(), "uatom", 100);
+        let mut ctx_b = transfer_chain(&chan_b, &chan_a);
+        let escrow_a = escrow_address(&transfer_port(), &chan_a);
+
+        // Chain A escrows its native tokens, and chain B mints vouchers for them.
+        let packet = transfer(&mut ctx_a, &chan_a, "uatom", 100, &alice, &bob).unwrap();
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 0);
+        assert_eq!(balance(&ctx_a, &escrow_a, "uatom"), 100);
+        assert!(on_recv_packet(bank(&mut ctx_b), &packet).is_success());
+
+        let voucher = DenomTrace::parse("transfer/channel-1/uatom").unwrap();
+        assert_eq!(
+            ctx_b
+                .transfer_module()
+                .keeper()
+                .get_denom_trace(&voucher.hash()),
+            Some(voucher.clone())
+        );
+        assert_eq!(balance(&ctx_b, &bob, &voucher.ibc_denom()), 100);
+
+        // Chain B burns the vouchers sent back, and chain A unescrows its native tokens.
+        let packet = transfer(&mut ctx_b, &chan_b, &voucher.ibc_denom(), 40, &bob, &alice).unwrap();
+        assert_eq!(balance(&ctx_b, &bob, &voucher.ibc_denom()), 60);
+        assert!(on_recv_packet(bank(&mut ctx_a), &packet).is_success());
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 40);
+        assert_eq!(balance(&ctx_a, &escrow_a, "uatom"), 60);
+
+        // Chain A never unescrows more tokens than the channel escrows.
+        let mut forged = packet;
+        forged.data = FungibleTokenPacketData {
+            amount: 100,
+            denom: "transfer/channel-1/uatom".to_string(),
+            receiver: alice.clone(),
+            sender: bob.clone(),
+        }
+        .to_bytes();
+        assert!(!on_recv_packet(bank(&mut ctx_a), &forged).is_success());
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 40);
+        assert_eq!(balance(&ctx_a, &escrow_a, "uatom"), 60);
+
+        // Unknown vouchers and insufficient funds are rejected when sending.
+        assert!(transfer(&mut ctx_b, &chan_b, "ibc/0000", 1, &bob, &alice).is_err());
+        assert!(transfer(&mut ctx_a, &chan_a, "uatom", 41, &alice, &bob).is_err());
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 40);
+    }
+
+    #[test]
+    fn refunds_on_timeout_and_error_acknowledgement() {
+        let (alice, bob): (Signer, Signer) = ("alice".parse().unwrap(), "bob".parse().unwrap());
+        let (chan_a, chan_b) = (ChannelId::new(0), ChannelId::new(1));
+        let mut ctx_a = transfer_chain(&chan_a, &chan_b).with_balance(alice.clone(), "uatom", 100);
+        let mut ctx_b = transfer_chain(&chan_b, &chan_a);
+        let escrow_a = escrow_address(&transfer_port(), &chan_a);
+
+        // Escrowed tokens are refunded on timeout.
+        let packet = transfer(&mut ctx_a, &chan_a, "uatom", 30, &alice, &bob).unwrap();
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 70);
+        on_timeout_packet(bank(&mut ctx_a), &packet).unwrap();
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 100);
+        assert_eq!(balance(&ctx_a, &escrow_a, "uatom"), 0);
+
+        // Escrowed tokens are refunded on error acknowledgements only.
+        let failed = transfer(&mut ctx_a, &chan_a, "uatom", 30, &alice, &bob).unwrap();
+        let error_ack = Acknowledgement::error("transfer failed").to_bytes();
+        on_acknowledgement_packet(bank(&mut ctx_a), &failed, &error_ack).unwrap();
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 100);
+
+        let packet = transfer(&mut ctx_a, &chan_a, "uatom", 30, &alice, &bob).unwrap();
+        assert!(on_recv_packet(bank(&mut ctx_b), &packet).is_success());
+        let success_ack = Acknowledgement::success().to_bytes();
+        on_acknowledgement_packet(bank(&mut ctx_a), &packet, &success_ack).unwrap();
+        assert_eq!(balance(&ctx_a, &alice, "uatom"), 70);
+        assert_eq!(balance(&ctx_a, &escrow_a, "uatom"), 30);
+        assert!(on_acknowledgement_packet(bank(&mut ctx_a), &packet, b"not an ack").is_err());
+
+        // Burnt vouchers are minted again on timeout.
+        let voucher = DenomTrace::parse("transfer/channel-1/uatom")
+            .unwrap()
+            .ibc_denom();
+        let packet = transfer(&mut ctx_b, &chan_b, &voucher, 30, &bob, &alice).unwrap();
+        assert_eq!(balance(&ctx_b, &bob, &voucher), 0);
+        on_timeout_packet(bank(&mut ctx_b), &packet).unwrap();
+        assert_eq!(balance(&ctx_b, &bob, &voucher), 30);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::application::ics20_fungible_token_transfer::VERSION;
+use crate::ics04_channel::channel::Order;
+
+/// Accepts the opening of a transfer channel only if it is unordered, and uses the ICS20 version.
+pub fn on_chan_open_init(order: Order, version: &str) -> Result<(), Error> {
+    if order != Order::Unordered {
+        return Err(Kind::InvalidChannelOrder(order).into());
+    }
+
+    validate_version(version)
+}
+
+/// Like `on_chan_open_init`, and additionally checks the version proposed by the counterparty.
+pub fn on_chan_open_try(
+    order: Order,
+    version: &str,
+    counterparty_version: &str,
+) -> Result<(), Error> {
+    on_chan_open_init(order, version)?;
+    validate_version(counterparty_version)
+}
+
+pub fn on_chan_open_ack(counterparty_version: &str) -> Result<(), Error> {
+    validate_version(counterparty_version)
+}
+
+fn validate_version(version: &str) -> Result<(), Error> {
+    if version != VERSION {
+        return Err(Kind::InvalidVersion(version.to_string(), VERSION.to_string()).into());
+    }
+
+    Ok(())
+}
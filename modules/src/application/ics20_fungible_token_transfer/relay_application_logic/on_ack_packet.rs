@@ -0,0 +1,26 @@
+use std::convert::TryFrom;
+
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::packet::Acknowledgement;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::refund_packet_token;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes the acknowledgement of a fungible token packet: if the transfer failed on the
+/// receiving chain, the tokens are refunded to the sender.
+pub fn on_acknowledgement_packet<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    acknowledgement: &[u8],
+) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    let acknowledgement = Acknowledgement::try_from(acknowledgement)?;
+
+    if acknowledgement.is_success() {
+        Ok(())
+    } else {
+        refund_packet_token(ctx, packet)
+    }
+}
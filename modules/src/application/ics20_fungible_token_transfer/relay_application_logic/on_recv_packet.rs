@@ -0,0 +1,49 @@
+use std::convert::TryFrom;
+
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::DenomTrace;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::packet::{
+    Acknowledgement, FungibleTokenPacketData,
+};
+use crate::ics04_channel::packet::Packet;
+
+/// Credits the receiver of a fungible token packet, and returns the acknowledgement to write for
+/// the packet. Any failure is reported in the acknowledgement, so that the sending chain refunds
+/// the tokens.
+pub fn on_recv_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Acknowledgement
+where
+    Ctx: Ics20Context,
+{
+    match process(ctx, packet) {
+        Ok(()) => Acknowledgement::success(),
+        Err(e) => Acknowledgement::error(e),
+    }
+}
+
+fn process<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    let data = FungibleTokenPacketData::try_from(packet.data.as_slice())?;
+    let trace = DenomTrace::parse(&data.denom)?;
+
+    if trace.has_prefix(&packet.source_port, &packet.source_channel) {
+        // The tokens return to the host chain, which escrowed them when they were sent out.
+        let denom = trace
+            .remove_prefix(&packet.source_port, &packet.source_channel)
+            .ibc_denom();
+        let escrow =
+            ctx.get_channel_escrow_address(&packet.destination_port, &packet.destination_channel);
+
+        ctx.send_coins(&escrow, &data.receiver, &denom, data.amount)
+    } else {
+        // The tokens come from the counterparty chain: mint vouchers for them.
+        let voucher = trace.add_prefix(&packet.destination_port, &packet.destination_channel);
+        if ctx.get_denom_trace(&voucher.hash()).is_none() {
+            ctx.store_denom_trace(voucher.clone())?;
+        }
+
+        ctx.mint_coins(&data.receiver, &voucher.ibc_denom(), data.amount)
+    }
+}
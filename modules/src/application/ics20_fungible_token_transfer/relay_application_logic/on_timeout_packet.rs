@@ -0,0 +1,12 @@
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::refund_packet_token;
+use crate::ics04_channel::packet::Packet;
+
+/// Refunds the tokens of a fungible token packet which timed out to the sender.
+pub fn on_timeout_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    refund_packet_token(ctx, packet)
+}
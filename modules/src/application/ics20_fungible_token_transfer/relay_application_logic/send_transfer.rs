@@ -1,17 +1,28 @@
 use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::{DenomTrace, IBC_DENOM_PREFIX};
 use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
 use crate::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
 use crate::handler::HandlerOutput;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::handler::send_packet::send_packet;
 use crate::ics04_channel::packet::Packet;
 use crate::ics04_channel::packet::PacketResult;
+use crate::ics05_port::capabilities::Capability;
 
-pub(crate) fn send_transfer<Ctx>(
+/// Processes a transfer message submitted to the host chain: builds the packet to send through the
+/// channel of `ctx`, whose capability `channel_cap` the transfer module presents, and escrows or
+/// burns the tokens sent in `keeper`. The host stores the resulting packet with
+/// `ChannelKeeper::store_packet_result`, as it does for ICS4 messages.
+pub fn send_transfer<Ctx, K>(
     ctx: &Ctx,
+    keeper: &mut K,
+    channel_cap: &Capability,
     msg: MsgTransfer,
 ) -> Result<HandlerOutput<PacketResult>, Error>
 where
-    Ctx: Ics20Context,
+    Ctx: ChannelReader,
+    K: Ics20Context,
 {
     let source_channel_end = ctx
         .channel_end(&(msg.source_port.clone(), msg.source_channel.clone()))
@@ -34,22 +45,52 @@ where
             Kind::SequenceSendNotFound(msg.source_port.clone(), msg.source_channel.clone())
         })?;
 
-    //TODO: Application LOGIC.
+    let token = msg.token.ok_or(Kind::MissingToken)?;
+    let amount = match token.amount.parse::<u64>() {
+        Ok(amount) if amount > 0 => amount,
+        _ => return Err(Kind::InvalidTokenAmount(token.amount).into()),
+    };
+
+    // Vouchers are held under their hashed denomination, so resolve their full trace.
+    let denom_trace = match token.denom.strip_prefix(IBC_DENOM_PREFIX) {
+        Some(hash) => keeper
+            .get_denom_trace(hash)
+            .ok_or_else(|| Kind::DenomTraceNotFound(hash.to_string()))?,
+        None => DenomTrace::parse(&token.denom)?,
+    };
+    if !denom_trace.path.is_empty() && denom_trace.ibc_denom() != token.denom {
+        return Err(Kind::InvalidDenom(token.denom).into());
+    }
+
+    let data = FungibleTokenPacketData {
+        amount,
+        denom: denom_trace.full_path(),
+        receiver: msg.receiver,
+        sender: msg.sender.clone(),
+    };
 
     let packet = Packet {
         sequence,
-        source_port: msg.source_port,
-        source_channel: msg.source_channel,
+        source_port: msg.source_port.clone(),
+        source_channel: msg.source_channel.clone(),
         destination_port,
         destination_channel: destination_channel.clone(),
-        data: vec![0],
+        data: data.to_bytes(),
         timeout_height: msg.timeout_height,
         timeout_timestamp: msg.timeout_timestamp,
     };
 
     let handler_output =
-        send_packet(ctx, packet).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+        send_packet(ctx, channel_cap, packet).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+    if denom_trace.has_prefix(&msg.source_port, &msg.source_channel) {
+        // The tokens are vouchers returning to their source chain: burn them.
+        keeper.burn_coins(&msg.sender, &token.denom, amount)?;
+    } else {
+        // The host chain is the source of the tokens: escrow them until they come back.
+        let escrow = keeper.get_channel_escrow_address(&msg.source_port, &msg.source_channel);
+        keeper.send_coins(&msg.sender, &escrow, &token.denom, amount)?;
+    }
 
-    //TODO:  add event/atributes and writes to the store issued by the application logic for packet sending.
     Ok(handler_output)
 }
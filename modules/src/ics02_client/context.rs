@@ -6,8 +6,9 @@ use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::error::Error;
-use crate::ics02_client::handler::ClientResult::{self, Create, Update, Upgrade};
+use crate::ics02_client::handler::ClientResult::{self, Create, Misbehaviour, Update, Upgrade};
 use crate::ics24_host::identifier::ClientId;
+use crate::timestamp::Timestamp;
 use crate::Height;
 
 /// Defines the read-only part of ICS2 (client functions) context.
@@ -16,9 +17,28 @@ pub trait ClientReader {
     fn client_state(&self, client_id: &ClientId) -> Option<AnyClientState>;
     fn consensus_state(&self, client_id: &ClientId, height: Height) -> Option<AnyConsensusState>;
 
+    /// Returns the heights of all the consensus states stored for the client `client_id`.
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Vec<Height>;
+
+    /// Returns the host timestamp at which the consensus state of the client `client_id` at
+    /// `height` was processed. Used to check that the delay period of a connection has passed.
+    fn client_update_time(&self, client_id: &ClientId, height: Height) -> Option<Timestamp>;
+
+    /// Returns the host height at which the consensus state of the client `client_id` at
+    /// `height` was processed. Used to check that the delay period of a connection has passed.
+    fn client_update_height(&self, client_id: &ClientId, height: Height) -> Option<Height>;
+
     /// Returns a natural number, counting how many clients have been created thus far.
     /// The value of this counter should increase only via method `ClientKeeper::increase_client_counter`.
     fn client_counter(&self) -> u64;
+
+    /// Returns the current height of the local (host) chain. The localhost client tracks this
+    /// height, as it verifies against the host's own store.
+    fn host_height(&self) -> Height;
+
+    /// Returns the current timestamp of the local (host) chain. Light clients use this value
+    /// to check header expiry and clock drift.
+    fn host_timestamp(&self) -> Timestamp;
 }
 
 /// Defines the write-only part of ICS2 (client functions) context.
@@ -27,35 +47,43 @@ pub trait ClientKeeper {
         match handler_res {
             Create(res) => {
                 let client_id = res.client_id.clone();
+                let height = res.client_state.latest_height();
 
                 self.store_client_type(client_id.clone(), res.client_type)?;
                 self.store_client_state(client_id.clone(), res.client_state.clone())?;
-                self.store_consensus_state(
-                    client_id,
-                    res.client_state.latest_height(),
-                    res.consensus_state,
-                )?;
+                self.store_consensus_state(client_id.clone(), height, res.consensus_state)?;
+                self.store_update_time(client_id.clone(), height, res.processed_time)?;
+                self.store_update_height(client_id, height, res.processed_height)?;
                 self.increase_client_counter();
                 Ok(())
             }
             Update(res) => {
+                let height = res.client_state.latest_height();
+
                 self.store_client_state(res.client_id.clone(), res.client_state.clone())?;
-                self.store_consensus_state(
-                    res.client_id.clone(),
-                    res.client_state.latest_height(),
-                    res.consensus_state,
-                )?;
+                self.store_consensus_state(res.client_id.clone(), height, res.consensus_state)?;
+                self.store_update_time(res.client_id.clone(), height, res.processed_time)?;
+                self.store_update_height(res.client_id.clone(), height, res.processed_height)?;
+
+                // Prune the consensus states which expired.
+                for pruned_height in res.pruned_heights {
+                    self.delete_consensus_state(res.client_id.clone(), pruned_height)?;
+                }
                 Ok(())
             }
             Upgrade(res) => {
+                let height = res.client_state.latest_height();
+
                 self.store_client_state(res.client_id.clone(), res.client_state.clone())?;
-                self.store_consensus_state(
-                    res.client_id.clone(),
-                    res.client_state.latest_height(),
-                    res.consensus_state,
-                )?;
+                self.store_consensus_state(res.client_id.clone(), height, res.consensus_state)?;
+                self.store_update_time(res.client_id.clone(), height, res.processed_time)?;
+                self.store_update_height(res.client_id, height, res.processed_height)?;
                 Ok(())
             }
+            Misbehaviour(res) => {
+                // Only the client state changes: it is now frozen.
+                self.store_client_state(res.client_id, res.client_state)
+            }
         }
     }
 
@@ -81,6 +109,28 @@ pub trait ClientKeeper {
         consensus_state: AnyConsensusState,
     ) -> Result<(), Error>;
 
+    /// Called upon client creation, update and upgrade. Stores the host timestamp at which the
+    /// consensus state of the client at `height` was processed.
+    fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), Error>;
+
+    /// Called upon client creation, update and upgrade. Stores the host height at which the
+    /// consensus state of the client at `height` was processed.
+    fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), Error>;
+
+    /// Called upon client update, to prune expired consensus states. Deletes the consensus state
+    /// of the client at `height`, along with its processed time and height.
+    fn delete_consensus_state(&mut self, client_id: ClientId, height: Height) -> Result<(), Error>;
+
     /// Called upon client creation.
     /// Increases the counter which keeps track of how many clients have been created.
     /// Should never fail.
@@ -109,6 +109,12 @@ pub enum Kind {
     #[error("upgrade verification failed")]
     UpgradeVerificationFailure,
 
+    #[error("misbehaviour verification failed")]
+    MisbehaviourVerificationFailure,
+
+    #[error("the misbehaviour is evidence against client {1}, but was submitted for client {0}")]
+    MisbehaviourClientIdMismatch(ClientId, ClientId),
+
     #[error("upgraded client height {0} must be at greater than current client height {1}")]
     LowUpgradeHeight(Height, Height),
 }
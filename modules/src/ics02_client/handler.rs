@@ -1,11 +1,14 @@
 //! This module implements the processing logic for ICS2 (client abstractions and functions) msgs.
 
 use crate::handler::HandlerOutput;
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::context::ClientReader;
 use crate::ics02_client::error::Error;
 use crate::ics02_client::msgs::ClientMsg;
+use crate::ics24_host::identifier::ClientId;
 
 pub mod create_client;
+pub mod misbehaviour;
 pub mod update_client;
 pub mod upgrade_client;
 
@@ -14,6 +17,16 @@ pub enum ClientResult {
     Create(create_client::Result),
     Update(update_client::Result),
     Upgrade(upgrade_client::Result),
+    Misbehaviour(misbehaviour::Result),
+}
+
+/// The state of client `client_id` once updated by the proofs it verified while processing a
+/// connection, channel or packet message (see `ClientDef::update_state_after_proof`). It is
+/// stored along with the result of that message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedClientState {
+    pub client_id: ClientId,
+    pub client_state: AnyClientState,
 }
 
 /// General entry point for processing any message related to ICS2 (client functions) protocols.
@@ -25,8 +38,6 @@ where
         ClientMsg::CreateClient(msg) => create_client::process(ctx, msg),
         ClientMsg::UpdateClient(msg) => update_client::process(ctx, msg),
         ClientMsg::UpgradeClient(msg) => upgrade_client::process(ctx, msg),
-        _ => {
-            unimplemented!()
-        }
+        ClientMsg::Misbehaviour(msg) => misbehaviour::process(ctx, msg),
     }
 }
@@ -0,0 +1,358 @@
+//! Protocol logic specific to processing ICS2 messages of type `MsgSubmitAnyMisbehaviour`.
+
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_def::{AnyClient, ClientDef};
+use crate::ics02_client::client_state::{AnyClientState, ClientState};
+use crate::ics02_client::context::ClientReader;
+use crate::ics02_client::error::{Error, Kind};
+use crate::ics02_client::events::Attributes;
+use crate::ics02_client::handler::ClientResult;
+use crate::ics02_client::misbehaviour::Misbehaviour;
+use crate::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+use crate::ics24_host::identifier::ClientId;
+
+/// The result following the successful processing of a `MsgSubmitAnyMisbehaviour` message.
+/// This data type should be used with a qualified name `misbehaviour::Result` to avoid ambiguity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Result {
+    pub client_id: ClientId,
+    pub client_state: AnyClientState,
+}
+
+pub fn process(
+    ctx: &dyn ClientReader,
+    msg: MsgSubmitAnyMisbehaviour,
+) -> HandlerResult<ClientResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let MsgSubmitAnyMisbehaviour {
+        client_id,
+        misbehaviour,
+        signer: _,
+    } = msg;
+
+    // Read client type from the host chain store. The client should already exist.
+    let client_type = ctx
+        .client_type(&client_id)
+        .ok_or_else(|| Kind::ClientNotFound(client_id.clone()))?;
+
+    let client_def = AnyClient::from_client_type(client_type);
+
+    // Read client state from the host chain store.
+    let client_state = ctx
+        .client_state(&client_id)
+        .ok_or_else(|| Kind::ClientNotFound(client_id.clone()))?;
+
+    if client_state.is_frozen() {
+        return Err(Kind::ClientFrozen(client_id).into());
+    }
+
+    // The evidence is verified against the consensus states of the client it is submitted for.
+    if misbehaviour.client_id() != &client_id {
+        return Err(Kind::MisbehaviourClientIdMismatch(
+            client_id,
+            misbehaviour.client_id().clone(),
+        )
+        .into());
+    }
+
+    let consensus_height = misbehaviour.height();
+
+    // Verify the evidence. If valid, this returns the client state frozen at the height of the
+    // conflicting headers, which will be persisted by the keeper.
+    let new_client_state = client_def
+        .check_misbehaviour_and_update_state(ctx, client_id.clone(), client_state, misbehaviour)
+        .map_err(|e| Kind::MisbehaviourVerificationFailure.context(e.to_string()))?;
+
+    let result = ClientResult::Misbehaviour(Result {
+        client_id: client_id.clone(),
+        client_state: new_client_state,
+    });
+
+    let event_attributes = Attributes {
+        client_id,
+        client_type,
+        consensus_height,
+        ..Default::default()
+    };
+    output.emit(IbcEvent::ClientMisbehaviour(event_attributes.into()));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use test_env_log::test;
+
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::ics02_client::client_state::{AnyClientState, ClientState};
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics02_client::context::{ClientKeeper, ClientReader};
+    use crate::ics02_client::error::Kind;
+    use crate::ics02_client::handler::dispatch;
+    use crate::ics02_client::handler::ClientResult::Misbehaviour;
+    use crate::ics02_client::misbehaviour::AnyMisbehaviour;
+    use crate::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+    use crate::ics02_client::msgs::ClientMsg;
+    use crate::ics07_tendermint::header::Header as TmHeader;
+    use crate::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+    use crate::ics24_host::identifier::{ChainId, ClientId};
+    use crate::mock::client_state::MockClientState;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::host::{HostBlock, HostType};
+    use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    /// Builds a mock misbehaviour for client `client_id`, made of two headers at `height` with
+    /// different timestamps.
+    fn mock_misbehaviour(client_id: &ClientId, height: Height) -> AnyMisbehaviour {
+        let header = |nanos| MockHeader {
+            height,
+            timestamp: Timestamp::from_nanoseconds(nanos).unwrap(),
+        };
+
+        AnyMisbehaviour::Mock(MockMisbehaviour {
+            client_id: client_id.clone(),
+            header1: header(1),
+            header2: header(2),
+        })
+    }
+
+    #[test]
+    fn test_misbehaviour_client_ok() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: mock_misbehaviour(&client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+
+        match dispatch(&ctx, ClientMsg::Misbehaviour(msg)) {
+            Ok(HandlerOutput {
+                result,
+                mut events,
+                log,
+            }) => {
+                assert_eq!(events.len(), 1);
+                let event = events.pop().unwrap();
+                assert!(
+                    matches!(event, IbcEvent::ClientMisbehaviour(e) if e.client_id() == &client_id)
+                );
+                assert!(log.is_empty());
+                // Check the result
+                match result {
+                    Misbehaviour(res) => {
+                        assert_eq!(res.client_id, client_id);
+                        assert_eq!(
+                            res.client_state,
+                            AnyClientState::Mock(
+                                MockClientState::new(MockHeader::new(Height::new(0, 42)))
+                                    .with_frozen_height(Height::new(0, 46))
+                            )
+                        );
+                    }
+                    _ => panic!("misbehaviour handler result has incorrect type"),
+                }
+            }
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_frozen_client() {
+        let client_id = ClientId::default();
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: mock_misbehaviour(&client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+
+        // Freeze the client, then submit the same evidence again.
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg.clone())).unwrap();
+        ctx.store_client_result(output.result).unwrap();
+        assert!(ctx.client_state(&client_id).unwrap().is_frozen());
+
+        match dispatch(&ctx, ClientMsg::Misbehaviour(msg)) {
+            Ok(_) => panic!("unexpected success (expected error)"),
+            Err(err) => assert_eq!(err.kind(), &Kind::ClientFrozen(client_id)),
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_nonexisting_client() {
+        let client_id = ClientId::from_str("mockclient1").unwrap();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let unknown_client_id = ClientId::from_str("nonexistingclient").unwrap();
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: unknown_client_id.clone(),
+            misbehaviour: mock_misbehaviour(&unknown_client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+
+        match dispatch(&ctx, ClientMsg::Misbehaviour(msg)) {
+            Ok(_) => panic!("unexpected success (expected error)"),
+            Err(err) => assert_eq!(err.kind(), &Kind::ClientNotFound(unknown_client_id)),
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_other_client() {
+        let client_id = ClientId::from_str("mockclient1").unwrap();
+        let other_client_id = ClientId::from_str("mockclient2").unwrap();
+        let ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 42))
+            .with_client(&other_client_id, Height::new(0, 42));
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: mock_misbehaviour(&other_client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+
+        match dispatch(&ctx, ClientMsg::Misbehaviour(msg)) {
+            Ok(_) => panic!("unexpected success (expected error)"),
+            Err(err) => assert_eq!(
+                err.kind(),
+                &Kind::MisbehaviourClientIdMismatch(client_id, other_client_id)
+            ),
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_non_conflicting_headers() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let header = MockHeader::new(Height::new(0, 46));
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: AnyMisbehaviour::Mock(MockMisbehaviour {
+                client_id,
+                header1: header,
+                header2: header,
+            }),
+            signer: get_dummy_account_id(),
+        };
+
+        match dispatch(&ctx, ClientMsg::Misbehaviour(msg)) {
+            Ok(_) => panic!("unexpected success (expected error)"),
+            Err(err) => assert_eq!(err.kind(), &Kind::MisbehaviourVerificationFailure),
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_tendermint_client() {
+        let chain_id = ChainId::new("mockgaia".to_string(), 1);
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+
+        // The synthetic blocks have their time set to a few seconds after the Unix epoch.
+        let ctx_at = |host_time: u64| {
+            MockContext::new(chain_id.clone(), HostType::Mock, 5, Height::new(1, 5))
+                .with_timestamp(Timestamp::from_nanoseconds(host_time * 1_000_000_000).unwrap())
+                .with_client_parametrized(
+                    &client_id,
+                    client_height,
+                    Some(ClientType::Tendermint),
+                    Some(client_height),
+                )
+        };
+        let ctx = ctx_at(100);
+
+        let header_on = |chain_id: &ChainId, height: u64, time: u64| {
+            let block = HostBlock::generate_tm_block_with_time(chain_id.clone(), height, time);
+            let mut header = TmHeader::from(block);
+            header.trusted_height = client_height;
+            header
+        };
+        let header_at = |height: u64, time: u64| header_on(&chain_id, height, time);
+
+        let submit_to = |ctx: &MockContext, header1: TmHeader, header2: TmHeader| {
+            let msg = MsgSubmitAnyMisbehaviour {
+                client_id: client_id.clone(),
+                misbehaviour: AnyMisbehaviour::Tendermint(TmMisbehaviour {
+                    client_id: client_id.clone(),
+                    header1,
+                    header2,
+                }),
+                signer: get_dummy_account_id(),
+            };
+            dispatch(ctx, ClientMsg::Misbehaviour(msg))
+        };
+        let submit = |header1: TmHeader, header2: TmHeader| submit_to(&ctx, header1, header2);
+
+        // A fork: two different blocks at the same height.
+        match submit(header_at(21, 21), header_at(21, 22)) {
+            Ok(HandlerOutput {
+                result: Misbehaviour(res),
+                ..
+            }) => match res.client_state {
+                AnyClientState::Tendermint(state) => {
+                    assert_eq!(state.frozen_height, Height::new(1, 21))
+                }
+                _ => panic!("unexpected client state type"),
+            },
+            Ok(_) => panic!("misbehaviour handler result has incorrect type"),
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+
+        // A BFT time violation: the higher block is not later than the lower one.
+        let output = submit(header_at(22, 21), header_at(21, 21)).unwrap();
+        assert!(matches!(output.result, Misbehaviour(res) if res.client_state.is_frozen()));
+
+        let other_chain_id = ChainId::new("othergaia".to_string(), 1);
+        let mut untrusted_header = header_at(21, 22);
+        untrusted_header.trusted_height = Height::new(1, 19);
+
+        // A block signed by validators unknown to the client, passed off as trusted.
+        let mut unknown_signers_header = TmHeader::from(
+            HostBlock::generate_tm_block_with_validators(chain_id.clone(), 21, 22, &["3", "4"]),
+        );
+        unknown_signers_header.trusted_height = client_height;
+        unknown_signers_header.trusted_validator_set = header_at(21, 21).trusted_validator_set;
+
+        // A block whose commit carries the signatures of another block.
+        let mut forged_header = header_at(21, 22);
+        forged_header.signed_header.commit.signatures =
+            header_at(21, 21).signed_header.commit.signatures;
+
+        let tests = vec![
+            // Two consecutive, non-conflicting blocks are no evidence of misbehaviour.
+            (ctx.clone(), header_at(22, 22), header_at(21, 21)),
+            // One of the conflicting blocks is for another chain.
+            (
+                ctx.clone(),
+                header_at(21, 21),
+                header_on(&other_chain_id, 21, 22),
+            ),
+            // There is no consensus state at the trusted height of one of the headers.
+            (ctx.clone(), header_at(21, 21), untrusted_header),
+            // The trusted consensus state is older than the trusting period.
+            (ctx_at(100_000), header_at(21, 21), header_at(21, 22)),
+            // One of the blocks is not signed by the trusted validators.
+            (ctx.clone(), header_at(21, 21), unknown_signers_header),
+            // One of the blocks carries invalid signatures.
+            (ctx.clone(), header_at(21, 21), forged_header),
+        ];
+
+        for (ctx, header1, header2) in tests {
+            match submit_to(&ctx, header1, header2) {
+                Ok(_) => panic!("unexpected success (expected error)"),
+                Err(err) => assert_eq!(err.kind(), &Kind::MisbehaviourVerificationFailure),
+            }
+        }
+    }
+}
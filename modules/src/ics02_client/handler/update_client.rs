@@ -11,6 +11,8 @@ use crate::ics02_client::events::Attributes;
 use crate::ics02_client::handler::ClientResult;
 use crate::ics02_client::msgs::update_client::MsgUpdateAnyClient;
 use crate::ics24_host::identifier::ClientId;
+use crate::timestamp::Timestamp;
+use crate::Height;
 
 /// The result following the successful processing of a `MsgUpdateAnyClient` message. Preferably
 /// this data type should be used with a qualified name `update_client::Result` to avoid ambiguity.
@@ -19,6 +21,12 @@ pub struct Result {
     pub client_id: ClientId,
     pub client_state: AnyClientState,
     pub consensus_state: AnyConsensusState,
+    /// The host time at which the consensus state was processed.
+    pub processed_time: Timestamp,
+    /// The host height at which the consensus state was processed.
+    pub processed_height: Height,
+    /// The heights of the consensus states which expired, to be pruned from the host store.
+    pub pruned_heights: Vec<Height>,
 }
 
 pub fn process(
@@ -53,13 +61,18 @@ pub fn process(
     // This function will return the new client_state (its latest_height changed) and a
     // consensus_state obtained from header. These will be later persisted by the keeper.
     let (new_client_state, new_consensus_state) = client_def
-        .check_header_and_update_state(client_state, header)
+        .check_header_and_update_state(ctx, client_id.clone(), client_state, header)
         .map_err(|e| Kind::HeaderVerificationFailure.context(e.to_string()))?;
 
+    let pruned_heights = expired_consensus_heights(ctx, &client_id, &new_client_state);
+
     let result = ClientResult::Update(Result {
         client_id: client_id.clone(),
         client_state: new_client_state,
         consensus_state: new_consensus_state,
+        processed_time: ctx.host_timestamp(),
+        processed_height: ctx.host_height(),
+        pruned_heights,
     });
 
     let event_attributes = Attributes {
@@ -71,25 +84,57 @@ pub fn process(
     Ok(output.with_result(result))
 }
 
+/// Returns the heights of the consensus states of the client `client_id` which are older than
+/// the trusting period of the client, as of the host timestamp. Like in ibc-go, these are pruned
+/// upon client update, as they can no longer be used to verify headers or proofs. The consensus
+/// state at the latest client height is never pruned.
+fn expired_consensus_heights(
+    ctx: &dyn ClientReader,
+    client_id: &ClientId,
+    client_state: &AnyClientState,
+) -> Vec<Height> {
+    let now = ctx.host_timestamp();
+
+    let mut heights: Vec<Height> = ctx
+        .consensus_state_heights(client_id)
+        .into_iter()
+        .filter(|height| *height < client_state.latest_height())
+        .filter(|height| {
+            ctx.consensus_state(client_id, *height)
+                .and_then(|cs| now.duration_since(&cs.timestamp()))
+                .map_or(false, |elapsed| client_state.expired(elapsed))
+        })
+        .collect();
+    heights.sort();
+
+    heights
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
+    use std::time::Duration;
     use test_env_log::test;
 
     use crate::events::IbcEvent;
     use crate::handler::HandlerOutput;
     use crate::ics02_client::client_state::AnyClientState;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics02_client::context::{ClientKeeper, ClientReader};
     use crate::ics02_client::error::Kind;
     use crate::ics02_client::handler::dispatch;
     use crate::ics02_client::handler::ClientResult::Update;
     use crate::ics02_client::header::Header;
     use crate::ics02_client::msgs::update_client::MsgUpdateAnyClient;
     use crate::ics02_client::msgs::ClientMsg;
-    use crate::ics24_host::identifier::ClientId;
+    use crate::ics07_tendermint::header::Header as TmHeader;
+    use crate::ics24_host::identifier::{ChainId, ClientId};
     use crate::mock::client_state::MockClientState;
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
+    use crate::mock::host::{HostBlock, HostType};
     use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::Timestamp;
     use crate::Height;
 
     #[test]
@@ -124,7 +169,7 @@ mod tests {
                         assert_eq!(upd_res.client_id, client_id);
                         assert_eq!(
                             upd_res.client_state,
-                            AnyClientState::Mock(MockClientState(MockHeader::new(
+                            AnyClientState::Mock(MockClientState::new(MockHeader::new(
                                 msg.header.height()
                             )))
                         )
@@ -208,4 +253,181 @@ mod tests {
             }
         }
     }
+
+    /// Builds a context for a chain with a Tendermint client at height `client_height`, and a
+    /// Tendermint header for `header_height` trusting the consensus state at `client_height`.
+    /// The synthetic blocks have their time set to `height` seconds after the Unix epoch.
+    fn tendermint_update_setup(
+        client_height: Height,
+        header_height: Height,
+        host_time: Duration,
+    ) -> (MockContext, ClientId, TmHeader) {
+        let chain_id = ChainId::new("mockgaia".to_string(), 1);
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+
+        let ctx = MockContext::new(chain_id.clone(), HostType::Mock, 5, Height::new(1, 5))
+            .with_timestamp(Timestamp::from_nanoseconds(host_time.as_nanos() as u64).unwrap())
+            .with_client_parametrized(
+                &client_id,
+                client_height,
+                Some(ClientType::Tendermint),
+                Some(client_height),
+            );
+
+        let mut header = TmHeader::from(HostBlock::generate_tm_block(
+            chain_id,
+            header_height.revision_height,
+        ));
+        header.trusted_height = client_height;
+
+        (ctx, client_id, header)
+    }
+
+    #[test]
+    fn test_update_tendermint_client_ok() {
+        let client_height = Height::new(1, 20);
+        for header_height in [client_height.increment(), Height::new(1, 25)].iter() {
+            let (ctx, client_id, header) =
+                tendermint_update_setup(client_height, *header_height, Duration::from_secs(100));
+
+            let msg = MsgUpdateAnyClient {
+                client_id: client_id.clone(),
+                header: header.into(),
+                signer: get_dummy_account_id(),
+            };
+
+            match dispatch(&ctx, ClientMsg::UpdateClient(msg)) {
+                Ok(HandlerOutput {
+                    result: Update(upd_res),
+                    ..
+                }) => {
+                    assert_eq!(upd_res.client_id, client_id);
+                    assert_eq!(upd_res.client_state.latest_height(), *header_height);
+                }
+                Ok(_) => panic!("update handler result has incorrect type"),
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_tendermint_client_fails() {
+        let client_height = Height::new(1, 20);
+
+        let tests = vec![
+            // The trusted consensus state is older than the trusting period.
+            tendermint_update_setup(
+                client_height,
+                Height::new(1, 21),
+                Duration::from_secs(100_000),
+            ),
+            // The header time is beyond the host time plus the maximum clock drift.
+            tendermint_update_setup(client_height, Height::new(1, 30), Duration::from_secs(21)),
+            // There is no consensus state at the trusted height.
+            {
+                let (ctx, client_id, mut header) = tendermint_update_setup(
+                    client_height,
+                    Height::new(1, 22),
+                    Duration::from_secs(100),
+                );
+                header.trusted_height = Height::new(1, 21);
+                (ctx, client_id, header)
+            },
+            // The validator set does not match the one committed to in the header.
+            {
+                let (ctx, client_id, mut header) = tendermint_update_setup(
+                    client_height,
+                    Height::new(1, 21),
+                    Duration::from_secs(100),
+                );
+                header.validator_set = tendermint::validator::Set::new(vec![], None);
+                (ctx, client_id, header)
+            },
+            // The header was produced by a chain other than the one tracked by the client.
+            {
+                let (ctx, client_id, mut header) = tendermint_update_setup(
+                    client_height,
+                    Height::new(1, 21),
+                    Duration::from_secs(100),
+                );
+                header.signed_header =
+                    HostBlock::generate_tm_block(ChainId::new("othergaia".to_string(), 1), 21)
+                        .signed_header;
+                (ctx, client_id, header)
+            },
+        ];
+
+        for (ctx, client_id, header) in tests {
+            let msg = MsgUpdateAnyClient {
+                client_id,
+                header: header.into(),
+                signer: get_dummy_account_id(),
+            };
+
+            match dispatch(&ctx, ClientMsg::UpdateClient(msg)) {
+                Ok(_) => panic!("unexpected success (expected error)"),
+                Err(err) => assert_eq!(err.kind(), &Kind::HeaderVerificationFailure),
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_tendermint_client_prunes_expired_consensus_states() {
+        let chain_id = ChainId::new("mockgaia".to_string(), 1);
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let host_time = Timestamp::from_nanoseconds(100_000_000_000_000).unwrap();
+
+        // The client is created at height 10, with a consensus state which expired by now, and
+        // was then updated to height 20, with a recent consensus state.
+        let mut ctx = MockContext::new(chain_id.clone(), HostType::Mock, 5, Height::new(1, 5))
+            .with_timestamp(host_time)
+            .with_client_parametrized(
+                &client_id,
+                Height::new(1, 10),
+                Some(ClientType::Tendermint),
+                Some(Height::new(1, 10)),
+            );
+        let trusted_block = HostBlock::generate_tm_block_with_time(chain_id.clone(), 20, 99_990);
+        ctx.store_consensus_state(client_id.clone(), Height::new(1, 20), trusted_block.into())
+            .unwrap();
+
+        let mut header =
+            TmHeader::from(HostBlock::generate_tm_block_with_time(chain_id, 21, 99_995));
+        header.trusted_height = Height::new(1, 20);
+
+        let msg = MsgUpdateAnyClient {
+            client_id: client_id.clone(),
+            header: header.into(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpdateClient(msg)).unwrap();
+        match &output.result {
+            Update(upd_res) => {
+                assert_eq!(upd_res.pruned_heights, vec![Height::new(1, 10)]);
+                assert_eq!(upd_res.processed_time, host_time);
+                assert_eq!(upd_res.processed_height, ctx.host_height());
+            }
+            _ => panic!("update handler result has incorrect type"),
+        }
+
+        ctx.store_client_result(output.result).unwrap();
+        assert!(ctx
+            .consensus_state(&client_id, Height::new(1, 10))
+            .is_none());
+        assert!(ctx
+            .client_update_time(&client_id, Height::new(1, 10))
+            .is_none());
+        assert!(ctx
+            .consensus_state(&client_id, Height::new(1, 20))
+            .is_some());
+        assert_eq!(
+            ctx.client_update_time(&client_id, Height::new(1, 21)),
+            Some(host_time)
+        );
+        assert_eq!(
+            ctx.client_update_height(&client_id, Height::new(1, 21)),
+            Some(ctx.host_height())
+        );
+    }
 }
@@ -12,6 +12,8 @@ use crate::ics02_client::events::Attributes;
 use crate::ics02_client::handler::ClientResult;
 use crate::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
 use crate::ics24_host::identifier::ClientId;
+use crate::timestamp::Timestamp;
+use crate::Height;
 
 /// The result following the successful processing of a `MsgUpgradeAnyClient` message.
 /// This data type should be used with a qualified name `upgrade_client::Result` to avoid ambiguity.
@@ -20,6 +22,10 @@ pub struct Result {
     pub client_id: ClientId,
     pub client_state: AnyClientState,
     pub consensus_state: AnyConsensusState,
+    /// The host time at which the consensus state was processed.
+    pub processed_time: Timestamp,
+    /// The host height at which the consensus state was processed.
+    pub processed_height: Height,
 }
 
 pub fn process(
@@ -70,6 +76,8 @@ pub fn process(
         client_id: client_id.clone(),
         client_state: new_client_state,
         consensus_state: new_consensus_state,
+        processed_time: ctx.host_timestamp(),
+        processed_height: ctx.host_height(),
     });
     let event_attributes = Attributes {
         client_id,
@@ -115,7 +123,7 @@ mod tests {
 
         let msg = MsgUpgradeAnyClient {
             client_id: client_id.clone(),
-            client_state: MockClientState(MockHeader::new(Height::new(1, 26))).into(),
+            client_state: MockClientState::new(MockHeader::new(Height::new(1, 26))).into(),
             consensus_state: MockConsensusState(MockHeader::new(Height::new(1, 26))).into(),
             proof_upgrade_client: MerkleProof::try_from(c_bytes).unwrap(),
             proof_upgrade_consensus_state: MerkleProof::try_from(cs_bytes).unwrap(),
@@ -166,7 +174,7 @@ mod tests {
 
         let msg = MsgUpgradeAnyClient {
             client_id: ClientId::from_str("nonexistingclient").unwrap(),
-            client_state: MockClientState(MockHeader::new(Height::new(1, 26))).into(),
+            client_state: MockClientState::new(MockHeader::new(Height::new(1, 26))).into(),
             consensus_state: MockConsensusState(MockHeader::new(Height::new(1, 26))).into(),
             proof_upgrade_client: MerkleProof::try_from(c_bytes).unwrap(),
             proof_upgrade_consensus_state: MerkleProof::try_from(cs_bytes).unwrap(),
@@ -200,7 +208,7 @@ mod tests {
 
         let msg = MsgUpgradeAnyClient {
             client_id,
-            client_state: MockClientState(MockHeader::new(Height::new(0, 26))).into(),
+            client_state: MockClientState::new(MockHeader::new(Height::new(0, 26))).into(),
             consensus_state: MockConsensusState(MockHeader::new(Height::new(0, 26))).into(),
             proof_upgrade_client: MerkleProof::try_from(c_bytes).unwrap(),
             proof_upgrade_consensus_state: MerkleProof::try_from(cs_bytes).unwrap(),
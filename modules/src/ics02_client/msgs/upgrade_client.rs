@@ -138,7 +138,7 @@ pub mod test_util {
         RawMsgUpgradeClient {
             client_id: "tendermint".parse().unwrap(),
             client_state: Some(
-                AnyClientState::Mock(MockClientState(MockHeader::new(height))).into(),
+                AnyClientState::Mock(MockClientState::new(MockHeader::new(height))).into(),
             ),
             consensus_state: Some(
                 AnyConsensusState::Mock(MockConsensusState(MockHeader::new(height))).into(),
@@ -177,7 +177,7 @@ mod tests {
 
         let height = Height::new(1, 1);
 
-        let client_state = AnyClientState::Mock(MockClientState(MockHeader::new(height)));
+        let client_state = AnyClientState::Mock(MockClientState::new(MockHeader::new(height)));
         let consensus_state = AnyConsensusState::Mock(MockConsensusState(MockHeader::new(height)));
 
         let proof = get_dummy_merkle_proof();
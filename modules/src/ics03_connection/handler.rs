@@ -1,6 +1,7 @@
 //! This module implements the processing logic for ICS3 (connection open handshake) messages.
 
 use crate::handler::HandlerOutput;
+use crate::ics02_client::handler::VerifiedClientState;
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics03_connection::context::ConnectionReader;
 use crate::ics03_connection::error::Error;
@@ -37,6 +38,10 @@ pub struct ConnectionResult {
 
     /// The connection end, which the handler produced as a result of processing the message.
     pub connection_end: ConnectionEnd,
+
+    /// The state of the client of the connection, if verifying the proofs of the message
+    /// updated it.
+    pub verified_client_state: Option<VerifiedClientState>,
 }
 
 /// General entry point for processing any type of message related to the ICS3 connection open
@@ -72,7 +72,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let verified_client_state = verify_proofs(
         ctx,
         msg.client_state(),
         &new_conn_end,
@@ -89,6 +89,7 @@ pub(crate) fn process(
         connection_id: msg.connection_id().clone(),
         connection_id_state: ConnectionIdState::Reused,
         connection_end: new_conn_end,
+        verified_client_state,
     };
 
     let event_attributes = Attributes {
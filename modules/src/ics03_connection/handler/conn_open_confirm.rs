@@ -52,7 +52,8 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(ctx, None, &new_conn_end, &expected_conn, msg.proofs())?;
+    let verified_client_state =
+        verify_proofs(ctx, None, &new_conn_end, &expected_conn, msg.proofs())?;
 
     output.log("success: connection verification passed");
 
@@ -63,6 +64,7 @@ pub(crate) fn process(
         connection_id: msg.connection_id().clone(),
         connection_id_state: ConnectionIdState::Reused,
         connection_end: new_conn_end,
+        verified_client_state,
     };
 
     let event_attributes = Attributes {
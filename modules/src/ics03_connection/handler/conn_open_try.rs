@@ -79,7 +79,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let verified_client_state = verify_proofs(
         ctx,
         msg.client_state(),
         &new_connection_end,
@@ -108,6 +108,7 @@ pub(crate) fn process(
             ConnectionIdState::Reused
         },
         connection_end: new_connection_end,
+        verified_client_state,
     };
 
     let event_attributes = Attributes {
@@ -2,6 +2,7 @@
 
 use crate::ics02_client::client_consensus::ConsensusState;
 use crate::ics02_client::client_state::{AnyClientState, ClientState};
+use crate::ics02_client::handler::VerifiedClientState;
 use crate::ics02_client::{client_def::AnyClient, client_def::ClientDef};
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics03_connection::context::ConnectionReader;
@@ -10,16 +11,30 @@ use crate::ics23_commitment::commitment::CommitmentProofBytes;
 use crate::proofs::{ConsensusProof, Proofs};
 use crate::Height;
 
-/// Entry point for verifying all proofs bundled in any ICS3 message.
+/// Entry point for verifying all proofs bundled in any ICS3 message. Returns the state of the
+/// client of the connection, if verifying the proofs updated it.
 pub fn verify_proofs(
     ctx: &dyn ConnectionReader,
     client_state: Option<AnyClientState>,
     connection_end: &ConnectionEnd,
     expected_conn: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    verify_connection_proof(
+) -> Result<Option<VerifiedClientState>, Error> {
+    let client_id = connection_end.client_id();
+
+    // Fetch the client state (IBC client on the local/host chain).
+    let stored_client_state = ctx
+        .client_state(client_id)
+        .ok_or_else(|| Kind::MissingClient(client_id.clone()))?;
+
+    // The client must not be frozen.
+    if stored_client_state.is_frozen() {
+        return Err(Kind::FrozenClient(client_id.clone()).into());
+    }
+
+    let mut verifying_client_state = verify_connection_proof(
         ctx,
+        stored_client_state.clone(),
         connection_end,
         expected_conn,
         proofs.height(),
@@ -28,8 +43,9 @@ pub fn verify_proofs(
 
     // If the message includes a client state, then verify the proof for that state.
     if let Some(expected_client_state) = client_state {
-        verify_client_proof(
+        verifying_client_state = verify_client_proof(
             ctx,
+            verifying_client_state,
             connection_end,
             expected_client_state,
             proofs.height(),
@@ -42,64 +58,61 @@ pub fn verify_proofs(
 
     // If a consensus proof is attached to the message, then verify it.
     if let Some(proof) = proofs.consensus_proof() {
-        Ok(verify_consensus_proof(
+        verifying_client_state = verify_consensus_proof(
             ctx,
+            verifying_client_state,
             connection_end,
             proofs.height(),
             &proof,
-        )?)
-    } else {
-        Ok(())
+        )?;
     }
+
+    Ok(
+        (verifying_client_state != stored_client_state).then(|| VerifiedClientState {
+            client_id: client_id.clone(),
+            client_state: verifying_client_state,
+        }),
+    )
 }
 
 /// Verifies the authenticity and semantic correctness of a commitment `proof`. The commitment
 /// claims to prove that an object of type connection exists on the source chain (i.e., the chain
 /// which created this proof). This object must match the state of `expected_conn`.
+/// Returns the client state once it verified the proof.
 pub fn verify_connection_proof(
     ctx: &dyn ConnectionReader,
+    client_state: AnyClientState,
     connection_end: &ConnectionEnd,
     expected_conn: &ConnectionEnd,
     proof_height: Height,
     proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local/host chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
-
+) -> Result<AnyClientState, Error> {
     // The client must have the consensus state for the height where this proof was created.
-    if ctx
+    let consensus_state = ctx
         .client_consensus_state(connection_end.client_id(), proof_height)
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(
-            proof_height,
-            connection_end.client_id().clone(),
-        )
-        .into());
-    }
+        .ok_or_else(|| {
+            Kind::MissingClientConsensusState(proof_height, connection_end.client_id().clone())
+        })?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the connection state against the expected connection end.
     // A counterparty connection id of None causes `unwrap()` below and indicates an internal
     // error as this is the connection id on the counterparty chain that must always be present.
-    Ok(client_def
+    client_def
         .verify_connection_state(
+            ctx,
             &client_state,
             proof_height,
             connection_end.counterparty().prefix(),
             proof,
+            consensus_state.root(),
             connection_end.counterparty().connection_id(),
             expected_conn,
         )
-        .map_err(|_| Kind::InvalidProof)?)
+        .map_err(|_| Kind::InvalidProof)?;
+
+    Ok(client_def.update_state_after_proof(client_state))
 }
 
 /// Verifies the client `proof` from a connection handshake message, typically from a
@@ -108,23 +121,15 @@ pub fn verify_connection_proof(
 /// is running on the counterparty chain (the chain which sent this message). This method does a
 /// complete verification: that the client state the counterparty stores is valid (i.e., not frozen,
 /// at the same revision as the current chain, with matching chain identifiers, etc) and that the
-/// `proof` is correct.
+/// `proof` is correct. Returns the client state once it verified the proof.
 pub fn verify_client_proof(
     ctx: &dyn ConnectionReader,
+    client_state: AnyClientState,
     connection_end: &ConnectionEnd,
     expected_client_state: AnyClientState,
     proof_height: Height,
     proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the local client state (IBC client running on the host chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
-
+) -> Result<AnyClientState, Error> {
     let consensus_state = ctx
         .client_consensus_state(connection_end.client_id(), proof_height)
         .ok_or_else(|| {
@@ -133,8 +138,9 @@ pub fn verify_client_proof(
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
-    Ok(client_def
+    client_def
         .verify_client_full_state(
+            ctx,
             &client_state,
             proof_height,
             consensus_state.root(),
@@ -146,44 +152,51 @@ pub fn verify_client_proof(
         .map_err(|e| {
             Kind::ClientStateVerificationFailure(connection_end.client_id().clone())
                 .context(e.to_string())
-        })?)
+        })?;
+
+    Ok(client_def.update_state_after_proof(client_state))
 }
 
+/// Verifies the consensus `proof` from a connection handshake message, against the consensus
+/// state of the host chain at the height claimed by the proof. Returns the client state once it
+/// verified the proof.
 pub fn verify_consensus_proof(
     ctx: &dyn ConnectionReader,
+    client_state: AnyClientState,
     connection_end: &ConnectionEnd,
     proof_height: Height,
     proof: &ConsensusProof,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
+) -> Result<AnyClientState, Error> {
+    let consensus_state = ctx
+        .client_consensus_state(connection_end.client_id(), proof_height)
+        .ok_or_else(|| {
+            Kind::MissingClientConsensusState(proof_height, connection_end.client_id().clone())
+        })?;
 
     // Fetch the expected consensus state from the historical (local) header data.
     let expected_consensus = ctx
         .host_consensus_state(proof.height())
         .ok_or_else(|| Kind::MissingLocalConsensusState.context(proof.height().to_string()))?;
 
-    let client = AnyClient::from_client_type(client_state.client_type());
+    let client_def = AnyClient::from_client_type(client_state.client_type());
 
-    Ok(client
+    client_def
         .verify_client_consensus_state(
+            ctx,
             &client_state,
             proof_height,
             connection_end.counterparty().prefix(),
             proof.proof(),
+            consensus_state.root(),
             connection_end.counterparty().client_id(),
             proof.height(),
             &expected_consensus,
         )
         .map_err(|e| {
             Kind::ConsensusStateVerificationFailure(proof.height()).context(e.to_string())
-        })?)
+        })?;
+
+    Ok(client_def.update_state_after_proof(client_state))
 }
 
 /// Checks that `claimed_height` is within normal bounds, i.e., fresh enough so that the chain has
@@ -2,10 +2,12 @@
 //! the interface that any host chain must implement to be able to process any `ChannelMsg`.
 //!
 
+use std::time::Duration;
+
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics03_connection::connection::ConnectionEnd;
-use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::channel::{ChannelEnd, State};
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::{error::Error, packet::Receipt};
 use crate::ics05_port::capabilities::Capability;
@@ -35,22 +37,65 @@ pub trait ChannelReader {
         height: Height,
     ) -> Option<AnyConsensusState>;
 
+    /// Returns the host timestamp at which the consensus state of the client `client_id` at
+    /// `height` was processed.
+    fn client_update_time(&self, client_id: &ClientId, height: Height) -> Option<Timestamp>;
+
+    /// Returns the host height at which the consensus state of the client `client_id` at
+    /// `height` was processed.
+    fn client_update_height(&self, client_id: &ClientId, height: Height) -> Option<Height>;
+
+    /// Returns the maximum expected time per block of the host chain, from which the number of
+    /// blocks of a connection delay period is derived.
+    fn max_expected_time_per_block(&self) -> Duration;
+
     fn authenticated_capability(&self, port_id: &PortId) -> Result<Capability, Error>;
 
+    /// Returns true if `capability` is the capability of the channel `channel_id` of the port
+    /// `port_id`, as claimed by the module bound to the port.
+    fn authenticate_channel_capability(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        capability: &Capability,
+    ) -> bool;
+
     fn get_next_sequence_send(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence>;
 
     fn get_next_sequence_recv(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence>;
 
     fn get_next_sequence_ack(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence>;
 
-    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String>;
+    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>>;
 
     fn get_packet_receipt(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Receipt>;
 
-    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String>;
+    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>>;
 
     /// A hashing function for packet commitments
-    fn hash(&self, value: String) -> String;
+    fn hash(&self, value: Vec<u8>) -> Vec<u8>;
+
+    /// Computes the commitment to a packet as ibc-go does: the hash of the timeout timestamp
+    /// (in nanoseconds), the revision number and the revision height of the timeout height,
+    /// all big-endian encoded, followed by the hash of the packet data.
+    fn packet_commitment(
+        &self,
+        packet_data: &[u8],
+        timeout_height: Height,
+        timeout_timestamp: Timestamp,
+    ) -> Vec<u8> {
+        let mut input = timeout_timestamp.as_nanoseconds().to_be_bytes().to_vec();
+        input.extend_from_slice(&timeout_height.revision_number.to_be_bytes());
+        input.extend_from_slice(&timeout_height.revision_height.to_be_bytes());
+        input.extend(self.hash(packet_data.to_vec()));
+
+        self.hash(input)
+    }
+
+    /// Computes the commitment to a packet acknowledgement as ibc-go does: its hash.
+    fn ack_commitment(&self, ack: &[u8]) -> Vec<u8> {
+        self.hash(ack.to_vec())
+    }
 
     /// Returns the current height of the local chain.
     fn host_height(&self) -> Height;
@@ -74,11 +119,19 @@ pub trait ChannelKeeper {
             &result.channel_end,
         )?;
 
+        // Modules cannot act on a closed channel anymore.
+        if result.channel_end.state_matches(&State::Closed) {
+            self.release_channel_capability(&result.port_id, &result.channel_id)?;
+        }
+
         // The channel identifier was freshly brewed.
         // Increase counter & initialize seq. nrs.
         if matches!(result.channel_id_state, ChannelIdState::Generated) {
             self.increase_channel_counter();
 
+            // Hand the capability of the new channel over to the module bound to its port.
+            self.claim_channel_capability(&result.port_id, &result.channel_id)?;
+
             // Associate also the channel end to its connection.
             self.store_connection_channels(
                 result.channel_end.connection_hops()[0].clone(),
@@ -159,6 +212,7 @@ pub trait ChannelKeeper {
                 if let Some(c) = res.channel {
                     //Ordered Channel
                     self.store_channel((res.port_id.clone(), res.channel_id.clone()), &c)?;
+                    self.release_channel_capability(&res.port_id, &res.channel_id)?;
                 }
                 self.delete_packet_commitment((
                     res.port_id.clone(),
@@ -229,6 +283,22 @@ pub trait ChannelKeeper {
         seq: Sequence,
     ) -> Result<(), Error>;
 
+    /// Creates the capability of the newly opened channel `channel_id` of the port `port_id`,
+    /// and has the module bound to the port claim it.
+    fn claim_channel_capability(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Capability, Error>;
+
+    /// Releases the capability of the closed channel `channel_id` of the port `port_id`, if the
+    /// module bound to the port still holds it.
+    fn release_channel_capability(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error>;
+
     /// Called upon channel identifier creation (Init or Try message processing).
     /// Increases the counter which keeps track of how many channels have been created.
     /// Should never fail.
@@ -6,6 +6,7 @@ pub type Error = anomaly::Error<Kind>;
 use super::packet::Sequence;
 use crate::ics04_channel::channel::State;
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::ics26_routing::context::ModuleId;
 use crate::timestamp::Timestamp;
 use crate::{ics02_client, Height};
 
@@ -79,6 +80,15 @@ pub enum Kind {
     #[error("the module associated with the port does not have the capability it needs")]
     InvalidPortCapability,
 
+    #[error("the channel {1} of port {0} has no capability associated")]
+    NoChannelCapability(PortId, ChannelId),
+
+    #[error("the module bound to port {0} failed to claim the capability of channel {1}")]
+    ChannelCapabilityClaim(PortId, ChannelId),
+
+    #[error("the capability presented is not the one of channel {1} of port {0}")]
+    InvalidChannelCapability(PortId, ChannelId),
+
     #[error("single version must be negociated on connection before opening channel")]
     InvalidVersionLengthConnection,
 
@@ -147,6 +157,18 @@ pub enum Kind {
     #[error("Missing client consensus state for client id {0} at height {1}")]
     MissingClientConsensusState(ClientId, Height),
 
+    #[error("Missing the processed time of the consensus state for client id {0} at height {1}")]
+    ProcessedTimeNotFound(ClientId, Height),
+
+    #[error("Missing the processed height of the consensus state for client id {0} at height {1}")]
+    ProcessedHeightNotFound(ClientId, Height),
+
+    #[error("The delay period has not passed: current timestamp {0} is earlier than {1}")]
+    NotEnoughTimeElapsed(Timestamp, Timestamp),
+
+    #[error("The delay period has not passed: current height {0} is lower than {1}")]
+    NotEnoughBlocksElapsed(Height, Height),
+
     #[error("Invalid channel id in counterparty")]
     InvalidCounterpartyChannelId,
 
@@ -173,6 +195,15 @@ pub enum Kind {
 
     #[error("Missing sequence number for ack packets")]
     MissingNextAckSeq,
+
+    #[error("no module is bound to the port {0}")]
+    UnboundPort(PortId),
+
+    #[error("the router has no route to the module {0}")]
+    RouteNotFound(ModuleId),
+
+    #[error("the application module callback failed")]
+    AppModule,
 }
 
 impl Kind {
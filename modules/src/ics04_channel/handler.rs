@@ -1,13 +1,16 @@
 //! This module implements the processing logic for ICS4 (channel) messages.
 
 use crate::handler::HandlerOutput;
+use crate::ics02_client::handler::VerifiedClientState;
 use crate::ics04_channel::channel::ChannelEnd;
-use crate::ics04_channel::context::ChannelReader;
-use crate::ics04_channel::error::Error;
+use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::msgs::ChannelMsg;
-use crate::ics04_channel::{msgs::PacketMsg, packet::PacketResult};
+use crate::ics04_channel::{msgs::PacketMsg, packet::Packet, packet::PacketResult};
 use crate::ics05_port::capabilities::Capability;
+use crate::ics05_port::context::PortReader;
 use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::context::{Ics26Context, Module, ModuleId, ModuleOutput, Router};
 
 pub mod acknowledgement;
 pub mod chan_close_confirm;
@@ -41,6 +44,9 @@ pub struct ChannelResult {
     pub channel_id_state: ChannelIdState,
     pub channel_cap: Capability,
     pub channel_end: ChannelEnd,
+    /// The state of the client of the channel's connection, if verifying the proofs of the
+    /// message updated it.
+    pub verified_client_state: Option<VerifiedClientState>,
 }
 
 /// General entry point for processing any type of message related to the ICS4 channel open and
@@ -74,3 +80,134 @@ where
         PacketMsg::ToClosePacket(msg) => timeout_on_close::process(ctx, msg),
     }
 }
+
+/// Returns the identifier of the application module bound to `port_id`.
+fn module_id_by_port<Ctx>(ctx: &Ctx, port_id: &PortId) -> Result<ModuleId, Error>
+where
+    Ctx: Ics26Context,
+{
+    ctx.lookup_module_by_port(port_id)
+        .map(|(module_id, _)| module_id)
+        .map_err(|e| Kind::UnboundPort(port_id.clone()).context(e).into())
+}
+
+/// Returns the application module registered under `module_id` with the router of the context.
+fn module_by_id<Ctx>(ctx: &mut Ctx, module_id: ModuleId) -> Result<&mut dyn Module, Error>
+where
+    Ctx: Ics26Context,
+{
+    ctx.router_mut()
+        .get_route_mut(&module_id)
+        .ok_or_else(|| Kind::RouteNotFound(module_id).into())
+}
+
+/// Invokes the callback of the application module bound to the channel port, once the channel
+/// handshake message `msg` was processed into `result`. Modules emit their events to `output`.
+pub fn channel_callback<Ctx>(
+    ctx: &mut Ctx,
+    msg: &ChannelMsg,
+    result: &ChannelResult,
+    output: &mut ModuleOutput,
+) -> Result<(), Error>
+where
+    Ctx: Ics26Context,
+{
+    let (port_id, channel_id, channel_end) =
+        (&result.port_id, &result.channel_id, &result.channel_end);
+    let module_id = module_id_by_port(ctx, port_id)?;
+
+    let module = module_by_id(ctx, module_id)?;
+
+    match msg {
+        ChannelMsg::ChannelOpenInit(_) => module.on_chan_open_init(
+            output,
+            *channel_end.ordering(),
+            channel_end.connection_hops(),
+            port_id,
+            channel_id,
+            channel_end.counterparty(),
+            &channel_end.version(),
+        ),
+        ChannelMsg::ChannelOpenTry(msg) => module.on_chan_open_try(
+            output,
+            *channel_end.ordering(),
+            channel_end.connection_hops(),
+            port_id,
+            channel_id,
+            channel_end.counterparty(),
+            &channel_end.version(),
+            &msg.counterparty_version,
+        ),
+        ChannelMsg::ChannelOpenAck(msg) => {
+            module.on_chan_open_ack(output, port_id, channel_id, msg.counterparty_version())
+        }
+        ChannelMsg::ChannelOpenConfirm(_) => {
+            module.on_chan_open_confirm(output, port_id, channel_id)
+        }
+        ChannelMsg::ChannelCloseInit(_) => module.on_chan_close_init(output, port_id, channel_id),
+        ChannelMsg::ChannelCloseConfirm(_) => {
+            module.on_chan_close_confirm(output, port_id, channel_id)
+        }
+    }
+}
+
+/// Invokes the callback of the application module bound to the port of the packet in `msg`,
+/// once the message was processed and its result stored. If the module acknowledges a received
+/// packet synchronously, the acknowledgement is written as well. Modules emit their events to
+/// `output`.
+pub fn packet_callback<Ctx>(
+    ctx: &mut Ctx,
+    msg: &PacketMsg,
+    output: &mut ModuleOutput,
+) -> Result<(), Error>
+where
+    Ctx: Ics26Context,
+{
+    let port_id = match msg {
+        PacketMsg::RecvPacket(msg) => &msg.packet.destination_port,
+        PacketMsg::AckPacket(msg) => &msg.packet.source_port,
+        PacketMsg::ToPacket(msg) => &msg.packet.source_port,
+        PacketMsg::ToClosePacket(msg) => &msg.packet.source_port,
+    };
+    let module_id = module_id_by_port(ctx, port_id)?;
+
+    let module = module_by_id(ctx, module_id)?;
+
+    match msg {
+        PacketMsg::RecvPacket(msg) => {
+            let ack = module.on_recv_packet(output, &msg.packet, &msg.signer)?;
+
+            if let Some(ack) = ack {
+                write_packet_acknowledgement(ctx, &msg.packet, ack, output)?;
+            }
+            Ok(())
+        }
+        PacketMsg::AckPacket(msg) => {
+            module.on_acknowledgement_packet(output, &msg.packet, &msg.acknowledgement, &msg.signer)
+        }
+        PacketMsg::ToPacket(msg) => module.on_timeout_packet(output, &msg.packet, &msg.signer),
+        PacketMsg::ToClosePacket(msg) => module.on_timeout_packet(output, &msg.packet, &msg.signer),
+    }
+}
+
+/// Writes the acknowledgement `ack` of a received `packet` to the store of the context.
+fn write_packet_acknowledgement<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    ack: Vec<u8>,
+    output: &mut ModuleOutput,
+) -> Result<(), Error>
+where
+    Ctx: Ics26Context,
+{
+    let ack_output = write_acknowledgement::process(ctx, packet.clone(), ack)?;
+    ctx.store_packet_result(ack_output.result)?;
+
+    ack_output.log.into_iter().for_each(|log| output.log(log));
+    ack_output
+        .events
+        .into_iter()
+        .for_each(|event| output.emit(event));
+
+    Ok(())
+}
@@ -63,7 +63,7 @@ pub(crate) fn process(
         channel_end.version(),
     );
 
-    verify_channel_proofs(
+    let verified_client_state = verify_channel_proofs(
         ctx,
         &channel_end,
         &conn,
@@ -83,6 +83,7 @@ pub(crate) fn process(
         channel_id_state: ChannelIdState::Reused,
         channel_cap,
         channel_end,
+        verified_client_state,
     };
 
     let event_attributes = Attributes {
@@ -57,6 +57,7 @@ pub(crate) fn process(
         channel_id_state: ChannelIdState::Reused,
         channel_cap,
         channel_end,
+        verified_client_state: None,
     };
 
     let event_attributes = Attributes {
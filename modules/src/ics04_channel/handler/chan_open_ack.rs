@@ -66,7 +66,7 @@ pub(crate) fn process(
         msg.counterparty_version().clone(),
     );
     //2. Verify proofs
-    verify_channel_proofs(
+    let verified_client_state = verify_channel_proofs(
         ctx,
         &channel_end,
         &conn,
@@ -88,6 +88,7 @@ pub(crate) fn process(
         channel_id_state: ChannelIdState::Reused,
         channel_cap,
         channel_end,
+        verified_client_state,
     };
 
     let event_attributes = Attributes {
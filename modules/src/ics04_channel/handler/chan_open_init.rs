@@ -72,6 +72,7 @@ pub(crate) fn process(
         channel_end: new_channel_end,
         channel_id_state: ChannelIdState::Generated,
         channel_cap,
+        verified_client_state: None,
     };
 
     let event_attributes = Attributes {
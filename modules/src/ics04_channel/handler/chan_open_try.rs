@@ -117,7 +117,7 @@ pub(crate) fn process(
     );
 
     // 2. Actual proofs are verified now.
-    verify_channel_proofs(
+    let verified_client_state = verify_channel_proofs(
         ctx,
         &new_channel_end,
         &conn,
@@ -141,6 +141,7 @@ pub(crate) fn process(
         },
         channel_id: channel_id.clone(),
         channel_end: new_channel_end,
+        verified_client_state,
     };
 
     let event_attributes = Attributes {
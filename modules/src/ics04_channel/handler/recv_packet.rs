@@ -1,5 +1,6 @@
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::handler::VerifiedClientState;
 use crate::ics02_client::height::Height;
 use crate::ics03_connection::connection::State as ConnectionState;
 use crate::ics04_channel::channel::{Counterparty, Order, State};
@@ -19,6 +20,7 @@ pub struct RecvPacketResult {
     pub seq: Sequence,
     pub seq_number: Sequence,
     pub receipt: Option<Receipt>,
+    pub verified_client_state: Option<VerifiedClientState>,
 }
 
 pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<PacketResult, Error> {
@@ -69,8 +71,6 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         return Err(Kind::ConnectionNotOpen(dest_channel_end.connection_hops()[0].clone()).into());
     }
 
-    let client_id = connection_end.client_id().clone();
-
     // Check if packet height is newer than the height of the local host chain
     let latest_height = ctx.host_height();
     if (!packet.timeout_height.is_zero()) && (packet.timeout_height <= latest_height) {
@@ -83,7 +83,8 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         return Err(Kind::LowPacketTimestamp.into());
     }
 
-    verify_packet_recv_proofs(ctx, packet, client_id, &msg.proofs)?;
+    let verified_client_state =
+        verify_packet_recv_proofs(ctx, packet, &connection_end, &msg.proofs)?;
 
     let result = if dest_channel_end.order_matches(&Order::Ordered) {
         let next_seq_recv = ctx
@@ -100,6 +101,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
             seq: packet.sequence,
             seq_number: next_seq_recv.increment(),
             receipt: None,
+            verified_client_state,
         })
     } else {
         let packet_rec = ctx.get_packet_receipt(&(
@@ -118,6 +120,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
                     seq: packet.sequence,
                     seq_number: 1.into(),
                     receipt: Some(Receipt::Ok),
+                    verified_client_state,
                 })
             }
         }
@@ -136,6 +139,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
+    use std::time::Duration;
     use test_env_log::test;
 
     use crate::ics03_connection::connection::ConnectionEnd;
@@ -212,6 +216,14 @@ mod tests {
             ZERO_DURATION,
         );
 
+        let delayed_connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            connection_end.counterparty().clone(),
+            get_compatible_versions(),
+            Duration::from_secs(10),
+        );
+
         let tests: Vec<Test> = vec![
             Test {
                 name: "Processing fails because no channel exists in the context".to_string(),
@@ -258,6 +270,24 @@ mod tests {
                 msg,
                 want_pass: true,
             },
+            Test {
+                name: "Processing fails because the connection delay period has not passed"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_timestamp(Timestamp::from_nanoseconds(1).unwrap())
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), delayed_connection_end)
+                    .with_port_capability(packet.destination_port.clone())
+                    .with_channel(
+                        packet.destination_port.clone(),
+                        packet.destination_channel.clone(),
+                        dest_channel_end.clone(),
+                    )
+                    .with_height(host_height),
+                msg: msg.clone(),
+                want_pass: false,
+            },
             Test {
                 name: "Packet timeout expired".to_string(),
                 ctx: context
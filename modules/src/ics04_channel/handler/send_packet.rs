@@ -6,6 +6,7 @@ use crate::ics04_channel::channel::State;
 use crate::ics04_channel::events::SendPacket;
 use crate::ics04_channel::packet::{PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind, packet::Packet};
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::{ChannelId, PortId};
 use crate::timestamp::{Expiry, Timestamp};
 use crate::Height;
@@ -21,7 +22,13 @@ pub struct SendPacketResult {
     pub data: Vec<u8>,
 }
 
-pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<PacketResult, Error> {
+/// Sends `packet` on behalf of the module bound to its source port, which presents the
+/// capability `channel_cap` of the source channel.
+pub fn send_packet(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    packet: Packet,
+) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
     let source_channel_end = ctx
@@ -35,7 +42,17 @@ pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<Pac
         return Err(Kind::ChannelClosed(packet.source_channel).into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    if !ctx.authenticate_channel_capability(
+        &packet.source_port,
+        &packet.source_channel,
+        channel_cap,
+    ) {
+        return Err(Kind::InvalidChannelCapability(
+            packet.source_port.clone(),
+            packet.source_channel,
+        )
+        .into());
+    }
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -126,10 +143,14 @@ mod tests {
     use crate::ics03_connection::connection::State as ConnectionState;
     use crate::ics03_connection::version::get_compatible_versions;
     use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::error::Kind;
     use crate::ics04_channel::handler::send_packet::send_packet;
     use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::ics04_channel::packet::Packet;
+    use crate::ics05_port::capabilities::{CapabilityKeeper, CapabilityName};
+    use crate::ics05_port::context::PortReader;
     use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::ics26_routing::context::ModuleId;
     use crate::mock::context::MockContext;
     use crate::timestamp::ZERO_DURATION;
 
@@ -174,6 +195,14 @@ mod tests {
 
         let client_height = Height::new(0, Height::default().revision_height + 1);
 
+        let good_context = context
+            .clone()
+            .with_client(&ClientId::default(), Height::default())
+            .with_connection(ConnectionId::default(), connection_end.clone())
+            .with_port_capability(PortId::default())
+            .with_channel(PortId::default(), ChannelId::default(), channel_end.clone())
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into());
+
         let tests: Vec<Test> = vec![
             Test {
                 name: "Processing fails because no channel exists in the context".to_string(),
@@ -194,14 +223,8 @@ mod tests {
             },
             Test {
                 name: "Good parameters".to_string(),
-                ctx: context
-                    .clone()
-                    .with_client(&ClientId::default(), Height::default())
-                    .with_connection(ConnectionId::default(), connection_end.clone())
-                    .with_port_capability(PortId::default())
-                    .with_channel(PortId::default(), ChannelId::default(), channel_end.clone())
-                    .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
-                packet,
+                ctx: good_context.clone(),
+                packet: packet.clone(),
                 want_pass: true,
             },
             Test {
@@ -219,8 +242,18 @@ mod tests {
         .into_iter()
         .collect();
 
+        // A capability minted by another keeper, which the contexts cannot know of.
+        let foreign_cap = CapabilityKeeper::default()
+            .new_capability(&ModuleId::new("other"), CapabilityName::new("foreign"))
+            .unwrap();
+
         for test in tests {
-            let res = send_packet(&test.ctx, test.packet.clone());
+            // The module bound to the port presents the channel capability it claimed.
+            let channel_cap = test
+                .ctx
+                .channel_capability(&PortId::default(), &ChannelId::default())
+                .unwrap_or_else(|| foreign_cap.clone());
+            let res = send_packet(&test.ctx, &channel_cap, test.packet.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
@@ -251,5 +284,15 @@ mod tests {
                 }
             }
         }
+
+        // Any other capability than the channel's is rejected, even one the module owns.
+        let (_, port_cap) = good_context
+            .lookup_module_by_port(&PortId::default())
+            .unwrap();
+        let res = send_packet(&good_context, &port_cap, packet);
+        assert!(matches!(
+            res.unwrap_err().kind(),
+            Kind::InvalidChannelCapability(_, _)
+        ));
     }
 }
@@ -1,4 +1,8 @@
-use crate::ics02_client::client_state::ClientState;
+use std::time::Duration;
+
+use crate::ics02_client::client_consensus::ConsensusState;
+use crate::ics02_client::client_state::{AnyClientState, ClientState};
+use crate::ics02_client::handler::VerifiedClientState;
 use crate::ics02_client::{client_def::AnyClient, client_def::ClientDef};
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics04_channel::channel::ChannelEnd;
@@ -7,187 +11,310 @@ use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::packet::{Packet, Sequence};
 use crate::ics24_host::identifier::ClientId;
 use crate::proofs::Proofs;
+use crate::timestamp::Expiry;
+use crate::Height;
 
 /// Entry point for verifying all proofs bundled in any ICS4 message for channel protocols.
+/// Returns the state of the client of the connection, if verifying the proofs updated it.
 pub fn verify_channel_proofs(
     ctx: &dyn ChannelReader,
     channel_end: &ChannelEnd,
     connection_end: &ConnectionEnd,
     expected_chan: &ChannelEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<Option<VerifiedClientState>, Error> {
     // This is the client which will perform proof verification.
-    let client_id = connection_end.client_id().clone();
+    let client_id = connection_end.client_id();
 
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
+    let client_state = client_state_for_proofs(ctx, client_id, None)?;
 
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
-
-    if ctx
-        .client_consensus_state(&client_id, proofs.height())
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(client_id, proofs.height()).into());
-    }
+    let consensus_state = ctx
+        .client_consensus_state(client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the channel state against the expected channel end.
     // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-    Ok(client_def
+    client_def
         .verify_channel_state(
+            ctx,
             &client_state,
             proofs.height(),
             connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             channel_end.counterparty().port_id(),
             channel_end.counterparty().channel_id().unwrap(),
             expected_chan,
         )
-        .map_err(|_| Kind::InvalidProof)?)
+        .map_err(|_| Kind::InvalidProof)?;
+
+    Ok(verified_client_state(
+        ctx,
+        client_id,
+        client_def.update_state_after_proof(client_state),
+    ))
 }
 
 /// Entry point for verifying all proofs bundled in a ICS4 packet recv. message.
+/// Returns the state of the client of the connection, if verifying the proofs updated it.
 pub fn verify_packet_recv_proofs(
     ctx: &dyn ChannelReader,
     packet: &Packet,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
+) -> Result<Option<VerifiedClientState>, Error> {
+    let client_id = connection_end.client_id();
+    let client_state = client_state_for_proofs(ctx, client_id, None)?;
 
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
+    let consensus_state = ctx
+        .client_consensus_state(client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
 
-    if ctx
-        .client_consensus_state(&client_id, proofs.height())
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(client_id, proofs.height()).into());
-    }
+    verify_delay_passed(ctx, proofs.height(), connection_end)?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data
+    let commitment = ctx.packet_commitment(
+        &packet.data,
+        packet.timeout_height,
+        packet.timeout_timestamp,
     );
-    let commitment = ctx.hash(input);
 
     // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    client_def
         .verify_packet_data(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &packet.source_port,
             &packet.source_channel,
             &packet.sequence,
             commitment,
         )
-        .map_err(|_| Kind::PacketVerificationFailed(packet.sequence))?)
+        .map_err(|_| Kind::PacketVerificationFailed(packet.sequence))?;
+
+    Ok(verified_client_state(
+        ctx,
+        client_id,
+        client_def.update_state_after_proof(client_state),
+    ))
 }
 
 /// Entry point for verifying all proofs bundled in an ICS4 packet ack message.
+/// Returns the state of the client of the connection, if verifying the proofs updated it.
 pub fn verify_packet_acknowledgement_proofs(
     ctx: &dyn ChannelReader,
     packet: &Packet,
     acknowledgement: Vec<u8>,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
+) -> Result<Option<VerifiedClientState>, Error> {
+    let client_id = connection_end.client_id();
+    let client_state = client_state_for_proofs(ctx, client_id, None)?;
 
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
+    let consensus_state = ctx
+        .client_consensus_state(client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
+
+    verify_delay_passed(ctx, proofs.height(), connection_end)?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    client_def
         .verify_packet_acknowledgement(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &packet.source_port,
             &packet.source_channel,
             &packet.sequence,
             acknowledgement,
         )
-        .map_err(|_| Kind::PacketVerificationFailed(packet.sequence))?)
+        .map_err(|_| Kind::PacketVerificationFailed(packet.sequence))?;
+
+    Ok(verified_client_state(
+        ctx,
+        client_id,
+        client_def.update_state_after_proof(client_state),
+    ))
 }
 
-/// Entry point for verifying all timeout proofs.
+/// Entry point for verifying all timeout proofs. The `verified_client_state` is the state of
+/// the client of the connection as updated by the proofs of the message verified before, if
+/// any. Returns the state of the client, if verifying the proofs updated it.
 pub fn verify_next_sequence_recv(
     ctx: &dyn ChannelReader,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     packet: Packet,
     seq: Sequence,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
+    verified_client_state: Option<VerifiedClientState>,
+) -> Result<Option<VerifiedClientState>, Error> {
+    let client_id = connection_end.client_id();
+    let client_state = client_state_for_proofs(ctx, client_id, verified_client_state)?;
 
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
+    let consensus_state = ctx
+        .client_consensus_state(client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
+
+    verify_delay_passed(ctx, proofs.height(), connection_end)?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    client_def
         .verify_next_sequence_recv(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             &seq,
         )
-        .map_err(|_| Kind::PacketVerificationFailed(seq))?)
+        .map_err(|_| Kind::PacketVerificationFailed(seq))?;
+
+    Ok(verified_client_state(
+        ctx,
+        client_id,
+        client_def.update_state_after_proof(client_state),
+    ))
 }
 
+/// Verifies the proof that the packet was not received. As for `verify_next_sequence_recv`,
+/// `verified_client_state` is the state of the client updated by the proofs verified before.
 pub fn verify_packet_receipt_absence(
     ctx: &dyn ChannelReader,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     packet: Packet,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
+    verified_client_state: Option<VerifiedClientState>,
+) -> Result<Option<VerifiedClientState>, Error> {
+    let client_id = connection_end.client_id();
+    let client_state = client_state_for_proofs(ctx, client_id, verified_client_state)?;
 
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
+    let consensus_state = ctx
+        .client_consensus_state(client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
+
+    verify_delay_passed(ctx, proofs.height(), connection_end)?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    client_def
         .verify_packet_receipt_absence(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             &packet.sequence,
         )
-        .map_err(|_| Kind::PacketVerificationFailed(packet.sequence))?)
+        .map_err(|_| Kind::PacketVerificationFailed(packet.sequence))?;
+
+    Ok(verified_client_state(
+        ctx,
+        client_id,
+        client_def.update_state_after_proof(client_state),
+    ))
+}
+
+/// Returns the state of client `client_id` against which to verify a proof: the state updated
+/// by the proofs of the message verified before, if any, or else the stored one. The client
+/// must not be frozen.
+fn client_state_for_proofs(
+    ctx: &dyn ChannelReader,
+    client_id: &ClientId,
+    verified_client_state: Option<VerifiedClientState>,
+) -> Result<AnyClientState, Error> {
+    let client_state = match verified_client_state {
+        Some(verified) => verified.client_state,
+        None => ctx
+            .client_state(client_id)
+            .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?,
+    };
+
+    // The client must not be frozen.
+    if client_state.is_frozen() {
+        return Err(Kind::FrozenClient(client_id.clone()).into());
+    }
+
+    Ok(client_state)
+}
+
+/// Returns the state of client `client_id` once it verified a proof, if it differs from the
+/// stored one.
+fn verified_client_state(
+    ctx: &dyn ChannelReader,
+    client_id: &ClientId,
+    client_state: AnyClientState,
+) -> Option<VerifiedClientState> {
+    (ctx.client_state(client_id).as_ref() != Some(&client_state)).then(|| VerifiedClientState {
+        client_id: client_id.clone(),
+        client_state,
+    })
+}
+
+/// Checks that the delay period of the connection `connection_end` has passed since the host
+/// processed the consensus state at `height` of the connection's client, both in time and in
+/// number of blocks. Packet proofs at `height` may only be verified once this is the case.
+pub fn verify_delay_passed(
+    ctx: &dyn ChannelReader,
+    height: Height,
+    connection_end: &ConnectionEnd,
+) -> Result<(), Error> {
+    let delay_period = connection_end.delay_period();
+    if delay_period == Duration::from_secs(0) {
+        return Ok(());
+    }
+
+    let client_id = connection_end.client_id();
+    let current_time = ctx.host_timestamp();
+    let current_height = ctx.host_height();
+
+    let processed_time = ctx
+        .client_update_time(client_id, height)
+        .ok_or_else(|| Kind::ProcessedTimeNotFound(client_id.clone(), height))?;
+    let earliest_time = (processed_time + delay_period)
+        .map_err(|e| Kind::NotEnoughTimeElapsed(current_time, processed_time).context(e))?;
+    if earliest_time.check_expiry(&current_time) != Expiry::NotExpired {
+        return Err(Kind::NotEnoughTimeElapsed(current_time, earliest_time).into());
+    }
+
+    let processed_height = ctx
+        .client_update_height(client_id, height)
+        .ok_or_else(|| Kind::ProcessedHeightNotFound(client_id.clone(), height))?;
+    let earliest_height =
+        processed_height.add(block_delay(delay_period, ctx.max_expected_time_per_block()));
+    if current_height < earliest_height {
+        return Err(Kind::NotEnoughBlocksElapsed(current_height, earliest_height).into());
+    }
+
+    Ok(())
+}
+
+/// The number of blocks of the `delay_period`, given the `max_expected_time_per_block`: the
+/// delay period divided by the block time, rounded up.
+fn block_delay(delay_period: Duration, max_expected_time_per_block: Duration) -> u64 {
+    let block_time = max_expected_time_per_block.as_nanos();
+    if block_time == 0 {
+        return 0;
+    }
+
+    ((delay_period.as_nanos() + block_time - 1) / block_time) as u64
 }
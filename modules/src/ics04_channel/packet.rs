@@ -5,6 +5,7 @@ use serde_derive::{Deserialize, Serialize};
 
 use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
 
+use crate::ics02_client::handler::VerifiedClientState;
 use crate::ics04_channel::error::Kind;
 use crate::ics24_host::identifier::{ChannelId, PortId};
 use crate::timestamp::{Expiry::Expired, Timestamp};
@@ -34,6 +35,19 @@ pub enum PacketResult {
     Timeout(TimeoutPacketResult),
 }
 
+impl PacketResult {
+    /// Returns the state of the client which verified the proofs of the packet message, if
+    /// verifying them updated it.
+    pub fn verified_client_state(&self) -> Option<&VerifiedClientState> {
+        match self {
+            PacketResult::Recv(res) => res.verified_client_state.as_ref(),
+            PacketResult::Ack(res) => res.verified_client_state.as_ref(),
+            PacketResult::Timeout(res) => res.verified_client_state.as_ref(),
+            PacketResult::Send(_) | PacketResult::WriteAck(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Receipt {
     Ok,
@@ -1,18 +1,243 @@
-//! Capabilities: this is a placeholder.
+//! Object capabilities, which authenticate the modules that own ports and channels. A capability
+//! is an unforgeable key, created by the host and claimed by modules under a name; a module may
+//! only act on a port or channel if it presents the capability it claimed for it. See:
+//! <https://github.com/cosmos/ibc/tree/master/spec/core/ics-005-port-allocation>
 
-#[derive(Clone, Debug, PartialEq)]
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use crate::ics05_port::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics24_host::Path;
+use crate::ics26_routing::context::ModuleId;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Capability {
     index: u64,
 }
 
 impl Capability {
-    pub fn new() -> Capability {
-        Self { index: 0x0 }
+    pub(crate) fn new(index: u64) -> Capability {
+        Self { index }
+    }
+
+    /// The index of this capability, unique among the capabilities created by the host.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Capability({})", self.index)
+    }
+}
+
+/// The name under which a module claims a capability.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CapabilityName(String);
+
+impl CapabilityName {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// The name of the capability of the port `port_id`.
+    pub fn port(port_id: &PortId) -> Self {
+        Self(Path::Ports(port_id.clone()).to_string())
+    }
+
+    /// The name of the capability of the channel `channel_id` of the port `port_id`.
+    pub fn channel(port_id: &PortId, channel_id: &ChannelId) -> Self {
+        Self(format!(
+            "capabilities/ports/{}/channels/{}",
+            port_id, channel_id
+        ))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CapabilityName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
-impl Default for Capability {
-    fn default() -> Self {
-        Self::new()
+/// An in-memory keeper of the capabilities created by a host, which the host may rely on to
+/// implement the `PortReader` and `PortKeeper` traits.
+#[derive(Clone, Debug, Default)]
+pub struct CapabilityKeeper {
+    /// Index of the latest capability created.
+    latest_index: u64,
+
+    /// The capabilities claimed by each module, by name.
+    claims: HashMap<(ModuleId, CapabilityName), Capability>,
+
+    /// The claims on each capability, by capability index.
+    owners: HashMap<u64, BTreeSet<(ModuleId, CapabilityName)>>,
+}
+
+impl CapabilityKeeper {
+    /// Creates a new capability, with a fresh index, claimed by `module_id` under `name`.
+    pub fn new_capability(
+        &mut self,
+        module_id: &ModuleId,
+        name: CapabilityName,
+    ) -> Result<Capability, Error> {
+        if self.get_capability(module_id, &name).is_some() {
+            return Err(Kind::CapabilityTaken(module_id.clone(), name).into());
+        }
+
+        self.latest_index += 1;
+        let capability = Capability::new(self.latest_index);
+        self.insert_claim(module_id, &capability, name);
+
+        Ok(capability)
+    }
+
+    /// Has `module_id` claim the existing `capability` under `name`.
+    pub fn claim_capability(
+        &mut self,
+        module_id: &ModuleId,
+        capability: &Capability,
+        name: CapabilityName,
+    ) -> Result<(), Error> {
+        if !self.owners.contains_key(&capability.index) {
+            return Err(Kind::UnknownCapability(capability.clone()).into());
+        }
+        if self.get_capability(module_id, &name).is_some() {
+            return Err(Kind::CapabilityTaken(module_id.clone(), name).into());
+        }
+
+        self.insert_claim(module_id, capability, name);
+        Ok(())
+    }
+
+    /// Releases all the claims of `module_id` on `capability`. The capability ceases to exist
+    /// once no module owns it anymore.
+    pub fn release_capability(
+        &mut self,
+        module_id: &ModuleId,
+        capability: &Capability,
+    ) -> Result<(), Error> {
+        let owners = self
+            .owners
+            .get_mut(&capability.index)
+            .ok_or_else(|| Kind::UnknownCapability(capability.clone()))?;
+
+        let released: Vec<_> = owners
+            .iter()
+            .filter(|(owner, _)| owner == module_id)
+            .cloned()
+            .collect();
+        if released.is_empty() {
+            return Err(Kind::CapabilityNotOwned(module_id.clone(), capability.clone()).into());
+        }
+
+        for claim in released {
+            owners.remove(&claim);
+            self.claims.remove(&claim);
+        }
+        if owners.is_empty() {
+            self.owners.remove(&capability.index);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the capability which `module_id` claimed under `name`, if any.
+    pub fn get_capability(
+        &self,
+        module_id: &ModuleId,
+        name: &CapabilityName,
+    ) -> Option<Capability> {
+        self.claims.get(&(module_id.clone(), name.clone())).cloned()
+    }
+
+    /// Returns the modules which claimed a capability under `name`, along with the capability.
+    pub fn lookup_modules(&self, name: &CapabilityName) -> Option<(Vec<ModuleId>, Capability)> {
+        let capability = self
+            .claims
+            .iter()
+            .find(|((_, claim_name), _)| claim_name == name)
+            .map(|(_, capability)| capability.clone())?;
+
+        let modules = self.owners[&capability.index]
+            .iter()
+            .filter(|(_, claim_name)| claim_name == name)
+            .map(|(module_id, _)| module_id.clone())
+            .collect();
+
+        Some((modules, capability))
+    }
+
+    fn insert_claim(
+        &mut self,
+        module_id: &ModuleId,
+        capability: &Capability,
+        name: CapabilityName,
+    ) {
+        self.claims
+            .insert((module_id.clone(), name.clone()), capability.clone());
+        self.owners
+            .entry(capability.index)
+            .or_default()
+            .insert((module_id.clone(), name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use super::{CapabilityKeeper, CapabilityName};
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::ics26_routing::context::ModuleId;
+
+    #[test]
+    fn capability_lifecycle() {
+        let mut keeper = CapabilityKeeper::default();
+        let (ibc, transfer) = (ModuleId::new("ibc"), ModuleId::new("transfer"));
+        let port_name = CapabilityName::port(&PortId::default());
+        let channel_name = CapabilityName::channel(&PortId::default(), &ChannelId::default());
+
+        let port_cap = keeper.new_capability(&transfer, port_name.clone()).unwrap();
+        let channel_cap = keeper.new_capability(&ibc, channel_name.clone()).unwrap();
+        assert_ne!(port_cap, channel_cap);
+        assert!(keeper.new_capability(&transfer, port_name.clone()).is_err());
+
+        // Several modules can own the same capability.
+        keeper
+            .claim_capability(&transfer, &channel_cap, channel_name.clone())
+            .unwrap();
+        assert!(keeper
+            .claim_capability(&transfer, &channel_cap, channel_name.clone())
+            .is_err());
+        assert_eq!(
+            keeper.get_capability(&transfer, &channel_name),
+            Some(channel_cap.clone())
+        );
+        let (mut owners, _) = keeper.lookup_modules(&channel_name).unwrap();
+        owners.sort();
+        assert_eq!(owners, vec![ibc.clone(), transfer.clone()]);
+
+        // The capability survives until its last owner releases it.
+        keeper.release_capability(&ibc, &channel_cap).unwrap();
+        assert!(keeper.release_capability(&ibc, &channel_cap).is_err());
+        assert_eq!(keeper.get_capability(&ibc, &channel_name), None);
+        keeper.release_capability(&transfer, &channel_cap).unwrap();
+        assert!(keeper.lookup_modules(&channel_name).is_none());
+        assert!(keeper
+            .claim_capability(&ibc, &channel_cap, channel_name)
+            .is_err());
+
+        // Released capabilities are never handed out again.
+        let new_cap = keeper
+            .new_capability(&ibc, CapabilityName::new("other"))
+            .unwrap();
+        assert!(new_cap.index() > channel_cap.index());
     }
 }
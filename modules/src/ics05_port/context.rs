@@ -1,10 +1,62 @@
-use crate::ics05_port::capabilities::Capability;
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics05_port::error::Error;
 use crate::ics24_host::identifier::PortId;
+use crate::ics26_routing::context::ModuleId;
 
 // A context supplying all the necessary read-only dependencies for processing any information regarding a port.
 pub trait PortReader {
-    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability>;
-    fn authenticate(&self, key: &Capability, port_id: &PortId) -> bool;
+    /// Returns the identifier of the module bound to `port_id`, along with the port capability.
+    fn lookup_module_by_port(&self, port_id: &PortId) -> Result<(ModuleId, Capability), Error>;
+
+    /// Returns the capability which the module `module_id` claimed under `name`, if any.
+    fn get_capability(&self, module_id: &ModuleId, name: &CapabilityName) -> Option<Capability>;
+
+    /// Returns true if the module `module_id` claimed `capability` under `name`.
+    fn authenticate_capability(
+        &self,
+        module_id: &ModuleId,
+        capability: &Capability,
+        name: &CapabilityName,
+    ) -> bool {
+        self.get_capability(module_id, name).as_ref() == Some(capability)
+    }
+
+    /// Returns true if `key` is the capability of the port `port_id`, as claimed by the module
+    /// bound to the port.
+    fn authenticate(&self, key: &Capability, port_id: &PortId) -> bool {
+        match self.lookup_module_by_port(port_id) {
+            Ok((module_id, _)) => {
+                self.authenticate_capability(&module_id, key, &CapabilityName::port(port_id))
+            }
+            Err(_) => false,
+        }
+    }
 }
 
-//  Result<Capability, Error>//return Ok(Capability::new());
+/// A context supplying the write operations on ports and capabilities.
+pub trait PortKeeper {
+    /// Binds the port `port_id` to the module `module_id`, which claims the new port capability.
+    fn bind_port(&mut self, port_id: PortId, module_id: ModuleId) -> Result<Capability, Error>;
+
+    /// Creates a new capability, claimed by `module_id` under `name`.
+    fn new_capability(
+        &mut self,
+        module_id: &ModuleId,
+        name: CapabilityName,
+    ) -> Result<Capability, Error>;
+
+    /// Has the module `module_id` claim the existing `capability` under `name`.
+    fn claim_capability(
+        &mut self,
+        module_id: &ModuleId,
+        capability: &Capability,
+        name: CapabilityName,
+    ) -> Result<(), Error>;
+
+    /// Releases the claims of the module `module_id` on `capability`.
+    fn release_capability(
+        &mut self,
+        module_id: &ModuleId,
+        capability: &Capability,
+    ) -> Result<(), Error>;
+}
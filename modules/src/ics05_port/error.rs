@@ -1,12 +1,28 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics24_host::identifier::PortId;
+use crate::ics26_routing::context::ModuleId;
+
 pub type Error = anomaly::Error<Kind>;
 
 #[derive(Clone, Debug, Error)]
 pub enum Kind {
-    #[error("port unknown")]
-    UnknownPort,
+    #[error("port {0} is unknown")]
+    UnknownPort(PortId),
+
+    #[error("port {0} is already bound")]
+    PortAlreadyBound(PortId),
+
+    #[error("module {0} already owns a capability named {1}")]
+    CapabilityTaken(ModuleId, CapabilityName),
+
+    #[error("{0} does not exist")]
+    UnknownCapability(Capability),
+
+    #[error("module {0} does not own {1}")]
+    CapabilityNotOwned(ModuleId, Capability),
 }
 
 impl Kind {
@@ -0,0 +1,580 @@
+use std::convert::TryFrom;
+
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    ChannelStateData, ClientStateData, ConnectionStateData, ConsensusStateData, DataType,
+    NextSequenceRecvData, PacketAcknowledgementData, PacketCommitmentData,
+    PacketReceiptAbsenceData,
+};
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_def::ClientDef;
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::packet::Sequence;
+use crate::ics06_solomachine::client_state::ClientState;
+use crate::ics06_solomachine::consensus_state::ConsensusState;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics06_solomachine::header::Header;
+use crate::ics06_solomachine::misbehaviour::{Misbehaviour, SignatureAndData};
+use crate::ics06_solomachine::signing::{
+    encode_message, sign_bytes, verify_signature, TimestampedSignature,
+};
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics23_commitment::merkle::apply_prefix;
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::ics24_host::Path;
+use crate::Height;
+
+/// The solo machine client. Instead of Merkle proofs, the proofs verified by this client are
+/// signatures by the solo machine over the expected values, at the current sequence. The
+/// sequence is advanced by header updates and by every verified proof, so that the proofs of a
+/// message are signed at consecutive sequences, starting at the proof height.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoloMachineClient;
+
+impl ClientDef for SoloMachineClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        if client_state.is_frozen() {
+            return Err(Error::from(Kind::ClientFrozen(client_state.frozen_sequence)).into());
+        }
+
+        if header.sequence != client_state.sequence {
+            return Err(Error::from(Kind::SequenceMismatch(
+                client_state.sequence,
+                header.sequence,
+            ))
+            .into());
+        }
+
+        let consensus_state = &client_state.consensus_state;
+        if header.timestamp < consensus_state.timestamp {
+            return Err(Error::from(Kind::TimestampRegression(
+                consensus_state.timestamp,
+                header.timestamp,
+            ))
+            .into());
+        }
+
+        let bytes = sign_bytes(
+            header.sequence,
+            header.timestamp,
+            &consensus_state.diversifier,
+            DataType::Header,
+            header.data(),
+        );
+        verify_signature(&consensus_state.public_key, &bytes, &header.signature)?;
+
+        let new_consensus_state = ConsensusState::new(
+            header.new_public_key,
+            header.new_diversifier,
+            header.timestamp,
+        );
+        let new_client_state = ClientState {
+            sequence: header.sequence + 1,
+            consensus_state: new_consensus_state.clone(),
+            ..client_state
+        };
+
+        Ok((new_client_state, new_consensus_state))
+    }
+
+    /// Checks that the two signatures in `misbehaviour` are valid signatures by the solo machine
+    /// over different data, at the same sequence. If so, returns the client state frozen at
+    /// that sequence.
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        if client_state.is_frozen() {
+            return Err(Error::from(Kind::ClientFrozen(client_state.frozen_sequence)).into());
+        }
+
+        let (one, two) = (&misbehaviour.signature_one, &misbehaviour.signature_two);
+        if one.signature == two.signature {
+            return Err(Error::from(Kind::InvalidMisbehaviour(
+                "identical signatures".to_string(),
+            ))
+            .into());
+        }
+        if one.data_type == two.data_type && one.data == two.data {
+            return Err(Error::from(Kind::InvalidMisbehaviour(
+                "identical signed data".to_string(),
+            ))
+            .into());
+        }
+
+        verify_signature_and_data(&client_state, misbehaviour.sequence, one)?;
+        verify_signature_and_data(&client_state, misbehaviour.sequence, two)?;
+
+        Ok(client_state.with_frozen_sequence(misbehaviour.sequence))
+    }
+
+    fn update_state_after_proof(&self, client_state: Self::ClientState) -> Self::ClientState {
+        ClientState {
+            sequence: client_state.sequence + 1,
+            ..client_state
+        }
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::ClientConsensusState {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let data = encode_message(&ConsensusStateData {
+            path: signed_path(prefix, path)?,
+            consensus_state: Some(expected_consensus_state.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ConsensusState, data)
+    }
+
+    fn verify_connection_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let connection_id = connection_id.ok_or(Kind::MissingConnectionId)?;
+        let path = Path::Connections(connection_id.clone());
+        let data = encode_message(&ConnectionStateData {
+            path: signed_path(prefix, path)?,
+            connection: Some(expected_connection_end.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ConnectionState, data)
+    }
+
+    fn verify_channel_state(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::ChannelEnds(port_id.clone(), channel_id.clone());
+        let data = encode_message(&ChannelStateData {
+            path: signed_path(prefix, path)?,
+            channel: Some(expected_channel_end.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ChannelState, data)
+    }
+
+    fn verify_client_full_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _root: &CommitmentRoot,
+        prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::ClientState(client_id.clone());
+        let data = encode_message(&ClientStateData {
+            path: signed_path(prefix, path)?,
+            client_state: Some(expected_client_state.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ClientState, data)
+    }
+
+    fn verify_packet_data(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::Commitments {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = encode_message(&PacketCommitmentData {
+            path: signed_path(prefix, path)?,
+            commitment,
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketCommitment,
+            data,
+        )
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::Acks {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = encode_message(&PacketAcknowledgementData {
+            path: signed_path(prefix, path)?,
+            acknowledgement: ack,
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketAcknowledgement,
+            data,
+        )
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::SeqRecvs(port_id.clone(), channel_id.clone());
+        let data = encode_message(&NextSequenceRecvData {
+            path: signed_path(prefix, path)?,
+            next_seq_recv: u64::from(*seq),
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::NextSequenceRecv,
+            data,
+        )
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::Receipts {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = encode_message(&PacketReceiptAbsenceData {
+            path: signed_path(prefix, path)?,
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketReceiptAbsence,
+            data,
+        )
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _client_state: &Self::ClientState,
+        _consensus_state: &Self::ConsensusState,
+        _proof_upgrade_client: RawMerkleProof,
+        _proof_upgrade_consensus_state: RawMerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        Err(Error::from(Kind::UpgradeNotSupported).into())
+    }
+}
+
+/// Returns the path signed by the solo machine for the given `path`, i.e., the path prefixed
+/// with `prefix`, formatted as `/{prefix}/{path}`.
+fn signed_path(
+    prefix: &CommitmentPrefix,
+    path: Path,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])?;
+
+    Ok(merkle_path
+        .key_path
+        .iter()
+        .map(|key| format!("/{}", key))
+        .collect::<String>()
+        .into_bytes())
+}
+
+/// Verifies that `proof` is a signature by the solo machine over `data` of the given type, at
+/// the current sequence of the client.
+fn verify_proof(
+    client_state: &ClientState,
+    height: Height,
+    proof: &CommitmentProofBytes,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    client_state.verify_height(height)?;
+
+    let proof = TimestampedSignature::try_from(proof)?;
+    let consensus_state = &client_state.consensus_state;
+    if proof.timestamp < consensus_state.timestamp {
+        return Err(Error::from(Kind::TimestampRegression(
+            consensus_state.timestamp,
+            proof.timestamp,
+        ))
+        .into());
+    }
+
+    let bytes = sign_bytes(
+        client_state.sequence,
+        proof.timestamp,
+        &consensus_state.diversifier,
+        data_type,
+        data,
+    );
+    verify_signature(&consensus_state.public_key, &bytes, &proof.signature_data)?;
+
+    Ok(())
+}
+
+/// Verifies one of the signatures carried by a misbehaviour.
+fn verify_signature_and_data(
+    client_state: &ClientState,
+    sequence: u64,
+    signature: &SignatureAndData,
+) -> Result<(), Error> {
+    let consensus_state = &client_state.consensus_state;
+    if signature.timestamp < consensus_state.timestamp {
+        return Err(
+            Kind::TimestampRegression(consensus_state.timestamp, signature.timestamp).into(),
+        );
+    }
+
+    let bytes = sign_bytes(
+        sequence,
+        signature.timestamp,
+        &consensus_state.diversifier,
+        signature.data_type,
+        signature.data.clone(),
+    );
+
+    verify_signature(&consensus_state.public_key, &bytes, &signature.signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use ibc_proto::ibc::lightclients::solomachine::v1::{ConnectionStateData, DataType};
+
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics03_connection::connection::ConnectionEnd;
+    use crate::ics06_solomachine::client_def::{signed_path, SoloMachineClient};
+    use crate::ics06_solomachine::client_state::test_util::{
+        get_dummy_solomachine_client_state, get_dummy_solomachine_public_key,
+        get_dummy_solomachine_signing_key, sign_dummy,
+    };
+    use crate::ics06_solomachine::client_state::ClientState;
+    use crate::ics06_solomachine::header::Header;
+    use crate::ics06_solomachine::signing::{encode_message, sign_bytes, TimestampedSignature};
+    use crate::ics23_commitment::commitment::{
+        CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+    };
+    use crate::ics24_host::identifier::ConnectionId;
+    use crate::ics24_host::Path;
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    #[test]
+    fn update_rotates_public_key() {
+        let client = SoloMachineClient;
+        let client_state = get_dummy_solomachine_client_state(1);
+
+        let mut header = Header {
+            sequence: 1,
+            timestamp: 10,
+            signature: vec![],
+            new_public_key: get_dummy_solomachine_public_key(2),
+            new_diversifier: "new diversifier".to_string(),
+        };
+        let bytes = sign_bytes(1, 10, "diversifier", DataType::Header, header.data());
+        header.signature = sign_dummy(&get_dummy_solomachine_signing_key(1), &bytes);
+
+        let ctx = MockContext::default();
+        let (new_client_state, new_consensus_state) = client
+            .check_header_and_update_state(
+                &ctx,
+                Default::default(),
+                client_state.clone(),
+                header.clone(),
+            )
+            .unwrap();
+
+        assert_eq!(new_client_state.sequence, 2);
+        assert_eq!(new_consensus_state.public_key, header.new_public_key);
+        assert_eq!(new_consensus_state.diversifier, header.new_diversifier);
+        assert_eq!(new_client_state.consensus_state, new_consensus_state);
+
+        // A header signed with a different key must be rejected.
+        header.signature = sign_dummy(&get_dummy_solomachine_signing_key(2), &bytes);
+        assert!(client
+            .check_header_and_update_state(&ctx, Default::default(), client_state, header)
+            .is_err());
+    }
+
+    #[test]
+    fn verify_connection_state_signature() {
+        let client = SoloMachineClient;
+        let client_state = get_dummy_solomachine_client_state(5);
+        let prefix = CommitmentPrefix::from(b"ibc".to_vec());
+        let connection_id = ConnectionId::new(0);
+        let connection_end = ConnectionEnd::default();
+
+        let data = encode_message(&ConnectionStateData {
+            path: signed_path(&prefix, Path::Connections(connection_id.clone())).unwrap(),
+            connection: Some(connection_end.clone().into()),
+        });
+        let bytes = sign_bytes(5, 10, "diversifier", DataType::ConnectionState, data);
+        let proof: CommitmentProofBytes = TimestampedSignature {
+            signature_data: sign_dummy(&get_dummy_solomachine_signing_key(1), &bytes),
+            timestamp: 10,
+        }
+        .into();
+
+        let ctx = MockContext::default();
+        let verify = |height: Height| {
+            client.verify_connection_state(
+                &ctx,
+                &client_state,
+                height,
+                &prefix,
+                &proof,
+                &CommitmentRoot::default(),
+                Some(&connection_id),
+                &connection_end,
+            )
+        };
+
+        assert!(verify(Height::new(0, 5)).is_ok());
+        // The proof height must not be past the sequence of the client.
+        assert!(verify(Height::new(0, 6)).is_err());
+    }
+
+    #[test]
+    fn proofs_are_signed_at_consecutive_sequences() {
+        let client = SoloMachineClient;
+        let prefix = CommitmentPrefix::from(b"ibc".to_vec());
+        let connection_id = ConnectionId::new(0);
+        let connection_end = ConnectionEnd::default();
+
+        let data = encode_message(&ConnectionStateData {
+            path: signed_path(&prefix, Path::Connections(connection_id.clone())).unwrap(),
+            connection: Some(connection_end.clone().into()),
+        });
+        let proof_at = |sequence: u64| -> CommitmentProofBytes {
+            let bytes = sign_bytes(
+                sequence,
+                10,
+                "diversifier",
+                DataType::ConnectionState,
+                data.clone(),
+            );
+            TimestampedSignature {
+                signature_data: sign_dummy(&get_dummy_solomachine_signing_key(1), &bytes),
+                timestamp: 10,
+            }
+            .into()
+        };
+
+        let ctx = MockContext::default();
+        let verify = |client_state: &ClientState, proof: &CommitmentProofBytes| {
+            client.verify_connection_state(
+                &ctx,
+                client_state,
+                Height::new(0, 5),
+                &prefix,
+                proof,
+                &CommitmentRoot::default(),
+                Some(&connection_id),
+                &connection_end,
+            )
+        };
+
+        // The first proof of a message is signed at the proof height, the next one at the
+        // following sequence.
+        let client_state = get_dummy_solomachine_client_state(5);
+        assert!(verify(&client_state, &proof_at(5)).is_ok());
+
+        let client_state = client.update_state_after_proof(client_state);
+        assert_eq!(client_state.sequence, 6);
+        assert!(verify(&client_state, &proof_at(6)).is_ok());
+        // A proof cannot be replayed once the client verified it.
+        assert!(verify(&client_state, &proof_at(5)).is_err());
+    }
+}
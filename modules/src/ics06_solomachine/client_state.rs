@@ -0,0 +1,171 @@
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v1::ClientState as RawClientState;
+
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics06_solomachine::consensus_state::ConsensusState;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics24_host::identifier::ChainId;
+use crate::Height;
+
+/// The client state of a solo machine. The `sequence` is incremented with every header update;
+/// it plays the role of the height of this client (with revision number zero).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    pub sequence: u64,
+    pub frozen_sequence: u64,
+    pub consensus_state: ConsensusState,
+    pub allow_update_after_proposal: bool,
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl ClientState {
+    pub fn new(sequence: u64, consensus_state: ConsensusState) -> Self {
+        Self {
+            sequence,
+            frozen_sequence: 0,
+            consensus_state,
+            allow_update_after_proposal: false,
+        }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_sequence != 0
+    }
+
+    /// Returns a copy of this client state, frozen at the given `sequence`.
+    pub fn with_frozen_sequence(self, sequence: u64) -> Self {
+        Self {
+            frozen_sequence: sequence,
+            ..self
+        }
+    }
+
+    /// Verify that the client is not frozen and that proofs computed at `height` can be verified
+    /// by this client, i.e., that `height` is not past the current sequence. The proofs of a
+    /// message are all computed at the sequence of its first proof, while the sequence of the
+    /// client advances with every proof verified.
+    pub fn verify_height(&self, height: Height) -> Result<(), Error> {
+        if self.is_frozen() {
+            return Err(Kind::ClientFrozen(self.frozen_sequence).into());
+        }
+
+        if height.revision_height > self.sequence {
+            return Err(Kind::SequenceMismatch(self.sequence, height.revision_height).into());
+        }
+
+        Ok(())
+    }
+}
+
+impl crate::ics02_client::client_state::ClientState for ClientState {
+    fn chain_id(&self) -> ChainId {
+        // Solo machines have no chain identifier, the diversifier is the closest equivalent.
+        ChainId::from_str(&self.consensus_state.diversifier).unwrap()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.is_frozen()
+    }
+
+    fn wrap_any(self) -> AnyClientState {
+        AnyClientState::SoloMachine(self)
+    }
+}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: raw.sequence,
+            frozen_sequence: raw.frozen_sequence,
+            consensus_state: raw
+                .consensus_state
+                .ok_or_else(|| Kind::InvalidRawClientState.context("missing consensus state"))?
+                .try_into()?,
+            allow_update_after_proposal: raw.allow_update_after_proposal,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            sequence: value.sequence,
+            frozen_sequence: value.frozen_sequence,
+            consensus_state: Some(value.consensus_state.into()),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mocks"))]
+pub mod test_util {
+    use k256::ecdsa::signature::Signer;
+    use k256::ecdsa::{Signature, SigningKey};
+
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::{Single, Sum};
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data;
+    use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+
+    use crate::ics06_solomachine::client_state::ClientState;
+    use crate::ics06_solomachine::consensus_state::{ConsensusState, PublicKey};
+
+    /// Returns a deterministic signing key for solo machine tests, derived from `seed`.
+    pub fn get_dummy_solomachine_signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed.max(1); 32]).unwrap()
+    }
+
+    pub fn get_dummy_solomachine_public_key(seed: u8) -> PublicKey {
+        let signing_key = get_dummy_solomachine_signing_key(seed);
+        PublicKey::from_bytes(&signing_key.verifying_key().to_bytes()).unwrap()
+    }
+
+    /// Returns a solo machine client state at `sequence`, whose public key is the one for the
+    /// signing key returned by `get_dummy_solomachine_signing_key(1)`.
+    pub fn get_dummy_solomachine_client_state(sequence: u64) -> ClientState {
+        ClientState::new(
+            sequence,
+            ConsensusState::new(
+                get_dummy_solomachine_public_key(1),
+                "diversifier".to_string(),
+                1,
+            ),
+        )
+    }
+
+    /// Signs `sign_bytes` with the given key, and encodes the signature as the solo machine
+    /// expects it, i.e., as a single-signature `SignatureDescriptor.Data`.
+    pub fn sign_dummy(signing_key: &SigningKey, sign_bytes: &[u8]) -> Vec<u8> {
+        let signature: Signature = signing_key.sign(sign_bytes);
+        let data = Data {
+            sum: Some(Sum::Single(Single {
+                mode: SignMode::Unspecified as i32,
+                signature: signature.as_ref().to_vec(),
+            })),
+        };
+
+        let mut buf = Vec::new();
+        prost::Message::encode(&data, &mut buf).unwrap();
+        buf
+    }
+}
@@ -0,0 +1,157 @@
+use std::convert::{TryFrom, TryInto};
+
+use k256::ecdsa::signature::Verifier;
+use k256::ecdsa::{Signature, VerifyingKey};
+use prost_types::Any;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v1::ConsensusState as RawConsensusState;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics23_commitment::commitment::CommitmentRoot;
+
+pub const SECP256K1_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// Protobuf encoding of a Cosmos SDK secp256k1 public key (`cosmos.crypto.secp256k1.PubKey`),
+/// which is not part of the IBC proto definitions.
+#[derive(Clone, PartialEq, prost::Message)]
+struct RawSecp256k1PubKey {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+}
+
+/// The public key of a solo machine, i.e., a compressed secp256k1 public key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey {
+    bytes: Vec<u8>,
+}
+
+impl PublicKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        VerifyingKey::from_sec1_bytes(bytes)
+            .map_err(|e| Kind::InvalidPublicKey.context(e.to_string()))?;
+
+        Ok(Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Verifies that `signature` is a valid signature of `msg` by this key. The signature is
+    /// expected in its 64-byte compact (`r || s`) form, as produced by the Cosmos SDK.
+    pub fn verify(&self, msg: &[u8], signature: &[u8]) -> Result<(), Error> {
+        let key = VerifyingKey::from_sec1_bytes(&self.bytes)
+            .map_err(|e| Kind::InvalidPublicKey.context(e.to_string()))?;
+        let signature = Signature::try_from(signature)
+            .map_err(|e| Kind::InvalidSignatureData.context(e.to_string()))?;
+
+        key.verify(msg, &signature)
+            .map_err(|_| Kind::SignatureVerificationFailure.into())
+    }
+}
+
+impl TryFrom<Any> for PublicKey {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        if raw.type_url != SECP256K1_PUB_KEY_TYPE_URL {
+            return Err(Kind::UnsupportedPublicKey(raw.type_url).into());
+        }
+
+        let raw_key: RawSecp256k1PubKey = prost::Message::decode(raw.value.as_ref())
+            .map_err(|e| Kind::InvalidPublicKey.context(e))?;
+
+        PublicKey::from_bytes(&raw_key.key)
+    }
+}
+
+impl From<PublicKey> for Any {
+    fn from(value: PublicKey) -> Self {
+        let mut buf = Vec::new();
+        prost::Message::encode(&RawSecp256k1PubKey { key: value.bytes }, &mut buf).unwrap();
+
+        Any {
+            type_url: SECP256K1_PUB_KEY_TYPE_URL.to_string(),
+            value: buf,
+        }
+    }
+}
+
+/// The consensus state of a solo machine: the public key currently used for signing, the
+/// diversifier and the timestamp of the latest update.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusState {
+    pub public_key: PublicKey,
+    pub diversifier: String,
+    pub timestamp: u64,
+
+    // Solo machines do not commit to a state root, hence this is always empty.
+    #[serde(skip)]
+    root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: u64) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            timestamp,
+            root: CommitmentRoot::default(),
+        }
+    }
+}
+
+impl crate::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn validate_basic(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.timestamp == 0 {
+            return Err(Error::from(
+                Kind::InvalidRawConsensusState.context("timestamp cannot be zero"),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::SoloMachine(self)
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        let public_key = raw
+            .public_key
+            .ok_or_else(|| Kind::InvalidRawConsensusState.context("missing public key"))?
+            .try_into()?;
+
+        Ok(Self::new(public_key, raw.diversifier, raw.timestamp))
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            public_key: Some(value.public_key.into()),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error)]
+pub enum Kind {
+    #[error("invalid raw client state")]
+    InvalidRawClientState,
+
+    #[error("invalid raw client consensus state")]
+    InvalidRawConsensusState,
+
+    #[error("invalid raw header")]
+    InvalidRawHeader,
+
+    #[error("invalid raw misbehaviour")]
+    InvalidRawMisbehaviour,
+
+    #[error("unsupported public key type: {0}")]
+    UnsupportedPublicKey(String),
+
+    #[error("invalid public key")]
+    InvalidPublicKey,
+
+    #[error("invalid signature data")]
+    InvalidSignatureData,
+
+    #[error("signature verification failed")]
+    SignatureVerificationFailure,
+
+    #[error("the client is frozen at sequence {0}")]
+    ClientFrozen(u64),
+
+    #[error("sequence mismatch: the client is at sequence {0}, got {1}")]
+    SequenceMismatch(u64, u64),
+
+    #[error("timestamp {1} is earlier than the consensus state timestamp {0}")]
+    TimestampRegression(u64, u64),
+
+    #[error("invalid commitment proof bytes")]
+    InvalidCommitmentProof,
+
+    #[error("missing the counterparty connection identifier")]
+    MissingConnectionId,
+
+    #[error("invalid misbehaviour: {0}")]
+    InvalidMisbehaviour(String),
+
+    #[error("solo machine clients cannot be upgraded")]
+    UpgradeNotSupported,
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
@@ -0,0 +1,97 @@
+use std::convert::{TryFrom, TryInto};
+
+use serde_derive::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v1::{Header as RawHeader, HeaderData};
+
+use crate::ics02_client::client_type::ClientType;
+use crate::ics02_client::header::AnyHeader;
+use crate::ics06_solomachine::consensus_state::PublicKey;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics06_solomachine::signing::encode_message;
+use crate::Height;
+
+/// Solo machine header, used to rotate the public key and diversifier of the solo machine.
+/// The header is signed with the current public key, over the new key and diversifier.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Header {
+    pub sequence: u64,
+    pub timestamp: u64,
+    pub signature: Vec<u8>,
+    pub new_public_key: PublicKey,
+    pub new_diversifier: String,
+}
+
+impl Header {
+    pub fn height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+
+    /// The protobuf encoding of the data signed by this header.
+    pub fn data(&self) -> Vec<u8> {
+        encode_message(&HeaderData {
+            new_pub_key: Some(self.new_public_key.clone().into()),
+            new_diversifier: self.new_diversifier.clone(),
+        })
+    }
+}
+
+impl crate::ics02_client::header::Header for Header {
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn height(&self) -> Height {
+        self.height()
+    }
+
+    fn wrap_any(self) -> AnyHeader {
+        AnyHeader::SoloMachine(self)
+    }
+}
+
+impl Protobuf<RawHeader> for Header {}
+
+impl TryFrom<RawHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+        if raw.sequence == 0 {
+            return Err(Kind::InvalidRawHeader
+                .context("sequence cannot be zero")
+                .into());
+        }
+        if raw.timestamp == 0 {
+            return Err(Kind::InvalidRawHeader
+                .context("timestamp cannot be zero")
+                .into());
+        }
+        if raw.signature.is_empty() {
+            return Err(Kind::InvalidRawHeader.context("missing signature").into());
+        }
+
+        Ok(Self {
+            sequence: raw.sequence,
+            timestamp: raw.timestamp,
+            signature: raw.signature,
+            new_public_key: raw
+                .new_public_key
+                .ok_or_else(|| Kind::InvalidRawHeader.context("missing new public key"))?
+                .try_into()?,
+            new_diversifier: raw.new_diversifier,
+        })
+    }
+}
+
+impl From<Header> for RawHeader {
+    fn from(value: Header) -> Self {
+        RawHeader {
+            sequence: value.sequence,
+            timestamp: value.timestamp,
+            signature: value.signature,
+            new_public_key: Some(value.new_public_key.into()),
+            new_diversifier: value.new_diversifier,
+        }
+    }
+}
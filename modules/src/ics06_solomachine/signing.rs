@@ -0,0 +1,96 @@
+//! Construction of the bytes signed by a solo machine, and verification of its signatures.
+//!
+//! A solo machine signs a protobuf-encoded `SignBytes` message, which carries the sequence,
+//! timestamp and diversifier at the time of signing, along with the signed data. The data is
+//! itself a protobuf-encoded message whose type depends on what is being signed (e.g., a header
+//! or a connection state), as indicated by the `DataType`.
+
+use std::convert::TryFrom;
+
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::Sum;
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data as RawSignatureData;
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    DataType, SignBytes, TimestampedSignatureData,
+};
+
+use crate::ics06_solomachine::consensus_state::PublicKey;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics23_commitment::commitment::CommitmentProofBytes;
+
+/// Returns the protobuf encoding of `message`.
+pub fn encode_message<M: prost::Message>(message: &M) -> Vec<u8> {
+    let mut buf = Vec::new();
+    prost::Message::encode(message, &mut buf).unwrap();
+    buf
+}
+
+/// Builds the bytes that the solo machine signs for `data` of the given type.
+pub fn sign_bytes(
+    sequence: u64,
+    timestamp: u64,
+    diversifier: &str,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Vec<u8> {
+    encode_message(&SignBytes {
+        sequence,
+        timestamp,
+        diversifier: diversifier.to_string(),
+        data_type: data_type as i32,
+        data,
+    })
+}
+
+/// Verifies that `signature_data`, a protobuf-encoded `SignatureDescriptor.Data`, holds a valid
+/// signature of `sign_bytes` by `public_key`. Only single signatures are supported.
+pub fn verify_signature(
+    public_key: &PublicKey,
+    sign_bytes: &[u8],
+    signature_data: &[u8],
+) -> Result<(), Error> {
+    let data: RawSignatureData = prost::Message::decode(signature_data)
+        .map_err(|e| Kind::InvalidSignatureData.context(e))?;
+
+    match data.sum {
+        Some(Sum::Single(single)) => public_key.verify(sign_bytes, &single.signature),
+        Some(Sum::Multi(_)) => Err(Kind::InvalidSignatureData
+            .context("multi-signatures are not supported")
+            .into()),
+        None => Err(Kind::InvalidSignatureData
+            .context("missing signature")
+            .into()),
+    }
+}
+
+/// A proof produced by a solo machine: a signature along with the timestamp at which it was
+/// produced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimestampedSignature {
+    pub signature_data: Vec<u8>,
+    pub timestamp: u64,
+}
+
+impl TryFrom<&CommitmentProofBytes> for TimestampedSignature {
+    type Error = Error;
+
+    fn try_from(proof: &CommitmentProofBytes) -> Result<Self, Self::Error> {
+        let bytes: Vec<u8> = proof.clone().into();
+        let raw: TimestampedSignatureData = prost::Message::decode(bytes.as_ref())
+            .map_err(|e| Kind::InvalidCommitmentProof.context(e))?;
+
+        Ok(Self {
+            signature_data: raw.signature_data,
+            timestamp: raw.timestamp,
+        })
+    }
+}
+
+impl From<TimestampedSignature> for CommitmentProofBytes {
+    fn from(value: TimestampedSignature) -> Self {
+        encode_message(&TimestampedSignatureData {
+            signature_data: value.signature_data,
+            timestamp: value.timestamp,
+        })
+        .into()
+    }
+}
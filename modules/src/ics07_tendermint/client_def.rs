@@ -1,17 +1,34 @@
-use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Utc};
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use tendermint::Time;
+use tendermint_light_client::operations::commit_validator::{CommitValidator, ProdCommitValidator};
+use tendermint_light_client::operations::voting_power::{
+    ProdVotingPowerCalculator, VotingPowerCalculator,
+};
+use tendermint_proto::Protobuf;
 
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_def::ClientDef;
 use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::packet::Sequence;
 use crate::ics07_tendermint::client_state::ClientState;
 use crate::ics07_tendermint::consensus_state::ConsensusState;
+use crate::ics07_tendermint::error::{Error, Kind};
 use crate::ics07_tendermint::header::Header;
+use crate::ics07_tendermint::misbehaviour::Misbehaviour;
 use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics23_commitment::merkle::{apply_prefix, MerkleProof};
 use crate::ics24_host::identifier::ConnectionId;
 use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
+use crate::ics24_host::Path;
+use crate::timestamp::{Expiry, Timestamp};
 use crate::Height;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -21,9 +38,12 @@ impl ClientDef for TendermintClient {
     type Header = Header;
     type ClientState = ClientState;
     type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
 
     fn check_header_and_update_state(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
@@ -34,7 +54,58 @@ impl ClientDef for TendermintClient {
             );
         }
 
-        // TODO: Additional verifications should be implemented here.
+        if !client_state.frozen_height.is_zero() {
+            return Err(Error::from(Kind::ClientFrozen(
+                client_state.frozen_height,
+                header.height(),
+            ))
+            .into());
+        }
+
+        // The header must have been produced by the chain the client is tracking.
+        let chain_id = header.signed_header.header.chain_id.as_str();
+        if chain_id != client_state.chain_id.as_str() {
+            return Err(Error::from(Kind::HeaderChainIdMismatch(
+                client_state.chain_id.to_string(),
+                chain_id.to_string(),
+            ))
+            .into());
+        }
+
+        // The header must belong to the revision the client is currently tracking.
+        if header.height().revision_number != client_state.chain_id.version() {
+            return Err(Error::from(Kind::MismatchedRevisions(
+                client_state.chain_id.version(),
+                header.height().revision_number,
+            ))
+            .into());
+        }
+
+        if header.trusted_height >= header.height() {
+            return Err(Error::from(Kind::LowUpdateHeight(
+                header.height(),
+                header.trusted_height,
+            ))
+            .into());
+        }
+
+        let trusted_consensus_state = match ctx.consensus_state(&client_id, header.trusted_height) {
+            Some(AnyConsensusState::Tendermint(cs)) => cs,
+            _ => {
+                return Err(
+                    Error::from(Kind::MissingTrustedConsensusState(header.trusted_height)).into(),
+                )
+            }
+        };
+
+        check_validator_sets(&header, &trusted_consensus_state)?;
+        check_timestamps(
+            &client_state,
+            &header,
+            &trusted_consensus_state,
+            ctx.host_timestamp(),
+        )?;
+        check_voting_power(&client_state, &header, &trusted_consensus_state)?;
 
         Ok((
             client_state.with_header(header.clone()),
@@ -42,114 +113,487 @@ impl ClientDef for TendermintClient {
         ))
     }
 
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        if !client_state.frozen_height.is_zero() {
+            return Err(Error::from(Kind::ClientFrozen(
+                client_state.frozen_height,
+                misbehaviour.header1.height(),
+            ))
+            .into());
+        }
+
+        let trusted_consensus_state =
+            |header: &Header| match ctx.consensus_state(&client_id, header.trusted_height) {
+                Some(AnyConsensusState::Tendermint(cs)) => Ok(cs),
+                _ => Err(Error::from(Kind::MissingTrustedConsensusState(
+                    header.trusted_height,
+                ))),
+            };
+        let trusted_consensus_state1 = trusted_consensus_state(&misbehaviour.header1)?;
+        let trusted_consensus_state2 = trusted_consensus_state(&misbehaviour.header2)?;
+
+        let frozen_height = verify_misbehaviour(
+            &client_state,
+            &misbehaviour,
+            &trusted_consensus_state1,
+            &trusted_consensus_state2,
+            ctx.host_timestamp(),
+        )?;
+        Ok(client_state.with_frozen_height(frozen_height))
+    }
+
     fn verify_client_consensus_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _client_id: &ClientId,
-        _consensus_height: Height,
-        _expected_consensus_state: &AnyConsensusState,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        client_state.verify_height(height)?;
+
+        let path = Path::ClientConsensusState {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let value = expected_consensus_state
+            .encode_vec()
+            .map_err(|e| Kind::ValueEncodingFailure.context(e))?;
+
+        verify_membership(client_state, prefix, proof, root, path, value)
     }
 
     fn verify_connection_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _connection_id: Option<&ConnectionId>,
-        _expected_connection_end: &ConnectionEnd,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        client_state.verify_height(height)?;
+
+        let connection_id = connection_id.ok_or(Kind::MissingConnectionId)?;
+        let path = Path::Connections(connection_id.clone());
+        let value = expected_connection_end
+            .encode_vec()
+            .map_err(|e| Kind::ValueEncodingFailure.context(e))?;
+
+        verify_membership(client_state, prefix, proof, root, path, value)
     }
 
     fn verify_channel_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _expected_channel_end: &ChannelEnd,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        client_state.verify_height(height)?;
+
+        let path = Path::ChannelEnds(port_id.clone(), channel_id.clone());
+        let value = expected_channel_end
+            .encode_vec()
+            .map_err(|e| Kind::ValueEncodingFailure.context(e))?;
+
+        verify_membership(client_state, prefix, proof, root, path, value)
     }
 
     fn verify_client_full_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _root: &CommitmentRoot,
-        _prefix: &CommitmentPrefix,
-        _client_id: &ClientId,
-        _proof: &CommitmentProofBytes,
-        _expected_client_state: &AnyClientState,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        root: &CommitmentRoot,
+        prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        unimplemented!()
+        client_state.verify_height(height)?;
+
+        let path = Path::ClientState(client_id.clone());
+        let value = expected_client_state
+            .encode_vec()
+            .map_err(|e| Kind::ValueEncodingFailure.context(e))?;
+
+        verify_membership(client_state, prefix, proof, root, path, value)
     }
 
     fn verify_packet_data(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-        _data: String,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        client_state.verify_height(height)?;
+
+        let path = Path::Commitments {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_membership(client_state, prefix, proof, root, path, commitment)
     }
 
     fn verify_packet_acknowledgement(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-        _data: Vec<u8>,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        client_state.verify_height(height)?;
+
+        let path = Path::Acks {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_membership(
+            client_state,
+            prefix,
+            proof,
+            root,
+            path,
+            ctx.ack_commitment(&ack),
+        )
     }
 
     fn verify_next_sequence_recv(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        client_state.verify_height(height)?;
+
+        let path = Path::SeqRecvs(port_id.clone(), channel_id.clone());
+        // The next sequence is stored as a big-endian encoded unsigned integer.
+        let value = u64::from(*seq).to_be_bytes().to_vec();
+
+        verify_membership(client_state, prefix, proof, root, path, value)
     }
 
     fn verify_packet_receipt_absence(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        client_state.verify_height(height)?;
+
+        let path = Path::Receipts {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_non_membership(client_state, prefix, proof, root, path)
     }
 
     fn verify_upgrade_and_update_state(
         &self,
         _client_state: &Self::ClientState,
         _consensus_state: &Self::ConsensusState,
-        _proof_upgrade_client: MerkleProof,
-        _proof_upgrade_consensus_state: MerkleProof,
+        _proof_upgrade_client: RawMerkleProof,
+        _proof_upgrade_consensus_state: RawMerkleProof,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
         todo!()
     }
 }
+
+/// Checks that the validator sets carried by the header match the hashes they commit to: the
+/// untrusted validator set must hash to the `validators_hash` of the signed header, and the
+/// trusted validator set must hash to the `next_validators_hash` of the trusted consensus state.
+fn check_validator_sets(
+    header: &Header,
+    trusted_consensus_state: &ConsensusState,
+) -> Result<(), Error> {
+    let validators_hash = header.validator_set.hash();
+    if validators_hash != header.signed_header.header.validators_hash {
+        return Err(Kind::InvalidValidatorSet(
+            header.signed_header.header.validators_hash,
+            validators_hash,
+        )
+        .into());
+    }
+
+    let trusted_validators_hash = header.trusted_validator_set.hash();
+    if trusted_validators_hash != trusted_consensus_state.next_validators_hash {
+        return Err(Kind::InvalidValidatorSet(
+            trusted_consensus_state.next_validators_hash,
+            trusted_validators_hash,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Verifies that `misbehaviour` is valid evidence against a client in state `client_state`:
+/// both headers must be for the chain tracked by the client, they must either be a fork (same
+/// height, different blocks) or violate the monotonicity of BFT time, and each of them must be
+/// trusted from the consensus state at its trusted height (`trusted_consensus_state1` and
+/// `trusted_consensus_state2`, respectively) at time `now`. Returns the height at which the
+/// client is to be frozen.
+pub fn verify_misbehaviour(
+    client_state: &ClientState,
+    misbehaviour: &Misbehaviour,
+    trusted_consensus_state1: &ConsensusState,
+    trusted_consensus_state2: &ConsensusState,
+    now: Timestamp,
+) -> Result<Height, Error> {
+    let (header1, header2) = (&misbehaviour.header1, &misbehaviour.header2);
+
+    for header in [header1, header2].iter() {
+        let chain_id = header.signed_header.header.chain_id.as_str();
+        if chain_id != client_state.chain_id.as_str() {
+            return Err(Kind::MisbehaviourChainIdMismatch(
+                client_state.chain_id.to_string(),
+                chain_id.to_string(),
+            )
+            .into());
+        }
+    }
+
+    if header1.compatible_with(header2) {
+        return Err(Kind::MisbehaviourHeadersNotConflicting.into());
+    }
+
+    check_misbehaviour_header(client_state, header1, trusted_consensus_state1, now)?;
+    check_misbehaviour_header(client_state, header2, trusted_consensus_state2, now)?;
+
+    Ok(std::cmp::min(header1.height(), header2.height()))
+}
+
+/// Checks that a header of a misbehaviour could have updated the client from the trusted
+/// consensus state at its trusted height. Unlike for client updates, the header may be older than
+/// the latest client height, and it need not be later than the host time.
+fn check_misbehaviour_header(
+    client_state: &ClientState,
+    header: &Header,
+    trusted_consensus_state: &ConsensusState,
+    now: Timestamp,
+) -> Result<(), Error> {
+    if header.trusted_height.revision_number != header.height().revision_number {
+        return Err(Kind::MismatchedRevisions(
+            header.trusted_height.revision_number,
+            header.height().revision_number,
+        )
+        .into());
+    }
+
+    if header.trusted_height >= header.height() {
+        return Err(Kind::LowUpdateHeight(header.height(), header.trusted_height).into());
+    }
+
+    check_validator_sets(header, trusted_consensus_state)?;
+    check_trusting_period(client_state, trusted_consensus_state, now)?;
+    check_commit(header)?;
+
+    ProdVotingPowerCalculator::default()
+        .check_enough_trust(
+            &header.signed_header,
+            &header.trusted_validator_set,
+            client_state.trust_level,
+        )
+        .map_err(|e| Kind::InsufficientVotingPower.context(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Checks that the trusted consensus state is still within the trusting period at time `now`.
+fn check_trusting_period(
+    client_state: &ClientState,
+    trusted_consensus_state: &ConsensusState,
+    now: Timestamp,
+) -> Result<(), Error> {
+    let trusted_timestamp = to_timestamp(trusted_consensus_state.timestamp);
+
+    let elapsed = now
+        .duration_since(&trusted_timestamp)
+        .ok_or(Kind::InvalidHostTimestamp(now, trusted_timestamp))?;
+    if client_state.expired(elapsed) {
+        return Err(Kind::TrustedStateExpired(elapsed, client_state.trusting_period).into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the trusted consensus state is still within the trusting period, that the header
+/// is not too far in the future with respect to the host clock (allowing for `max_clock_drift`),
+/// and that the header timestamp is later than the trusted one.
+fn check_timestamps(
+    client_state: &ClientState,
+    header: &Header,
+    trusted_consensus_state: &ConsensusState,
+    now: Timestamp,
+) -> Result<(), Error> {
+    check_trusting_period(client_state, trusted_consensus_state, now)?;
+
+    let trusted_timestamp = to_timestamp(trusted_consensus_state.timestamp);
+    let header_timestamp = to_timestamp(header.signed_header.header.time);
+
+    let drift_limit = (now + client_state.max_clock_drift)
+        .map_err(|e| Kind::HeaderFromFuture(header_timestamp, now).context(e))?;
+    if header_timestamp.check_expiry(&drift_limit) != Expiry::NotExpired {
+        return Err(Kind::HeaderFromFuture(header_timestamp, now).into());
+    }
+
+    if header_timestamp.check_expiry(&trusted_timestamp) != Expiry::Expired {
+        return Err(Kind::NonMonotonicTimestamp(header_timestamp, trusted_timestamp).into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the signed header matches the block its commit signs, and that the commit is
+/// valid for the header's validator set.
+fn check_commit(header: &Header) -> Result<(), Error> {
+    let signed_header = &header.signed_header;
+
+    if signed_header.header.hash() != signed_header.commit.block_id.hash {
+        return Err(Kind::HeaderCommitMismatch.into());
+    }
+
+    ProdCommitValidator::default()
+        .validate(signed_header, &header.validator_set)
+        .map_err(|e| Kind::InvalidCommit.context(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Checks the commit of the signed header against its validator set, then checks that enough
+/// voting power from the trusted validator set signed the header. For an adjacent header the
+/// validator set must be exactly the one the trusted state committed to, otherwise the trusted
+/// validators must account for at least the client's trust level of the voting power.
+fn check_voting_power(
+    client_state: &ClientState,
+    header: &Header,
+    trusted_consensus_state: &ConsensusState,
+) -> Result<(), Error> {
+    check_commit(header)?;
+
+    let signed_header = &header.signed_header;
+    let calculator = ProdVotingPowerCalculator::default();
+
+    if header.trusted_height.increment() == header.height() {
+        if signed_header.header.validators_hash != trusted_consensus_state.next_validators_hash {
+            return Err(Kind::InvalidValidatorSet(
+                trusted_consensus_state.next_validators_hash,
+                signed_header.header.validators_hash,
+            )
+            .into());
+        }
+    } else {
+        calculator
+            .check_enough_trust(
+                signed_header,
+                &header.trusted_validator_set,
+                client_state.trust_level,
+            )
+            .map_err(|e| Kind::InsufficientVotingPower.context(e.to_string()))?;
+    }
+
+    calculator
+        .check_signers_overlap(signed_header, &header.validator_set)
+        .map_err(|e| Kind::InsufficientVotingPower.context(e.to_string()))?;
+
+    Ok(())
+}
+
+fn to_timestamp(time: Time) -> Timestamp {
+    let date: DateTime<Utc> = time.into();
+    Timestamp::from_datetime(date)
+}
+
+/// Decodes the `proof` bytes into a `MerkleProof`.
+fn merkle_proof(proof: &CommitmentProofBytes) -> Result<MerkleProof, Error> {
+    MerkleProof::try_from(proof.clone()).map_err(|e| Kind::InvalidCommitmentProof.context(e).into())
+}
+
+/// Verifies that `value` is committed under `path` (prefixed with `prefix`) in the store whose
+/// root is `root`, against the proof specifications of the client state.
+fn verify_membership(
+    client_state: &ClientState,
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+    value: Vec<u8>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])?;
+    let merkle_proof = merkle_proof(proof)?;
+
+    merkle_proof
+        .verify_membership(&client_state.proof_specs, root, &merkle_path, value, 0)
+        .map_err(|e| Error::from(Kind::ProofVerificationFailure.context(e)))?;
+
+    Ok(())
+}
+
+/// Verifies that no value is committed under `path` (prefixed with `prefix`) in the store whose
+/// root is `root`, against the proof specifications of the client state.
+fn verify_non_membership(
+    client_state: &ClientState,
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])?;
+    let merkle_proof = merkle_proof(proof)?;
+
+    merkle_proof
+        .verify_non_membership(&client_state.proof_specs, root, &merkle_path)
+        .map_err(|e| Error::from(Kind::ProofVerificationFailure.context(e)))?;
+
+    Ok(())
+}
@@ -28,7 +28,9 @@ pub struct ClientState {
     pub max_clock_drift: Duration,
     pub frozen_height: Height,
     pub latest_height: Height,
-    // pub proof_specs: ::std::vec::Vec<super::super::super::super::ics23::ProofSpec>,
+    /// The specifications of the proofs of the chain's store, against which membership and
+    /// non-membership proofs are verified
+    pub proof_specs: ProofSpecs,
     pub upgrade_path: Vec<String>,
     pub allow_update: AllowUpdate,
 }
@@ -92,6 +94,8 @@ impl ClientState {
             max_clock_drift,
             frozen_height,
             latest_height,
+            // The client states created by the relayer are for Cosmos-SDK chains
+            proof_specs: ProofSpecs::cosmos(),
             upgrade_path,
             allow_update,
         })
@@ -111,6 +115,14 @@ impl ClientState {
         }
     }
 
+    /// Returns a copy of this client state, frozen at the given `height`.
+    pub fn with_frozen_height(self, height: Height) -> Self {
+        ClientState {
+            frozen_height: height,
+            ..self
+        }
+    }
+
     /// Helper function to verify the upgrade client procedure.
     /// Resets all fields except the blockchain-specific ones.
     pub fn zero_custom_fields(mut client_state: Self) -> Self {
@@ -136,6 +148,20 @@ impl ClientState {
     pub fn expired(&self, elapsed: Duration) -> bool {
         elapsed > self.trusting_period
     }
+
+    /// Verify that the client is at a sufficient height and not frozen at the given height,
+    /// i.e., that proofs computed at `height` can be verified by this client.
+    pub fn verify_height(&self, height: Height) -> Result<(), Error> {
+        if self.latest_height < height {
+            return Err(Kind::InsufficientHeight(self.latest_height, height).into());
+        }
+
+        if !self.frozen_height.is_zero() && self.frozen_height <= height {
+            return Err(Kind::ClientFrozen(self.frozen_height, height).into());
+        }
+
+        Ok(())
+    }
 }
 
 impl crate::ics02_client::client_state::ClientState for ClientState {
@@ -202,6 +228,8 @@ impl TryFrom<RawClientState> for ClientState {
                 .ok_or_else(|| Kind::InvalidRawClientState.context("missing frozen height"))?
                 .try_into()
                 .map_err(|_| Kind::InvalidRawHeight)?,
+            proof_specs: ProofSpecs::try_from(raw.proof_specs)
+                .map_err(|e| Kind::InvalidRawClientState.context(e))?,
             upgrade_path: raw.upgrade_path,
             allow_update: AllowUpdate {
                 after_expiry: raw.allow_update_after_expiry,
@@ -224,7 +252,7 @@ impl From<ClientState> for RawClientState {
             max_clock_drift: Some(value.max_clock_drift.into()),
             frozen_height: Some(value.frozen_height.into()),
             latest_height: Some(value.latest_height.into()),
-            proof_specs: ProofSpecs::cosmos().into(),
+            proof_specs: value.proof_specs.into(),
             allow_update_after_expiry: false,
             allow_update_after_misbehaviour: false,
             upgrade_path: value.upgrade_path,
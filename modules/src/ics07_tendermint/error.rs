@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use anomaly::{BoxError, Context};
+use tendermint::Hash;
 use thiserror::Error;
 
 use crate::ics24_host::error::ValidationKind;
+use crate::timestamp::Timestamp;
+use crate::Height;
 
 pub type Error = anomaly::Error<Kind>;
 
@@ -39,6 +44,68 @@ pub enum Kind {
 
     #[error("invalid raw misbehaviour")]
     InvalidRawMisbehaviour,
+
+    #[error("insufficient client height: latest height {0} is lower than the proof height {1}")]
+    InsufficientHeight(Height, Height),
+
+    #[error("the client is frozen at height {0}, cannot verify proofs at height {1}")]
+    ClientFrozen(Height, Height),
+
+    #[error("invalid commitment proof bytes")]
+    InvalidCommitmentProof,
+
+    #[error("missing the counterparty connection identifier")]
+    MissingConnectionId,
+
+    #[error("failed to encode the expected value")]
+    ValueEncodingFailure,
+
+    #[error("failed to verify the commitment proof")]
+    ProofVerificationFailure,
+
+    #[error("the header's revision number {1} does not match the client's revision number {0}")]
+    MismatchedRevisions(u64, u64),
+
+    #[error("the header is for chain {1}, but the client tracks chain {0}")]
+    HeaderChainIdMismatch(String, String),
+
+    #[error("the header height {0} must be higher than its trusted height {1}")]
+    LowUpdateHeight(Height, Height),
+
+    #[error("no consensus state found for the trusted height {0}")]
+    MissingTrustedConsensusState(Height),
+
+    #[error("the host timestamp {0} is not set or is earlier than the trusted timestamp {1}")]
+    InvalidHostTimestamp(Timestamp, Timestamp),
+
+    #[error("the trusted consensus state has expired: {0:?} elapsed, more than the trusting period {1:?}")]
+    TrustedStateExpired(Duration, Duration),
+
+    #[error(
+        "the header timestamp {0} is too far in the future compared to the host timestamp {1}"
+    )]
+    HeaderFromFuture(Timestamp, Timestamp),
+
+    #[error("the header timestamp {0} is not later than the trusted timestamp {1}")]
+    NonMonotonicTimestamp(Timestamp, Timestamp),
+
+    #[error("validator set hash mismatch: expected {0}, got {1}")]
+    InvalidValidatorSet(Hash, Hash),
+
+    #[error("the header does not match the block hash signed by its commit")]
+    HeaderCommitMismatch,
+
+    #[error("invalid commit in the signed header")]
+    InvalidCommit,
+
+    #[error("insufficient voting power in the commit")]
+    InsufficientVotingPower,
+
+    #[error("the misbehaviour headers do not conflict: no fork nor BFT time violation")]
+    MisbehaviourHeadersNotConflicting,
+
+    #[error("the misbehaviour header is for chain {1}, but the client tracks chain {0}")]
+    MisbehaviourChainIdMismatch(String, String),
 }
 
 impl Kind {
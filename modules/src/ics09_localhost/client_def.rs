@@ -0,0 +1,311 @@
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_def::ClientDef;
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
+use crate::ics02_client::misbehaviour::AnyMisbehaviour;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::packet::Sequence;
+use crate::ics09_localhost::client_state::ClientState;
+use crate::ics09_localhost::consensus_state::ConsensusState;
+use crate::ics09_localhost::error::{Error, Kind};
+use crate::ics09_localhost::header::Header;
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::Height;
+
+/// The localhost (loopback) client. Both ends of a connection using this client live on the
+/// host chain, so instead of verifying proofs, the client checks the expected values directly
+/// against the host store, which it reads through the context passed to each verification method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalhostClient;
+
+impl ClientDef for LocalhostClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    // The host chain cannot submit evidence against itself, so there is no localhost
+    // misbehaviour type.
+    type Misbehaviour = AnyMisbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        let host_height = ctx.host_height();
+        if header.height > host_height {
+            return Err(Error::from(Kind::HeaderFromFuture(host_height, header.height)).into());
+        }
+
+        if header.height <= client_state.latest_height() {
+            return Err(Error::from(Kind::LowUpdateHeight(
+                client_state.latest_height(),
+                header.height,
+            ))
+            .into());
+        }
+
+        Ok((
+            client_state.with_height(header.height),
+            ConsensusState::default(),
+        ))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _client_state: Self::ClientState,
+        _misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Err(Error::from(Kind::MisbehaviourNotSupported).into())
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        _consensus_height: Height,
+        _expected_consensus_state: &AnyConsensusState,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // The counterparty client is itself a localhost client, which trusts the host chain
+        // directly and stores no consensus data to compare against.
+        match ctx.client_state(client_id) {
+            Some(AnyClientState::Localhost(_)) => Ok(()),
+            _ => Err(Kind::NotLocalhostClient(client_id.clone()).into()),
+        }
+    }
+
+    fn verify_connection_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let connection_id = connection_id.ok_or(Kind::MissingConnectionId)?;
+
+        match ctx.connection_end(connection_id) {
+            Some(connection_end) if connection_end == *expected_connection_end => Ok(()),
+            _ => Err(Kind::ConnectionStateMismatch(connection_id.clone()).into()),
+        }
+    }
+
+    fn verify_channel_state(
+        &self,
+        ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match ctx.channel_end(&(port_id.clone(), channel_id.clone())) {
+            Some(channel_end) if channel_end == *expected_channel_end => Ok(()),
+            _ => Err(Kind::ChannelStateMismatch(port_id.clone(), channel_id.clone()).into()),
+        }
+    }
+
+    fn verify_client_full_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _root: &CommitmentRoot,
+        _prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        _proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match ctx.client_state(client_id) {
+            Some(client_state) if client_state == *expected_client_state => Ok(()),
+            _ => Err(Kind::ClientStateMismatch(client_id.clone()).into()),
+        }
+    }
+
+    fn verify_packet_data(
+        &self,
+        ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let key = (port_id.clone(), channel_id.clone(), *seq);
+
+        match ctx.get_packet_commitment(&key) {
+            Some(stored) if stored == commitment => Ok(()),
+            _ => Err(Kind::PacketCommitmentMismatch(*seq).into()),
+        }
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let key = (port_id.clone(), channel_id.clone(), *seq);
+        // The host stores the commitments to the acknowledgements.
+        let ack_commitment = ctx.ack_commitment(&ack);
+
+        match ctx.get_packet_acknowledgement(&key) {
+            Some(stored) if stored == ack_commitment => Ok(()),
+            _ => Err(Kind::PacketAcknowledgementMismatch(*seq).into()),
+        }
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match ctx.get_next_sequence_recv(&(port_id.clone(), channel_id.clone())) {
+            Some(next_seq_recv) if next_seq_recv == *seq => Ok(()),
+            _ => Err(Kind::NextSequenceRecvMismatch(*seq).into()),
+        }
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let key = (port_id.clone(), channel_id.clone(), *seq);
+
+        match ctx.get_packet_receipt(&key) {
+            None => Ok(()),
+            Some(_) => Err(Kind::PacketReceiptExists(*seq).into()),
+        }
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _client_state: &Self::ClientState,
+        _consensus_state: &Self::ConsensusState,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        Err(Error::from(Kind::UpgradeNotSupported).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics03_connection::connection::ConnectionEnd;
+    use crate::ics09_localhost::client_def::LocalhostClient;
+    use crate::ics09_localhost::client_state::ClientState;
+    use crate::ics09_localhost::header::Header;
+    use crate::ics23_commitment::commitment::{
+        CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+    };
+    use crate::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
+    use crate::mock::context::MockContext;
+    use crate::mock::host::HostType;
+    use crate::Height;
+
+    #[test]
+    fn update_tracks_host_height() {
+        let chain_id = ChainId::new("localchain".to_string(), 1);
+        let ctx = MockContext::new(chain_id.clone(), HostType::Mock, 5, Height::new(1, 10));
+        let client_state = ClientState::new(chain_id, Height::new(1, 5));
+
+        let update = |height: Height| {
+            LocalhostClient.check_header_and_update_state(
+                &ctx,
+                ClientId::default(),
+                client_state.clone(),
+                Header::new(height),
+            )
+        };
+
+        let (new_state, _) = update(Height::new(1, 10)).unwrap();
+        assert_eq!(new_state.latest_height(), Height::new(1, 10));
+
+        // The client cannot go backwards, nor beyond the host height.
+        assert!(update(Height::new(1, 4)).is_err());
+        assert!(update(Height::new(1, 11)).is_err());
+    }
+
+    #[test]
+    fn verify_connection_state_against_host_store() {
+        let connection_id = ConnectionId::new(0);
+        let connection_end = ConnectionEnd::default();
+        let ctx = MockContext::default()
+            .with_client_parametrized(
+                &ClientId::default(),
+                Height::new(0, 5),
+                Some(ClientType::Localhost),
+                None,
+            )
+            .with_connection(connection_id.clone(), connection_end.clone());
+
+        let client_state =
+            ClientState::new(ChainId::new("mockgaia".to_string(), 0), Height::new(0, 5));
+        let verify = |connection_id: &ConnectionId| {
+            LocalhostClient.verify_connection_state(
+                &ctx,
+                &client_state,
+                Height::new(0, 5),
+                &CommitmentPrefix::from(vec![]),
+                &CommitmentProofBytes::from(vec![]),
+                &CommitmentRoot::default(),
+                Some(connection_id),
+                &connection_end,
+            )
+        };
+
+        assert!(verify(&connection_id).is_ok());
+        // A connection that is not in the host store cannot be verified.
+        assert!(verify(&ConnectionId::new(1)).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics23_commitment::commitment::CommitmentRoot;
+
+/// The consensus state of a localhost client. It carries no data: the localhost client verifies
+/// against the store of the host chain, rather than against a commitment root, and it uses no
+/// timestamp (hence packets sent over localhost channels can only time out on height).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ConsensusState {
+    #[serde(skip)]
+    root: CommitmentRoot,
+}
+
+impl crate::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn validate_basic(&self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::Localhost(self)
+    }
+}
@@ -0,0 +1,62 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+use crate::ics04_channel::packet::Sequence;
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::Height;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error)]
+pub enum Kind {
+    #[error("invalid raw client state")]
+    InvalidRawClientState,
+
+    #[error("invalid raw header")]
+    InvalidRawHeader,
+
+    #[error("header at height {1} is ahead of the host chain height {0}")]
+    HeaderFromFuture(Height, Height),
+
+    #[error("header height {1} must be higher than the client height {0}")]
+    LowUpdateHeight(Height, Height),
+
+    #[error("localhost clients cannot be upgraded")]
+    UpgradeNotSupported,
+
+    #[error("localhost clients cannot be frozen for misbehaviour")]
+    MisbehaviourNotSupported,
+
+    #[error("missing the counterparty connection identifier")]
+    MissingConnectionId,
+
+    #[error("the counterparty client {0} is not a localhost client")]
+    NotLocalhostClient(ClientId),
+
+    #[error("client state of {0} does not match the one in the host store")]
+    ClientStateMismatch(ClientId),
+
+    #[error("connection end {0} does not match the one in the host store")]
+    ConnectionStateMismatch(ConnectionId),
+
+    #[error("channel end {0}/{1} does not match the one in the host store")]
+    ChannelStateMismatch(PortId, ChannelId),
+
+    #[error("packet commitment for sequence {0} does not match the one in the host store")]
+    PacketCommitmentMismatch(Sequence),
+
+    #[error("packet acknowledgement for sequence {0} does not match the one in the host store")]
+    PacketAcknowledgementMismatch(Sequence),
+
+    #[error("next sequence to receive {0} does not match the one in the host store")]
+    NextSequenceRecvMismatch(Sequence),
+
+    #[error("a receipt for packet sequence {0} exists in the host store")]
+    PacketReceiptExists(Sequence),
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
@@ -51,14 +51,17 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::ics02_client::client_type::ClientType;
-    use crate::ics02_client::header::Header;
+    use crate::ics02_client::header::{AnyHeader, Header};
     use crate::ics18_relayer::context::Ics18Context;
     use crate::ics18_relayer::utils::build_client_update_datagram;
     use crate::ics24_host::identifier::{ChainId, ClientId};
     use crate::ics26_routing::msgs::Ics26Envelope;
     use crate::mock::context::MockContext;
     use crate::mock::host::HostType;
+    use crate::timestamp::Timestamp;
     use crate::Height;
     use test_env_log::test;
 
@@ -76,6 +79,12 @@ mod tests {
         let client_on_a_for_b = ClientId::new(ClientType::Tendermint, 0).unwrap();
         let client_on_b_for_a = ClientId::new(ClientType::Mock, 0).unwrap();
 
+        // The synthetic Tendermint blocks of chain B have their time set to `height` seconds after
+        // the Unix epoch. The clock of chain A must be ahead of all of them, so that the Tendermint
+        // client on A does not reject B's headers as coming from the future.
+        let chain_a_timestamp =
+            Timestamp::from_nanoseconds(Duration::from_secs(100).as_nanos() as u64).unwrap();
+
         // Create two mock contexts, one for each chain.
         let mut ctx_a = MockContext::new(
             ChainId::new("mockgaiaA".to_string(), 1),
@@ -83,6 +92,7 @@ mod tests {
             5,
             chain_a_start_height,
         )
+        .with_timestamp(chain_a_timestamp)
         .with_client_parametrized(
             &client_on_a_for_b,
             client_on_a_for_b_height,
@@ -149,9 +159,19 @@ mod tests {
                 .latest_height();
             assert_eq!(client_height_b, ctx_a.query_latest_height());
 
-            // Update client on chain B to latest height of B.
-            // - create the client update message with the latest header from B
-            let b_latest_header = ctx_b.query_latest_header().unwrap();
+            // Update client on chain A to latest height of B.
+            // - create the client update message with the latest header from B, trusting the
+            // consensus state that the client on A holds for its latest height
+            let b_latest_header = match ctx_b.query_latest_header().unwrap() {
+                AnyHeader::Tendermint(mut header) => {
+                    header.trusted_height = ctx_a
+                        .query_client_full_state(&client_on_a_for_b)
+                        .unwrap()
+                        .latest_height();
+                    AnyHeader::Tendermint(header)
+                }
+                header => header,
+            };
             assert_eq!(
                 b_latest_header.client_type(),
                 ClientType::Tendermint,
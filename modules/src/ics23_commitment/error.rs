@@ -8,4 +8,34 @@ pub enum Error {
 
     #[error("failed to decode commitment proof")]
     CommitmentProofDecodingFailed(DecodeError),
+
+    #[error("empty commitment prefix")]
+    EmptyCommitmentPrefix,
+
+    #[error("empty merkle proof")]
+    EmptyMerkleProof,
+
+    #[error("empty merkle root")]
+    EmptyMerkleRoot,
+
+    #[error("empty verified value")]
+    EmptyVerifiedValue,
+
+    #[error("mismatch between the number of proofs ({0}) and the number of proof specs ({1})")]
+    NumberOfSpecsMismatch(usize, usize),
+
+    #[error("mismatch between the number of proofs ({0}) and the number of keys ({1})")]
+    NumberOfKeysMismatch(usize, usize),
+
+    #[error("invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[error("failed to verify membership or non-membership of key {0}")]
+    VerificationFailure(String),
+
+    #[error("empty proof specs")]
+    EmptyProofSpecs,
+
+    #[error("failed to decode proof spec")]
+    ProofSpecDecodingFailed(DecodeError),
 }
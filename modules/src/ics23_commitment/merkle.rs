@@ -1,17 +1,25 @@
-use tendermint::merkle::proof::Proof;
+use std::convert::TryFrom;
+
+use ics23::commitment_proof::Proof;
+use ics23::{
+    calculate_existence_root, verify_membership, verify_non_membership, CommitmentProof,
+    NonExistenceProof,
+};
+use tendermint::merkle::proof::Proof as TendermintProof;
 
 use ibc_proto::ibc::core::commitment::v1::MerklePath;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
 
-use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
 use crate::ics23_commitment::error::Error;
+use crate::ics23_commitment::specs::ProofSpecs;
 
 pub fn apply_prefix(
     prefix: &CommitmentPrefix,
     mut path: Vec<String>,
 ) -> Result<MerklePath, Box<dyn std::error::Error>> {
     if prefix.is_empty() {
-        return Err("empty prefix".into());
+        return Err(Error::EmptyCommitmentPrefix.into());
     }
 
     let mut result: Vec<String> = vec![format!("{:?}", prefix)];
@@ -20,9 +28,12 @@ pub fn apply_prefix(
     Ok(MerklePath { key_path: result })
 }
 
+/// Domain type for a merkle proof, i.e., a list of ICS23 commitment proofs ordered from the
+/// lowest subtree (e.g., the IAVL store of a module) up to the root (e.g., the multistore of
+/// the application).
 #[derive(Clone, Debug, PartialEq)]
 pub struct MerkleProof {
-    pub proof: Vec<CommitmentProofBytes>,
+    pub proofs: Vec<CommitmentProof>,
 }
 
 // Merkle Proof serialization notes:
@@ -30,54 +41,204 @@ pub struct MerkleProof {
 // - TmProof: in tendermint-rs/src/merkle/proof.rs:Proof
 // - RawProofOps: in tendermint-proto/tendermint.cyrpto.rs:ProofOps
 // - RawMerkleProof: in ibc-proto/ibc.core.commitment.v1.rs:MerkleProof
-//     - structure that includes a RawProofOps in its only `proof` field.
+//     - structure that includes a vector of `ibc_proto::ics23::CommitmentProof`
 //         #[derive(Clone, PartialEq, ::prost::Message)]
 //         pub struct MerkleProof {
-//             #[prost(message, optional, tag="1")]
-//             pub proof: ::std::option::Option<::tendermint_proto::crypto::ProofOps>,
+//             #[prost(message, repeated, tag="1")]
+//             pub proofs: ::std::vec::Vec<super::super::super::super::ics23::CommitmentProof>,
 //         }
 //  - Vec<u8>: RawMerkleProof is not explicitly used but, serialized as Vec<u8>, it is
 //       included in all handshake messages that require proofs (i.e. all except the two `OpenInit`),
 //       and also in all queries that require proofs
-//  - MerkleProof: Domain type for RawMerkleProof, currently not used and identical to RawMerkleProof.
-//       This will change with verification implementation.
+//  - MerkleProof: Domain type for RawMerkleProof, holding the `ics23::CommitmentProof` type
+//       from the `ics23` crate, which implements the actual verification.
 //  - CommitmentProof: Defined in ibc-rs as Vec<u8> and currently used in all its messages
 //
 // Here are a couple of flows that illustrate the different conversions:
 // IBC Messages and Handlers: sink happens in the handle verification
-//    Vec<u8> -> CommitmentProof -> RawMerkleProof -> MerkleProof
+//    Vec<u8> -> CommitmentProof -> RawMerkleProof -> MerkleProof -> verify()
 //
 // Relayer: from the proof in the  query response to the proof being included in a message
-//    TmProof -> RawProofOps => RawMerkleProof -> MerkleProof -> verify()
-//      -> MerkleProof -> RawMerkleProof -> CommitmentProof -> Vec<u8>
-// Note: current implementation for ^ is simplified since verification is not yet implemented:
 //    TmProof -> RawProofOps => RawMerkleProof -> CommitmentProof -> Vec<u8>
 //
 // Implementations of (de)serializers and conversions:
 //  - commitment.rs:
 //      Vec<u8> <-> CommitmentProof
 //      CommitmentProof <-> RawMerkleProof
+//      MerkleProof -> CommitmentProof
 //  - merkle.rs:
 //      RawMerkleProof <-> MerkleProof
 //  - tendermint-rs/src/merkle/proof.rs:
 //      TmProof <-> RawProofOps
 //  - cosmos.rs:abci_query() converts from query proof to Merkle proof:
 //      RawProofOps => RawMerkleProof
-//
-// impl TryFrom<RawMerkleProof> for MerkleProof {
-//     type Error = Error;
-//     fn try_from(value: RawMerkleProof) -> Result<Self, Self::Error> {
-//         Ok(MerkleProof { proof: value.proofs.into_iter().map(|v| v.into()).collect() })
-//     }
-// }
-//
-// impl From<MerkleProof> for RawMerkleProof {
-//     fn from(value: MerkleProof) -> Self {
-//         RawMerkleProof { proof: value.proof }
-//     }
-// }
 
-pub fn convert_tm_to_ics_merkle_proof(tm_proof: &Proof) -> Result<RawMerkleProof, Error> {
+impl TryFrom<RawMerkleProof> for MerkleProof {
+    type Error = Error;
+
+    fn try_from(value: RawMerkleProof) -> Result<Self, Self::Error> {
+        // The `ics23` and `ibc_proto::ics23` commitment proofs are generated from the same
+        // protobuf definitions, hence we convert between them by encoding, then decoding.
+        let proofs = value
+            .proofs
+            .into_iter()
+            .map(|raw| {
+                let mut encoded = Vec::new();
+                prost::Message::encode(&raw, &mut encoded).unwrap();
+                prost::Message::decode(&*encoded).map_err(Error::CommitmentProofDecodingFailed)
+            })
+            .collect::<Result<Vec<CommitmentProof>, _>>()?;
+
+        Ok(MerkleProof { proofs })
+    }
+}
+
+impl TryFrom<CommitmentProofBytes> for MerkleProof {
+    type Error = Error;
+
+    fn try_from(value: CommitmentProofBytes) -> Result<Self, Self::Error> {
+        let raw = RawMerkleProof::try_from(value)?;
+        MerkleProof::try_from(raw)
+    }
+}
+
+impl From<MerkleProof> for RawMerkleProof {
+    fn from(value: MerkleProof) -> Self {
+        // Safety note: the source and target data structures are identical, hence the
+        // encode/decode conversion here should be infallible.
+        let proofs = value
+            .proofs
+            .iter()
+            .map(|proof| {
+                let mut encoded = Vec::new();
+                prost::Message::encode(proof, &mut encoded).unwrap();
+                prost::Message::decode(&*encoded).unwrap()
+            })
+            .collect();
+
+        RawMerkleProof { proofs }
+    }
+}
+
+impl MerkleProof {
+    /// Verifies that `value` is stored under the given `keys` in the tree committed to by `root`.
+    ///
+    /// The keys are ordered from root to leaf (as returned by `apply_prefix`), whereas the
+    /// proofs are ordered from leaf to root. Proofs at positions lower than `start_index` are
+    /// skipped; this is used to chain a non-membership proof for the lowest subtree with
+    /// membership proofs for the subtrees above it.
+    pub fn verify_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: &CommitmentRoot,
+        keys: &MerklePath,
+        value: Vec<u8>,
+        start_index: usize,
+    ) -> Result<(), Error> {
+        self.validate_arguments(specs, root, keys)?;
+        if value.is_empty() {
+            return Err(Error::EmptyVerifiedValue);
+        }
+
+        let mut subroot = value.clone();
+        let mut value = value;
+
+        for ((proof, spec), key) in self
+            .proofs
+            .iter()
+            .zip(specs.iter())
+            .zip(keys.key_path.iter().rev())
+            .skip(start_index)
+        {
+            match &proof.proof {
+                Some(Proof::Exist(existence_proof)) => {
+                    subroot = calculate_existence_root(existence_proof)
+                        .map_err(|_| Error::InvalidMerkleProof)?;
+
+                    if !verify_membership(proof, spec, &subroot, key.as_bytes(), &value) {
+                        return Err(Error::VerificationFailure(key.clone()));
+                    }
+
+                    value = subroot.clone();
+                }
+                _ => return Err(Error::InvalidMerkleProof),
+            }
+        }
+
+        if root.as_bytes() != subroot.as_slice() {
+            return Err(Error::VerificationFailure(keys.key_path.join("/")));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that no value is stored under the given `keys` in the tree committed to by `root`.
+    pub fn verify_non_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: &CommitmentRoot,
+        keys: &MerklePath,
+    ) -> Result<(), Error> {
+        self.validate_arguments(specs, root, keys)?;
+
+        // Verify the absence of the key in the lowest subtree.
+        let proof = &self.proofs[0];
+        let spec = specs.iter().next().ok_or(Error::InvalidMerkleProof)?;
+        let key = keys.key_path.last().ok_or(Error::InvalidMerkleProof)?;
+
+        match &proof.proof {
+            Some(Proof::Nonexist(non_existence_proof)) => {
+                let subroot = calculate_non_existence_root(non_existence_proof)?;
+
+                if !verify_non_membership(proof, spec, &subroot, key.as_bytes()) {
+                    return Err(Error::VerificationFailure(key.clone()));
+                }
+
+                // Verify the membership of the lowest subtree root in the remaining subtrees.
+                self.verify_membership(specs, root, keys, subroot, 1)
+            }
+            _ => Err(Error::InvalidMerkleProof),
+        }
+    }
+
+    fn validate_arguments(
+        &self,
+        specs: &ProofSpecs,
+        root: &CommitmentRoot,
+        keys: &MerklePath,
+    ) -> Result<(), Error> {
+        if self.proofs.is_empty() {
+            return Err(Error::EmptyMerkleProof);
+        }
+        if root.as_bytes().is_empty() {
+            return Err(Error::EmptyMerkleRoot);
+        }
+
+        let num = self.proofs.len();
+        if specs.len() != num {
+            return Err(Error::NumberOfSpecsMismatch(num, specs.len()));
+        }
+        if keys.key_path.len() != num {
+            return Err(Error::NumberOfKeysMismatch(num, keys.key_path.len()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Computes the root of the tree from a non-existence proof, using whichever of the left or
+/// right neighbours is present.
+fn calculate_non_existence_root(proof: &NonExistenceProof) -> Result<Vec<u8>, Error> {
+    let neighbour = proof
+        .left
+        .as_ref()
+        .or_else(|| proof.right.as_ref())
+        .ok_or(Error::InvalidMerkleProof)?;
+
+    calculate_existence_root(neighbour).map_err(|_| Error::InvalidMerkleProof)
+}
+
+pub fn convert_tm_to_ics_merkle_proof(tm_proof: &TendermintProof) -> Result<RawMerkleProof, Error> {
     let mut proofs = vec![];
 
     for op in &tm_proof.ops {
@@ -90,3 +251,142 @@ pub fn convert_tm_to_ics_merkle_proof(tm_proof: &Proof) -> Result<RawMerkleProof
 
     Ok(RawMerkleProof { proofs })
 }
+
+#[cfg(test)]
+mod tests {
+    use ics23::commitment_proof::Proof;
+    use ics23::{
+        calculate_existence_root, tendermint_spec, CommitmentProof, ExistenceProof,
+        NonExistenceProof,
+    };
+    use test_env_log::test;
+
+    use ibc_proto::ibc::core::commitment::v1::MerklePath;
+
+    use crate::ics23_commitment::commitment::CommitmentRoot;
+    use crate::ics23_commitment::specs::ProofSpecs;
+
+    use super::MerkleProof;
+
+    const STORE_KEY: &str = "ibc";
+    const KEY: &str = "commitments/ports/transfer/channels/channel-0/sequences/2";
+    const VALUE: &[u8] = b"commitment";
+
+    fn existence_proof(key: &str, value: &[u8]) -> ExistenceProof {
+        ExistenceProof {
+            key: key.as_bytes().to_vec(),
+            value: value.to_vec(),
+            leaf: tendermint_spec().leaf_spec,
+            path: vec![],
+        }
+    }
+
+    fn specs() -> ProofSpecs {
+        vec![tendermint_spec(), tendermint_spec()].into()
+    }
+
+    fn path(key: &str) -> MerklePath {
+        MerklePath {
+            key_path: vec![STORE_KEY.to_string(), key.to_string()],
+        }
+    }
+
+    /// Returns a proof that `VALUE` is stored under `KEY` in a store committed to under
+    /// `STORE_KEY` in the root store, and a proof that `absent_key` is not stored, along with the
+    /// root of the root store.
+    fn proofs(absent_key: &str) -> (MerkleProof, MerkleProof, CommitmentRoot) {
+        let store_proof = existence_proof(KEY, VALUE);
+        let store_root = calculate_existence_root(&store_proof).unwrap();
+        let root_proof = existence_proof(STORE_KEY, &store_root);
+        let root = calculate_existence_root(&root_proof).unwrap();
+
+        let root_proof = CommitmentProof {
+            proof: Some(Proof::Exist(root_proof)),
+        };
+        let membership = MerkleProof {
+            proofs: vec![
+                CommitmentProof {
+                    proof: Some(Proof::Exist(store_proof.clone())),
+                },
+                root_proof.clone(),
+            ],
+        };
+        let non_membership = MerkleProof {
+            proofs: vec![
+                CommitmentProof {
+                    proof: Some(Proof::Nonexist(NonExistenceProof {
+                        key: absent_key.as_bytes().to_vec(),
+                        left: None,
+                        right: Some(store_proof),
+                    })),
+                },
+                root_proof,
+            ],
+        };
+
+        (
+            membership,
+            non_membership,
+            CommitmentRoot::from_bytes(&root),
+        )
+    }
+
+    #[test]
+    fn verify_membership() {
+        let (proof, _, root) = proofs("");
+
+        assert!(proof
+            .verify_membership(&specs(), &root, &path(KEY), VALUE.to_vec(), 0)
+            .is_ok());
+
+        // Another value, key or root does not verify
+        assert!(proof
+            .verify_membership(&specs(), &root, &path(KEY), b"other".to_vec(), 0)
+            .is_err());
+        assert!(proof
+            .verify_membership(&specs(), &root, &path("other"), VALUE.to_vec(), 0)
+            .is_err());
+        let other_root = CommitmentRoot::from_bytes(&[1; 32]);
+        assert!(proof
+            .verify_membership(&specs(), &other_root, &path(KEY), VALUE.to_vec(), 0)
+            .is_err());
+
+        // Proofs which do not follow the specifications do not verify
+        let mut other_spec = tendermint_spec();
+        other_spec.leaf_spec.as_mut().unwrap().prefix = vec![1];
+        let other_specs: ProofSpecs = vec![other_spec, tendermint_spec()].into();
+        assert!(proof
+            .verify_membership(&other_specs, &root, &path(KEY), VALUE.to_vec(), 0)
+            .is_err());
+        let single_spec: ProofSpecs = vec![tendermint_spec()].into();
+        assert!(proof
+            .verify_membership(&single_spec, &root, &path(KEY), VALUE.to_vec(), 0)
+            .is_err());
+    }
+
+    #[test]
+    fn verify_non_membership() {
+        // The absent key sorts before `KEY`, the right neighbour in the proof
+        let absent_key = "commitments/ports/transfer/channels/channel-0/sequences/1";
+        let (membership, proof, root) = proofs(absent_key);
+
+        assert!(proof
+            .verify_non_membership(&specs(), &root, &path(absent_key))
+            .is_ok());
+
+        // A key which sorts after its right neighbour may be present
+        let (_, proof, root) = proofs("commitments/ports/transfer/channels/channel-0/sequences/3");
+        assert!(proof
+            .verify_non_membership(
+                &specs(),
+                &root,
+                &path("commitments/ports/transfer/channels/channel-0/sequences/3")
+            )
+            .is_err());
+
+        // A membership proof does not prove non-membership
+        assert!(membership
+            .verify_non_membership(&specs(), &root, &path(KEY))
+            .is_err());
+    }
+}
@@ -1,7 +1,14 @@
+use std::convert::TryFrom;
+
 use ics23::ProofSpec;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use subtle_encoding::{Encoding, Hex};
 
 use ibc_proto::ics23::ProofSpec as ProtoProofSpec;
 
+use crate::ics23_commitment::error::Error;
+
 /// An array of proof specifications.
 ///
 /// This type encapsulates different types of proof specifications, mostly predefined, e.g., for
@@ -9,6 +16,7 @@ use ibc_proto::ics23::ProofSpec as ProtoProofSpec;
 /// Additionally, this type also aids in the conversion from `ProofSpec` types from crate `ics23`
 /// into proof specifications as represented in the `ibc_proto` type; see the
 /// `From` trait(s) below.
+#[derive(Clone, Debug, PartialEq)]
 pub struct ProofSpecs {
     specs: Vec<ProofSpec>,
 }
@@ -23,6 +31,91 @@ impl ProofSpecs {
             ],
         }
     }
+
+    /// Returns an iterator over the proof specifications, ordered from the lowest subtree to the
+    /// root, i.e., matching the order of the proofs in a `MerkleProof`.
+    pub fn iter(&self) -> impl Iterator<Item = &ProofSpec> {
+        self.specs.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.specs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.specs.is_empty()
+    }
+}
+
+// The `ics23` proof specifications hold no floating point values.
+impl Eq for ProofSpecs {}
+
+impl From<Vec<ProofSpec>> for ProofSpecs {
+    fn from(specs: Vec<ProofSpec>) -> Self {
+        Self { specs }
+    }
+}
+
+/// Converts from the proto type to the domain type, by encoding then decoding each specification,
+/// as both types are generated from the same protobuf definitions.
+impl TryFrom<Vec<ProtoProofSpec>> for ProofSpecs {
+    type Error = Error;
+
+    fn try_from(raw_specs: Vec<ProtoProofSpec>) -> Result<Self, Self::Error> {
+        if raw_specs.is_empty() {
+            return Err(Error::EmptyProofSpecs);
+        }
+
+        let specs = raw_specs
+            .iter()
+            .map(|raw| {
+                let mut encoded = Vec::new();
+                prost::Message::encode(raw, &mut encoded).unwrap();
+                prost::Message::decode(&*encoded).map_err(Error::ProofSpecDecodingFailed)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { specs })
+    }
+}
+
+/// Serializes the proof specifications as the hex-encoded protobuf encodings of each of them.
+impl Serialize for ProofSpecs {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let encoded: Vec<String> = self
+            .specs
+            .iter()
+            .map(|spec| {
+                let mut encoded = Vec::new();
+                prost::Message::encode(spec, &mut encoded).unwrap();
+                Hex::lower_case().encode_to_string(encoded).unwrap()
+            })
+            .collect();
+
+        encoded.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ProofSpecs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let specs = Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|encoded| {
+                let bytes = Hex::lower_case()
+                    .decode(encoded)
+                    .map_err(D::Error::custom)?;
+                prost::Message::decode(&*bytes).map_err(D::Error::custom)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { specs })
+    }
 }
 
 /// Converts from the domain type (which is represented as a vector of `ics23::ProofSpec`
@@ -1,8 +1,16 @@
-use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use std::any::Any;
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::handler::HandlerOutputBuilder;
 use crate::ics02_client::context::{ClientKeeper, ClientReader};
 use crate::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
+use crate::ics04_channel::channel::{Counterparty, Order};
 use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
-use crate::ics05_port::context::PortReader;
+use crate::ics04_channel::error::Error;
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::context::{PortKeeper, PortReader};
+use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::signer::Signer;
 
 /// This trait captures all the functional dependencies (i.e., context) which the ICS26 module
 /// requires to be able to dispatch and process IBC messages. In other words, this is the
@@ -15,7 +23,171 @@ pub trait Ics26Context:
     + ChannelKeeper
     + ChannelReader
     + PortReader
-    + Ics20Context
+    + PortKeeper
     + Clone
 {
+    type Router: Router;
+
+    /// The router to the application modules bound to the ports of this chain.
+    fn router(&self) -> &Self::Router;
+
+    fn router_mut(&mut self) -> &mut Self::Router;
+}
+
+/// Identifier of an application module, under which the module is registered with the `Router`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModuleId(String);
+
+impl ModuleId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for ModuleId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Output of the module callbacks. Modules use it to emit their own events and logs, which are
+/// appended to the output of the ICS4 handler that triggered the callback.
+pub type ModuleOutput = HandlerOutputBuilder<()>;
+
+/// The ICS26 callbacks that an IBC application module implements to be notified of the channel
+/// handshake and packet lifecycle events on the ports it is bound to. See:
+/// <https://github.com/cosmos/ibc/tree/master/spec/core/ics-026-routing-module>
+///
+/// The callbacks run once the corresponding ICS4 handler validated the message; an error returned
+/// by a callback aborts the processing of the whole message. By default, the callbacks accept the
+/// channel handshake steps, and ignore packets.
+#[dyn_clonable::clonable]
+pub trait Module: Clone + Debug + Send + Sync + AsAny {
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &str,
+        _counterparty_version: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_chan_open_ack(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_chan_open_confirm(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_chan_close_init(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_chan_close_confirm(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Processes a received packet. Returns the acknowledgement to write for this packet, if the
+    /// module acknowledges it synchronously.
+    fn on_recv_packet(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        Ok(None)
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _packet: &Packet,
+        _acknowledgement: &[u8],
+        _relayer: &Signer,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        _output: &mut ModuleOutput,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Gives access to a module as `Any`, so that the host can downcast the modules registered with
+/// its router back to their concrete type, e.g. to inspect their state.
+pub trait AsAny: Any {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<M: Any> AsAny for M {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Routes the ICS26 callbacks to the application modules, by module identifier. The module bound
+/// to a given port is found through `PortReader::lookup_module_by_port`.
+pub trait Router {
+    /// Returns a mutable reference to the module registered under `module_id`, if any.
+    fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module>;
+
+    /// Returns true if a module is registered under `module_id`.
+    fn has_route(&self, module_id: &ModuleId) -> bool;
 }
@@ -11,6 +11,9 @@ pub enum Kind {
     #[error("error raised by the keeper functionality in message handler")]
     KeeperRaisedError,
 
+    #[error("error raised by the application module callback")]
+    ModuleCallbackFailed,
+
     #[error("unknown type URL {0}")]
     UnknownMessageTypeUrl(String),
 
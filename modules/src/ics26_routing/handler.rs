@@ -1,15 +1,20 @@
 use prost_types::Any;
 use tendermint_proto::Protobuf;
 
-use crate::application::ics20_fungible_token_transfer::msgs::transfer;
-use crate::application::ics20_fungible_token_transfer::relay_application_logic::send_transfer::send_transfer as ics20_msg_dispatcher;
+use crate::ics02_client::context::ClientReader;
+use crate::ics02_client::error::Kind as ClientKind;
 use crate::ics02_client::handler::dispatch as ics2_msg_dispatcher;
-use crate::ics02_client::msgs::{create_client, update_client, upgrade_client, ClientMsg};
+use crate::ics02_client::handler::{update_client, ClientResult, VerifiedClientState};
+use crate::ics02_client::msgs::{
+    create_client, misbehavior, update_client, upgrade_client, ClientMsg,
+};
 use crate::ics03_connection::handler::dispatch as ics3_msg_dispatcher;
 use crate::ics03_connection::msgs::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try, ConnectionMsg,
 };
+use crate::ics04_channel::handler::channel_callback as ics4_channel_callback;
 use crate::ics04_channel::handler::channel_dispatch as ics4_msg_dispatcher;
+use crate::ics04_channel::handler::packet_callback as ics04_packet_callback;
 use crate::ics04_channel::handler::packet_dispatch as ics04_packet_msg_dispatcher;
 use crate::{events::IbcEvent, handler::HandlerOutput};
 
@@ -20,7 +25,7 @@ use crate::ics04_channel::msgs::{
 use crate::ics26_routing::context::Ics26Context;
 use crate::ics26_routing::error::{Error, Kind};
 use crate::ics26_routing::msgs::Ics26Envelope::{
-    self, Ics20Msg, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
+    self, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
 };
 
 /// Mimics the DeliverTx ABCI interface, but a slightly lower level. No need for authentication
@@ -58,6 +63,11 @@ where
                     .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
                 Ok(Ics2Msg(ClientMsg::UpgradeClient(domain_msg)))
             }
+            misbehavior::TYPE_URL => {
+                let domain_msg = misbehavior::MsgSubmitAnyMisbehaviour::decode_vec(&any_msg.value)
+                    .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics2Msg(ClientMsg::Misbehaviour(domain_msg)))
+            }
 
             // ICS03
             conn_open_init::TYPE_URL => {
@@ -119,12 +129,6 @@ where
                         .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
                 Ok(Ics4ChannelMsg(ChannelMsg::ChannelCloseConfirm(domain_msg)))
             }
-            // ICS20 - 04 - Send packet
-            transfer::TYPE_URL => {
-                let domain_msg = transfer::MsgTransfer::decode_vec(&any_msg.value)
-                    .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
-                Ok(Ics20Msg(domain_msg))
-            }
             // ICS04 packet messages
             recv_packet::TYPE_URL => {
                 let domain_msg = recv_packet::MsgRecvPacket::decode_vec(&any_msg.value)
@@ -188,6 +192,9 @@ where
                 ics3_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
             // Apply any results to the host chain store.
+            if let Some(verified) = handler_output.result.verified_client_state.clone() {
+                store_verified_client_state(ctx, verified)?;
+            }
             ctx.store_connection_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
 
@@ -198,44 +205,54 @@ where
         }
 
         Ics4ChannelMsg(msg) => {
-            let handler_output =
-                ics4_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
-
-            // Apply any results to the host chain store.
-            ctx.store_channel_result(handler_output.result)
-                .map_err(|e| Kind::KeeperRaisedError.context(e))?;
-
-            HandlerOutput::builder()
-                .with_log(handler_output.log)
-                .with_events(handler_output.events)
-                .with_result(())
-        }
+            let handler_output = ics4_msg_dispatcher(ctx, msg.clone())
+                .map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
-        Ics20Msg(msg) => {
-            let handler_output =
-                ics20_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+            // Let the application module bound to the port accept (or reject) the handshake step.
+            let mut module_output = HandlerOutput::builder();
+            ics4_channel_callback(ctx, &msg, &handler_output.result, &mut module_output)
+                .map_err(|e| Kind::ModuleCallbackFailed.context(e))?;
+            let module_output = module_output.with_result(());
 
             // Apply any results to the host chain store.
-            ctx.store_packet_result(handler_output.result)
+            if let Some(verified) = handler_output.result.verified_client_state.clone() {
+                store_verified_client_state(ctx, verified)?;
+            }
+            ctx.store_channel_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
 
             HandlerOutput::builder()
                 .with_log(handler_output.log)
                 .with_events(handler_output.events)
+                .with_log(module_output.log)
+                .with_events(module_output.events)
                 .with_result(())
         }
 
         Ics4PacketMsg(msg) => {
-            let handler_output = ics04_packet_msg_dispatcher(ctx, msg)
+            let handler_output = ics04_packet_msg_dispatcher(ctx, msg.clone())
                 .map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
-            // Apply any results to the host chain store.
+            if let Some(verified) = handler_output.result.verified_client_state().cloned() {
+                store_verified_client_state(ctx, verified)?;
+            }
+
+            // Hand the packet over to the application module bound to the port, before storing
+            // the result, so that a failing module leaves the packet unprocessed.
+            let mut module_output = HandlerOutput::builder();
+            ics04_packet_callback(ctx, &msg, &mut module_output)
+                .map_err(|e| Kind::ModuleCallbackFailed.context(e))?;
+            let module_output = module_output.with_result(());
+
+            // Apply the result to the host chain store.
             ctx.store_packet_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
 
             HandlerOutput::builder()
                 .with_log(handler_output.log)
                 .with_events(handler_output.events)
+                .with_log(module_output.log)
+                .with_events(module_output.events)
                 .with_result(())
         }
     };
@@ -243,6 +260,44 @@ where
     Ok(output)
 }
 
+/// Stores the state of a client updated by the verification of the proofs of a message, along
+/// with the consensus state of the client, which the proofs leave unchanged, at its new latest
+/// height. Proofs of later messages are verified at that height.
+fn store_verified_client_state<Ctx>(
+    ctx: &mut Ctx,
+    verified: VerifiedClientState,
+) -> Result<(), Error>
+where
+    Ctx: Ics26Context,
+{
+    let client_id = verified.client_id;
+    let latest_height = ClientReader::client_state(ctx, &client_id)
+        .map(|client_state| client_state.latest_height())
+        .ok_or_else(|| {
+            Kind::KeeperRaisedError.context(ClientKind::ClientNotFound(client_id.clone()))
+        })?;
+    let consensus_state = ctx
+        .consensus_state(&client_id, latest_height)
+        .ok_or_else(|| {
+            Kind::KeeperRaisedError.context(ClientKind::ConsensusStateNotFound(
+                client_id.clone(),
+                latest_height,
+            ))
+        })?;
+
+    let result = update_client::Result {
+        client_id,
+        client_state: verified.client_state,
+        consensus_state,
+        processed_time: ClientReader::host_timestamp(ctx),
+        processed_height: ClientReader::host_height(ctx),
+        pruned_heights: vec![],
+    };
+
+    ctx.store_client_result(ClientResult::Update(result))
+        .map_err(|e| Kind::KeeperRaisedError.context(e).into())
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
@@ -253,6 +308,9 @@ mod tests {
     use crate::ics02_client::client_state::AnyClientState;
     use crate::{
         application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer,
+        application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer,
+        application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData,
+        application::ics20_fungible_token_transfer::VERSION,
         ics23_commitment::commitment::test_util::get_dummy_merkle_proof,
     };
 
@@ -279,13 +337,25 @@ mod tests {
         ChannelMsg, PacketMsg,
     };
 
-    use crate::ics24_host::identifier::ConnectionId;
+    use crate::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::context::ChannelReader;
+    use crate::ics04_channel::error::{Error as Ics4Error, Kind as Ics4Kind};
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics18_relayer::context::Ics18Context;
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::ics26_routing::context::{Module, ModuleId, ModuleOutput};
     use crate::ics26_routing::handler::dispatch;
     use crate::ics26_routing::msgs::Ics26Envelope;
     use crate::mock::client_state::{MockClientState, MockConsensusState};
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
+    use crate::signer::Signer;
     use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
     use crate::Height;
 
     #[test]
@@ -295,9 +365,22 @@ mod tests {
     /// `ConnectionKeeper`, and `ChannelKeeper` traits).
     fn routing_module_and_keepers() {
         // Test parameters
+        #[derive(Clone, Debug)]
+        enum TestMsg {
+            Ics26(Ics26Envelope),
+            // Transfers are processed by the transfer module, rather than routed by ICS26.
+            Transfer(MsgTransfer),
+        }
+
+        impl From<Ics26Envelope> for TestMsg {
+            fn from(msg: Ics26Envelope) -> Self {
+                Self::Ics26(msg)
+            }
+        }
+
         struct Test {
             name: String,
-            msg: Ics26Envelope,
+            msg: TestMsg,
             want_pass: bool,
         }
         let default_signer = get_dummy_account_id();
@@ -313,10 +396,10 @@ mod tests {
         let upgrade_client_height_second = Height::new(1, 1);
 
         // We reuse this same context across all tests. Nothing in particular needs parametrizing.
-        let mut ctx = MockContext::default();
+        let mut ctx = MockContext::default().with_balance(default_signer.clone(), "uatom", 100);
 
         let create_client_msg = MsgCreateAnyClient::new(
-            AnyClientState::from(MockClientState(MockHeader::new(start_client_height))),
+            AnyClientState::from(MockClientState::new(MockHeader::new(start_client_height))),
             AnyConsensusState::from(MockConsensusState(MockHeader::new(start_client_height))),
             default_signer.clone(),
         )
@@ -349,18 +432,23 @@ mod tests {
         //
         // Channel handshake messages.
         //
-        let msg_chan_init =
+        // The channel is opened by the transfer module, which negotiates the ICS20 version.
+        let mut msg_chan_init =
             MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init()).unwrap();
+        msg_chan_init.channel.version = VERSION.to_string();
 
         // The handler will fail to process this b/c the associated connection does not exist
         let mut incorrect_msg_chan_init = msg_chan_init.clone();
         incorrect_msg_chan_init.channel.connection_hops = vec![ConnectionId::new(590)];
 
-        let msg_chan_try =
+        let mut msg_chan_try =
             MsgChannelOpenTry::try_from(get_dummy_raw_msg_chan_open_try(client_height)).unwrap();
+        msg_chan_try.channel.version = VERSION.to_string();
+        msg_chan_try.counterparty_version = VERSION.to_string();
 
-        let msg_chan_ack =
+        let mut msg_chan_ack =
             MsgChannelOpenAck::try_from(get_dummy_raw_msg_chan_open_ack(client_height)).unwrap();
+        msg_chan_ack.counterparty_version = VERSION.to_string();
 
         let msg_chan_close_init =
             MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap();
@@ -378,6 +466,13 @@ mod tests {
         msg_to_on_close.packet.sequence = 2.into();
         msg_to_on_close.packet.timeout_height = msg_transfer_two.timeout_height;
         msg_to_on_close.packet.timeout_timestamp = msg_transfer_two.timeout_timestamp;
+        msg_to_on_close.packet.data = FungibleTokenPacketData {
+            amount: 10,
+            denom: "uatom".to_string(),
+            receiver: msg_transfer_two.receiver.clone(),
+            sender: msg_transfer_two.sender.clone(),
+        }
+        .to_bytes();
 
         let msg_recv_packet = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(35)).unwrap();
 
@@ -394,7 +489,7 @@ mod tests {
             res
         );
 
-        ctx.add_port(msg_chan_init.port_id().clone());
+        ctx = ctx.with_transfer_port(msg_chan_init.port_id().clone());
 
         // Figure out the ID of the client that was just created.
         let mut events = res.unwrap().events;
@@ -416,7 +511,8 @@ mod tests {
                     client_id: client_id.clone(),
                     header: MockHeader::new(update_client_height).into(),
                     signer: default_signer.clone(),
-                })),
+                }))
+                .into(),
                 want_pass: true,
             },
             Test {
@@ -425,14 +521,16 @@ mod tests {
                     client_id: client_id.clone(),
                     header: MockHeader::new(update_client_height).into(),
                     signer: default_signer.clone(),
-                })),
+                }))
+                .into(),
                 want_pass: false,
             },
             Test {
                 name: "Connection open init succeeds".to_string(),
                 msg: Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenInit(
                     msg_conn_init.with_client_id(client_id.clone()),
-                )),
+                ))
+                .into(),
                 want_pass: true,
             },
             Test {
@@ -440,50 +538,55 @@ mod tests {
                     .to_string(),
                 msg: Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenTry(Box::new(
                     incorrect_msg_conn_try,
-                ))),
+                )))
+                .into(),
                 want_pass: false,
             },
             Test {
                 name: "Connection open try succeeds".to_string(),
                 msg: Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenTry(Box::new(
                     correct_msg_conn_try.with_client_id(client_id.clone()),
-                ))),
+                )))
+                .into(),
                 want_pass: true,
             },
             Test {
                 name: "Connection open ack succeeds".to_string(),
                 msg: Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenAck(Box::new(
                     msg_conn_ack,
-                ))),
+                )))
+                .into(),
                 want_pass: true,
             },
             // ICS04
             Test {
                 name: "Channel open init succeeds".to_string(),
-                msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init)),
+                msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init))
+                    .into(),
                 want_pass: true,
             },
             Test {
                 name: "Channel open init fail due to missing connection".to_string(),
                 msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(
                     incorrect_msg_chan_init,
-                )),
+                ))
+                .into(),
                 want_pass: false,
             },
             Test {
                 name: "Channel open try succeeds".to_string(),
-                msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenTry(msg_chan_try)),
+                msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenTry(msg_chan_try)).into(),
                 want_pass: true,
             },
             Test {
                 name: "Channel open ack succeeds".to_string(),
-                msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(msg_chan_ack)),
+                msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(msg_chan_ack)).into(),
                 want_pass: true,
             },
             //ICS20-04-packet
             Test {
                 name: "Packet send".to_string(),
-                msg: Ics26Envelope::Ics20Msg(msg_transfer),
+                msg: TestMsg::Transfer(msg_transfer),
                 want_pass: true,
             },
             // The client update is required in this test, because the proof associated with
@@ -494,22 +597,24 @@ mod tests {
                     client_id: client_id.clone(),
                     header: MockHeader::new(update_client_height_after_send).into(),
                     signer: default_signer.clone(),
-                })),
+                }))
+                .into(),
                 want_pass: true,
             },
             Test {
                 name: "Receive packet".to_string(),
-                msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet.clone())),
+                msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet.clone()))
+                    .into(),
                 want_pass: true,
             },
             Test {
                 name: "Re-Receive packet".to_string(),
-                msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet)),
+                msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet)).into(),
                 want_pass: false,
             },
             Test {
                 name: "Packet send".to_string(),
-                msg: Ics26Envelope::Ics20Msg(msg_transfer_two),
+                msg: TestMsg::Transfer(msg_transfer_two),
                 want_pass: true,
             },
             Test {
@@ -518,7 +623,8 @@ mod tests {
                     client_id: client_id.clone(),
                     header: MockHeader::new(update_client_height_after_second_send).into(),
                     signer: default_signer.clone(),
-                })),
+                }))
+                .into(),
                 want_pass: true,
             },
             //ICS04-close channel
@@ -526,41 +632,46 @@ mod tests {
                 name: "Channel close init succeeds".to_string(),
                 msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelCloseInit(
                     msg_chan_close_init,
-                )),
+                ))
+                .into(),
                 want_pass: true,
             },
             Test {
                 name: "Channel close confirm fails cause channel is already closed".to_string(),
                 msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelCloseConfirm(
                     msg_chan_close_confirm,
-                )),
+                ))
+                .into(),
                 want_pass: false,
             },
             //ICS04-to_on_close
             Test {
                 name: "Timeout on close".to_string(),
-                msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::ToClosePacket(msg_to_on_close)),
+                msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::ToClosePacket(msg_to_on_close)).into(),
                 want_pass: true,
             },
             Test {
                 name: "Client upgrade successful".to_string(),
                 msg: Ics26Envelope::Ics2Msg(ClientMsg::UpgradeClient(MsgUpgradeAnyClient::new(
                     client_id.clone(),
-                    AnyClientState::Mock(MockClientState(MockHeader::new(upgrade_client_height))),
+                    AnyClientState::Mock(MockClientState::new(MockHeader::new(
+                        upgrade_client_height,
+                    ))),
                     AnyConsensusState::Mock(MockConsensusState(MockHeader::new(
                         upgrade_client_height,
                     ))),
                     get_dummy_merkle_proof(),
                     get_dummy_merkle_proof(),
                     default_signer.clone(),
-                ))),
+                )))
+                .into(),
                 want_pass: true,
             },
             Test {
                 name: "Client upgrade un-successful".to_string(),
                 msg: Ics26Envelope::Ics2Msg(ClientMsg::UpgradeClient(MsgUpgradeAnyClient::new(
                     client_id,
-                    AnyClientState::Mock(MockClientState(MockHeader::new(
+                    AnyClientState::Mock(MockClientState::new(MockHeader::new(
                         upgrade_client_height_second,
                     ))),
                     AnyConsensusState::Mock(MockConsensusState(MockHeader::new(
@@ -569,7 +680,8 @@ mod tests {
                     get_dummy_merkle_proof(),
                     get_dummy_merkle_proof(),
                     default_signer,
-                ))),
+                )))
+                .into(),
                 want_pass: false,
             },
         ]
@@ -577,7 +689,15 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = dispatch(&mut ctx, test.msg.clone());
+            let res = match test.msg.clone() {
+                TestMsg::Ics26(msg) => dispatch(&mut ctx, msg)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                TestMsg::Transfer(msg) => ctx
+                    .send_transfer(msg)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+            };
 
             assert_eq!(
                 test.want_pass,
@@ -588,5 +708,152 @@ mod tests {
                 res
             );
         }
+
+        // The capability of the closed channel was released.
+        assert!(ctx
+            .channel_capability(&PortId::default(), &ChannelId::default())
+            .is_none());
+    }
+
+    /// An application module which either rejects new channels, or accepts them and acknowledges
+    /// every packet it receives with `ack`.
+    #[derive(Clone, Debug)]
+    struct TestModule {
+        reject_channels: bool,
+        ack: Vec<u8>,
+    }
+
+    impl Module for TestModule {
+        fn on_chan_open_init(
+            &mut self,
+            output: &mut ModuleOutput,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            _version: &str,
+        ) -> Result<(), Ics4Error> {
+            if self.reject_channels {
+                return Err(Ics4Kind::AppModule.context("channel rejected").into());
+            }
+            output.log(format!("test module: channel {} initialized", channel_id));
+            Ok(())
+        }
+
+        fn on_recv_packet(
+            &mut self,
+            _output: &mut ModuleOutput,
+            _packet: &Packet,
+            _relayer: &Signer,
+        ) -> Result<Option<Vec<u8>>, Ics4Error> {
+            Ok(Some(self.ack.clone()))
+        }
+    }
+
+    #[test]
+    fn routing_module_callbacks() {
+        let module_id = ModuleId::new("testmodule");
+        let module = |reject_channels| TestModule {
+            reject_channels,
+            ack: vec![1],
+        };
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let msg_chan_init =
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init()).unwrap();
+        let port_id = msg_chan_init.port_id().clone();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            msg_conn_init.client_id().clone(),
+            msg_conn_init.counterparty().clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+        let chan_init_ctx = |reject_channels| {
+            MockContext::default()
+                .with_connection(ConnectionId::default(), conn_end.clone())
+                .with_module(port_id.clone(), module_id.clone(), module(reject_channels))
+        };
+
+        // The module bound to the port is notified of the new channel.
+        let mut ctx = chan_init_ctx(false);
+        let output = dispatch(
+            &mut ctx,
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init.clone())),
+        )
+        .unwrap();
+        assert!(output
+            .log
+            .iter()
+            .any(|log| log.starts_with("test module: channel")));
+        assert_eq!(ChannelReader::channel_counter(&ctx), 1);
+
+        // The module can reject the channel, which is then not created.
+        let mut ctx = chan_init_ctx(true);
+        let res = dispatch(
+            &mut ctx,
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init)),
+        );
+        assert!(res.is_err());
+        assert_eq!(ChannelReader::channel_counter(&ctx), 0);
+
+        // The acknowledgement returned by the module for a received packet is written.
+        let host_height = MockContext::default().query_latest_height().increment();
+        let client_height = host_height.increment();
+        let msg_recv_packet =
+            MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(client_height.revision_height))
+                .unwrap();
+        let packet = msg_recv_packet.packet.clone();
+
+        let dest_channel_end = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(
+                packet.source_port.clone(),
+                Some(packet.source_channel.clone()),
+            ),
+            vec![ConnectionId::default()],
+            "ics20".to_string(),
+        );
+        let open_conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let mut ctx = MockContext::default()
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), open_conn_end)
+            .with_module(packet.destination_port.clone(), module_id, module(false))
+            .with_channel(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+                dest_channel_end,
+            )
+            .with_height(host_height)
+            .with_timestamp(Timestamp::from_nanoseconds(1).unwrap())
+            .with_recv_sequence(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+                packet.sequence,
+            );
+
+        let output = dispatch(
+            &mut ctx,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet)),
+        )
+        .unwrap();
+        assert!(output
+            .events
+            .iter()
+            .any(|event| matches!(event, IbcEvent::WriteAcknowledgement(e) if e.ack == vec![1])));
     }
 }
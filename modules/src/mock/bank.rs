@@ -0,0 +1,76 @@
+//! Bank mock, holding the account balances and denomination traces of the transfer module
+//! registered with the router of a mock context.
+
+use std::collections::HashMap;
+
+use crate::application::ics20_fungible_token_transfer::context::{BankKeeper, Ics20Context};
+use crate::application::ics20_fungible_token_transfer::denom::DenomTrace;
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::signer::Signer;
+
+/// The state of the transfer module of a mock chain.
+#[derive(Clone, Debug, Default)]
+pub struct MockBank {
+    /// The balances of the accounts, indexed by account and denomination.
+    balances: HashMap<(Signer, String), u64>,
+
+    /// The traces of the denominations of the vouchers minted by the transfer module, indexed by
+    /// their hash.
+    denom_traces: HashMap<String, DenomTrace>,
+}
+
+impl Ics20Context for MockBank {
+    fn get_denom_trace(&self, hash: &str) -> Option<DenomTrace> {
+        self.denom_traces.get(hash).cloned()
+    }
+
+    fn store_denom_trace(&mut self, denom_trace: DenomTrace) -> Result<(), Error> {
+        self.denom_traces.insert(denom_trace.hash(), denom_trace);
+        Ok(())
+    }
+}
+
+impl BankKeeper for MockBank {
+    fn balance(&self, account: &Signer, denom: &str) -> u64 {
+        self.balances
+            .get(&(account.clone(), denom.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn send_coins(
+        &mut self,
+        from: &Signer,
+        to: &Signer,
+        denom: &str,
+        amount: u64,
+    ) -> Result<(), Error> {
+        self.burn_coins(from, denom, amount)?;
+        self.mint_coins(to, denom, amount)
+    }
+
+    fn mint_coins(&mut self, account: &Signer, denom: &str, amount: u64) -> Result<(), Error> {
+        *self
+            .balances
+            .entry((account.clone(), denom.to_string()))
+            .or_insert(0) += amount;
+        Ok(())
+    }
+
+    fn burn_coins(&mut self, account: &Signer, denom: &str, amount: u64) -> Result<(), Error> {
+        let balance = self.balance(account, denom);
+        if balance < amount {
+            return Err(Kind::InsufficientFunds(
+                account.clone(),
+                amount,
+                denom.to_string(),
+                balance,
+            )
+            .into());
+        }
+
+        self.balances
+            .insert((account.clone(), denom.to_string()), balance - amount);
+        Ok(())
+    }
+}
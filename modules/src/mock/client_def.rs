@@ -3,8 +3,11 @@ use ibc_proto::ibc::core::commitment::v1::MerkleProof;
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_def::ClientDef;
 use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::packet::Sequence;
 use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
 use crate::ics23_commitment::merkle::apply_prefix;
@@ -12,6 +15,7 @@ use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::ics24_host::Path;
 use crate::mock::client_state::{MockClientState, MockConsensusState};
 use crate::mock::header::MockHeader;
+use crate::mock::misbehaviour::Misbehaviour;
 use crate::Height;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -21,9 +25,12 @@ impl ClientDef for MockClient {
     type Header = MockHeader;
     type ClientState = MockClientState;
     type ConsensusState = MockConsensusState;
+    type Misbehaviour = Misbehaviour;
 
     fn check_header_and_update_state(
         &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
@@ -32,15 +39,31 @@ impl ClientDef for MockClient {
                 "received header height is lower than (or equal to) client latest height".into(),
             );
         }
-        Ok((MockClientState(header), MockConsensusState(header)))
+        Ok((MockClientState::new(header), MockConsensusState(header)))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let (header1, header2) = (misbehaviour.header1, misbehaviour.header2);
+        if header1.height() != header2.height() || header1 == header2 {
+            return Err("misbehaviour headers do not conflict".into());
+        }
+        Ok(client_state.with_frozen_height(header1.height()))
     }
 
     fn verify_client_consensus_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         client_id: &ClientId,
         _consensus_height: Height,
         _expected_consensus_state: &AnyConsensusState,
@@ -54,20 +77,19 @@ impl ClientDef for MockClient {
 
         let _path = apply_prefix(prefix, vec![client_prefixed_path])?;
 
-        // TODO - add ctx to all client verification functions
-        // let cs = ctx.fetch_self_consensus_state(height);
-        // TODO - implement this
-        // proof.verify_membership(cs.root(), path, expected_consensus_state)
+        // The mock client does not check proofs, see the Tendermint client for actual verification.
 
         Ok(())
     }
 
     fn verify_connection_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         _height: Height,
         _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _connection_id: Option<&ConnectionId>,
         _expected_connection_end: &ConnectionEnd,
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -76,10 +98,12 @@ impl ClientDef for MockClient {
 
     fn verify_channel_state(
         &self,
+        _ctx: &dyn ChannelReader,
         _client_state: &Self::ClientState,
         _height: Height,
         _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _expected_channel_end: &ChannelEnd,
@@ -89,6 +113,7 @@ impl ClientDef for MockClient {
 
     fn verify_client_full_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         _height: Height,
         _root: &CommitmentRoot,
@@ -102,22 +127,28 @@ impl ClientDef for MockClient {
 
     fn verify_packet_data(
         &self,
+        _ctx: &dyn ChannelReader,
         _client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-        _data: String,
+        _commitment: Vec<u8>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         Ok(())
     }
 
     fn verify_packet_acknowledgement(
         &self,
+        _ctx: &dyn ChannelReader,
         _client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
@@ -128,9 +159,12 @@ impl ClientDef for MockClient {
 
     fn verify_next_sequence_recv(
         &self,
+        _ctx: &dyn ChannelReader,
         _client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
@@ -140,9 +174,12 @@ impl ClientDef for MockClient {
 
     fn verify_packet_receipt_absence(
         &self,
+        _ctx: &dyn ChannelReader,
         _client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
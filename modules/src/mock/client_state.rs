@@ -31,19 +31,42 @@ pub struct MockClientRecord {
 
     /// Mapping of heights to consensus states for this client.
     pub consensus_states: HashMap<Height, AnyConsensusState>,
+
+    /// Mapping of consensus state heights to the host timestamps at which they were processed.
+    pub processed_times: HashMap<Height, Timestamp>,
+
+    /// Mapping of consensus state heights to the host heights at which they were processed.
+    pub processed_heights: HashMap<Height, Height>,
 }
 
 /// A mock of a client state. For an example of a real structure that this mocks, you can see
 /// `ClientState` of ics07_tendermint/client_state.rs.
-// TODO: `MockClientState` should evolve, at the very least needs a `is_frozen` boolean field.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct MockClientState(pub MockHeader);
+pub struct MockClientState {
+    pub header: MockHeader,
+    pub frozen_height: Option<Height>,
+}
 
 impl Protobuf<RawMockClientState> for MockClientState {}
 
 impl MockClientState {
+    pub fn new(header: MockHeader) -> Self {
+        Self {
+            header,
+            frozen_height: None,
+        }
+    }
+
     pub fn latest_height(&self) -> Height {
-        (self.0).height
+        self.header.height
+    }
+
+    /// Returns a copy of this client state, frozen at the given `height`.
+    pub fn with_frozen_height(self, height: Height) -> Self {
+        Self {
+            frozen_height: Some(height),
+            ..self
+        }
     }
 
     pub fn refresh_time(&self) -> Option<Duration> {
@@ -64,7 +87,7 @@ impl TryFrom<RawMockClientState> for MockClientState {
     type Error = Error;
 
     fn try_from(raw: RawMockClientState) -> Result<Self, Self::Error> {
-        Ok(MockClientState(raw.header.unwrap().try_into()?))
+        Ok(MockClientState::new(raw.header.unwrap().try_into()?))
     }
 }
 
@@ -72,8 +95,8 @@ impl From<MockClientState> for RawMockClientState {
     fn from(value: MockClientState) -> Self {
         RawMockClientState {
             header: Some(ibc_proto::ibc::mock::Header {
-                height: Some(value.0.height().into()),
-                timestamp: (value.0).timestamp.as_nanoseconds(),
+                height: Some(value.header.height().into()),
+                timestamp: value.header.timestamp.as_nanoseconds(),
             }),
         }
     }
@@ -89,12 +112,11 @@ impl ClientState for MockClientState {
     }
 
     fn latest_height(&self) -> Height {
-        self.0.height()
+        self.header.height()
     }
 
     fn is_frozen(&self) -> bool {
-        // TODO
-        false
+        self.frozen_height.is_some()
     }
 
     fn wrap_any(self) -> AnyClientState {
@@ -104,7 +126,7 @@ impl ClientState for MockClientState {
 
 impl From<MockConsensusState> for MockClientState {
     fn from(cs: MockConsensusState) -> Self {
-        Self(cs.0)
+        Self::new(cs.0)
     }
 }
 
@@ -3,17 +3,25 @@
 use std::cmp::min;
 use std::collections::HashMap;
 use std::error::Error;
+use std::time::Duration;
 
 use prost_types::Any;
 use sha2::Digest;
 
-use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer as ics20;
+use crate::application::ics20_fungible_token_transfer::context::BankKeeper;
+use crate::application::ics20_fungible_token_transfer::error::{
+    Error as Ics20Error, Kind as Ics20Kind,
+};
+use crate::application::ics20_fungible_token_transfer::module::TransferModule;
+use crate::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
 use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
 use crate::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::context::{ClientKeeper, ClientReader};
-use crate::ics02_client::error::Error as Ics02Error;
+use crate::ics02_client::error::{Error as Ics02Error, Kind as Ics02ErrorKind};
 use crate::ics02_client::header::AnyHeader;
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
@@ -21,24 +29,33 @@ use crate::ics03_connection::error::Error as Ics3Error;
 use crate::ics04_channel::channel::ChannelEnd;
 use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::ics04_channel::error::{Error as Ics4Error, Kind as Ics4Kind};
-use crate::ics04_channel::packet::{Receipt, Sequence};
-use crate::ics05_port::capabilities::Capability;
-use crate::ics05_port::context::PortReader;
+use crate::ics04_channel::packet::{PacketResult, Receipt, Sequence};
+use crate::ics05_port::capabilities::{Capability, CapabilityKeeper, CapabilityName};
+use crate::ics05_port::context::{PortKeeper, PortReader};
+use crate::ics05_port::error::{Error as Ics05Error, Kind as Ics05Kind};
+use crate::ics06_solomachine::client_state::test_util::get_dummy_solomachine_client_state;
 use crate::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+use crate::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use crate::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
 use crate::ics18_relayer::context::Ics18Context;
 use crate::ics18_relayer::error::{Error as Ics18Error, Kind as Ics18ErrorKind};
 use crate::ics23_commitment::commitment::CommitmentPrefix;
 use crate::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
-use crate::ics26_routing::context::Ics26Context;
+use crate::ics26_routing::context::{Ics26Context, Module, ModuleId};
 use crate::ics26_routing::handler::{deliver, dispatch};
 use crate::ics26_routing::msgs::Ics26Envelope;
+use crate::mock::bank::MockBank;
 use crate::mock::client_state::{MockClientRecord, MockClientState, MockConsensusState};
 use crate::mock::header::MockHeader;
 use crate::mock::host::{HostBlock, HostType};
+use crate::mock::router::{MockRouter, MOCK_MODULE_ID};
 use crate::signer::Signer;
 use crate::timestamp::Timestamp;
 use crate::Height;
 
+/// The maximum expected time per block of the mock host chain, in seconds.
+pub const DEFAULT_BLOCK_TIME_SECS: u64 = 3;
+
 /// A context implementing the dependencies necessary for testing any IBC module.
 #[derive(Clone, Debug)]
 pub struct MockContext {
@@ -95,13 +112,16 @@ pub struct MockContext {
     /// Tracks the sequence number for the next packet to be acknowledged.
     next_sequence_ack: HashMap<(PortId, ChannelId), Sequence>,
 
-    packet_acknowledgement: HashMap<(PortId, ChannelId, Sequence), String>,
+    packet_acknowledgement: HashMap<(PortId, ChannelId, Sequence), Vec<u8>>,
+
+    /// The capabilities of the ports and channels, and the modules owning them.
+    capabilities: CapabilityKeeper,
 
-    /// Maps ports to their capabilities
-    port_capabilities: HashMap<PortId, Capability>,
+    /// Routes the callbacks to the application modules.
+    router: MockRouter,
 
     /// Constant-size commitments to packets data fields
-    packet_commitment: HashMap<(PortId, ChannelId, Sequence), String>,
+    packet_commitment: HashMap<(PortId, ChannelId, Sequence), Vec<u8>>,
 
     // Used by unordered channel
     packet_receipt: HashMap<(PortId, ChannelId, Sequence), Receipt>,
@@ -173,7 +193,8 @@ impl MockContext {
             next_sequence_send: Default::default(),
             next_sequence_recv: Default::default(),
             next_sequence_ack: Default::default(),
-            port_capabilities: Default::default(),
+            capabilities: Default::default(),
+            router: Default::default(),
             packet_commitment: Default::default(),
             packet_receipt: Default::default(),
             packet_acknowledgement: Default::default(),
@@ -208,7 +229,7 @@ impl MockContext {
         let (client_state, consensus_state) = match client_type {
             // If it's a mock client, create the corresponding mock states.
             ClientType::Mock => (
-                Some(MockClientState(MockHeader::new(client_state_height)).into()),
+                Some(MockClientState::new(MockHeader::new(client_state_height)).into()),
                 MockConsensusState(MockHeader::new(cs_height)).into(),
             ),
             // If it's a Tendermint client, we need TM states.
@@ -224,13 +245,37 @@ impl MockContext {
                 // Return the tuple.
                 (Some(client_state), consensus_state)
             }
+            // If it's a solo machine client, the client sequence is the height.
+            ClientType::SoloMachine => {
+                let client_state =
+                    get_dummy_solomachine_client_state(client_state_height.revision_height);
+                let consensus_state =
+                    AnyConsensusState::SoloMachine(client_state.consensus_state.clone());
+
+                (
+                    Some(AnyClientState::SoloMachine(client_state)),
+                    consensus_state,
+                )
+            }
+            // If it's a localhost client, it tracks the host chain itself.
+            ClientType::Localhost => (
+                Some(AnyClientState::Localhost(LocalhostClientState::new(
+                    self.host_chain_id.clone(),
+                    client_state_height,
+                ))),
+                AnyConsensusState::Localhost(LocalhostConsensusState::default()),
+            ),
         };
         let consensus_states = vec![(cs_height, consensus_state)].into_iter().collect();
+        let processed_times = vec![(cs_height, self.timestamp)].into_iter().collect();
+        let processed_heights = vec![(cs_height, self.latest_height)].into_iter().collect();
 
         let client_record = MockClientRecord {
             client_type,
             client_state,
             consensus_states,
+            processed_times,
+            processed_heights,
         };
         self.clients.insert(client_id.clone(), client_record);
         self
@@ -246,18 +291,55 @@ impl MockContext {
         self
     }
 
+    /// Binds the port `port_id` to the default `MockModule`.
     pub fn with_port_capability(mut self, port_id: PortId) -> Self {
-        self.port_capabilities.insert(port_id, Capability::new());
+        self.add_port(port_id);
+        self
+    }
+
+    /// Registers the application `module` under `module_id` with the router of this context,
+    /// and binds the port `port_id` to it.
+    pub fn with_module(
+        mut self,
+        port_id: PortId,
+        module_id: ModuleId,
+        module: impl Module + 'static,
+    ) -> Self {
+        self.router.add_route(module_id.clone(), module);
+        self.bind_port(port_id, module_id)
+            .expect("the port must not be bound already");
+        self
+    }
+
+    /// Binds the port `port_id` to the transfer module.
+    pub fn with_transfer_port(mut self, port_id: PortId) -> Self {
+        self.bind_port(port_id, ModuleId::new(ics20::MODULE_ID_STR))
+            .expect("the port must not be bound already");
+        self
+    }
+
+    /// Credits `amount` tokens of denomination `denom` to `account`, in the bank of the transfer
+    /// module.
+    pub fn with_balance(mut self, account: Signer, denom: &str, amount: u64) -> Self {
+        self.transfer_module_mut()
+            .keeper_mut()
+            .mint_coins(&account, denom, amount)
+            .expect("minting never fails");
         self
     }
 
-    /// Associates a channel (in an arbitrary state) to this context.
+    /// Associates a channel (in an arbitrary state) to this context. If the port `port_id` is
+    /// already bound, the module bound to it also claims the channel capability.
     pub fn with_channel(
-        self,
+        mut self,
         port_id: PortId,
         chan_id: ChannelId,
         channel_end: ChannelEnd,
     ) -> Self {
+        if self.channel_capability(&port_id, &chan_id).is_none() {
+            let _ = self.claim_channel_capability(&port_id, &chan_id);
+        }
+
         let mut channels = self.channels.clone();
         channels.insert((port_id, chan_id), channel_end);
         Self { channels, ..self }
@@ -334,7 +416,7 @@ impl MockContext {
         port_id: PortId,
         chan_id: ChannelId,
         seq: Sequence,
-        data: String,
+        data: Vec<u8>,
     ) -> Self {
         let mut packet_commitment = self.packet_commitment.clone();
         packet_commitment.insert((port_id, chan_id, seq), data);
@@ -417,7 +499,64 @@ impl MockContext {
     }
 
     pub fn add_port(&mut self, port_id: PortId) {
-        self.port_capabilities.insert(port_id, Capability::new());
+        self.bind_port(port_id, ModuleId::new(MOCK_MODULE_ID))
+            .expect("the port must not be bound already");
+    }
+
+    /// Returns the capability of the channel `channel_id` of the port `port_id`, as claimed by
+    /// the module bound to the port. Modules present it to send packets through the channel.
+    pub fn channel_capability(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Option<Capability> {
+        let (module_id, _) = PortReader::lookup_module_by_port(self, port_id).ok()?;
+        PortReader::get_capability(
+            self,
+            &module_id,
+            &CapabilityName::channel(port_id, channel_id),
+        )
+    }
+
+    /// The transfer module registered with the router of this context.
+    pub fn transfer_module(&self) -> &TransferModule<MockBank> {
+        self.router
+            .module(&ModuleId::new(ics20::MODULE_ID_STR))
+            .expect("the transfer module must be registered")
+    }
+
+    pub fn transfer_module_mut(&mut self) -> &mut TransferModule<MockBank> {
+        self.router
+            .module_mut(&ModuleId::new(ics20::MODULE_ID_STR))
+            .expect("the transfer module must be registered")
+    }
+
+    /// Processes the transfer message `msg` with the transfer module, and stores the packet to
+    /// send, like the host chain does for the transfer messages submitted to it. The transfer
+    /// module presents the capability it claimed for the source channel, if any.
+    pub fn send_transfer(
+        &mut self,
+        msg: MsgTransfer,
+    ) -> Result<HandlerOutput<PacketResult>, Ics20Error> {
+        let channel_cap = PortReader::get_capability(
+            self,
+            &ModuleId::new(ics20::MODULE_ID_STR),
+            &CapabilityName::channel(&msg.source_port, &msg.source_channel),
+        )
+        .ok_or_else(|| {
+            Ics20Kind::HandlerRaisedError.context(Ics4Kind::NoChannelCapability(
+                msg.source_port.clone(),
+                msg.source_channel.clone(),
+            ))
+        })?;
+
+        let mut module = self.transfer_module().clone();
+        let output = module.send_transfer(self, &channel_cap, msg)?;
+
+        self.store_packet_result(output.result.clone())
+            .map_err(|e| Ics20Kind::HandlerRaisedError.context(e))?;
+        *self.transfer_module_mut() = module;
+        Ok(output)
     }
 
     pub fn consensus_states(&self, client_id: &ClientId) -> Vec<AnyConsensusStateWithHeight> {
@@ -432,17 +571,77 @@ impl MockContext {
     }
 }
 
-impl Ics26Context for MockContext {}
+impl Ics26Context for MockContext {
+    type Router = MockRouter;
+
+    fn router(&self) -> &Self::Router {
+        &self.router
+    }
 
-impl Ics20Context for MockContext {}
+    fn router_mut(&mut self) -> &mut Self::Router {
+        &mut self.router
+    }
+}
 
 impl PortReader for MockContext {
-    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability> {
-        self.port_capabilities.get(port_id).cloned()
+    fn lookup_module_by_port(
+        &self,
+        port_id: &PortId,
+    ) -> Result<(ModuleId, Capability), Ics05Error> {
+        self.capabilities
+            .lookup_modules(&CapabilityName::port(port_id))
+            .and_then(|(modules, capability)| {
+                modules
+                    .into_iter()
+                    .next()
+                    .map(|module_id| (module_id, capability))
+            })
+            .ok_or_else(|| Ics05Kind::UnknownPort(port_id.clone()).into())
+    }
+
+    fn get_capability(&self, module_id: &ModuleId, name: &CapabilityName) -> Option<Capability> {
+        self.capabilities.get_capability(module_id, name)
+    }
+}
+
+impl PortKeeper for MockContext {
+    fn bind_port(
+        &mut self,
+        port_id: PortId,
+        module_id: ModuleId,
+    ) -> Result<Capability, Ics05Error> {
+        if self.lookup_module_by_port(&port_id).is_ok() {
+            return Err(Ics05Kind::PortAlreadyBound(port_id).into());
+        }
+
+        self.capabilities
+            .new_capability(&module_id, CapabilityName::port(&port_id))
+    }
+
+    fn new_capability(
+        &mut self,
+        module_id: &ModuleId,
+        name: CapabilityName,
+    ) -> Result<Capability, Ics05Error> {
+        self.capabilities.new_capability(module_id, name)
+    }
+
+    fn claim_capability(
+        &mut self,
+        module_id: &ModuleId,
+        capability: &Capability,
+        name: CapabilityName,
+    ) -> Result<(), Ics05Error> {
+        self.capabilities
+            .claim_capability(module_id, capability, name)
     }
 
-    fn authenticate(&self, _cap: &Capability, _port_id: &PortId) -> bool {
-        true
+    fn release_capability(
+        &mut self,
+        module_id: &ModuleId,
+        capability: &Capability,
+    ) -> Result<(), Ics05Error> {
+        self.capabilities.release_capability(module_id, capability)
     }
 }
 
@@ -471,17 +670,46 @@ impl ChannelReader for MockContext {
         ClientReader::consensus_state(self, client_id, height)
     }
 
+    fn client_update_time(&self, client_id: &ClientId, height: Height) -> Option<Timestamp> {
+        ClientReader::client_update_time(self, client_id, height)
+    }
+
+    fn client_update_height(&self, client_id: &ClientId, height: Height) -> Option<Height> {
+        ClientReader::client_update_height(self, client_id, height)
+    }
+
+    fn max_expected_time_per_block(&self) -> Duration {
+        Duration::from_secs(DEFAULT_BLOCK_TIME_SECS)
+    }
+
     fn authenticated_capability(&self, port_id: &PortId) -> Result<Capability, Ics4Error> {
         let cap = PortReader::lookup_module_by_port(self, port_id);
         match cap {
-            Some(key) => {
+            Ok((_, key)) => {
                 if !PortReader::authenticate(self, &key, port_id) {
                     Err(Ics4Kind::InvalidPortCapability.into())
                 } else {
                     Ok(key)
                 }
             }
-            None => Err(Ics4Kind::NoPortCapability(port_id.clone()).into()),
+            Err(_) => Err(Ics4Kind::NoPortCapability(port_id.clone()).into()),
+        }
+    }
+
+    fn authenticate_channel_capability(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        capability: &Capability,
+    ) -> bool {
+        match PortReader::lookup_module_by_port(self, port_id) {
+            Ok((module_id, _)) => PortReader::authenticate_capability(
+                self,
+                &module_id,
+                capability,
+                &CapabilityName::channel(port_id, channel_id),
+            ),
+            Err(_) => false,
         }
     }
 
@@ -497,7 +725,7 @@ impl ChannelReader for MockContext {
         self.next_sequence_ack.get(port_channel_id).cloned()
     }
 
-    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String> {
+    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
         self.packet_commitment.get(key).cloned()
     }
 
@@ -505,13 +733,12 @@ impl ChannelReader for MockContext {
         self.packet_receipt.get(key).cloned()
     }
 
-    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String> {
+    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
         self.packet_acknowledgement.get(key).cloned()
     }
 
-    fn hash(&self, input: String) -> String {
-        let r = sha2::Sha256::digest(input.as_bytes());
-        format!("{:x}", r)
+    fn hash(&self, input: Vec<u8>) -> Vec<u8> {
+        sha2::Sha256::digest(&input).to_vec()
     }
 
     fn host_height(&self) -> Height {
@@ -535,9 +762,9 @@ impl ChannelKeeper for MockContext {
         timeout_height: Height,
         data: Vec<u8>,
     ) -> Result<(), Ics4Error> {
-        let input = format!("{:?},{:?},{:?}", timeout_timestamp, timeout_height, data);
-        self.packet_commitment
-            .insert(key, ChannelReader::hash(self, input));
+        let commitment =
+            ChannelReader::packet_commitment(self, &data, timeout_height, timeout_timestamp);
+        self.packet_commitment.insert(key, commitment);
         Ok(())
     }
 
@@ -546,9 +773,8 @@ impl ChannelKeeper for MockContext {
         key: (PortId, ChannelId, Sequence),
         ack: Vec<u8>,
     ) -> Result<(), Ics4Error> {
-        let input = format!("{:?}", ack);
-        self.packet_acknowledgement
-            .insert(key, ChannelReader::hash(self, input));
+        let ack_commitment = ChannelReader::ack_commitment(self, &ack);
+        self.packet_acknowledgement.insert(key, ack_commitment);
         Ok(())
     }
 
@@ -612,6 +838,46 @@ impl ChannelKeeper for MockContext {
         self.channel_ids_counter += 1;
     }
 
+    fn claim_channel_capability(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Capability, Ics4Error> {
+        let (module_id, _) = PortReader::lookup_module_by_port(self, port_id)
+            .map_err(|_| Ics4Kind::NoPortCapability(port_id.clone()))?;
+
+        PortKeeper::new_capability(
+            self,
+            &module_id,
+            CapabilityName::channel(port_id, channel_id),
+        )
+        .map_err(|e| {
+            Ics4Kind::ChannelCapabilityClaim(port_id.clone(), channel_id.clone())
+                .context(e)
+                .into()
+        })
+    }
+
+    fn release_channel_capability(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics4Error> {
+        let (module_id, _) = PortReader::lookup_module_by_port(self, port_id)
+            .map_err(|_| Ics4Kind::NoPortCapability(port_id.clone()))?;
+        let name = CapabilityName::channel(port_id, channel_id);
+
+        match PortReader::get_capability(self, &module_id, &name) {
+            Some(capability) => PortKeeper::release_capability(self, &module_id, &capability)
+                .map_err(|e| {
+                    Ics4Kind::NoChannelCapability(port_id.clone(), channel_id.clone())
+                        .context(e)
+                        .into()
+                }),
+            None => Ok(()),
+        }
+    }
+
     fn delete_packet_commitment(
         &mut self,
         key: (PortId, ChannelId, Sequence),
@@ -723,9 +989,36 @@ impl ClientReader for MockContext {
         }
     }
 
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Vec<Height> {
+        match self.clients.get(client_id) {
+            Some(client_record) => client_record.consensus_states.keys().cloned().collect(),
+            None => vec![],
+        }
+    }
+
+    fn client_update_time(&self, client_id: &ClientId, height: Height) -> Option<Timestamp> {
+        self.clients
+            .get(client_id)
+            .and_then(|client_record| client_record.processed_times.get(&height).cloned())
+    }
+
+    fn client_update_height(&self, client_id: &ClientId, height: Height) -> Option<Height> {
+        self.clients
+            .get(client_id)
+            .and_then(|client_record| client_record.processed_heights.get(&height).cloned())
+    }
+
     fn client_counter(&self) -> u64 {
         self.client_ids_counter
     }
+
+    fn host_height(&self) -> Height {
+        self.latest_height
+    }
+
+    fn host_timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
 }
 
 impl ClientKeeper for MockContext {
@@ -738,6 +1031,8 @@ impl ClientKeeper for MockContext {
             client_type,
             consensus_states: Default::default(),
             client_state: Default::default(),
+            processed_times: Default::default(),
+            processed_heights: Default::default(),
         });
 
         client_record.client_type = client_type;
@@ -753,6 +1048,8 @@ impl ClientKeeper for MockContext {
             client_type: client_state.client_type(),
             consensus_states: Default::default(),
             client_state: Default::default(),
+            processed_times: Default::default(),
+            processed_heights: Default::default(),
         });
 
         client_record.client_state = Some(client_state);
@@ -769,6 +1066,8 @@ impl ClientKeeper for MockContext {
             client_type: ClientType::Mock,
             consensus_states: Default::default(),
             client_state: Default::default(),
+            processed_times: Default::default(),
+            processed_heights: Default::default(),
         });
 
         client_record
@@ -777,6 +1076,52 @@ impl ClientKeeper for MockContext {
         Ok(())
     }
 
+    fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), Ics02Error> {
+        let client_record = self
+            .clients
+            .get_mut(&client_id)
+            .ok_or_else(|| Ics02ErrorKind::ClientNotFound(client_id.clone()))?;
+
+        client_record.processed_times.insert(height, timestamp);
+        Ok(())
+    }
+
+    fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), Ics02Error> {
+        let client_record = self
+            .clients
+            .get_mut(&client_id)
+            .ok_or_else(|| Ics02ErrorKind::ClientNotFound(client_id.clone()))?;
+
+        client_record.processed_heights.insert(height, host_height);
+        Ok(())
+    }
+
+    fn delete_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), Ics02Error> {
+        let client_record = self
+            .clients
+            .get_mut(&client_id)
+            .ok_or_else(|| Ics02ErrorKind::ClientNotFound(client_id.clone()))?;
+
+        client_record.consensus_states.remove(&height);
+        client_record.processed_times.remove(&height);
+        client_record.processed_heights.remove(&height);
+        Ok(())
+    }
+
     fn increase_client_counter(&mut self) {
         self.client_ids_counter += 1
     }
@@ -813,12 +1158,40 @@ impl Ics18Context for MockContext {
 
 #[cfg(test)]
 mod tests {
+    use subtle_encoding::hex;
+
+    use crate::ics04_channel::context::ChannelReader;
     use crate::ics24_host::identifier::ChainId;
     use crate::mock::context::MockContext;
     use crate::mock::host::HostType;
+    use crate::timestamp::Timestamp;
     use crate::Height;
     use test_env_log::test;
 
+    #[test]
+    fn packet_and_ack_commitments_as_ibc_go() {
+        let ctx = MockContext::default();
+
+        // sha256(timeout timestamp || revision number || revision height || sha256(data))
+        let commitment = ctx.packet_commitment(
+            b"hello",
+            Height::new(1, 20),
+            Timestamp::from_nanoseconds(1000).unwrap(),
+        );
+        assert_eq!(
+            commitment,
+            hex::decode("78bb5bdfd497ef4f5f0b773b18c643f2aed070f8cb14410fee7c40b238854c74")
+                .unwrap()
+        );
+
+        // sha256(ack)
+        assert_eq!(
+            ctx.ack_commitment(b"ack"),
+            hex::decode("64a37929fb113e18daa6263a1fb1f90c51d262552efa5a50596f5f653ba955f8")
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_history_manipulation() {
         pub struct Test {
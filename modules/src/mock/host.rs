@@ -1,10 +1,10 @@
 //! Host chain types and methods, used by context mock.
 
-use std::convert::TryFrom;
-
-use tendermint::chain::Id as TMChainId;
 use tendermint_testgen::light_block::TmLightBlock;
-use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+use tendermint_testgen::{
+    Commit as TestgenCommit, Generator, Header as TestgenHeader, LightBlock as TestgenLightBlock,
+    Validator as TestgenValidator,
+};
 
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::header::AnyHeader;
@@ -59,11 +59,40 @@ impl HostBlock {
         }
     }
 
+    /// Generates a Tendermint light block at `height` for the given chain identifier. The chain
+    /// identifier is set before the commit is signed, so the block passes light client
+    /// verification. All blocks share the same validator set, and the block time is `height`
+    /// seconds after the Unix epoch.
     pub fn generate_tm_block(chain_id: ChainId, height: u64) -> TmLightBlock {
-        let mut block = TestgenLightBlock::new_default(height).generate().unwrap();
-        block.signed_header.header.chain_id = TMChainId::try_from(chain_id.to_string()).unwrap();
+        Self::generate_tm_block_with_time(chain_id, height, height)
+    }
+
+    /// Same as `generate_tm_block`, with the block time set to `time` seconds after the Unix
+    /// epoch. Useful to build conflicting blocks (e.g., evidence of misbehaviour).
+    pub fn generate_tm_block_with_time(chain_id: ChainId, height: u64, time: u64) -> TmLightBlock {
+        Self::generate_tm_block_with_validators(chain_id, height, time, &["1", "2"])
+    }
+
+    /// Same as `generate_tm_block_with_time`, with the block signed by validators of the given
+    /// identifiers, with equal voting power.
+    pub fn generate_tm_block_with_validators(
+        chain_id: ChainId,
+        height: u64,
+        time: u64,
+        validator_ids: &[&str],
+    ) -> TmLightBlock {
+        let validators: Vec<TestgenValidator> = validator_ids
+            .iter()
+            .map(|id| TestgenValidator::new(id).voting_power(50))
+            .collect();
+        let header = TestgenHeader::new(&validators)
+            .height(height)
+            .chain_id(chain_id.as_str())
+            .next_validators(&validators)
+            .time(time);
+        let commit = TestgenCommit::new(header.clone(), 1);
 
-        block
+        TestgenLightBlock::new(header, commit).generate().unwrap()
     }
 }
 
@@ -0,0 +1,66 @@
+//! Router mock, with a no-op application module bound to the ports of a mock context by default,
+//! and a transfer module.
+
+use std::collections::HashMap;
+
+use crate::application::ics20_fungible_token_transfer as ics20;
+use crate::application::ics20_fungible_token_transfer::module::TransferModule;
+use crate::ics26_routing::context::{AsAny, Module, ModuleId, Router};
+use crate::mock::bank::MockBank;
+
+/// Identifier of the `MockModule` registered by default with the router of a `MockContext`.
+pub const MOCK_MODULE_ID: &str = "mockmodule";
+
+/// An application module which accepts all channel handshakes, and does not acknowledge packets.
+#[derive(Clone, Debug, Default)]
+pub struct MockModule;
+
+impl Module for MockModule {}
+
+/// A router over the modules registered with a mock context.
+#[derive(Clone, Debug)]
+pub struct MockRouter(HashMap<ModuleId, Box<dyn Module>>);
+
+impl MockRouter {
+    /// Registers `module` under `module_id`, replacing any module previously registered there.
+    pub fn add_route(&mut self, module_id: ModuleId, module: impl Module + 'static) {
+        self.0.insert(module_id, Box::new(module));
+    }
+
+    /// Returns the module registered under `module_id`, if it is of type `M`.
+    pub fn module<M: Module>(&self, module_id: &ModuleId) -> Option<&M> {
+        self.0
+            .get(module_id)
+            .and_then(|module| AsAny::as_any(&**module).downcast_ref())
+    }
+
+    /// Returns a mutable reference to the module registered under `module_id`, if it is of type
+    /// `M`.
+    pub fn module_mut<M: Module>(&mut self, module_id: &ModuleId) -> Option<&mut M> {
+        self.0
+            .get_mut(module_id)
+            .and_then(|module| AsAny::as_any_mut(&mut **module).downcast_mut())
+    }
+}
+
+impl Default for MockRouter {
+    fn default() -> Self {
+        let mut router = Self(HashMap::new());
+        router.add_route(ModuleId::new(MOCK_MODULE_ID), MockModule);
+        router.add_route(
+            ModuleId::new(ics20::MODULE_ID_STR),
+            TransferModule::new(MockBank::default()),
+        );
+        router
+    }
+}
+
+impl Router for MockRouter {
+    fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module> {
+        self.0.get_mut(module_id).map(|module| module.as_mut())
+    }
+
+    fn has_route(&self, module_id: &ModuleId) -> bool {
+        self.0.contains_key(module_id)
+    }
+}
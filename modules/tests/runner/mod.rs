@@ -148,7 +148,7 @@ impl IbcTestRunner {
     }
 
     pub fn client_state(height: u64) -> AnyClientState {
-        AnyClientState::Mock(MockClientState(Self::mock_header(height)))
+        AnyClientState::Mock(MockClientState::new(Self::mock_header(height)))
     }
 
     pub fn consensus_state(height: u64) -> AnyConsensusState {
@@ -133,6 +133,8 @@ impl CompileCmd {
         let mut proto_paths = vec![
             format!("{}/../proto/definitions/mock", root),
             format!("{}/proto/cosmos/auth", sdk_dir.display()),
+            format!("{}/proto/cosmos/bank", sdk_dir.display()),
+            format!("{}/proto/cosmos/feegrant", sdk_dir.display()),
             format!("{}/proto/cosmos/gov", sdk_dir.display()),
             format!("{}/proto/cosmos/tx", sdk_dir.display()),
             format!("{}/proto/cosmos/base", sdk_dir.display()),
@@ -22,6 +22,11 @@ pub mod cosmos {
             include!("prost/cosmos.staking.v1beta1.rs");
         }
     }
+    pub mod bank {
+        pub mod v1beta1 {
+            include!("prost/cosmos.bank.v1beta1.rs");
+        }
+    }
     pub mod base {
         pub mod abci {
             pub mod v1beta1 {
@@ -95,6 +100,11 @@ pub mod cosmos {
             include!("prost/cosmos.gov.v1beta1.rs");
         }
     }
+    pub mod feegrant {
+        pub mod v1beta1 {
+            include!("prost/cosmos.feegrant.v1beta1.rs");
+        }
+    }
 }
 
 pub mod ibc {
@@ -20,14 +20,19 @@ pub struct ChainHandlePair {
 
 impl ChainHandlePair {
     /// Spawn the source and destination chain runtime from the configuration and chain identifiers,
-    /// and return the pair of associated handles.
+    /// and return the pair of associated handles. If the two identifiers are the same (e.g., for
+    /// localhost clients), a single runtime is spawned and both handles share it.
     pub fn spawn(
         config: &Config,
         src_chain_id: &ChainId,
         dst_chain_id: &ChainId,
     ) -> Result<Self, Error> {
         let src = spawn_chain_runtime(config, src_chain_id)?;
-        let dst = spawn_chain_runtime(config, dst_chain_id)?;
+        let dst = if dst_chain_id == src_chain_id {
+            src.clone()
+        } else {
+            spawn_chain_runtime(config, dst_chain_id)?
+        };
 
         Ok(ChainHandlePair { src, dst })
     }
@@ -16,11 +16,12 @@ use crate::config::Config;
 use crate::DEFAULT_CONFIG_PATH;
 
 use self::{
-    config::ConfigCmd, create::CreateCmds, keys::KeysCmd, listen::ListenCmd,
+    clear::ClearCmds, config::ConfigCmd, create::CreateCmds, keys::KeysCmd, listen::ListenCmd,
     misbehaviour::MisbehaviourCmd, query::QueryCmd, start::StartCmd, tx::TxCmd, update::UpdateCmds,
     upgrade::UpgradeCmds, version::VersionCmd,
 };
 
+mod clear;
 mod config;
 mod create;
 mod keys;
@@ -78,6 +79,10 @@ pub enum CliCmd {
     #[options(help = "Create and send IBC transactions")]
     Tx(TxCmd),
 
+    /// The `clear` subcommand
+    #[options(help = "Clear objects, such as the pending packets of a channel")]
+    Clear(ClearCmds),
+
     /// The `listen` subcommand
     #[options(help = "Listen to and display IBC events emitted by a chain")]
     Listen(ListenCmd),
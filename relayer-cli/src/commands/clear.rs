@@ -0,0 +1,16 @@
+//! `clear` subcommand
+use abscissa_core::{Command, Help, Options, Runnable};
+
+mod packets;
+
+/// `clear` subcommand
+#[derive(Command, Debug, Options, Runnable)]
+pub enum ClearCmds {
+    /// The `help` subcommand
+    #[options(help = "Get usage information")]
+    Help(Help<Self>),
+
+    /// The `clear packets` subcommand
+    #[options(help = "Relay the pending packets of a channel, in both directions")]
+    Packets(packets::ClearPacketsCmd),
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeSet;
+
+use abscissa_core::{Command, Options, Runnable};
+use serde::Serialize;
+
+use ibc::ics04_channel::packet::Sequence;
+use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer::chain::counterparty::counterparty_chain_from_channel;
+use ibc_relayer::link::{Link, LinkParameters, PacketOutcome, RelaySummary, SequenceRanges};
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::Output;
+use crate::error::{Error, Kind};
+use crate::prelude::*;
+
+/// This command relays the pending packets of a channel in both directions:
+/// 1. the receive and timeout messages of the packets sent from the channel, and from its
+///    counterparty channel,
+/// 2. the acknowledgements of the packets received on the channel, and on its counterparty.
+#[derive(Clone, Command, Debug, Options)]
+pub struct ClearPacketsCmd {
+    #[options(free, required, help = "identifier of the chain")]
+    chain_id: ChainId,
+
+    #[options(free, required, help = "identifier of the port")]
+    port_id: PortId,
+
+    #[options(free, required, help = "identifier of the channel")]
+    channel_id: ChannelId,
+
+    #[options(
+        help = "only clear the packets sent from the channel with the given sequences, e.g., `10..20,35` (inclusive ranges)",
+        short = "s"
+    )]
+    sequences: Option<SequenceRanges>,
+}
+
+/// The outcome of the relaying of the pending packets of a channel.
+#[derive(Debug, Serialize)]
+struct ClearedPackets {
+    packets: Vec<ClearedPacket>,
+    /// The requested sequences of the packets sent from the channel which were not pending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    not_pending: Option<String>,
+}
+
+/// The outcome of the relaying of a pending packet.
+#[derive(Debug, Serialize)]
+struct ClearedPacket {
+    /// The chain, port and channel from which the packet was sent
+    chain_id: ChainId,
+    port_id: PortId,
+    channel_id: ChannelId,
+    sequence: Sequence,
+    #[serde(flatten)]
+    outcome: PacketOutcome,
+}
+
+impl ClearPacketsCmd {
+    fn execute(&self) -> Result<ClearedPackets, Error> {
+        let config = app_config();
+
+        debug!("Options: {:?}", self);
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)?;
+        let counterparty_chain_id =
+            counterparty_chain_from_channel(chain.as_ref(), &self.channel_id, &self.port_id)
+                .map_err(|e| Kind::Query.context(e))?;
+        let counterparty_chain = if counterparty_chain_id == self.chain_id {
+            chain.clone()
+        } else {
+            spawn_chain_runtime(&config, &counterparty_chain_id)?
+        };
+
+        let opts = LinkParameters {
+            src_port_id: self.port_id.clone(),
+            src_channel_id: self.channel_id.clone(),
+        };
+        let mut link = Link::new_from_opts(chain, counterparty_chain, opts)
+            .map_err(|e| Kind::Tx.context(e))?;
+        let mut reversed = link.reversed();
+
+        // The sequences select packets sent from the channel: their receive and timeout
+        // messages are relayed by the link, and their acknowledgements by the reversed link.
+        // The packets sent from the counterparty channel are all cleared.
+        link.a_to_b.set_sequence_filter(self.sequences.clone());
+
+        // Relay the receive and timeout messages in both directions first, so that the
+        // acknowledgements of the packets they receive are relayed next
+        let channel_recvs = clear_recv_packets(&mut link)?;
+        let counterparty_recvs = clear_recv_packets(&mut reversed)?;
+
+        link.a_to_b.set_sequence_filter(None);
+        reversed.a_to_b.set_sequence_filter(self.sequences.clone());
+
+        let counterparty_acks = clear_ack_packets(&mut link)?;
+        let channel_acks = clear_ack_packets(&mut reversed)?;
+
+        // The requested packets which were found were pending, whatever their outcome
+        let not_pending = self.sequences.as_ref().and_then(|sequences| {
+            let found = channel_recvs
+                .iter()
+                .chain(channel_acks.iter())
+                .map(|packet| packet.sequence)
+                .collect::<BTreeSet<_>>();
+
+            sequences.excluding(&found).map(|s| s.to_string())
+        });
+
+        let packets = channel_recvs
+            .into_iter()
+            .chain(counterparty_recvs)
+            .chain(counterparty_acks)
+            .chain(channel_acks)
+            .collect();
+
+        Ok(ClearedPackets {
+            packets,
+            not_pending,
+        })
+    }
+}
+
+/// Relays the receive and timeout messages of the pending packets sent from the source of
+/// the `link`.
+fn clear_recv_packets(link: &mut Link) -> Result<Vec<ClearedPacket>, Error> {
+    let summary = link
+        .build_and_send_recv_packet_messages()
+        .map_err(|e| Kind::Tx.context(e))?;
+
+    let path = &link.a_to_b;
+    let src_channel_id = path.src_channel_id().map_err(|e| Kind::Tx.context(e))?;
+
+    Ok(cleared_packets(
+        &summary,
+        path.src_chain().id(),
+        path.src_port_id(),
+        src_channel_id,
+    ))
+}
+
+/// Relays the acknowledgements of the pending packets received on the source of the `link`,
+/// which were sent from its destination.
+fn clear_ack_packets(link: &mut Link) -> Result<Vec<ClearedPacket>, Error> {
+    let summary = link
+        .build_and_send_ack_packet_messages()
+        .map_err(|e| Kind::Tx.context(e))?;
+
+    let path = &link.a_to_b;
+    let dst_channel_id = path.dst_channel_id().map_err(|e| Kind::Tx.context(e))?;
+
+    Ok(cleared_packets(
+        &summary,
+        path.dst_chain().id(),
+        path.dst_port_id(),
+        dst_channel_id,
+    ))
+}
+
+fn cleared_packets(
+    summary: &RelaySummary,
+    chain_id: ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Vec<ClearedPacket> {
+    summary
+        .packet_outcomes()
+        .into_iter()
+        .map(|(sequence, outcome)| ClearedPacket {
+            chain_id: chain_id.clone(),
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+            outcome,
+        })
+        .collect()
+}
+
+impl Runnable for ClearPacketsCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(cleared) => Output::success(cleared).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
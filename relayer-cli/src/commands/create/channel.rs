@@ -53,6 +53,12 @@ pub struct CreateChannelCommand {
 
     #[options(help = "the version for the new channel")]
     version: Option<String>,
+
+    #[options(
+        help = "create new localhost clients, which track their host chain; `<chain-b-id>` must then be the same as `<chain-a-id>`",
+        no_short
+    )]
+    localhost: bool,
 }
 
 impl Runnable for CreateChannelCommand {
@@ -76,6 +82,13 @@ impl CreateChannelCommand {
             )
             .exit();
         }
+        if self.localhost && chain_b_id != &self.chain_a_id {
+            return Output::error(
+                "Option `--localhost` requires `<chain-b-id>` to be the same as `<chain-a-id>`"
+                    .to_string(),
+            )
+            .exit();
+        }
 
         let chains = ChainHandlePair::spawn(&config, &self.chain_a_id, chain_b_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
@@ -87,10 +100,18 @@ impl CreateChannelCommand {
             self.order
         );
 
-        let client_a = ForeignClient::new(chains.src.clone(), chains.dst.clone())
-            .unwrap_or_else(exit_with_unrecoverable_error);
-        let client_b = ForeignClient::new(chains.dst.clone(), chains.src.clone())
-            .unwrap_or_else(exit_with_unrecoverable_error);
+        let client_a = if self.localhost {
+            ForeignClient::new_localhost(chains.src.clone())
+        } else {
+            ForeignClient::new(chains.src.clone(), chains.dst.clone())
+        }
+        .unwrap_or_else(exit_with_unrecoverable_error);
+        let client_b = if self.localhost {
+            ForeignClient::new_localhost(chains.dst.clone())
+        } else {
+            ForeignClient::new(chains.dst.clone(), chains.src.clone())
+        }
+        .unwrap_or_else(exit_with_unrecoverable_error);
 
         // Create the connection.
         let con = Connection::new(client_a, client_b, connection_delay())
@@ -113,6 +134,14 @@ impl CreateChannelCommand {
     fn run_reusing_connection(&self) {
         let config = app_config();
 
+        if self.localhost {
+            return Output::error(
+                "Option `--localhost` is only valid when creating new clients with `<chain-b-id>`"
+                    .to_string(),
+            )
+            .exit();
+        }
+
         // Validate & spawn runtime for side a.
         let chain_a = spawn_chain_runtime(&config, &self.chain_a_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
@@ -141,8 +170,12 @@ impl CreateChannelCommand {
             .unwrap_or_else(exit_with_unrecoverable_error);
 
         // Spawn the runtime for side b.
-        let chain_b =
-            spawn_chain_runtime(&config, &chain_b_id).unwrap_or_else(exit_with_unrecoverable_error);
+        // A connection between localhost clients has both ends on chain_a.
+        let chain_b = if chain_b_id == self.chain_a_id {
+            chain_a.clone()
+        } else {
+            spawn_chain_runtime(&config, &chain_b_id).unwrap_or_else(exit_with_unrecoverable_error)
+        };
 
         // Create the foreign client handles.
         let client_a = ForeignClient::find(chain_b.clone(), chain_a.clone(), conn_end.client_id())
@@ -42,11 +42,18 @@ pub struct CreateConnectionCommand {
         no_short
     )]
     delay: u64,
+
+    #[options(
+        help = "create new localhost clients, which track their host chain; `<chain-b-id>` must then be the same as `<chain-a-id>`",
+        no_short
+    )]
+    localhost: bool,
 }
 
 // cargo run --bin hermes -- create connection ibc-0 ibc-1
 // cargo run --bin hermes -- create connection ibc-0 ibc-1 --delay 100
 // cargo run --bin hermes -- create connection ibc-0 --client-a-id 07-tendermint-0 --client-b-id 07-tendermint-0
+// cargo run --bin hermes -- create connection ibc-0 ibc-0 --localhost
 impl Runnable for CreateConnectionCommand {
     fn run(&self) {
         match &self.chain_b_id {
@@ -77,16 +84,31 @@ impl CreateConnectionCommand {
             )
             .exit();
         }
+        if self.localhost && chain_b_id != &self.chain_a_id {
+            return Output::error(
+                "Option `--localhost` requires `<chain-b-id>` to be the same as `<chain-a-id>`"
+                    .to_string(),
+            )
+            .exit();
+        }
 
         info!(
             "Creating new clients hosted on chains {} and {}",
             self.chain_a_id, chain_b_id
         );
 
-        let client_a = ForeignClient::new(chains.src.clone(), chains.dst.clone())
-            .unwrap_or_else(exit_with_unrecoverable_error);
-        let client_b = ForeignClient::new(chains.dst.clone(), chains.src.clone())
-            .unwrap_or_else(exit_with_unrecoverable_error);
+        let client_a = if self.localhost {
+            ForeignClient::new_localhost(chains.src.clone())
+        } else {
+            ForeignClient::new(chains.src.clone(), chains.dst.clone())
+        }
+        .unwrap_or_else(exit_with_unrecoverable_error);
+        let client_b = if self.localhost {
+            ForeignClient::new_localhost(chains.dst.clone())
+        } else {
+            ForeignClient::new(chains.dst.clone(), chains.src.clone())
+        }
+        .unwrap_or_else(exit_with_unrecoverable_error);
 
         // Finally, execute the connection handshake.
         let delay = Duration::from_secs(self.delay);
@@ -100,6 +122,14 @@ impl CreateConnectionCommand {
     fn run_reusing_clients(&self) {
         let config = app_config();
 
+        if self.localhost {
+            return Output::error(
+                "Option `--localhost` is only valid when creating new clients with `<chain-b-id>`"
+                    .to_string(),
+            )
+            .exit();
+        }
+
         // Validate & spawn runtime for chain_a.
         let chain_a = match spawn_chain_runtime(&config, &self.chain_a_id) {
             Ok(handle) => handle,
@@ -130,10 +160,15 @@ impl CreateConnectionCommand {
             }
         };
 
-        // Validate & spawn runtime for chain_b.
-        let chain_b = match spawn_chain_runtime(&config, &chain_b_id) {
-            Ok(handle) => handle,
-            Err(e) => return Output::error(format!("{}", e)).exit(),
+        // Validate & spawn runtime for chain_b, unless both clients are localhost clients of
+        // chain_a, in which case the runtime for chain_a is shared.
+        let chain_b = if chain_b_id == self.chain_a_id {
+            chain_a.clone()
+        } else {
+            match spawn_chain_runtime(&config, &chain_b_id) {
+                Ok(handle) => handle,
+                Err(e) => return Output::error(format!("{}", e)).exit(),
+            }
         };
 
         // Unwrap the identifier of the client on chain_b.
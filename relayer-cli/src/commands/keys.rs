@@ -3,6 +3,7 @@ use abscissa_core::{Command, Help, Options, Runnable};
 
 mod add;
 mod list;
+mod migrate;
 mod restore;
 
 /// `keys` subcommand
@@ -23,4 +24,8 @@ pub enum KeysCmd {
     /// The `keys restore` subcommand
     #[options(help = "restore a key to a configured chain using a mnemonic")]
     Restore(restore::KeyRestoreCmd),
+
+    /// The `keys migrate` subcommand
+    #[options(help = "encrypt the plaintext keys of a configured chain")]
+    Migrate(migrate::KeysMigrateCmd),
 }
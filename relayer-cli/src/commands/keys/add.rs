@@ -10,7 +10,7 @@ use anomaly::BoxError;
 use ibc::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{HDPath, KeyEntry, KeyRing, Store},
+    keyring::{HDPath, KeyEntry, KeyRing},
 };
 
 use crate::application::app_config;
@@ -32,10 +32,9 @@ pub struct KeysAddCmd {
 
     #[options(
         short = "p",
-        help = "derivation path for this key",
-        default = "m/44'/118'/0'/0/0"
+        help = "derivation path for this key (defaults to the `hd_path` defined in the config)"
     )]
-    hd_path: String,
+    hd_path: Option<String>,
 }
 
 impl KeysAddCmd {
@@ -49,8 +48,11 @@ impl KeysAddCmd {
             .clone()
             .unwrap_or_else(|| chain_config.key_name.clone());
 
-        let hd_path = HDPath::from_str(&self.hd_path)
-            .map_err(|_| format!("invalid derivation path: {}", self.hd_path))?;
+        let hd_path = match &self.hd_path {
+            Some(hd_path) => HDPath::from_str(hd_path)
+                .map_err(|_| format!("invalid derivation path: {}", hd_path))?,
+            None => chain_config.key_hd_path()?,
+        };
 
         Ok(KeysAddOptions {
             config: chain_config.clone(),
@@ -97,7 +99,7 @@ pub fn add_key(
     file: &Path,
     hd_path: &HDPath,
 ) -> Result<KeyEntry, BoxError> {
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut keyring = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)?;
 
     let key_contents = fs::read_to_string(file).map_err(|_| "error reading the key file")?;
     let key = keyring.key_from_seed_file(&key_contents, hd_path)?;
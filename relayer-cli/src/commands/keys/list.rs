@@ -6,7 +6,7 @@ use anomaly::BoxError;
 use ibc::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{KeyEntry, KeyRing, Store},
+    keyring::{KeyEntry, KeyRing},
 };
 
 use crate::conclude::Output;
@@ -62,7 +62,7 @@ pub struct KeysListOptions {
 }
 
 pub fn list_keys(config: ChainConfig) -> Result<Vec<(String, KeyEntry)>, BoxError> {
-    let keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let keyring = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)?;
     let keys = keyring.keys()?;
     Ok(keys)
 }
@@ -0,0 +1,86 @@
+use abscissa_core::{Command, Options, Runnable};
+use anomaly::BoxError;
+
+use ibc::ics24_host::identifier::ChainId;
+use ibc_relayer::{
+    config::{ChainConfig, Config},
+    keyring::{self, KeyRing, Store},
+};
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct KeysMigrateCmd {
+    #[options(free, required, help = "identifier of the chain")]
+    chain_id: ChainId,
+
+    #[options(
+        short = "d",
+        help = "delete the plaintext key files once their keys are encrypted"
+    )]
+    delete_plaintext: bool,
+}
+
+impl KeysMigrateCmd {
+    fn options(&self, config: &Config) -> Result<KeysMigrateOptions, BoxError> {
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))?;
+
+        Ok(KeysMigrateOptions {
+            config: chain_config.clone(),
+            delete_plaintext: self.delete_plaintext,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeysMigrateOptions {
+    pub config: ChainConfig,
+    pub delete_plaintext: bool,
+}
+
+impl Runnable for KeysMigrateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.options(&config) {
+            Err(err) => return Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        match migrate_keys(&opts.config, opts.delete_plaintext) {
+            Ok(migrated) => Output::success_msg(format!(
+                "Encrypted keys {:?} of chain {}; set `key_store_type = 'encrypted'` in its \
+                configuration to use them",
+                migrated, opts.config.id
+            ))
+            .exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Encrypts the keys of the test key store of the chain into its encrypted key store,
+/// then deletes the plaintext key files if `delete_plaintext` is set.
+pub fn migrate_keys(config: &ChainConfig, delete_plaintext: bool) -> Result<Vec<String>, BoxError> {
+    let mut test = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut encrypted = KeyRing::new(Store::Encrypted, &config.account_prefix, &config.id)?;
+
+    let migrated = keyring::migrate_keys(&test, &mut encrypted)?;
+
+    if delete_plaintext {
+        // Decrypt the keys from their files again, rather than trusting the decrypted copies
+        let encrypted = encrypted.reloaded();
+
+        for (name, key) in test.keys()? {
+            // Only delete the keys which are in the encrypted store, migrated now or before
+            if encrypted.get_key(&name)? == key {
+                test.remove_key(&name)?;
+            }
+        }
+    }
+
+    Ok(migrated)
+}
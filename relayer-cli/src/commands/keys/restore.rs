@@ -6,7 +6,7 @@ use anomaly::BoxError;
 use ibc::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{HDPath, KeyEntry, KeyRing, Store},
+    keyring::{HDPath, KeyEntry, KeyRing},
 };
 
 use crate::application::app_config;
@@ -28,10 +28,9 @@ pub struct KeyRestoreCmd {
 
     #[options(
         short = "p",
-        help = "derivation path for this key",
-        default = "m/44'/118'/0'/0/0"
+        help = "derivation path for this key (defaults to the `hd_path` defined in the config)"
     )]
-    hd_path: String,
+    hd_path: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -48,8 +47,11 @@ impl KeyRestoreCmd {
             .find_chain(&self.chain_id)
             .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))?;
 
-        let hd_path = HDPath::from_str(&self.hd_path)
-            .map_err(|_| format!("invalid derivation path: {}", self.hd_path))?;
+        let hd_path = match &self.hd_path {
+            Some(hd_path) => HDPath::from_str(hd_path)
+                .map_err(|_| format!("invalid derivation path: {}", hd_path))?,
+            None => chain_config.key_hd_path().map_err(|e| e.to_string())?,
+        };
 
         let key_name = self
             .name
@@ -93,8 +95,8 @@ pub fn restore_key(
     hdpath: &HDPath,
     config: &ChainConfig,
 ) -> Result<KeyEntry, BoxError> {
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
-    let key_entry = keyring.key_from_mnemonic(mnemonic, hdpath)?;
+    let mut keyring = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)?;
+    let key_entry = keyring.key_from_mnemonic(mnemonic, hdpath, config.key_type)?;
 
     keyring.add_key(key_name, key_entry.clone())?;
     Ok(key_entry)
@@ -139,12 +139,9 @@ fn subscribe(
     chain_config: &ChainConfig,
     rt: Arc<TokioRuntime>,
 ) -> Result<(EventMonitor, EventReceiver), BoxError> {
-    let (mut event_monitor, rx, _) = EventMonitor::new(
-        chain_config.id.clone(),
-        chain_config.websocket_addr.clone(),
-        rt,
-    )
-    .map_err(|e| format!("could not initialize event monitor: {}", e))?;
+    let (mut event_monitor, rx, _) =
+        EventMonitor::new(chain_config.id.clone(), chain_config.websocket_addrs(), rt)
+            .map_err(|e| format!("could not initialize event monitor: {}", e))?;
 
     event_monitor
         .subscribe()
@@ -5,6 +5,7 @@ use tokio::runtime::Runtime as TokioRuntime;
 
 use ibc::ics24_host::identifier::{ChainId, PortChannelId};
 use ibc_proto::ibc::core::channel::v1::QueryChannelsRequest;
+use ibc_relayer::chain::pagination::Pages;
 use ibc_relayer::chain::{Chain, CosmosSdkChain};
 
 use crate::conclude::Output;
@@ -37,11 +38,9 @@ impl Runnable for QueryChannelsCmd {
         let rt = Arc::new(TokioRuntime::new().unwrap());
         let chain = CosmosSdkChain::bootstrap(chain_config.clone(), rt).unwrap();
 
-        let req = QueryChannelsRequest {
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
-        };
+        let req = QueryChannelsRequest { pagination: None };
 
-        let res = chain.query_channels(req);
+        let res = Pages::new(req, Box::new(|req| chain.query_channels(req))).collect_items();
 
         match res {
             Ok(channels) => {
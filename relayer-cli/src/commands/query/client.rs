@@ -13,6 +13,7 @@ use ibc::query::QueryTxRequest;
 use ibc::Height;
 use ibc_proto::ibc::core::client::v1::QueryConsensusStatesRequest;
 use ibc_proto::ibc::core::connection::v1::QueryClientConnectionsRequest;
+use ibc_relayer::chain::pagination::{self, Pages};
 use ibc_relayer::chain::Chain;
 use ibc_relayer::chain::CosmosSdkChain;
 
@@ -129,10 +130,17 @@ impl Runnable for QueryClientConsensusCmd {
                 }
             }
             None => {
-                let res = chain.query_consensus_states(QueryConsensusStatesRequest {
+                let req = QueryConsensusStatesRequest {
                     client_id: self.client_id.to_string(),
-                    pagination: ibc_proto::cosmos::base::query::pagination::all(),
-                });
+                    pagination: None,
+                };
+
+                let res = Pages::new(req, Box::new(|req| chain.query_consensus_states(req)))
+                    .collect_items()
+                    .map(|mut states| {
+                        pagination::sort_consensus_states(&mut states);
+                        states
+                    });
 
                 match res {
                     Ok(states) => {
@@ -7,6 +7,7 @@ use tokio::runtime::Runtime as TokioRuntime;
 use ibc::ics02_client::client_state::ClientState;
 use ibc::ics24_host::identifier::{ChainId, ClientId};
 use ibc_proto::ibc::core::client::v1::QueryClientStatesRequest;
+use ibc_relayer::chain::pagination::{self, Pages};
 use ibc_relayer::chain::{Chain, CosmosSdkChain};
 
 use crate::conclude::Output;
@@ -60,12 +61,14 @@ impl Runnable for QueryAllClientsCmd {
         let rt = Arc::new(TokioRuntime::new().unwrap());
         let chain = CosmosSdkChain::bootstrap(chain_config.clone(), rt).unwrap();
 
-        let req = QueryClientStatesRequest {
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
-        };
+        let req = QueryClientStatesRequest { pagination: None };
 
-        let res: Result<_, Error> = chain
-            .query_clients(req)
+        let res: Result<_, Error> = Pages::new(req, Box::new(|req| chain.query_clients(req)))
+            .collect_items()
+            .map(|mut clients| {
+                pagination::sort_clients(&mut clients);
+                clients
+            })
             .map_err(|e| Kind::Query.context(e).into());
 
         match res {
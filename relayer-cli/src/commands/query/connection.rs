@@ -9,6 +9,7 @@ use ibc::{
     ics24_host::identifier::{ChainId, PortChannelId},
 };
 use ibc_proto::ibc::core::channel::v1::QueryConnectionChannelsRequest;
+use ibc_relayer::chain::pagination::Pages;
 use ibc_relayer::chain::{Chain, CosmosSdkChain};
 
 use crate::conclude::Output;
@@ -101,12 +102,13 @@ impl Runnable for QueryConnectionChannelsCmd {
 
         let req = QueryConnectionChannelsRequest {
             connection: self.connection_id.to_string(),
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            pagination: None,
         };
 
-        let res: Result<_, Error> = chain
-            .query_connection_channels(req)
-            .map_err(|e| Kind::Query.context(e).into());
+        let res: Result<_, Error> =
+            Pages::new(req, Box::new(|req| chain.query_connection_channels(req)))
+                .collect_items()
+                .map_err(|e| Kind::Query.context(e).into());
 
         match res {
             Ok(channels) => {
@@ -5,6 +5,7 @@ use tokio::runtime::Runtime as TokioRuntime;
 
 use ibc::ics24_host::identifier::{ChainId, ConnectionId};
 use ibc_proto::ibc::core::connection::v1::QueryConnectionsRequest;
+use ibc_relayer::chain::pagination::Pages;
 use ibc_relayer::chain::{Chain, CosmosSdkChain};
 
 use crate::conclude::Output;
@@ -37,11 +38,9 @@ impl Runnable for QueryConnectionsCmd {
         let rt = Arc::new(TokioRuntime::new().unwrap());
         let chain = CosmosSdkChain::bootstrap(chain_config.clone(), rt).unwrap();
 
-        let req = QueryConnectionsRequest {
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
-        };
+        let req = QueryConnectionsRequest { pagination: None };
 
-        let res = chain.query_connections(req);
+        let res = Pages::new(req, Box::new(|req| chain.query_connections(req))).collect_items();
 
         match res {
             Ok(connections) => {
@@ -39,7 +39,7 @@ impl QueryPacketAcknowledgementsCmd {
         let grpc_request = QueryPacketAcknowledgementsRequest {
             port_id: self.port_id.to_string(),
             channel_id: self.channel_id.to_string(),
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            pagination: None,
         };
 
         // Transform the list fo raw packet state into the list of sequence numbers
@@ -39,7 +39,7 @@ impl QueryPacketCommitmentsCmd {
         let grpc_request = QueryPacketCommitmentsRequest {
             port_id: self.port_id.to_string(),
             channel_id: self.channel_id.to_string(),
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            pagination: None,
         };
 
         chain
@@ -69,7 +69,7 @@ impl QueryUnreceivedAcknowledgementCmd {
         let acks_request = QueryPacketAcknowledgementsRequest {
             port_id: channel.channel_end.counterparty().port_id.to_string(),
             channel_id: counterparty_channel_id,
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            pagination: None,
         };
 
         let sequences: Vec<u64> = counterparty_chain
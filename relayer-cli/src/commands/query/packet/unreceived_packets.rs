@@ -77,7 +77,7 @@ impl QueryUnreceivedPacketsCmd {
         let commitments_request = QueryPacketCommitmentsRequest {
             port_id: channel.channel_end.counterparty().port_id.to_string(),
             channel_id: counterparty_channel_id,
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            pagination: None,
         };
 
         let commitments = counterparty_chain
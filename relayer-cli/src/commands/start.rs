@@ -14,18 +14,28 @@ use crate::conclude::Output;
 use crate::prelude::*;
 
 #[derive(Clone, Command, Debug, Options)]
-pub struct StartCmd {}
+pub struct StartCmd {
+    #[options(
+        help = "only simulate the packet transactions to estimate their gas and fee, without signing or broadcasting them"
+    )]
+    dry_run: bool,
+}
 
 impl Runnable for StartCmd {
     fn run(&self) {
         let config = (*app_config()).clone();
         let config = Arc::new(RwLock::new(config));
 
-        let (supervisor, tx_cmd) = make_supervisor(config.clone()).unwrap_or_else(|e| {
+        let (mut supervisor, tx_cmd) = make_supervisor(config.clone()).unwrap_or_else(|e| {
             Output::error(format!("Hermes failed to start, last error: {}", e)).exit();
             unreachable!()
         });
 
+        if self.dry_run {
+            info!("dry run: the packet transactions are only simulated, nothing is relayed");
+            supervisor.set_dry_run(true);
+        }
+
         match crate::config::config_path() {
             Some(config_path) => {
                 let reload = ConfigReload::new(config_path, config, tx_cmd.clone());
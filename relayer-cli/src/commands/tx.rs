@@ -2,7 +2,10 @@
 use abscissa_core::{config::Override, Command, Help, Options, Runnable};
 use ibc_relayer::config::Config;
 
-use crate::commands::tx::client::{TxCreateClientCmd, TxUpdateClientCmd, TxUpgradeClientCmd};
+use crate::commands::tx::client::{
+    TxCreateClientCmd, TxCreateSoloMachineClientCmd, TxUpdateClientCmd,
+    TxUpdateSoloMachineClientCmd, TxUpgradeClientCmd,
+};
 
 mod channel;
 pub(crate) mod client;
@@ -42,6 +45,14 @@ pub enum TxRawCommands {
     #[options(help = "Upgrade the specified client on destination chain")]
     UpgradeClient(TxUpgradeClientCmd),
 
+    /// The `tx raw create-solo-machine-client` subcommand submits a MsgCreateClient for a solo machine
+    #[options(help = "Create a client for a solo machine on destination chain")]
+    CreateSoloMachineClient(TxCreateSoloMachineClientCmd),
+
+    /// The `tx raw update-solo-machine-client` subcommand submits a MsgUpdateClient signed by a solo machine
+    #[options(help = "Update the specified solo machine client on destination chain")]
+    UpdateSoloMachineClient(TxUpdateSoloMachineClientCmd),
+
     /// The `tx raw conn-init` subcommand
     #[options(help = "Initialize a connection (ConnectionOpenInit)")]
     ConnInit(connection::TxRawConnInitCmd),
@@ -1,9 +1,12 @@
 use abscissa_core::{Command, Options, Runnable};
 
 use ibc::events::IbcEvent;
-use ibc::ics02_client::client_state::ClientState;
+use ibc::ics02_client::client_state::{AnyClientState, ClientState};
 use ibc::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer::config::Config;
 use ibc_relayer::foreign_client::ForeignClient;
+use ibc_relayer::keyring::KeyRing;
+use ibc_relayer::solo_machine::{self, SoloMachine};
 
 use crate::application::app_config;
 use crate::cli_utils::{spawn_chain_runtime, ChainHandlePair};
@@ -17,16 +20,34 @@ pub struct TxCreateClientCmd {
 
     #[options(free, required, help = "identifier of the source chain")]
     src_chain_id: ChainId,
+
+    #[options(
+        help = "create a localhost client, which tracks its host chain; the source and destination chains must be the same",
+        no_short
+    )]
+    localhost: bool,
 }
 
 /// Sample to run this tx:
 ///     `hermes tx raw create-client ibc-0 ibc-1`
+/// To create a localhost client, the destination and source chains must be the same:
+///     `hermes tx raw create-client ibc-0 ibc-0 --localhost`
 impl Runnable for TxCreateClientCmd {
     fn run(&self) {
         let config = app_config();
 
-        if self.src_chain_id == self.dst_chain_id {
-            Output::error("source and destination chains must be different".to_string()).exit()
+        if self.localhost && self.src_chain_id != self.dst_chain_id {
+            return Output::error(
+                "source and destination chains must be the same for a localhost client".to_string(),
+            )
+            .exit();
+        }
+        if !self.localhost && self.src_chain_id == self.dst_chain_id {
+            return Output::error(
+                "source and destination chains must be different, unless `--localhost` is set"
+                    .to_string(),
+            )
+            .exit();
         }
 
         let chains = match ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id) {
@@ -37,9 +58,12 @@ impl Runnable for TxCreateClientCmd {
         let client = ForeignClient::restore(ClientId::default(), chains.dst, chains.src);
 
         // Trigger client creation via the "build" interface, so that we obtain the resulting event
-        let res: Result<IbcEvent, Error> = client
-            .build_create_client_and_send()
-            .map_err(|e| Kind::Tx.context(e).into());
+        let res = if self.localhost {
+            client.build_create_localhost_client_and_send()
+        } else {
+            client.build_create_client_and_send()
+        };
+        let res: Result<IbcEvent, Error> = res.map_err(|e| Kind::Tx.context(e).into());
 
         match res {
             Ok(receipt) => Output::success(receipt).exit(),
@@ -163,3 +187,160 @@ impl Runnable for TxUpgradeClientCmd {
         }
     }
 }
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct TxCreateSoloMachineClientCmd {
+    #[options(free, required, help = "identifier of the destination chain")]
+    dst_chain_id: ChainId,
+
+    #[options(
+        required,
+        help = "name of the key of the solo machine, in the keyring of the destination chain",
+        short = "k"
+    )]
+    key_name: String,
+
+    #[options(help = "diversifier of the solo machine (default: empty)", short = "d")]
+    diversifier: Option<String>,
+}
+
+/// Sample to run this tx:
+///     `hermes tx raw create-solo-machine-client ibc-0 -k solo -d hermes`
+impl Runnable for TxCreateSoloMachineClientCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let solo_machine = match build_solo_machine(
+            &config,
+            &self.dst_chain_id,
+            &self.key_name,
+            self.diversifier.clone().unwrap_or_default(),
+        ) {
+            Ok(solo_machine) => solo_machine,
+            Err(e) => return Output::error(e).exit(),
+        };
+
+        let dst_chain = match spawn_chain_runtime(&config, &self.dst_chain_id) {
+            Ok(handle) => handle,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
+        let res: Result<IbcEvent, Error> =
+            solo_machine::create_client(dst_chain.as_ref(), &solo_machine)
+                .map_err(|e| Kind::Tx.context(e).into());
+
+        match res {
+            Ok(receipt) => Output::success(receipt).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct TxUpdateSoloMachineClientCmd {
+    #[options(free, required, help = "identifier of the destination chain")]
+    dst_chain_id: ChainId,
+
+    #[options(
+        free,
+        required,
+        help = "identifier of the solo machine client to be updated on destination chain"
+    )]
+    dst_client_id: ClientId,
+
+    #[options(
+        required,
+        help = "name of the current key of the solo machine, in the keyring of the destination chain",
+        short = "k"
+    )]
+    key_name: String,
+
+    #[options(help = "name of the new key of the solo machine (default: the current key)")]
+    new_key: Option<String>,
+
+    #[options(help = "new diversifier of the solo machine (default: the current diversifier)")]
+    new_diversifier: Option<String>,
+}
+
+/// Sample to run this tx, which rotates the key of the solo machine from `solo` to `solo-2`:
+///     `hermes tx raw update-solo-machine-client ibc-0 06-solomachine-0 -k solo --new-key solo-2`
+impl Runnable for TxUpdateSoloMachineClientCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let dst_chain = match spawn_chain_runtime(&config, &self.dst_chain_id) {
+            Ok(handle) => handle,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
+        // The header is signed over the diversifier currently tracked by the client.
+        let diversifier =
+            match dst_chain.query_client_state(&self.dst_client_id, ibc::Height::zero()) {
+                Ok(AnyClientState::SoloMachine(cs)) => cs.consensus_state.diversifier,
+                Ok(cs) => {
+                    return Output::error(format!(
+                        "client '{}' on chain '{}' is a {} client, not a solo machine client",
+                        self.dst_client_id,
+                        self.dst_chain_id,
+                        cs.client_type()
+                    ))
+                    .exit()
+                }
+                Err(e) => {
+                    return Output::error(format!(
+                        "Query of client '{}' on chain '{}' failed with error: {}",
+                        self.dst_client_id, self.dst_chain_id, e
+                    ))
+                    .exit()
+                }
+            };
+
+        let current = build_solo_machine(
+            &config,
+            &self.dst_chain_id,
+            &self.key_name,
+            diversifier.clone(),
+        );
+        let next = build_solo_machine(
+            &config,
+            &self.dst_chain_id,
+            self.new_key.as_ref().unwrap_or(&self.key_name),
+            self.new_diversifier.clone().unwrap_or(diversifier),
+        );
+
+        let (current, next) = match (current, next) {
+            (Ok(current), Ok(next)) => (current, next),
+            (Err(e), _) | (_, Err(e)) => return Output::error(e).exit(),
+        };
+
+        let res: Result<IbcEvent, Error> =
+            solo_machine::update_client(dst_chain.as_ref(), &self.dst_client_id, &current, &next)
+                .map_err(|e| Kind::Tx.context(e).into());
+
+        match res {
+            Ok(receipt) => Output::success(receipt).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Builds the solo machine whose key `key_name` is held in the keyring of chain `chain_id`.
+fn build_solo_machine(
+    config: &Config,
+    chain_id: &ChainId,
+    key_name: &str,
+    diversifier: String,
+) -> Result<SoloMachine, String> {
+    let chain_config = config
+        .find_chain(chain_id)
+        .ok_or_else(|| format!("chain '{}' not found in configuration file", chain_id))?;
+
+    let keyring = KeyRing::new(
+        chain_config.key_store_type,
+        &chain_config.account_prefix,
+        &chain_config.id,
+    )
+    .map_err(|e| format!("could not open the keyring of chain '{}': {}", chain_id, e))?;
+
+    Ok(SoloMachine::new(keyring, key_name.to_string(), diversifier))
+}
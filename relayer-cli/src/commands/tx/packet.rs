@@ -1,8 +1,7 @@
 use abscissa_core::{Command, Options, Runnable};
 
-use ibc::events::IbcEvent;
 use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
-use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::link::{Link, LinkParameters, RelaySummary};
 
 use crate::cli_utils::ChainHandlePair;
 use crate::conclude::Output;
@@ -22,6 +21,11 @@ pub struct TxRawPacketRecvCmd {
 
     #[options(free, required, help = "identifier of the source channel")]
     src_channel_id: ChannelId,
+
+    #[options(
+        help = "only simulate the transactions to estimate their gas and fee, without signing or broadcasting them"
+    )]
+    dry_run: bool,
 }
 
 impl Runnable for TxRawPacketRecvCmd {
@@ -41,13 +45,14 @@ impl Runnable for TxRawPacketRecvCmd {
             Ok(link) => link,
             Err(e) => return Output::error(format!("{}", e)).exit(),
         };
+        link.a_to_b.set_dry_run(self.dry_run);
 
-        let res: Result<Vec<IbcEvent>, Error> = link
+        let res: Result<RelaySummary, Error> = link
             .build_and_send_recv_packet_messages()
             .map_err(|e| Kind::Tx.context(e).into());
 
         match res {
-            Ok(ev) => Output::success(ev).exit(),
+            Ok(summary) => Output::success(summary).exit(),
             Err(e) => Output::error(format!("{}", e)).exit(),
         }
     }
@@ -66,6 +71,11 @@ pub struct TxRawPacketAckCmd {
 
     #[options(free, required, help = "identifier of the source channel")]
     src_channel_id: ChannelId,
+
+    #[options(
+        help = "only simulate the transactions to estimate their gas and fee, without signing or broadcasting them"
+    )]
+    dry_run: bool,
 }
 
 impl Runnable for TxRawPacketAckCmd {
@@ -85,13 +95,14 @@ impl Runnable for TxRawPacketAckCmd {
             Ok(link) => link,
             Err(e) => return Output::error(format!("{}", e)).exit(),
         };
+        link.a_to_b.set_dry_run(self.dry_run);
 
-        let res: Result<Vec<IbcEvent>, Error> = link
+        let res: Result<RelaySummary, Error> = link
             .build_and_send_ack_packet_messages()
             .map_err(|e| Kind::Tx.context(e).into());
 
         match res {
-            Ok(ev) => Output::success(ev).exit(),
+            Ok(summary) => Output::success(summary).exit(),
             Err(e) => Output::error(format!("{}", e)).exit(),
         }
     }
@@ -12,6 +12,7 @@ use thiserror::Error;
 use ibc::ics24_host::identifier::ChainId;
 use tendermint_light_client::types::TrustThreshold;
 
+use ibc_relayer::config::ChainConfig;
 pub use ibc_relayer::config::Config;
 
 use crate::application::app_reader;
@@ -41,6 +42,10 @@ pub enum Error {
     /// Invalid trust threshold
     #[error("config file specifies an invalid trust threshold ({0}) for the chain with id {1}, caused by: {2}")]
     InvalidTrustThreshold(TrustThreshold, ChainId, String),
+
+    /// Invalid fee payer
+    #[error("config file specifies a fee payer ({0}) for the chain with id {1} which cannot sign its transactions, caused by: {2}")]
+    InvalidFeePayer(String, ChainId, String),
 }
 
 /// Method for syntactic validation of the input configuration file.
@@ -53,6 +58,7 @@ pub fn validate_config(config: &Config) -> Result<(), Error> {
         }
 
         validate_trust_threshold(&c.id, c.trust_threshold)?;
+        validate_fee_payer(c)?;
     }
 
     Ok(())
@@ -90,3 +96,32 @@ fn validate_trust_threshold(id: &ChainId, trust_threshold: TrustThreshold) -> Re
 
     Ok(())
 }
+
+/// Check that the fee payer, if any, can sign the transactions of the chain. The fee payer
+/// must be among the signers of a transaction, and the relayer's transactions are signed by a
+/// single key, so the fee payer can only be the account of that key. Therefore no other keys
+/// may be configured. The account of the key itself is checked once the chain is started.
+fn validate_fee_payer(chain: &ChainConfig) -> Result<(), Error> {
+    let fee_payer = match &chain.fee_payer {
+        Some(fee_payer) => fee_payer,
+        None => return Ok(()),
+    };
+
+    if fee_payer.is_empty() {
+        return Err(Error::InvalidFeePayer(
+            fee_payer.clone(),
+            chain.id.clone(),
+            "the fee payer cannot be empty".to_string(),
+        ));
+    }
+
+    if chain.key_names().len() > 1 {
+        return Err(Error::InvalidFeePayer(
+            fee_payer.clone(),
+            chain.id.clone(),
+            "the transactions of the extra keys are not signed by the fee payer".to_string(),
+        ));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use prost_types::Any;
+use serde::Serialize;
 use tendermint::block::Height;
 use tokio::runtime::Runtime as TokioRuntime;
 
@@ -34,6 +36,7 @@ use ibc_proto::ibc::core::connection::v1::{
     QueryClientConnectionsRequest, QueryConnectionsRequest,
 };
 
+use crate::chain::pagination::Page;
 use crate::connection::ConnectionMsgType;
 use crate::error::{Error, Kind};
 use crate::event::monitor::TxMonitorCmd;
@@ -41,9 +44,12 @@ use crate::keyring::{KeyEntry, KeyRing};
 use crate::light_client::LightClient;
 use crate::{config::ChainConfig, event::monitor::EventReceiver};
 
+pub mod broadcaster;
 pub(crate) mod cosmos;
 pub mod counterparty;
+pub mod endpoints;
 pub mod handle;
+pub mod pagination;
 pub mod runtime;
 
 #[cfg(test)]
@@ -58,6 +64,46 @@ pub struct QueryResponse {
     pub height: Height,
 }
 
+/// The outcome of sending messages to a chain: the events which they generated, with the
+/// receipts of the transactions which included them.
+#[derive(Clone, Debug, Default)]
+pub struct SentMsgs {
+    pub events: Vec<IbcEvent>,
+    pub txs: Vec<TxReceipt>,
+    /// Time spent signing and broadcasting the transactions
+    pub broadcast_time: Duration,
+    /// Time spent waiting for the transactions to be committed
+    pub confirm_time: Duration,
+}
+
+/// The receipt of a transaction sent to a chain.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TxReceipt {
+    pub hash: String,
+    pub msg_count: usize,
+    pub gas_wanted: u64,
+    /// The gas used by the transaction, if it was committed
+    pub gas_used: Option<u64>,
+    /// The fee paid for the transaction, e.g., `2500stake`
+    pub fee: String,
+    /// The error of the transaction, if it failed
+    pub error: Option<String>,
+}
+
+/// The estimated cost of a transaction which would be sent to a chain, from its simulation.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TxEstimate {
+    pub msg_count: usize,
+    /// The gas used by the simulation of the transaction
+    pub gas_estimate: u64,
+    /// The gas limit of the transaction, once the gas adjustment is applied
+    pub gas_wanted: u64,
+    /// The fee the transaction would pay, e.g., `2500stake`
+    pub fee: String,
+    /// The error of the simulation, if it failed
+    pub error: Option<String>,
+}
+
 /// Packet query options
 #[derive(Debug)]
 pub struct QueryPacketOptions {
@@ -107,10 +153,34 @@ pub trait Chain: Sized {
     /// Sends one or more transactions with `msgs` to chain.
     fn send_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error>;
 
+    /// Sends one or more transactions with `msgs` to chain, and returns their events with the
+    /// receipts of the transactions. Chains which do not track transactions return no receipts.
+    fn send_msgs_with_receipts(&mut self, proto_msgs: Vec<Any>) -> Result<SentMsgs, Error> {
+        let start = Instant::now();
+        let events = self.send_msgs(proto_msgs)?;
+
+        Ok(SentMsgs {
+            events,
+            broadcast_time: start.elapsed(),
+            ..SentMsgs::default()
+        })
+    }
+
+    /// Simulates the transactions with `msgs` which `send_msgs` would send to the chain, without
+    /// signing or broadcasting them, and returns their estimated gas and fee.
+    fn simulate_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<TxEstimate>, Error>;
+
     fn get_signer(&mut self) -> Result<Signer, Error>;
 
     fn get_key(&mut self) -> Result<KeyEntry, Error>;
 
+    /// Selects the configured key with which the next transactions are signed, and which
+    /// `get_signer` and `get_key` return. `None` selects the default `key_name`.
+    fn select_key(&mut self, key_name: Option<&str>) -> Result<(), Error>;
+
+    /// Selects the chain from which the next transactions relay, named in their memo, if known.
+    fn select_counterparty(&mut self, counterparty_chain_id: Option<&ChainId>);
+
     // Queries
 
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error>;
@@ -123,11 +193,12 @@ pub trait Chain: Sized {
     /// Query the latest height the chain is at
     fn query_latest_height(&self) -> Result<ICSHeight, Error>;
 
-    /// Performs a query to retrieve the state of all clients that a chain hosts.
+    /// Performs a query to retrieve the state of the clients that a chain hosts,
+    /// one page at a time.
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
-    ) -> Result<Vec<IdentifiedAnyClientState>, Error>;
+    ) -> Result<Page<IdentifiedAnyClientState>, Error>;
 
     fn query_client_state(
         &self,
@@ -138,7 +209,7 @@ pub trait Chain: Sized {
     fn query_consensus_states(
         &self,
         request: QueryConsensusStatesRequest,
-    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error>;
+    ) -> Result<Page<AnyConsensusStateWithHeight>, Error>;
 
     /// Performs a query to retrieve the consensus state (for a specific height `consensus_height`)
     /// that an on-chain client stores.
@@ -159,11 +230,11 @@ pub trait Chain: Sized {
         height: ICSHeight,
     ) -> Result<(Self::ConsensusState, MerkleProof), Error>;
 
-    /// Performs a query to retrieve the identifiers of all connections.
+    /// Performs a query to retrieve the connections, one page at a time.
     fn query_connections(
         &self,
         request: QueryConnectionsRequest,
-    ) -> Result<Vec<IdentifiedConnectionEnd>, Error>;
+    ) -> Result<Page<IdentifiedConnectionEnd>, Error>;
 
     /// Performs a query to retrieve the identifiers of all connections.
     fn query_client_connections(
@@ -177,17 +248,18 @@ pub trait Chain: Sized {
         height: ICSHeight,
     ) -> Result<ConnectionEnd, Error>;
 
-    /// Performs a query to retrieve the identifiers of all channels associated with a connection.
+    /// Performs a query to retrieve the channels associated with a connection,
+    /// one page at a time.
     fn query_connection_channels(
         &self,
         request: QueryConnectionChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error>;
+    ) -> Result<Page<IdentifiedChannelEnd>, Error>;
 
-    /// Performs a query to retrieve the identifiers of all channels.
+    /// Performs a query to retrieve the channels, one page at a time.
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error>;
+    ) -> Result<Page<IdentifiedChannelEnd>, Error>;
 
     fn query_channel(
         &self,
@@ -213,20 +285,26 @@ pub trait Chain: Sized {
         request: QueryChannelClientStateRequest,
     ) -> Result<Option<IdentifiedAnyClientState>, Error>;
 
+    /// Queries one page of the packet commitments of a channel at `height`, or at the latest
+    /// height if none, with the height of the query.
     fn query_packet_commitments(
         &self,
         request: QueryPacketCommitmentsRequest,
-    ) -> Result<(Vec<PacketState>, ICSHeight), Error>;
+        height: Option<ICSHeight>,
+    ) -> Result<(Page<PacketState>, ICSHeight), Error>;
 
     fn query_unreceived_packets(
         &self,
         request: QueryUnreceivedPacketsRequest,
     ) -> Result<Vec<u64>, Error>;
 
+    /// Queries one page of the packet acknowledgements of a channel at `height`, or at the latest
+    /// height if none, with the height of the query.
     fn query_packet_acknowledgements(
         &self,
         request: QueryPacketAcknowledgementsRequest,
-    ) -> Result<(Vec<PacketState>, ICSHeight), Error>;
+        height: Option<ICSHeight>,
+    ) -> Result<(Page<PacketState>, ICSHeight), Error>;
 
     fn query_unreceived_acknowledgements(
         &self,
@@ -240,12 +318,14 @@ pub trait Chain: Sized {
 
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error>;
 
-    // Provable queries
+    // Provable queries. The client and consensus states are returned in their `Any` form, since
+    // the queried client is not necessarily of the same type as the client for this chain (e.g.,
+    // it may be a localhost client).
     fn proven_client_state(
         &self,
         client_id: &ClientId,
         height: ICSHeight,
-    ) -> Result<(Self::ClientState, MerkleProof), Error>;
+    ) -> Result<(AnyClientState, MerkleProof), Error>;
 
     fn proven_connection(
         &self,
@@ -258,7 +338,7 @@ pub trait Chain: Sized {
         client_id: &ClientId,
         consensus_height: ICSHeight,
         height: ICSHeight,
-    ) -> Result<(Self::ConsensusState, MerkleProof), Error>;
+    ) -> Result<(AnyConsensusState, MerkleProof), Error>;
 
     fn proven_channel(
         &self,
@@ -304,7 +384,7 @@ pub trait Chain: Sized {
         connection_id: &ConnectionId,
         client_id: &ClientId,
         height: ICSHeight,
-    ) -> Result<(Option<Self::ClientState>, Proofs), Error> {
+    ) -> Result<(Option<AnyClientState>, Proofs), Error> {
         let (connection_end, connection_proof) = self.proven_connection(connection_id, height)?;
 
         // Check that the connection state is compatible with the message
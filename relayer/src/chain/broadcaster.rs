@@ -0,0 +1,136 @@
+//! Broadcasters submit the transactions signed with the extra keys of a chain. Each key has a
+//! broadcaster of its own, running on a separate thread with its own instance of the chain, so
+//! that the transactions of different keys are submitted concurrently, without holding up the
+//! requests served by the chain runtime.
+
+use std::{sync::Arc, thread};
+
+use crossbeam_channel as channel;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, error};
+
+use ibc::ics24_host::identifier::ChainId;
+
+use crate::{
+    config::ChainConfig,
+    error::{Error, Kind},
+};
+
+use super::{handle::ReplyTo, Chain, SentMsgs, TxEstimate};
+
+/// A request for the broadcaster of a key, or for the chain runtime on behalf of a key.
+#[derive(Debug)]
+pub enum BroadcastRequest {
+    SendMsgs {
+        proto_msgs: Vec<prost_types::Any>,
+        counterparty_chain_id: Option<ChainId>,
+        reply_to: ReplyTo<SentMsgs>,
+    },
+
+    SimulateMsgs {
+        proto_msgs: Vec<prost_types::Any>,
+        counterparty_chain_id: Option<ChainId>,
+        reply_to: ReplyTo<Vec<TxEstimate>>,
+    },
+}
+
+impl BroadcastRequest {
+    /// Processes the request with the given chain, whose key is already selected, and sends the
+    /// result back to the requester.
+    pub fn process<C: Chain>(self, chain: &mut C) -> Result<(), Error> {
+        match self {
+            Self::SendMsgs {
+                proto_msgs,
+                counterparty_chain_id,
+                reply_to,
+            } => {
+                chain.select_counterparty(counterparty_chain_id.as_ref());
+                let result = chain.send_msgs_with_receipts(proto_msgs);
+                reply_to.send(result).map_err(Kind::channel)?;
+            }
+
+            Self::SimulateMsgs {
+                proto_msgs,
+                counterparty_chain_id,
+                reply_to,
+            } => {
+                chain.select_counterparty(counterparty_chain_id.as_ref());
+                let result = chain.simulate_msgs(proto_msgs);
+                reply_to.send(result).map_err(Kind::channel)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends the error `e` back to the requester, instead of processing the request.
+    pub fn fail(self, e: Error) -> Result<(), Error> {
+        match self {
+            Self::SendMsgs { reply_to, .. } => reply_to.send(Err(e)).map_err(Kind::channel)?,
+            Self::SimulateMsgs { reply_to, .. } => reply_to.send(Err(e)).map_err(Kind::channel)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// The broadcaster of the transactions signed with a single key.
+pub struct TxBroadcaster<C: Chain> {
+    /// The instance of the chain, with the key of this broadcaster selected
+    chain: C,
+
+    /// The name of the key of this broadcaster
+    key_name: String,
+
+    /// The receiving side of the channel through which the chain runtime forwards requests. The
+    /// broadcaster stops once the runtime drops the sending side.
+    request_receiver: channel::Receiver<BroadcastRequest>,
+}
+
+impl<C: Chain + Send + 'static> TxBroadcaster<C> {
+    /// Spawns the broadcaster of the key `key_name` of the chain with the given configuration,
+    /// and returns the channel through which requests are forwarded to it.
+    pub fn spawn(
+        config: ChainConfig,
+        key_name: String,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<channel::Sender<BroadcastRequest>, Error> {
+        let mut chain = C::bootstrap(config, rt)?;
+        chain.select_key(Some(&key_name))?;
+
+        let (request_sender, request_receiver) = channel::unbounded();
+
+        let broadcaster = Self {
+            chain,
+            key_name,
+            request_receiver,
+        };
+        thread::spawn(move || broadcaster.run());
+
+        Ok(request_sender)
+    }
+
+    fn run(mut self) {
+        while let Ok(request) = self.request_receiver.recv() {
+            if let Err(e) = request.process(&mut self.chain) {
+                error!(
+                    "[{}] broadcaster of key '{}' failed to reply: {}",
+                    self.chain.id(),
+                    self.key_name,
+                    e
+                );
+            }
+        }
+
+        debug!(
+            "[{}] stopping broadcaster of key '{}'",
+            self.chain.id(),
+            self.key_name
+        );
+
+        let chain_id = self.chain.id().clone();
+        if let Err(e) = self.chain.shutdown() {
+            error!("[{}] failed to shut down: {}", chain_id, e);
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use std::{
     cmp::min,
+    collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
     future::Future,
     str::FromStr,
-    sync::Arc,
+    sync::{Arc, Mutex},
     thread,
     time::{Duration, Instant},
 };
@@ -12,6 +13,7 @@ use anomaly::fail;
 use bech32::{ToBase32, Variant};
 use bitcoin::hashes::hex::ToHex;
 use itertools::Itertools;
+use once_cell::sync::Lazy;
 use prost::Message;
 use prost_types::Any;
 use tendermint::abci::Path as TendermintABCIPath;
@@ -25,6 +27,8 @@ use tendermint_rpc::query::{EventType, Query};
 use tendermint_rpc::{endpoint::broadcast::tx_sync::Response, Client, HttpClient, Order};
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::codegen::http::Uri;
+use tonic::metadata::AsciiMetadataValue;
+use tonic::transport::Channel;
 use tracing::{debug, trace, warn};
 
 use ibc::downcast;
@@ -54,6 +58,7 @@ use ibc_proto::cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest};
 use ibc_proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient;
 use ibc_proto::cosmos::base::tendermint::v1beta1::GetNodeInfoRequest;
 use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::cosmos::feegrant::v1beta1::{Grant, QueryAllowanceRequest};
 use ibc_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
 use ibc_proto::cosmos::tx::v1beta1::{
     AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, SimulateRequest, SimulateResponse, Tx, TxBody,
@@ -74,25 +79,50 @@ use ibc_proto::ibc::core::connection::v1::{
     QueryClientConnectionsRequest, QueryConnectionsRequest,
 };
 
+use crate::chain::endpoints::{EndpointKind, Endpoints};
+use crate::chain::pagination::{self, Page};
 use crate::config::{ChainConfig, GasPrice};
 use crate::error::{Error, Kind};
 use crate::event::monitor::{EventMonitor, EventReceiver};
-use crate::keyring::{KeyEntry, KeyRing, Store};
+use crate::keyring::{KeyEntry, KeyRing};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::LightClient;
 use crate::light_client::Verified;
 use crate::{chain::QueryResponse, event::monitor::TxMonitorCmd};
 
-use super::Chain;
+use super::{Chain, SentMsgs, TxEstimate, TxReceipt};
 
 mod compatibility;
 
+mod gas;
+use gas::{GasPricing, BANK_BALANCE_QUERY_PATH};
+
+mod memo;
+use memo::TxOptions;
+
 const DEFAULT_MAX_GAS: u64 = 300_000;
 const DEFAULT_GAS_PRICE_ADJUSTMENT: f64 = 0.1;
 
 const DEFAULT_MAX_MSG_NUM: usize = 30;
 const DEFAULT_MAX_TX_SIZE: usize = 2 * 1048576; // 2 MBytes
 
+/// The check-tx error code of the Cosmos SDK for an incorrect account sequence, and the
+/// description which ends the log of the error
+const INCORRECT_ACCOUNT_SEQUENCE_CODE: u32 = 32;
+const INCORRECT_ACCOUNT_SEQUENCE_DESCRIPTION: &str = "incorrect account sequence";
+
+/// How many times a transaction is re-signed and resent after an account sequence mismatch
+const MAX_SEQUENCE_MISMATCH_RETRIES: usize = 3;
+
+/// The check-tx error code of the Cosmos SDK for an insufficient fee, and the description which
+/// ends the log of the error
+const INSUFFICIENT_FEE_CODE: u32 = 13;
+const INSUFFICIENT_FEE_DESCRIPTION: &str = "insufficient fee";
+
+/// The chains whose fee grants were checked by this process, so that they are checked once,
+/// rather than by each of the chain instances of the broadcasters of a chain
+static FEE_GRANTS_CHECKED: Lazy<Mutex<HashSet<ChainId>>> = Lazy::new(Default::default);
+
 mod retry_strategy {
     use crate::util::retry::Fixed;
     use std::time::Duration;
@@ -106,16 +136,22 @@ mod retry_strategy {
 
 pub struct CosmosSdkChain {
     config: ChainConfig,
-    rpc_client: HttpClient,
-    grpc_addr: Uri,
+    rpc: Endpoints<HttpClient>,
+    grpc: Endpoints<Uri>,
     rt: Arc<TokioRuntime>,
     keybase: KeyRing,
-    /// A cached copy of the account information
-    account: Option<BaseAccount>,
+    /// The name of the key that currently signs transactions
+    key_name: String,
+    /// A cached copy of the account information of each key, with its own sequence
+    accounts: HashMap<String, BaseAccount>,
+    gas_pricing: GasPricing,
+    tx_options: TxOptions,
+    /// The chain from which the transactions currently sent relay, if known
+    counterparty_chain_id: Option<ChainId>,
 }
 
 impl CosmosSdkChain {
-    /// Does multiple RPC calls to the full node, to check for
+    /// Does multiple RPC calls to each of the full node endpoints, to check for
     /// reachability and that some basic APIs are available.
     ///
     /// Currently this checks that:
@@ -124,29 +160,28 @@ impl CosmosSdkChain {
     ///     - the SDK version is supported.
     ///
     /// Emits a log warning in case anything is amiss.
-    /// Exits early if any health check fails, without doing any
-    /// further checks.
+    /// Exits early for an endpoint if any of its health checks fails, without doing any
+    /// further checks, and marks it as unhealthy. The preferred healthy endpoints then
+    /// become the active RPC and gRPC endpoints.
     fn health_checkup(&self) {
-        async fn do_health_checkup(chain: &CosmosSdkChain) -> Result<(), Error> {
-            let chain_id = chain.id();
-            let grpc_address = chain.grpc_addr.to_string();
-            let rpc_address = chain.config.rpc_addr.to_string();
-
+        async fn rpc_health_checkup(
+            chain_id: &ChainId,
+            rpc_address: &tendermint_rpc::Url,
+            rpc_client: &HttpClient,
+        ) -> Result<(), Error> {
             // Checkup on the self-reported health endpoint
-            chain
-                .rpc_client
+            rpc_client
                 .health()
                 .await
                 .map_err(|e| Kind::HealthCheckJsonRpc {
                     chain_id: chain_id.clone(),
-                    address: rpc_address.clone(),
+                    address: rpc_address.to_string(),
                     endpoint: "/health".to_string(),
                     cause: e,
                 })?;
 
             // Checkup on transaction indexing
-            chain
-                .rpc_client
+            rpc_client
                 .tx_search(
                     Query::from(EventType::NewBlock),
                     false,
@@ -157,18 +192,26 @@ impl CosmosSdkChain {
                 .await
                 .map_err(|e| Kind::HealthCheckJsonRpc {
                     chain_id: chain_id.clone(),
-                    address: rpc_address.clone(),
+                    address: rpc_address.to_string(),
                     endpoint: "/tx_search".to_string(),
                     cause: e,
                 })?;
 
-            let mut client = ServiceClient::connect(chain.grpc_addr.clone())
+            Ok(())
+        }
+
+        async fn grpc_health_checkup(
+            chain_id: &ChainId,
+            grpc_address: &tendermint_rpc::Url,
+            grpc_uri: &Uri,
+        ) -> Result<(), Error> {
+            let mut client = ServiceClient::connect(grpc_uri.clone())
                 .await
                 .map_err(|e| {
                     // Failed to create the gRPC client to call into `/node_info`.
                     Kind::HealthCheckGrpc {
                         chain_id: chain_id.clone(),
-                        address: grpc_address.clone(),
+                        address: grpc_address.to_string(),
                         endpoint: "tendermint::ServiceClient".to_string(),
                         cause: e.to_string(),
                     }
@@ -182,7 +225,7 @@ impl CosmosSdkChain {
                     .await
                     .map_err(|e| Kind::HealthCheckGrpc {
                         chain_id: chain_id.clone(),
-                        address: grpc_address.clone(),
+                        address: grpc_address.to_string(),
                         endpoint: "tendermint::GetNodeInfoRequest".to_string(),
                         cause: e.to_string(),
                     })?;
@@ -193,7 +236,7 @@ impl CosmosSdkChain {
                     .application_version
                     .ok_or_else(|| Kind::HealthCheckGrpc {
                         chain_id: chain_id.clone(),
-                        address: grpc_address.clone(),
+                        address: grpc_address.to_string(),
                         endpoint: "tendermint::GetNodeInfoRequest".to_string(),
                         cause: "the gRPC response contains no application version information"
                             .to_string(),
@@ -203,7 +246,7 @@ impl CosmosSdkChain {
             if let Some(diagnostic) = compatibility::run_diagnostic(version) {
                 return Err(Kind::SdkModuleVersion {
                     chain_id: chain_id.clone(),
-                    address: grpc_address.clone(),
+                    address: grpc_address.to_string(),
                     cause: diagnostic.to_string(),
                 }
                 .into());
@@ -212,33 +255,122 @@ impl CosmosSdkChain {
             Ok(())
         }
 
-        if let Err(e) = self.block_on(do_health_checkup(self)) {
-            warn!("{}", e);
+        for (index, (address, client)) in self.rpc.iter().enumerate() {
+            let result = self.block_on(rpc_health_checkup(self.id(), address, client));
+            if let Err(e) = &result {
+                warn!("{}", e);
+            }
+            self.rpc.set_healthy(index, result.is_ok());
+        }
+
+        for (index, (address, uri)) in self.grpc.iter().enumerate() {
+            let result = self.block_on(grpc_health_checkup(self.id(), address, uri));
+            if let Err(e) = &result {
+                warn!("{}", e);
+            }
+            self.grpc.set_healthy(index, result.is_ok());
+        }
+
+        let rpc_healthy = self.rpc.activate_healthy();
+        let grpc_healthy = self.grpc.activate_healthy();
+
+        if !(rpc_healthy && grpc_healthy) {
             warn!("some Hermes features may not work in this mode!");
         }
     }
 
-    /// The unbonding period of this chain
-    pub fn unbonding_period(&self) -> Result<Duration, Error> {
-        crate::time!("unbonding_period");
+    /// Checks that the configured `fee_granter`, if any, grants a fee allowance to each
+    /// signing key, without which the transactions signed with the key would be rejected.
+    ///
+    /// Emits a log warning for each key which is not granted an allowance.
+    /// The allowances of a chain are only checked once per process.
+    fn fee_grant_checkup(&self) {
+        let granter = match &self.config.fee_granter {
+            Some(granter) => granter,
+            None => return,
+        };
+
+        let first_check = FEE_GRANTS_CHECKED
+            .lock()
+            .map_or(true, |mut checked| checked.insert(self.id().clone()));
+        if !first_check {
+            return;
+        }
 
-        let mut client = self
-            .block_on(
-                ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+        for key_name in self.config.key_names() {
+            let allowance = self
+                .keybase
+                .get_key(&key_name)
+                .map_err(|e| -> Error { Kind::KeyBase.context(e).into() })
+                .and_then(|key| self.query_fee_allowance(granter, &key.account));
+
+            match allowance {
+                Ok(Some(grant)) => debug!(
+                    "[{}] key '{}' is granted a fee allowance by {}: {:?}",
+                    self.id(),
+                    key_name,
+                    granter,
+                    grant.allowance
                 ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+                Ok(None) => warn!(
+                    "[{}] key '{}' is not granted any fee allowance by the fee granter {}",
+                    self.id(),
+                    key_name,
+                    granter
+                ),
+                Err(e) => warn!(
+                    "[{}] failed to query the fee allowance of key '{}' by {}: {}",
+                    self.id(),
+                    key_name,
+                    granter,
+                    e
+                ),
+            }
+        }
+    }
 
-        let request =
-            tonic::Request::new(ibc_proto::cosmos::staking::v1beta1::QueryParamsRequest {});
+    /// Queries the fee allowance granted by the account `granter` to the account `grantee`.
+    pub fn query_fee_allowance(
+        &self,
+        granter: &str,
+        grantee: &str,
+    ) -> Result<Option<Grant>, Error> {
+        crate::time!("query_fee_allowance");
+
+        let request = QueryAllowanceRequest {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+        };
+
+        let response = self.block_on(grpc_failover(&self.grpc, |channel| {
+            let request = request.clone();
+            async move {
+                ibc_proto::cosmos::feegrant::v1beta1::query_client::QueryClient::new(channel)
+                    .allowance(request)
+                    .await
+            }
+        }));
+
+        match response {
+            Ok(response) => Ok(response.allowance),
+            Err(e) if e.code() == Some(tonic::Code::NotFound) => Ok(None),
+            Err(e) => Err(Kind::Grpc.context(e).into()),
+        }
+    }
+
+    /// The unbonding period of this chain
+    pub fn unbonding_period(&self) -> Result<Duration, Error> {
+        crate::time!("unbonding_period");
 
         let response = self
-            .block_on(client.params(request))
+            .block_on(grpc_failover(&self.grpc, |channel| async move {
+                ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::new(channel)
+                    .params(ibc_proto::cosmos::staking::v1beta1::QueryParamsRequest {})
+                    .await
+            }))
             .map_err(|e| Kind::Grpc.context(e))?;
 
         let res = response
-            .into_inner()
             .params
             .ok_or_else(|| Kind::Grpc.context("none staking params".to_string()))?
             .unbonding_time
@@ -247,10 +379,6 @@ impl CosmosSdkChain {
         Ok(Duration::new(res.seconds as u64, res.nanos as u32))
     }
 
-    fn rpc_client(&self) -> &HttpClient {
-        &self.rpc_client
-    }
-
     pub fn config(&self) -> &ChainConfig {
         &self.config
     }
@@ -261,8 +389,8 @@ impl CosmosSdkChain {
         crate::time!("query_consensus_params");
 
         Ok(self
-            .block_on(self.rpc_client().genesis())
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?
+            .block_on(self.rpc.failover(|client| client.genesis()))
+            .map_err(|e| Kind::Rpc(self.rpc.active_address().clone()).context(e))?
             .consensus_params)
     }
 
@@ -272,10 +400,20 @@ impl CosmosSdkChain {
         self.rt.block_on(f)
     }
 
-    fn send_tx(&mut self, proto_msgs: Vec<Any>) -> Result<Response, Error> {
+    /// Submits a transaction with the messages `proto_msgs`, and returns the response of its
+    /// broadcast with the fee it was signed with.
+    fn send_tx(&mut self, proto_msgs: Vec<Any>) -> Result<(Response, Fee), Error> {
         crate::time!("send_tx");
-        let account_seq = self.account_sequence()?;
+        send_tx_with_recovery(self, &proto_msgs)
+    }
 
+    /// Signs a transaction with the messages `proto_msgs` and the account sequence `account_seq`,
+    /// and broadcasts it. Returns the response of the broadcast, with the fee of the transaction.
+    fn sign_and_broadcast_tx(
+        &mut self,
+        proto_msgs: &[Any],
+        account_seq: u64,
+    ) -> Result<(Response, Fee), Error> {
         debug!(
             "[{}] send_tx: sending {} messages using nonce {}",
             self.id(),
@@ -284,8 +422,9 @@ impl CosmosSdkChain {
         );
 
         let signer_info = self.signer(account_seq)?;
-        let fee = self.default_fee();
-        let (body, body_buf) = tx_body_and_bytes(proto_msgs)?;
+        let gas_price = self.gas_price();
+        let fee = self.default_fee(&gas_price);
+        let (body, body_buf) = self.tx_body(proto_msgs)?;
 
         let (auth_info, auth_buf) = auth_info_and_bytes(signer_info.clone(), fee.clone())?;
         let signed_doc = self.signed_doc(body_buf.clone(), auth_buf, account_seq)?;
@@ -317,7 +456,7 @@ impl CosmosSdkChain {
             .into());
         }
 
-        let adjusted_fee = self.fee_with_gas(estimated_gas);
+        let adjusted_fee = self.fee_with_gas(estimated_gas, &gas_price);
 
         trace!(
             "[{}] send_tx: based on the estimated gas, adjusting fee from {:?} to {:?}",
@@ -326,7 +465,8 @@ impl CosmosSdkChain {
             adjusted_fee
         );
 
-        let (_auth_adjusted, auth_buf_adjusted) = auth_info_and_bytes(signer_info, adjusted_fee)?;
+        let (_auth_adjusted, auth_buf_adjusted) =
+            auth_info_and_bytes(signer_info, adjusted_fee.clone())?;
         let account_number = self.account_number()?;
         let signed_doc =
             self.signed_doc(body_buf.clone(), auth_buf_adjusted.clone(), account_number)?;
@@ -342,13 +482,106 @@ impl CosmosSdkChain {
 
         let response = self
             .block_on(broadcast_tx_sync(self, tx_bytes))
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
+            .map_err(|e| Kind::Rpc(self.rpc.active_address().clone()).context(e))?;
 
         debug!("[{}] send_tx: broadcast_tx_sync: {:?}", self.id(), response);
 
-        self.incr_account_sequence()?;
+        Ok((response, adjusted_fee))
+    }
 
-        Ok(response)
+    /// Simulates a transaction with the messages `proto_msgs`, without signing or broadcasting
+    /// it, and estimates its gas and fee as `sign_and_broadcast_tx` would.
+    fn simulate_tx(&mut self, proto_msgs: &[Any]) -> Result<TxEstimate, Error> {
+        let account_seq = self.account_sequence()?;
+
+        debug!(
+            "[{}] simulate_tx: simulating {} messages using nonce {}",
+            self.id(),
+            proto_msgs.len(),
+            account_seq,
+        );
+
+        let signer_info = self.signer(account_seq)?;
+        let gas_price = self.gas_price();
+        let (body, _) = self.tx_body(proto_msgs)?;
+        let (auth_info, _) = auth_info_and_bytes(signer_info, self.default_fee(&gas_price))?;
+
+        // The simulation does not verify the signature, which is left empty
+        let simulated = self.send_tx_simulate(SimulateRequest {
+            tx: Some(Tx {
+                body: Some(body),
+                auth_info: Some(auth_info),
+                signatures: vec![vec![]],
+            }),
+        });
+
+        let (gas_estimate, error) = match simulated {
+            Ok(response) => (
+                response.gas_info.map_or(self.max_gas(), |g| g.gas_used),
+                None,
+            ),
+            Err(e) => (self.max_gas(), Some(e.to_string())),
+        };
+
+        let error = error.or_else(|| {
+            (gas_estimate > self.max_gas()).then(|| {
+                Kind::TxSimulateGasEstimateExceeded {
+                    chain_id: self.id().clone(),
+                    estimated_gas: gas_estimate,
+                    max_gas: self.max_gas(),
+                }
+                .to_string()
+            })
+        });
+
+        let fee = self.fee_with_gas(gas_estimate, &gas_price);
+
+        Ok(TxEstimate {
+            msg_count: proto_msgs.len(),
+            gas_estimate,
+            gas_wanted: fee.gas_limit,
+            fee: format_fee(&fee),
+            error,
+        })
+    }
+
+    /// The body of a transaction with the messages `proto_msgs`, with its memo and extension
+    /// options, and its encoding.
+    fn tx_body(&self, proto_msgs: &[Any]) -> Result<(TxBody, Vec<u8>), Error> {
+        let memo = self
+            .tx_options
+            .memo(proto_msgs, self.counterparty_chain_id.as_ref());
+
+        tx_body_and_bytes(
+            proto_msgs.to_vec(),
+            memo,
+            self.tx_options.non_critical_extension_options().to_vec(),
+        )
+    }
+
+    /// Splits the messages `proto_msgs` in the batches of the transactions with which they are
+    /// sent, such that no transaction exceeds the configured maximum number of messages
+    /// per transaction and the maximum transaction size.
+    fn batch_msgs(&self, proto_msgs: &[Any]) -> Vec<Vec<Any>> {
+        let mut batches = vec![];
+
+        let mut size = 0;
+        let mut msg_batch = vec![];
+        for msg in proto_msgs.iter() {
+            msg_batch.push(msg.clone());
+            let mut buf = Vec::new();
+            prost::Message::encode(msg, &mut buf).unwrap();
+            size += buf.len();
+            if msg_batch.len() >= self.max_msg_num() || size >= self.max_tx_size() {
+                batches.push(std::mem::take(&mut msg_batch));
+                size = 0;
+            }
+        }
+        if !msg_batch.is_empty() {
+            batches.push(msg_batch);
+        }
+
+        batches
     }
 
     /// The maximum amount of gas the relayer is willing to pay for a transaction
@@ -356,9 +589,31 @@ impl CosmosSdkChain {
         self.config.max_gas.unwrap_or(DEFAULT_MAX_GAS)
     }
 
-    /// The gas price
-    fn gas_price(&self) -> &GasPrice {
-        &self.config.gas_price
+    /// The gas price of the next transaction, in the first accepted fee denomination in which
+    /// the balance of the account paying the fees covers the maximum fee. The fees are paid
+    /// by the configured `fee_granter`, if any, and otherwise by the signing key.
+    fn gas_price(&mut self) -> GasPrice {
+        let max_gas = self.max_gas();
+        let account = if !self.gas_pricing.has_alternatives() {
+            None
+        } else if let Some(granter) = &self.config.fee_granter {
+            Some(granter.clone())
+        } else {
+            self.key().map(|key| key.account).ok()
+        };
+
+        let (chain_id, rpc, rt) = (&self.config.id, &self.rpc, &self.rt);
+        self.gas_pricing
+            .select(max_gas, account.as_deref(), |account, denom| {
+                rt.block_on(query_balance(rpc, account, denom))
+                    .map_err(|e| {
+                        warn!(
+                            "[{}] failed to query the balance in {}: {}",
+                            chain_id, denom, e
+                        )
+                    })
+                    .ok()
+            })
     }
 
     /// The gas price adjustment
@@ -378,14 +633,9 @@ impl CosmosSdkChain {
         )
     }
 
-    /// The maximum fee the relayer pays for a transaction
-    fn max_fee_in_coins(&self) -> Coin {
-        calculate_fee(self.max_gas(), self.gas_price())
-    }
-
     /// The fee in coins based on gas amount
-    fn fee_from_gas_in_coins(&self, gas: u64) -> Coin {
-        calculate_fee(gas, self.gas_price())
+    fn fee_from_gas_in_coins(&self, gas: u64, gas_price: &GasPrice) -> Coin {
+        calculate_fee(gas, gas_price)
     }
 
     /// The maximum number of messages included in a transaction
@@ -451,19 +701,16 @@ impl CosmosSdkChain {
     fn send_tx_simulate(&self, request: SimulateRequest) -> Result<SimulateResponse, Error> {
         crate::time!("tx simulate");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::cosmos::tx::v1beta1::service_client::ServiceClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
         let response = self
-            .block_on(client.simulate(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::cosmos::tx::v1beta1::service_client::ServiceClient::new(channel)
+                        .simulate(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         Ok(response)
     }
@@ -471,7 +718,7 @@ impl CosmosSdkChain {
     fn key(&self) -> Result<KeyEntry, Error> {
         Ok(self
             .keybase()
-            .get_key(&self.config.key_name)
+            .get_key(&self.key_name)
             .map_err(|e| Kind::KeyBase.context(e))?)
     }
 
@@ -488,24 +735,25 @@ impl CosmosSdkChain {
     }
 
     fn account(&mut self) -> Result<&mut BaseAccount, Error> {
-        if self.account == None {
+        if !self.accounts.contains_key(&self.key_name) {
             let account = self
                 .block_on(query_account(self, self.key()?.account))
                 .map_err(|e| Kind::Grpc.context(e))?;
 
             debug!(
+                key = %self.key_name,
                 sequence = %account.sequence,
                 number = %account.account_number,
                 "[{}] send_tx: retrieved account",
                 self.id()
             );
 
-            self.account = Some(account);
+            self.accounts.insert(self.key_name.clone(), account);
         }
 
         Ok(self
-            .account
-            .as_mut()
+            .accounts
+            .get_mut(&self.key_name)
             .expect("account was supposedly just cached"))
     }
 
@@ -513,20 +761,11 @@ impl CosmosSdkChain {
         Ok(self.account()?.account_number)
     }
 
-    fn account_sequence(&mut self) -> Result<u64, Error> {
-        Ok(self.account()?.sequence)
-    }
-
-    fn incr_account_sequence(&mut self) -> Result<(), Error> {
-        self.account()?.sequence += 1;
-        Ok(())
-    }
-
     fn signer(&self, sequence: u64) -> Result<SignerInfo, Error> {
         let (_key, pk_buf) = self.key_and_bytes()?;
         // Create a MsgSend proto Any message
         let pk_any = Any {
-            type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+            type_url: self.config.key_type.pub_key_type_url().to_string(),
             value: pk_buf,
         };
 
@@ -541,21 +780,16 @@ impl CosmosSdkChain {
         Ok(signer_info)
     }
 
-    fn default_fee(&self) -> Fee {
-        Fee {
-            amount: vec![self.max_fee_in_coins()],
-            gas_limit: self.max_gas(),
-            payer: "".to_string(),
-            granter: "".to_string(),
-        }
+    fn default_fee(&self, gas_price: &GasPrice) -> Fee {
+        default_fee(&self.config, gas_price)
     }
 
-    fn fee_with_gas(&self, gas_limit: u64) -> Fee {
+    fn fee_with_gas(&self, gas_limit: u64, gas_price: &GasPrice) -> Fee {
         let adjusted_gas_limit = self.apply_adjustment_to_gas(gas_limit);
         Fee {
-            amount: vec![self.fee_from_gas_in_coins(adjusted_gas_limit)],
+            amount: vec![self.fee_from_gas_in_coins(adjusted_gas_limit, gas_price)],
             gas_limit: adjusted_gas_limit,
-            ..self.default_fee()
+            ..self.default_fee(gas_price)
         }
     }
 
@@ -579,12 +813,33 @@ impl CosmosSdkChain {
         // Sign doc
         let signed = self
             .keybase
-            .sign_msg(&self.config.key_name, signdoc_buf)
+            .sign_msg(&self.key_name, signdoc_buf, self.config.key_type)
             .map_err(|e| Kind::KeyBase.context(e))?;
 
         Ok(signed)
     }
 
+    /// Queries the committed transaction with the hash `tx`, if any.
+    fn query_tx_result(&self, tx: &QueryTxHash) -> Result<Option<ResultTx>, Error> {
+        let mut response = self
+            .block_on(self.rpc.failover(|client| {
+                client.tx_search(
+                    tx_hash_query(tx),
+                    false,
+                    1,
+                    1, // get only the first Tx matching the query
+                    Order::Ascending,
+                )
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
+
+        if response.txs.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(response.txs.remove(0)))
+        }
+    }
+
     /// Given a vector of `TxSyncResult` elements,
     /// each including a transaction response hash for one or more messages, periodically queries the chain
     /// with the transaction hashes to get the list of IbcEvents included in those transactions.
@@ -621,7 +876,13 @@ impl CosmosSdkChain {
                     return RetryResult::Ok(());
                 }
 
-                for TxSyncResult { response, events } in tx_sync_results.iter_mut() {
+                for TxSyncResult {
+                    response,
+                    events,
+                    gas_used,
+                    ..
+                } in tx_sync_results.iter_mut()
+                {
                     // If this transaction was not committed, determine whether it was because it failed
                     // or because it hasn't been committed yet.
                     if empty_event_present(&events) {
@@ -634,12 +895,16 @@ impl CosmosSdkChain {
                         ))];
 
                         // Otherwise, try to resolve transaction hash to the corresponding events.
-                        } else if let Ok(events_per_tx) =
-                            self.query_txs(QueryTxRequest::Transaction(QueryTxHash(response.hash)))
+                        } else if let Ok(Some(tx)) =
+                            self.query_tx_result(&QueryTxHash(response.hash))
                         {
-                            // If we get events back, progress was made, so we replace the events
-                            // with the new ones. in both cases we will check in the next iteration
-                            // whether or not the transaction was fully committed.
+                            // If we get the transaction back, progress was made, so we replace
+                            // the events with its events. in both cases we will check in the next
+                            // iteration whether or not the transaction was fully committed.
+                            *gas_used = Some(u64::from(tx.tx_result.gas_used));
+
+                            let events_per_tx =
+                                all_ibc_events_from_tx_search_response(self.id(), tx);
                             if !events_per_tx.is_empty() {
                                 *events = events_per_tx;
                             }
@@ -681,26 +946,48 @@ impl Chain for CosmosSdkChain {
     type ClientState = ClientState;
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let rpc_client = HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Kind::Rpc(config.rpc_addr.clone()).context(e))?;
+        let rpc = Endpoints::new(
+            config.id.clone(),
+            EndpointKind::Rpc,
+            config.rpc_addrs(),
+            |address| {
+                HttpClient::new(address.clone())
+                    .map_err(|e| Error::from(Kind::Rpc(address.clone()).context(e)))
+            },
+        )?;
 
         // Initialize key store and load key
-        let keybase = KeyRing::new(Store::Test, &config.account_prefix, &config.id)
+        let keybase = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)
             .map_err(|e| Kind::KeyBase.context(e))?;
+        check_fee_payer(&config, &keybase)?;
+
+        let grpc = Endpoints::new(
+            config.id.clone(),
+            EndpointKind::Grpc,
+            config.grpc_addrs(),
+            |address| {
+                Uri::from_str(&address.to_string()).map_err(|e| Error::from(Kind::Grpc.context(e)))
+            },
+        )?;
 
-        let grpc_addr =
-            Uri::from_str(&config.grpc_addr.to_string()).map_err(|e| Kind::Grpc.context(e))?;
+        let gas_pricing = GasPricing::new(&config);
+        let tx_options = TxOptions::new(&config)?;
 
         let chain = Self {
+            key_name: config.key_name.clone(),
             config,
-            rpc_client,
-            grpc_addr,
+            rpc,
+            grpc,
             rt,
             keybase,
-            account: None,
+            accounts: HashMap::new(),
+            gas_pricing,
+            tx_options,
+            counterparty_chain_id: None,
         };
 
         chain.health_checkup();
+        chain.fee_grant_checkup();
 
         Ok(chain)
     }
@@ -712,11 +999,17 @@ impl Chain for CosmosSdkChain {
 
         let peer_id: PeerId = self
             .rt
-            .block_on(self.rpc_client.status())
+            .block_on(self.rpc.failover(|client| client.status()))
             .map(|s| s.node_info.id)
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
-
-        let light_client = TmLightClient::from_config(&self.config, peer_id)?;
+            .map_err(|e| Kind::Rpc(self.rpc.active_address().clone()).context(e))?;
+
+        // The light client starts with the node which answered, whose peer id it uses
+        let light_client = TmLightClient::from_config(
+            &self.config,
+            self.rt.clone(),
+            self.rpc.active_address(),
+            peer_id,
+        )?;
 
         Ok(Box::new(light_client))
     }
@@ -727,12 +1020,9 @@ impl Chain for CosmosSdkChain {
     ) -> Result<(EventReceiver, TxMonitorCmd), Error> {
         crate::time!("init_event_monitor");
 
-        let (mut event_monitor, event_receiver, monitor_tx) = EventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            rt,
-        )
-        .map_err(Kind::EventMonitor)?;
+        let (mut event_monitor, event_receiver, monitor_tx) =
+            EventMonitor::new(self.config.id.clone(), self.config.websocket_addrs(), rt)
+                .map_err(Kind::EventMonitor)?;
 
         event_monitor.subscribe().map_err(Kind::EventMonitor)?;
 
@@ -766,52 +1056,61 @@ impl Chain for CosmosSdkChain {
     /// TODO - more work is required here for a smarter split maybe iteratively accumulating/ evaluating
     /// msgs in a Tx until any of the max size, max num msgs, max fee are exceeded.
     fn send_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error> {
+        self.send_msgs_with_receipts(proto_msgs)
+            .map(|sent| sent.events)
+    }
+
+    fn send_msgs_with_receipts(&mut self, proto_msgs: Vec<Any>) -> Result<SentMsgs, Error> {
         crate::time!("send_msgs");
 
         if proto_msgs.is_empty() {
-            return Ok(vec![]);
+            return Ok(SentMsgs::default());
         }
+        let start = Instant::now();
         let mut tx_sync_results = vec![];
 
-        let mut n = 0;
-        let mut size = 0;
-        let mut msg_batch = vec![];
-        for msg in proto_msgs.iter() {
-            msg_batch.push(msg.clone());
-            let mut buf = Vec::new();
-            prost::Message::encode(msg, &mut buf).unwrap();
-            n += 1;
-            size += buf.len();
-            if n >= self.max_msg_num() || size >= self.max_tx_size() {
-                let events_per_tx = vec![IbcEvent::Empty("".to_string()); msg_batch.len()];
-                let tx_sync_result = self.send_tx(msg_batch)?;
-                tx_sync_results.push(TxSyncResult {
-                    response: tx_sync_result,
-                    events: events_per_tx,
-                });
-                n = 0;
-                size = 0;
-                msg_batch = vec![];
-            }
-        }
-        if !msg_batch.is_empty() {
+        for msg_batch in self.batch_msgs(&proto_msgs) {
             let events_per_tx = vec![IbcEvent::Empty("".to_string()); msg_batch.len()];
-            let tx_sync_result = self.send_tx(msg_batch)?;
+            let msg_count = msg_batch.len();
+            let (tx_sync_result, fee) = self.send_tx(msg_batch)?;
             tx_sync_results.push(TxSyncResult {
                 response: tx_sync_result,
                 events: events_per_tx,
+                msg_count,
+                fee: Some(fee),
+                gas_used: None,
             });
         }
 
+        let broadcast_time = start.elapsed();
+
+        let start = Instant::now();
         let tx_sync_results = self.wait_for_block_commits(tx_sync_results)?;
+        let confirm_time = start.elapsed();
 
-        let events = tx_sync_results
-            .into_iter()
-            .map(|el| el.events)
-            .flatten()
-            .collect();
+        let mut sent = SentMsgs {
+            broadcast_time,
+            confirm_time,
+            ..SentMsgs::default()
+        };
+        for result in tx_sync_results {
+            sent.txs.push(result.receipt());
+            sent.events.extend(result.events);
+        }
+
+        Ok(sent)
+    }
+
+    /// Simulates the transactions in which `send_msgs` would split the `proto_msgs`.
+    /// Each transaction is simulated on its own, so that the simulation of a transaction which
+    /// depends on the messages of the previous ones, e.g., on their client update, may fail.
+    fn simulate_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<TxEstimate>, Error> {
+        crate::time!("simulate_msgs");
 
-        Ok(events)
+        self.batch_msgs(&proto_msgs)
+            .iter()
+            .map(|msg_batch| self.simulate_tx(msg_batch))
+            .collect()
     }
 
     /// Get the account for the signer
@@ -821,7 +1120,7 @@ impl Chain for CosmosSdkChain {
         // Get the key from key seed file
         let key = self
             .keybase()
-            .get_key(&self.config.key_name)
+            .get_key(&self.key_name)
             .map_err(|e| Kind::KeyBase.context(e))?;
 
         let bech32 = encode_to_bech32(&key.address.to_hex(), &self.config.account_prefix)?;
@@ -835,12 +1134,28 @@ impl Chain for CosmosSdkChain {
         // Get the key from key seed file
         let key = self
             .keybase()
-            .get_key(&self.config.key_name)
+            .get_key(&self.key_name)
             .map_err(|e| Kind::KeyBase.context(e))?;
 
         Ok(key)
     }
 
+    fn select_key(&mut self, key_name: Option<&str>) -> Result<(), Error> {
+        let key_name = key_name.unwrap_or(&self.config.key_name);
+
+        if !self.config.key_names().iter().any(|k| k == key_name) {
+            return Err(Kind::UnknownKey(key_name.to_string(), self.id().clone()).into());
+        }
+
+        self.key_name = key_name.to_string();
+
+        Ok(())
+    }
+
+    fn select_counterparty(&mut self, counterparty_chain_id: Option<&ChainId>) {
+        self.counterparty_chain_id = counterparty_chain_id.cloned();
+    }
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         crate::time!("query_commitment_prefix");
 
@@ -855,14 +1170,14 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_latest_height");
 
         let status = self
-            .block_on(self.rpc_client().status())
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
+            .block_on(self.rpc.failover(|client| client.status()))
+            .map_err(|e| Kind::Rpc(self.rpc.active_address().clone()).context(e))?;
 
         if status.sync_info.catching_up {
             fail!(
-                Kind::LightClient(self.config.rpc_addr.to_string()),
+                Kind::LightClient(self.rpc.active_address().to_string()),
                 "node at {} running chain {} not caught up",
-                self.config().rpc_addr,
+                self.rpc.active_address(),
                 self.config().id,
             );
         }
@@ -876,22 +1191,19 @@ impl Chain for CosmosSdkChain {
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
-    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+    ) -> Result<Page<IdentifiedAnyClientState>, Error> {
         crate::time!("query_chain_clients");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
         let response = self
-            .block_on(client.client_states(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::client::v1::query_client::QueryClient::new(channel)
+                        .client_states(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         // Deserialize into domain type
         let mut clients: Vec<IdentifiedAnyClientState> = response
@@ -901,13 +1213,9 @@ impl Chain for CosmosSdkChain {
             .collect();
 
         // Sort by client identifier counter
-        clients.sort_by(|a, b| {
-            client_id_suffix(&a.client_id)
-                .unwrap_or(0) // Fallback to `0` suffix (no sorting) if client id is malformed
-                .cmp(&client_id_suffix(&b.client_id).unwrap_or(0))
-        });
+        pagination::sort_clients(&mut clients);
 
-        Ok(clients)
+        Ok(Page::new(clients, response.pagination))
     }
 
     fn query_client_state(
@@ -937,21 +1245,15 @@ impl Chain for CosmosSdkChain {
     ) -> Result<(Self::ClientState, MerkleProof), Error> {
         crate::time!("query_upgraded_client_state");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let req = tonic::Request::new(QueryCurrentPlanRequest {});
         let response = self
-            .block_on(client.current_plan(req))
+            .block_on(grpc_failover(&self.grpc, |channel| async move {
+                ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::new(channel)
+                    .current_plan(QueryCurrentPlanRequest {})
+                    .await
+            }))
             .map_err(|e| Kind::Grpc.context(e))?;
 
         let upgraded_client_state_raw = response
-            .into_inner()
             .plan
             .ok_or(Kind::EmptyResponseValue)?
             .upgraded_client_state
@@ -985,23 +1287,21 @@ impl Chain for CosmosSdkChain {
         let tm_height =
             Height::try_from(height.revision_height).map_err(|e| Kind::InvalidHeight.context(e))?;
 
-        let mut client = self
-            .block_on(
-                ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let req = tonic::Request::new(QueryUpgradedConsensusStateRequest {
+        let req = QueryUpgradedConsensusStateRequest {
             last_height: tm_height.into(),
-        });
+        };
         let response = self
-            .block_on(client.upgraded_consensus_state(req))
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let req = req.clone();
+                async move {
+                    ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::new(channel)
+                        .upgraded_consensus_state(req)
+                        .await
+                }
+            }))
             .map_err(|e| Kind::Grpc.context(e))?;
 
         let upgraded_consensus_state_raw = response
-            .into_inner()
             .upgraded_consensus_state
             .ok_or(Kind::EmptyResponseValue)?;
 
@@ -1028,31 +1328,27 @@ impl Chain for CosmosSdkChain {
     fn query_consensus_states(
         &self,
         request: QueryConsensusStatesRequest,
-    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
+    ) -> Result<Page<AnyConsensusStateWithHeight>, Error> {
         crate::time!("query_chain_clients");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
         let response = self
-            .block_on(client.consensus_states(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::client::v1::query_client::QueryClient::new(channel)
+                        .consensus_states(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         let mut consensus_states: Vec<AnyConsensusStateWithHeight> = response
             .consensus_states
             .into_iter()
             .filter_map(|cs| TryFrom::try_from(cs).ok())
             .collect();
-        consensus_states.sort_by(|a, b| a.height.cmp(&b.height));
-        consensus_states.reverse();
-        Ok(consensus_states)
+        pagination::sort_consensus_states(&mut consensus_states);
+        Ok(Page::new(consensus_states, response.pagination))
     }
 
     fn query_consensus_state(
@@ -1066,7 +1362,7 @@ impl Chain for CosmosSdkChain {
         let consensus_state = self
             .proven_client_consensus(&client_id, consensus_height, query_height)?
             .0;
-        Ok(AnyConsensusState::Tendermint(consensus_state))
+        Ok(consensus_state)
     }
 
     fn query_client_connections(
@@ -1075,19 +1371,18 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Vec<ConnectionId>, Error> {
         crate::time!("query_connections");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
+        let response = self.block_on(grpc_failover(&self.grpc, |channel| {
+            let request = request.clone();
+            async move {
+                ibc_proto::ibc::core::connection::v1::query_client::QueryClient::new(channel)
+                    .client_connections(request)
+                    .await
+            }
+        }));
 
-        let response = match self.block_on(client.client_connections(request)) {
-            Ok(res) => res.into_inner(),
-            Err(e) if e.code() == tonic::Code::NotFound => return Ok(vec![]),
+        let response = match response {
+            Ok(res) => res,
+            Err(e) if e.code() == Some(tonic::Code::NotFound) => return Ok(vec![]),
             Err(e) => return Err(Kind::Grpc.context(e).into()),
         };
 
@@ -1106,23 +1401,19 @@ impl Chain for CosmosSdkChain {
     fn query_connections(
         &self,
         request: QueryConnectionsRequest,
-    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+    ) -> Result<Page<IdentifiedConnectionEnd>, Error> {
         crate::time!("query_connections");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
-
         let response = self
-            .block_on(client.connections(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::connection::v1::query_client::QueryClient::new(channel)
+                        .connections(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         // TODO: add warnings for any identifiers that fail to parse (below).
         //      similar to the parsing in `query_connection_channels`.
@@ -1133,7 +1424,7 @@ impl Chain for CosmosSdkChain {
             .filter_map(|co| IdentifiedConnectionEnd::try_from(co).ok())
             .collect();
 
-        Ok(connections)
+        Ok(Page::new(connections, response.pagination))
     }
 
     fn query_connection(
@@ -1147,34 +1438,33 @@ impl Chain for CosmosSdkChain {
             height: ICSHeight,
         ) -> Result<ConnectionEnd, Error> {
             use ibc_proto::ibc::core::connection::v1 as connection;
-            use tonic::{metadata::MetadataValue, IntoRequest};
 
-            let mut client =
-                connection::query_client::QueryClient::connect(chain.grpc_addr.clone())
-                    .await
-                    .map_err(|e| Kind::Grpc.context(e))?;
-
-            let mut request = connection::QueryConnectionRequest {
-                connection_id: connection_id.to_string(),
-            }
-            .into_request();
+            let height_param = height_metadata(height)?;
 
-            let height_param = MetadataValue::from_str(&height.revision_height.to_string())
-                .map_err(|e| Kind::Grpc.context(e))?;
-
-            request
-                .metadata_mut()
-                .insert("x-cosmos-block-height", height_param);
+            let response = grpc_failover(&chain.grpc, |channel| {
+                let request = at_height(
+                    connection::QueryConnectionRequest {
+                        connection_id: connection_id.to_string(),
+                    },
+                    Some(height_param.clone()),
+                );
 
-            let response = client.connection(request).await.map_err(|e| {
-                if e.code() == tonic::Code::NotFound {
+                async move {
+                    connection::query_client::QueryClient::new(channel)
+                        .connection(request)
+                        .await
+                }
+            })
+            .await
+            .map_err(|e| {
+                if e.code() == Some(tonic::Code::NotFound) {
                     Kind::ConnectionNotFound(connection_id.clone()).into()
                 } else {
                     Kind::Grpc.context(e)
                 }
             })?;
 
-            match response.into_inner().connection {
+            match response.connection {
                 Some(raw_connection) => {
                     let connection_end = raw_connection
                         .try_into()
@@ -1198,23 +1488,19 @@ impl Chain for CosmosSdkChain {
     fn query_connection_channels(
         &self,
         request: QueryConnectionChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+    ) -> Result<Page<IdentifiedChannelEnd>, Error> {
         crate::time!("query_connection_channels");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
-
         let response = self
-            .block_on(client.connection_channels(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .connection_channels(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         // TODO: add warnings for any identifiers that fail to parse (below).
         //  https://github.com/informalsystems/ibc-rs/pull/506#discussion_r555945560
@@ -1224,36 +1510,32 @@ impl Chain for CosmosSdkChain {
             .into_iter()
             .filter_map(|ch| IdentifiedChannelEnd::try_from(ch).ok())
             .collect();
-        Ok(channels)
+        Ok(Page::new(channels, response.pagination))
     }
 
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+    ) -> Result<Page<IdentifiedChannelEnd>, Error> {
         crate::time!("query_connections");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
-
         let response = self
-            .block_on(client.channels(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .channels(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         let channels = response
             .channels
             .into_iter()
             .filter_map(|ch| IdentifiedChannelEnd::try_from(ch).ok())
             .collect();
-        Ok(channels)
+        Ok(Page::new(channels, response.pagination))
     }
 
     fn query_channel(
@@ -1280,20 +1562,16 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Option<IdentifiedAnyClientState>, Error> {
         crate::time!("query_channel_client_state");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
-
         let response = self
-            .block_on(client.channel_client_state(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .channel_client_state(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         let client_state: Option<IdentifiedAnyClientState> = response
             .identified_client_state
@@ -1306,23 +1584,22 @@ impl Chain for CosmosSdkChain {
     fn query_packet_commitments(
         &self,
         request: QueryPacketCommitmentsRequest,
-    ) -> Result<(Vec<PacketState>, ICSHeight), Error> {
+        height: Option<ICSHeight>,
+    ) -> Result<(Page<PacketState>, ICSHeight), Error> {
         crate::time!("query_packet_commitments");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
+        let height_param = height.map(height_metadata).transpose()?;
 
         let response = self
-            .block_on(client.packet_commitments(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = at_height(request.clone(), height_param.clone());
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .packet_commitments(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         let pc = response.commitments;
 
@@ -1332,7 +1609,7 @@ impl Chain for CosmosSdkChain {
             .try_into()
             .map_err(|_| Kind::Grpc.context("invalid height in response"))?;
 
-        Ok((pc, height))
+        Ok((Page::new(pc, response.pagination), height))
     }
 
     /// Queries the unreceived packet sequences associated with a channel.
@@ -1342,20 +1619,16 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Vec<u64>, Error> {
         crate::time!("query_unreceived_packets");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
-
         let mut response = self
-            .block_on(client.unreceived_packets(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .unreceived_packets(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         response.sequences.sort_unstable();
         Ok(response.sequences)
@@ -1365,23 +1638,22 @@ impl Chain for CosmosSdkChain {
     fn query_packet_acknowledgements(
         &self,
         request: QueryPacketAcknowledgementsRequest,
-    ) -> Result<(Vec<PacketState>, ICSHeight), Error> {
+        height: Option<ICSHeight>,
+    ) -> Result<(Page<PacketState>, ICSHeight), Error> {
         crate::time!("query_packet_acknowledgements");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
+        let height_param = height.map(height_metadata).transpose()?;
 
         let response = self
-            .block_on(client.packet_acknowledgements(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = at_height(request.clone(), height_param.clone());
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .packet_acknowledgements(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         let pc = response.acknowledgements;
 
@@ -1391,7 +1663,7 @@ impl Chain for CosmosSdkChain {
             .try_into()
             .map_err(|_| Kind::Grpc.context("invalid height in response"))?;
 
-        Ok((pc, height))
+        Ok((Page::new(pc, response.pagination), height))
     }
 
     /// Queries the unreceived acknowledgements sequences associated with a channel.
@@ -1401,20 +1673,16 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Vec<u64>, Error> {
         crate::time!("query_unreceived_acknowledgements");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
-
         let mut response = self
-            .block_on(client.unreceived_acks(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .unreceived_acks(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         response.sequences.sort_unstable();
         Ok(response.sequences)
@@ -1426,20 +1694,16 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Sequence, Error> {
         crate::time!("query_next_sequence_receive");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
-
-        let request = tonic::Request::new(request);
-
         let response = self
-            .block_on(client.next_sequence_receive(request))
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner();
+            .block_on(grpc_failover(&self.grpc, |channel| {
+                let request = request.clone();
+                async move {
+                    ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new(channel)
+                        .next_sequence_receive(request)
+                        .await
+                }
+            }))
+            .map_err(|e| Kind::Grpc.context(e))?;
 
         Ok(Sequence::from(response.next_sequence_receive))
     }
@@ -1467,13 +1731,15 @@ impl Chain for CosmosSdkChain {
                 for seq in &request.sequences {
                     // query first (and only) Tx that includes the event specified in the query request
                     let response = self
-                        .block_on(self.rpc_client.tx_search(
-                            packet_query(&request, *seq),
-                            false,
-                            1,
-                            1, // get only the first Tx matching the query
-                            Order::Ascending,
-                        ))
+                        .block_on(self.rpc.failover(|client| {
+                            client.tx_search(
+                                packet_query(&request, *seq),
+                                false,
+                                1,
+                                1, // get only the first Tx matching the query
+                                Order::Ascending,
+                            )
+                        }))
                         .map_err(|e| Kind::Grpc.context(e))?;
 
                     assert!(
@@ -1507,13 +1773,15 @@ impl Chain for CosmosSdkChain {
                 // failed on chain. Therefore only one Tx is of interest and current API returns
                 // the first one.
                 let mut response = self
-                    .block_on(self.rpc_client.tx_search(
-                        header_query(&request),
-                        false,
-                        1,
-                        1, // get only the first Tx matching the query
-                        Order::Ascending,
-                    ))
+                    .block_on(self.rpc.failover(|client| {
+                        client.tx_search(
+                            header_query(&request),
+                            false,
+                            1,
+                            1, // get only the first Tx matching the query
+                            Order::Ascending,
+                        )
+                    }))
                     .map_err(|e| Kind::Grpc.context(e))?;
 
                 if response.txs.is_empty() {
@@ -1532,24 +1800,10 @@ impl Chain for CosmosSdkChain {
                 Ok(event.into_iter().collect())
             }
 
-            QueryTxRequest::Transaction(tx) => {
-                let mut response = self
-                    .block_on(self.rpc_client.tx_search(
-                        tx_hash_query(&tx),
-                        false,
-                        1,
-                        1, // get only the first Tx matching the query
-                        Order::Ascending,
-                    ))
-                    .map_err(|e| Kind::Grpc.context(e))?;
-
-                if response.txs.is_empty() {
-                    Ok(vec![])
-                } else {
-                    let tx = response.txs.remove(0);
-                    Ok(all_ibc_events_from_tx_search_response(self.id(), tx))
-                }
-            }
+            QueryTxRequest::Transaction(tx) => match self.query_tx_result(&tx)? {
+                Some(tx) => Ok(all_ibc_events_from_tx_search_response(self.id(), tx)),
+                None => Ok(vec![]),
+            },
         }
     }
 
@@ -1557,7 +1811,7 @@ impl Chain for CosmosSdkChain {
         &self,
         client_id: &ClientId,
         height: ICSHeight,
-    ) -> Result<(Self::ClientState, MerkleProof), Error> {
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
         crate::time!("proven_client_state");
 
         let res = self
@@ -1567,11 +1821,6 @@ impl Chain for CosmosSdkChain {
         let client_state = AnyClientState::decode_vec(&res.value)
             .map_err(|e| Kind::Query("client state".into()).context(e))?;
 
-        let client_state =
-            downcast!(client_state => AnyClientState::Tendermint).ok_or_else(|| {
-                Kind::Query("client state".into()).context("unexpected client state type")
-            })?;
-
         Ok((
             client_state,
             res.proof.ok_or_else(|| {
@@ -1585,7 +1834,7 @@ impl Chain for CosmosSdkChain {
         client_id: &ClientId,
         consensus_height: ICSHeight,
         height: ICSHeight,
-    ) -> Result<(Self::ConsensusState, MerkleProof), Error> {
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
         crate::time!("proven_client_consensus");
 
         let res = self
@@ -1603,11 +1852,6 @@ impl Chain for CosmosSdkChain {
         let consensus_state = AnyConsensusState::decode_vec(&res.value)
             .map_err(|e| Kind::Query("client consensus".into()).context(e))?;
 
-        let consensus_state = downcast!(consensus_state => AnyConsensusState::Tendermint)
-            .ok_or_else(|| {
-                Kind::Query("client consensus".into()).context("unexpected client consensus type")
-            })?;
-
         Ok((
             consensus_state,
             res.proof.ok_or_else(|| {
@@ -1887,6 +2131,82 @@ fn all_ibc_events_from_tx_search_response(chain_id: &ChainId, response: ResultTx
     result
 }
 
+/// The failure of a gRPC request: either no connection to the endpoint, or an error status.
+#[derive(Debug, thiserror::Error)]
+pub enum GrpcError {
+    #[error("cannot connect to the gRPC endpoint: {0}")]
+    Connect(#[from] tonic::transport::Error),
+
+    #[error("{0}")]
+    Status(#[from] tonic::Status),
+}
+
+impl GrpcError {
+    /// The code of the error status, if the endpoint answered with one.
+    pub fn code(&self) -> Option<tonic::Code> {
+        match self {
+            Self::Connect(_) => None,
+            Self::Status(status) => Some(status.code()),
+        }
+    }
+}
+
+/// Whether an error status means that the endpoint could not serve a request, because it is
+/// unreachable or did not answer in time, rather than that the node rejected it. The SDK
+/// answers application and query errors with `Unknown`, which is thus not such a failure.
+fn is_endpoint_failure(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        tonic::Code::Unavailable | tonic::Code::DeadlineExceeded
+    )
+}
+
+/// Sends a gRPC request, made by `request` with a channel to an endpoint, to the active gRPC
+/// endpoint and, if the endpoint cannot be connected to or cannot serve it, to the other
+/// endpoints in turn. An error status by which a node rejects the request, e.g., because the
+/// queried object does not exist, is returned without failing over.
+async fn grpc_failover<R, F, Fut>(grpc: &Endpoints<Uri>, request: F) -> Result<R, GrpcError>
+where
+    F: Fn(Channel) -> Fut,
+    Fut: Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+{
+    let request = &request;
+
+    let result = grpc
+        .failover(|uri| async move {
+            let channel = Channel::builder(uri.clone()).connect().await?;
+
+            match request(channel).await {
+                Ok(response) => Ok(Ok(response.into_inner())),
+                Err(status) if !is_endpoint_failure(&status) => Ok(Err(status)),
+                Err(status) => Err(GrpcError::Status(status)),
+            }
+        })
+        .await?;
+
+    Ok(result?)
+}
+
+/// The value of the `x-cosmos-block-height` metadata of a gRPC query at `height`.
+fn height_metadata(height: ICSHeight) -> Result<AsciiMetadataValue, Error> {
+    Ok(
+        AsciiMetadataValue::from_str(&height.revision_height.to_string())
+            .map_err(|e| Kind::Grpc.context(e))?,
+    )
+}
+
+/// The gRPC request of `message`, at the height of the `x-cosmos-block-height` metadata
+/// `height_param`, if any, or else at the latest height.
+fn at_height<T>(message: T, height_param: Option<AsciiMetadataValue>) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    if let Some(height_param) = height_param {
+        request
+            .metadata_mut()
+            .insert("x-cosmos-block-height", height_param);
+    }
+    request
+}
+
 /// Perform a generic `abci_query`, and return the corresponding deserialized response data.
 async fn abci_query(
     chain: &CosmosSdkChain,
@@ -1903,14 +2223,16 @@ async fn abci_query(
 
     // Use the Tendermint-rs RPC client to do the query.
     let response = chain
-        .rpc_client()
-        .abci_query(Some(path), data.into_bytes(), height, prove)
+        .rpc
+        .failover(|client| {
+            client.abci_query(Some(path.clone()), data.clone().into_bytes(), height, prove)
+        })
         .await
-        .map_err(|e| Kind::Rpc(chain.config.rpc_addr.clone()).context(e))?;
+        .map_err(|e| Kind::Rpc(chain.rpc.active_address().clone()).context(e))?;
 
     if !response.code.is_ok() {
         // Fail with response log.
-        return Err(Kind::Rpc(chain.config.rpc_addr.clone())
+        return Err(Kind::Rpc(chain.rpc.active_address().clone())
             .context(response.log.to_string())
             .into());
     }
@@ -1941,30 +2263,31 @@ async fn broadcast_tx_sync(
     data: Vec<u8>,
 ) -> Result<Response, anomaly::Error<Kind>> {
     let response = chain
-        .rpc_client()
-        .broadcast_tx_sync(data.into())
+        .rpc
+        .failover(|client| client.broadcast_tx_sync(data.clone().into()))
         .await
-        .map_err(|e| Kind::Rpc(chain.config.rpc_addr.clone()).context(e))?;
+        .map_err(|e| Kind::Rpc(chain.rpc.active_address().clone()).context(e))?;
 
     Ok(response)
 }
 
 /// Uses the GRPC client to retrieve the account sequence
 async fn query_account(chain: &CosmosSdkChain, address: String) -> Result<BaseAccount, Error> {
-    let mut client = ibc_proto::cosmos::auth::v1beta1::query_client::QueryClient::connect(
-        chain.grpc_addr.clone(),
-    )
-    .await
-    .map_err(|e| Kind::Grpc.context(e))?;
-
-    let request = tonic::Request::new(QueryAccountRequest { address });
+    let request = QueryAccountRequest { address };
 
-    let response = client.account(request).await;
+    let response = grpc_failover(&chain.grpc, |channel| {
+        let request = request.clone();
+        async move {
+            ibc_proto::cosmos::auth::v1beta1::query_client::QueryClient::new(channel)
+                .account(request)
+                .await
+        }
+    })
+    .await;
 
     let base_account = BaseAccount::decode(
         response
             .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner()
             .account
             .unwrap()
             .value
@@ -1975,6 +2298,78 @@ async fn query_account(chain: &CosmosSdkChain, address: String) -> Result<BaseAc
     Ok(base_account)
 }
 
+/// The fee of a transaction with the maximum gas, paid by the configured `fee_payer`, if any,
+/// from the allowance of the configured `fee_granter`, if any
+fn default_fee(config: &ChainConfig, gas_price: &GasPrice) -> Fee {
+    let max_gas = config.max_gas.unwrap_or(DEFAULT_MAX_GAS);
+
+    Fee {
+        amount: vec![calculate_fee(max_gas, gas_price)],
+        gas_limit: max_gas,
+        payer: config.fee_payer.clone().unwrap_or_default(),
+        granter: config.fee_granter.clone().unwrap_or_default(),
+    }
+}
+
+/// Checks that the configured `fee_payer`, if any, is the account of the key signing the
+/// transactions, as the fee payer must sign them. The check is left to the chain if the key
+/// cannot be loaded.
+fn check_fee_payer(config: &ChainConfig, keybase: &KeyRing) -> Result<(), Error> {
+    let fee_payer = match &config.fee_payer {
+        Some(fee_payer) => fee_payer,
+        None => return Ok(()),
+    };
+
+    match keybase.get_key(&config.key_name) {
+        Ok(key) if &key.account != fee_payer => Err(Kind::Config
+            .context(format!(
+                "the fee payer {} of chain {} is not the account {} of the key '{}' signing its transactions",
+                fee_payer, config.id, key.account, config.key_name
+            ))
+            .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Queries the balance of the account at `address` in the denomination `denom`
+async fn query_balance(
+    rpc: &Endpoints<HttpClient>,
+    address: &str,
+    denom: &str,
+) -> Result<u128, Error> {
+    let path = TendermintABCIPath::from_str(BANK_BALANCE_QUERY_PATH).unwrap();
+
+    let request = QueryBalanceRequest {
+        address: address.to_string(),
+        denom: denom.to_string(),
+    };
+    let mut data = Vec::new();
+    prost::Message::encode(&request, &mut data).unwrap();
+
+    let response = rpc
+        .failover(|client| client.abci_query(Some(path.clone()), data.clone(), None, false))
+        .await
+        .map_err(|e| Kind::Rpc(rpc.active_address().clone()).context(e))?;
+
+    if !response.code.is_ok() {
+        return Err(Kind::Rpc(rpc.active_address().clone())
+            .context(response.log.to_string())
+            .into());
+    }
+
+    let balance = QueryBalanceResponse::decode(response.value.as_ref())
+        .map_err(|e| Kind::Query("balance".to_string()).context(e))?
+        .balance;
+
+    match balance {
+        Some(coin) => Ok(coin
+            .amount
+            .parse()
+            .map_err(|e| Kind::Query("balance".to_string()).context(e))?),
+        None => Ok(0),
+    }
+}
+
 fn encode_to_bech32(address: &str, account_prefix: &str) -> Result<String, Error> {
     let account =
         AccountId::from_str(address).map_err(|_| Kind::InvalidKeyAddress(address.to_string()))?;
@@ -1985,16 +2380,168 @@ fn encode_to_bech32(address: &str, account_prefix: &str) -> Result<String, Error
     Ok(encoded)
 }
 
-/// Returns the suffix counter for a CosmosSDK client id.
-/// Returns `None` if the client identifier is malformed
-/// and the suffix could not be parsed.
-fn client_id_suffix(client_id: &ClientId) -> Option<u64> {
-    client_id
-        .as_str()
-        .split('-')
-        .last()
-        .map(|e| e.parse::<u64>().ok())
-        .flatten()
+/// The account and broadcast operations with which `send_tx` submits a transaction.
+trait TxSubmitter {
+    /// Returns the sequence of the signing account, from the cached account if any.
+    fn account_sequence(&mut self) -> Result<u64, Error>;
+
+    /// Increments the cached sequence of the signing account, after a successful check-tx.
+    fn incr_account_sequence(&mut self) -> Result<(), Error>;
+
+    /// Sets the cached sequence of the signing account, to the one the chain expects.
+    fn set_account_sequence(&mut self, sequence: u64) -> Result<(), Error>;
+
+    /// Discards the cached signing account and queries it again.
+    fn refresh_account(&mut self) -> Result<(), Error>;
+
+    /// Raises the gas price after a rejection for insufficient fees, if dynamic gas pricing is
+    /// enabled. Returns `false` if the gas price was not raised.
+    fn raise_gas_price(&mut self) -> bool;
+
+    /// Lowers the gas price after a transaction was accepted, if dynamic gas pricing is enabled.
+    fn lower_gas_price(&mut self);
+
+    /// Signs a transaction with the given messages and account sequence, and broadcasts it.
+    /// Returns the response of the broadcast, with the fee of the transaction.
+    fn sign_and_broadcast(
+        &mut self,
+        proto_msgs: &[Any],
+        sequence: u64,
+    ) -> Result<(Response, Fee), Error>;
+}
+
+impl TxSubmitter for CosmosSdkChain {
+    fn account_sequence(&mut self) -> Result<u64, Error> {
+        Ok(self.account()?.sequence)
+    }
+
+    fn incr_account_sequence(&mut self) -> Result<(), Error> {
+        self.account()?.sequence += 1;
+        Ok(())
+    }
+
+    fn set_account_sequence(&mut self, sequence: u64) -> Result<(), Error> {
+        self.account()?.sequence = sequence;
+        Ok(())
+    }
+
+    fn refresh_account(&mut self) -> Result<(), Error> {
+        self.accounts.remove(&self.key_name);
+        self.account().map(|_| ())
+    }
+
+    fn raise_gas_price(&mut self) -> bool {
+        self.gas_pricing.raise()
+    }
+
+    fn lower_gas_price(&mut self) {
+        self.gas_pricing.lower()
+    }
+
+    fn sign_and_broadcast(
+        &mut self,
+        proto_msgs: &[Any],
+        sequence: u64,
+    ) -> Result<(Response, Fee), Error> {
+        self.sign_and_broadcast_tx(proto_msgs, sequence)
+    }
+}
+
+/// Signs and broadcasts a transaction with the cached account sequence. If its check-tx fails
+/// because the sequence is stale, e.g., because another process submitted transactions with
+/// the same key or a previous transaction was dropped, the transaction is re-signed with the
+/// sequence the chain expects and resent, at most `MAX_SEQUENCE_MISMATCH_RETRIES` times. The
+/// expected sequence is taken from the error log, as the account queried from the node may lag
+/// behind its mempool, and the account is only queried again if the log does not name it.
+/// If it fails because of an insufficient fee, it is resent with a raised gas price, as long as
+/// the dynamic gas pricing allows, and the gas price is lowered again once a transaction passes.
+fn send_tx_with_recovery(
+    submitter: &mut impl TxSubmitter,
+    proto_msgs: &[Any],
+) -> Result<(Response, Fee), Error> {
+    let mut retries = 0;
+
+    loop {
+        let sequence = submitter.account_sequence()?;
+        let (response, fee) = submitter.sign_and_broadcast(proto_msgs, sequence)?;
+
+        if is_sequence_mismatch(&response) && retries < MAX_SEQUENCE_MISMATCH_RETRIES {
+            retries += 1;
+
+            match expected_sequence(&response.log.to_string()) {
+                Some(sequence) => {
+                    warn!(
+                        "send_tx: account sequence mismatch ({}), resending with sequence {}, retry {}/{}",
+                        response.log, sequence, retries, MAX_SEQUENCE_MISMATCH_RETRIES
+                    );
+
+                    submitter.set_account_sequence(sequence)?;
+                }
+                None => {
+                    warn!(
+                        "send_tx: account sequence mismatch ({}), refreshing the account and resending, retry {}/{}",
+                        response.log, retries, MAX_SEQUENCE_MISMATCH_RETRIES
+                    );
+
+                    submitter.refresh_account()?;
+                }
+            }
+
+            continue;
+        }
+
+        if is_insufficient_fee(&response) && submitter.raise_gas_price() {
+            warn!(
+                "send_tx: insufficient fee ({}), raising the gas price and resending",
+                response.log
+            );
+
+            continue;
+        }
+
+        if response.code.is_ok() {
+            submitter.incr_account_sequence()?;
+            submitter.lower_gas_price();
+        }
+
+        return Ok((response, fee));
+    }
+}
+
+/// Whether the check-tx of a transaction failed because of its account sequence.
+fn is_sequence_mismatch(response: &Response) -> bool {
+    is_sdk_error(
+        response,
+        INCORRECT_ACCOUNT_SEQUENCE_CODE,
+        INCORRECT_ACCOUNT_SEQUENCE_DESCRIPTION,
+    )
+}
+
+/// Whether the check-tx of a transaction failed with the error of the `sdk` codespace of the
+/// given code. Error codes are only unique within a codespace, and the broadcast response does
+/// not carry it, so the error is also matched on the description the SDK registers for it, with
+/// which it ends the log.
+fn is_sdk_error(response: &Response, code: u32, description: &str) -> bool {
+    response.code.value() == code && response.log.to_string().trim_end().ends_with(description)
+}
+
+/// Whether the check-tx of a transaction failed because its fee is below the minimum gas
+/// price of the node.
+fn is_insufficient_fee(response: &Response) -> bool {
+    is_sdk_error(
+        response,
+        INSUFFICIENT_FEE_CODE,
+        INSUFFICIENT_FEE_DESCRIPTION,
+    )
+}
+
+/// Parses the sequence expected by the chain from an "expected N, got M" error log.
+fn expected_sequence(log: &str) -> Option<u64> {
+    let start = log.find("expected ")? + "expected ".len();
+    let rest = &log[start..];
+    let end = rest.find(", got ")?;
+
+    rest[..end].trim().parse().ok()
 }
 
 pub struct TxSyncResult {
@@ -2002,6 +2549,38 @@ pub struct TxSyncResult {
     response: Response,
     // the events generated by a Tx once executed
     events: Vec<IbcEvent>,
+    // the number of messages in the Tx
+    msg_count: usize,
+    // the fee of the Tx, if it was signed
+    fee: Option<Fee>,
+    // the gas used by the Tx, once committed
+    gas_used: Option<u64>,
+}
+
+impl TxSyncResult {
+    fn receipt(&self) -> TxReceipt {
+        let fee = self.fee.as_ref();
+
+        TxReceipt {
+            hash: self.response.hash.to_string(),
+            msg_count: self.msg_count,
+            gas_wanted: fee.map_or(0, |fee| fee.gas_limit),
+            gas_used: self.gas_used,
+            fee: fee.map(format_fee).unwrap_or_default(),
+            error: self.events.iter().find_map(|event| match event {
+                IbcEvent::ChainError(e) => Some(e.clone()),
+                _ => None,
+            }),
+        }
+    }
+}
+
+/// Formats the amount of a fee, e.g., `2500stake`.
+fn format_fee(fee: &Fee) -> String {
+    fee.amount
+        .iter()
+        .map(|coin| format!("{}{}", coin.amount, coin.denom))
+        .join(",")
 }
 
 fn auth_info_and_bytes(signer_info: SignerInfo, fee: Fee) -> Result<(AuthInfo, Vec<u8>), Error> {
@@ -2016,14 +2595,18 @@ fn auth_info_and_bytes(signer_info: SignerInfo, fee: Fee) -> Result<(AuthInfo, V
     Ok((auth_info, auth_buf))
 }
 
-fn tx_body_and_bytes(proto_msgs: Vec<Any>) -> Result<(TxBody, Vec<u8>), Error> {
+fn tx_body_and_bytes(
+    proto_msgs: Vec<Any>,
+    memo: String,
+    non_critical_extension_options: Vec<Any>,
+) -> Result<(TxBody, Vec<u8>), Error> {
     // Create TxBody
     let body = TxBody {
         messages: proto_msgs.to_vec(),
-        memo: "".to_string(),
+        memo,
         timeout_height: 0_u64,
         extension_options: Vec::<Any>::new(),
-        non_critical_extension_options: Vec::<Any>::new(),
+        non_critical_extension_options,
     };
     // A protobuf serialization of a TxBody
     let mut body_buf = Vec::new();
@@ -2051,6 +2634,258 @@ fn mul_ceil(a: u64, f: f64) -> u64 {
 
 #[cfg(test)]
 mod tests {
+    use prost_types::Any;
+    use tendermint::abci::{transaction::Hash, Code, Log};
+    use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+
+    use ibc_proto::cosmos::tx::v1beta1::Fee;
+
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::config::GasPrice;
+    use crate::error::Error;
+
+    use super::{send_tx_with_recovery, TxSubmitter, MAX_SEQUENCE_MISMATCH_RETRIES};
+
+    /// A stubbed RPC endpoint, whose check-tx only accepts the sequence the chain expects
+    #[derive(Default)]
+    struct StubRpc {
+        /// The next sequence of the account on chain
+        chain_sequence: u64,
+        /// The sequence of the account cached by the relayer
+        cached_sequence: Option<u64>,
+        /// Whether another process uses the same key, which commits a transaction before
+        /// each of the relayer's
+        competing: bool,
+        refreshes: usize,
+        broadcast_sequences: Vec<u64>,
+        /// Whether the sequence mismatch errors omit the expected sequence from their log
+        opaque_logs: bool,
+        /// Whether the check-tx fails with an error of another codespace than `sdk`, with the
+        /// same code as the incorrect account sequence error
+        foreign_error: bool,
+        /// How many times the gas price must be raised before the fee is sufficient
+        required_raises: usize,
+        /// How many times the dynamic gas pricing allows raising the gas price
+        max_raises: usize,
+        raises: usize,
+        lowers: usize,
+    }
+
+    impl TxSubmitter for StubRpc {
+        fn account_sequence(&mut self) -> Result<u64, Error> {
+            let chain_sequence = self.chain_sequence;
+            Ok(*self.cached_sequence.get_or_insert(chain_sequence))
+        }
+
+        fn incr_account_sequence(&mut self) -> Result<(), Error> {
+            *self.cached_sequence.as_mut().unwrap() += 1;
+            Ok(())
+        }
+
+        fn set_account_sequence(&mut self, sequence: u64) -> Result<(), Error> {
+            self.cached_sequence = Some(sequence);
+            Ok(())
+        }
+
+        fn refresh_account(&mut self) -> Result<(), Error> {
+            self.refreshes += 1;
+            self.cached_sequence = Some(self.chain_sequence);
+            Ok(())
+        }
+
+        fn raise_gas_price(&mut self) -> bool {
+            if self.raises < self.max_raises {
+                self.raises += 1;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn lower_gas_price(&mut self) {
+            self.lowers += 1;
+        }
+
+        fn sign_and_broadcast(
+            &mut self,
+            _proto_msgs: &[Any],
+            sequence: u64,
+        ) -> Result<(Response, Fee), Error> {
+            self.broadcast_sequences.push(sequence);
+
+            if self.competing {
+                self.chain_sequence += 1;
+            }
+
+            let (code, log) = if self.foreign_error {
+                (
+                    Code::Err(32),
+                    "channel handshake failed: no route".to_string(),
+                )
+            } else if self.raises < self.required_raises {
+                (
+                    Code::Err(13),
+                    "insufficient fees; got: 100stake required: 200stake: insufficient fee"
+                        .to_string(),
+                )
+            } else if sequence == self.chain_sequence {
+                self.chain_sequence += 1;
+                (Code::Ok, "[]".to_string())
+            } else if self.opaque_logs {
+                (Code::Err(32), "incorrect account sequence".to_string())
+            } else {
+                (
+                    Code::Err(32),
+                    format!(
+                        "account sequence mismatch, expected {}, got {}: incorrect account sequence",
+                        self.chain_sequence, sequence
+                    ),
+                )
+            };
+
+            let response = Response {
+                code,
+                data: vec![].into(),
+                log: Log::from(log.as_str()),
+                hash: Hash::new([0; 32]),
+            };
+
+            Ok((response, Fee::default()))
+        }
+    }
+
+    #[test]
+    fn expected_sequence() {
+        let log = "account sequence mismatch, expected 12, got 10: incorrect account sequence";
+        assert_eq!(super::expected_sequence(log), Some(12));
+        assert_eq!(super::expected_sequence("out of gas"), None);
+    }
+
+    #[test]
+    fn send_tx_with_cached_sequence() {
+        let mut rpc = StubRpc {
+            chain_sequence: 5,
+            ..StubRpc::default()
+        };
+
+        for _ in 0..2 {
+            let (response, _) = send_tx_with_recovery(&mut rpc, &[]).unwrap();
+            assert!(response.code.is_ok());
+        }
+
+        assert_eq!(rpc.broadcast_sequences, vec![5, 6]);
+        assert_eq!(rpc.cached_sequence, Some(7));
+        assert_eq!(rpc.refreshes, 0);
+    }
+
+    #[test]
+    fn send_tx_recovers_from_stale_sequence() {
+        let mut rpc = StubRpc {
+            chain_sequence: 8,
+            cached_sequence: Some(5),
+            ..StubRpc::default()
+        };
+
+        let (response, _) = send_tx_with_recovery(&mut rpc, &[]).unwrap();
+
+        assert!(response.code.is_ok());
+        assert_eq!(rpc.broadcast_sequences, vec![5, 8]);
+        assert_eq!(rpc.refreshes, 0);
+        assert_eq!(rpc.cached_sequence, Some(9));
+    }
+
+    #[test]
+    fn send_tx_refreshes_account_without_expected_sequence() {
+        let mut rpc = StubRpc {
+            chain_sequence: 8,
+            cached_sequence: Some(5),
+            opaque_logs: true,
+            ..StubRpc::default()
+        };
+
+        let (response, _) = send_tx_with_recovery(&mut rpc, &[]).unwrap();
+
+        assert!(response.code.is_ok());
+        assert_eq!(rpc.broadcast_sequences, vec![5, 8]);
+        assert_eq!(rpc.refreshes, 1);
+        assert_eq!(rpc.cached_sequence, Some(9));
+    }
+
+    #[test]
+    fn send_tx_ignores_errors_of_other_codespaces() {
+        let mut rpc = StubRpc {
+            chain_sequence: 2,
+            foreign_error: true,
+            ..StubRpc::default()
+        };
+
+        let (response, _) = send_tx_with_recovery(&mut rpc, &[]).unwrap();
+
+        assert_eq!(response.code, Code::Err(32));
+        assert_eq!(rpc.broadcast_sequences, vec![2]);
+        assert_eq!(rpc.cached_sequence, Some(2));
+    }
+
+    #[test]
+    fn send_tx_gives_up_after_bounded_retries() {
+        let mut rpc = StubRpc {
+            chain_sequence: 1,
+            competing: true,
+            ..StubRpc::default()
+        };
+
+        let (response, _) = send_tx_with_recovery(&mut rpc, &[]).unwrap();
+
+        assert_eq!(response.code, Code::Err(32));
+        assert_eq!(
+            rpc.broadcast_sequences.len(),
+            MAX_SEQUENCE_MISMATCH_RETRIES + 1
+        );
+        assert_eq!(rpc.refreshes, 0);
+        // The sequence of a rejected transaction is not consumed
+        assert_eq!(rpc.cached_sequence, Some(rpc.chain_sequence - 1));
+    }
+
+    #[test]
+    fn send_tx_raises_gas_price_up_to_ceiling() {
+        let mut rpc = StubRpc {
+            chain_sequence: 3,
+            required_raises: 2,
+            max_raises: 3,
+            ..StubRpc::default()
+        };
+
+        let (response, _) = send_tx_with_recovery(&mut rpc, &[]).unwrap();
+        assert!(response.code.is_ok());
+        assert_eq!(rpc.broadcast_sequences, vec![3, 3, 3]);
+        assert_eq!(rpc.raises, 2);
+        assert_eq!(rpc.lowers, 1);
+
+        rpc.required_raises = 5;
+        let (response, _) = send_tx_with_recovery(&mut rpc, &[]).unwrap();
+        assert_eq!(response.code, Code::Err(13));
+        assert_eq!(rpc.broadcast_sequences, vec![3, 3, 3, 4, 4]);
+        assert_eq!(rpc.cached_sequence, Some(4));
+        assert_eq!(rpc.lowers, 1);
+    }
+
+    #[test]
+    fn default_fee_sets_payer_and_granter() {
+        let mut config = get_basic_chain_config("chain_a");
+        let gas_price = GasPrice::new(0.001, "stake".to_string());
+
+        let fee = super::default_fee(&config, &gas_price);
+        assert_eq!((fee.payer.as_str(), fee.granter.as_str()), ("", ""));
+
+        config.fee_payer = Some("cosmos1payer".to_string());
+        config.fee_granter = Some("cosmos1granter".to_string());
+
+        let fee = super::default_fee(&config, &gas_price);
+        assert_eq!(fee.payer, "cosmos1payer");
+        assert_eq!(fee.granter, "cosmos1granter");
+        assert_eq!(fee.gas_limit, super::DEFAULT_MAX_GAS);
+    }
+
     #[test]
     fn mul_ceil() {
         assert_eq!(super::mul_ceil(300_000, 0.001), 300);
@@ -0,0 +1,230 @@
+//! Selection of the gas price of transactions, among the fee denominations accepted by a chain.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::config::{ChainConfig, DynamicGasPrice, GasPrice};
+
+use super::calculate_fee;
+
+/// The gRPC path of the balance query of the bank module, routed through ABCI queries
+pub const BANK_BALANCE_QUERY_PATH: &str = "/cosmos.bank.v1beta1.Query/Balance";
+
+/// How long a queried account balance is used to select the fee denomination, before it is
+/// queried again
+const BALANCE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The gas prices with which the relayer pays the fees of its transactions to a chain.
+#[derive(Clone, Debug)]
+pub struct GasPricing {
+    /// The configured gas prices of the accepted fee denominations, in order of preference
+    gas_prices: Vec<GasPrice>,
+    dynamic: Option<DynamicGasPrice>,
+    /// The ratio of the configured gas prices currently paid
+    multiplier: f64,
+    /// The balances of the signing accounts, indexed by account and denomination, with the
+    /// time at which they were queried
+    balances: HashMap<(String, String), (u128, Instant)>,
+}
+
+impl GasPricing {
+    pub fn new(config: &ChainConfig) -> Self {
+        let mut gas_prices = vec![config.gas_price.clone()];
+        gas_prices.extend(config.extra_gas_prices.iter().cloned());
+
+        Self {
+            gas_prices,
+            dynamic: config.dynamic_gas_price.clone(),
+            multiplier: 1.0,
+            balances: HashMap::new(),
+        }
+    }
+
+    /// Whether more than one fee denomination is accepted.
+    pub fn has_alternatives(&self) -> bool {
+        self.gas_prices.len() > 1
+    }
+
+    /// Returns the current gas price of the first denomination in which the balance of
+    /// `account` covers the fee of `gas`, or of the preferred denomination if there is no such
+    /// denomination or no account. The balances are cached, and queried with `query_balance`
+    /// once expired.
+    pub fn select(
+        &mut self,
+        gas: u64,
+        account: Option<&str>,
+        mut query_balance: impl FnMut(&str, &str) -> Option<u128>,
+    ) -> GasPrice {
+        let gas_prices = self.current();
+        let account = match account {
+            Some(account) if self.has_alternatives() => account,
+            _ => return gas_prices[0].clone(),
+        };
+
+        gas_prices
+            .iter()
+            .find(|gas_price| {
+                let fee = calculate_fee(gas, gas_price);
+                let balance = self.balance(account, &gas_price.denom, &mut query_balance);
+                match (balance, fee.amount.parse::<u128>()) {
+                    (Some(balance), Ok(fee)) => balance >= fee,
+                    _ => false,
+                }
+            })
+            .unwrap_or(&gas_prices[0])
+            .clone()
+    }
+
+    /// Raises the gas prices by one step of the dynamic pricing, up to its ceiling.
+    /// Returns `false` if dynamic pricing is disabled or the prices are at their ceiling.
+    pub fn raise(&mut self) -> bool {
+        let dynamic = match &self.dynamic {
+            Some(dynamic) => dynamic,
+            None => return false,
+        };
+
+        let raised = (self.multiplier + dynamic.step).min(dynamic.max_multiplier);
+        if raised <= self.multiplier {
+            return false;
+        }
+
+        self.multiplier = raised;
+        true
+    }
+
+    /// Lowers the gas prices by one step of the dynamic pricing after a transaction was
+    /// accepted, down to the configured prices, so that a raise does not outlast the congestion
+    /// which caused it.
+    pub fn lower(&mut self) {
+        if let Some(dynamic) = &self.dynamic {
+            self.multiplier = (self.multiplier - dynamic.step).max(1.0);
+        }
+    }
+
+    /// The balance of `account` in `denom`, from the cache if it has not expired.
+    fn balance(
+        &mut self,
+        account: &str,
+        denom: &str,
+        query_balance: impl FnOnce(&str, &str) -> Option<u128>,
+    ) -> Option<u128> {
+        let key = (account.to_string(), denom.to_string());
+        if let Some((balance, queried_at)) = self.balances.get(&key) {
+            if queried_at.elapsed() < BALANCE_CACHE_TTL {
+                return Some(*balance);
+            }
+        }
+
+        let balance = query_balance(account, denom)?;
+        self.balances.insert(key, (balance, Instant::now()));
+        Some(balance)
+    }
+
+    /// The current gas prices, in order of preference.
+    fn current(&self) -> Vec<GasPrice> {
+        self.gas_prices
+            .iter()
+            .map(|gas_price| {
+                GasPrice::new(gas_price.price * self.multiplier, gas_price.denom.clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::config::{DynamicGasPrice, GasPrice};
+
+    use super::GasPricing;
+
+    fn gas_pricing(dynamic_gas_price: Option<DynamicGasPrice>) -> GasPricing {
+        let mut config = get_basic_chain_config("chain_a");
+        config.gas_price = GasPrice::new(0.1, "stake".to_string());
+        config.extra_gas_prices = vec![GasPrice::new(0.01, "uatom".to_string())];
+        config.dynamic_gas_price = dynamic_gas_price;
+        GasPricing::new(&config)
+    }
+
+    #[test]
+    fn first_denom_with_sufficient_balance() {
+        let mut pricing = gas_pricing(None);
+        assert!(pricing.has_alternatives());
+
+        // The fee of 1000 gas is 100stake or 10uatom
+        let selected = pricing.select(1000, Some("alice"), |_, denom| match denom {
+            "stake" => Some(99),
+            "uatom" => Some(10),
+            _ => None,
+        });
+        assert_eq!(selected.denom, "uatom");
+
+        let selected = pricing.select(1000, Some("bob"), |_, _| Some(100));
+        assert_eq!(selected.denom, "stake");
+
+        // Without sufficient balance or account, the preferred denomination is used
+        let selected = pricing.select(1000, Some("carol"), |_, _| None);
+        assert_eq!(selected.denom, "stake");
+        let selected = pricing.select(1000, None, |_, _| Some(100));
+        assert_eq!(selected.denom, "stake");
+    }
+
+    #[test]
+    fn cached_balances() {
+        let mut pricing = gas_pricing(None);
+        let mut queries = Vec::new();
+
+        for _ in 0..2 {
+            let selected = pricing.select(1000, Some("alice"), |account, denom| {
+                queries.push((account.to_string(), denom.to_string()));
+                Some(10)
+            });
+            assert_eq!(selected.denom, "uatom");
+        }
+
+        // Each balance is only queried once
+        assert_eq!(
+            queries,
+            vec![
+                ("alice".to_string(), "stake".to_string()),
+                ("alice".to_string(), "uatom".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn raise_up_to_ceiling() {
+        let mut pricing = gas_pricing(None);
+        assert!(!pricing.raise());
+
+        let mut pricing = gas_pricing(Some(DynamicGasPrice {
+            step: 0.5,
+            max_multiplier: 2.2,
+        }));
+
+        let price = |pricing: &mut GasPricing| {
+            pricing
+                .select(1000, Some("alice"), |_, _| Some(u128::MAX))
+                .price
+        };
+        assert!((price(&mut pricing) - 0.1).abs() < f64::EPSILON);
+
+        assert!(pricing.raise());
+        assert!(pricing.raise());
+        assert!((price(&mut pricing) - 0.2).abs() < f64::EPSILON);
+
+        assert!(pricing.raise());
+        assert!((price(&mut pricing) - 0.22).abs() < 1e-9);
+        assert!(!pricing.raise());
+
+        // Accepted transactions lower the price back to the configured one
+        pricing.lower();
+        assert!((price(&mut pricing) - 0.17).abs() < 1e-9);
+        for _ in 0..3 {
+            pricing.lower();
+        }
+        assert!((price(&mut pricing) - 0.1).abs() < f64::EPSILON);
+    }
+}
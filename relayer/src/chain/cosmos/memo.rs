@@ -0,0 +1,151 @@
+//! The memo and the extension options of the relayer's transactions to a chain.
+
+use std::collections::BTreeMap;
+
+use prost_types::Any;
+
+use ibc::ics24_host::identifier::ChainId;
+
+use crate::config::{ChainConfig, ExtensionOption};
+use crate::error::{Error, Kind};
+
+/// The version of the relayer, which replaces `{version}` in memo templates
+const RELAYER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The memo templates and the non-critical extension options of the transactions to a chain.
+#[derive(Clone, Debug)]
+pub struct TxOptions {
+    chain_id: ChainId,
+    memo_template: Option<String>,
+    /// The memo templates by the type URL of the messages to which they apply
+    memo_overrides: BTreeMap<String, String>,
+    non_critical_extension_options: Vec<Any>,
+}
+
+impl TxOptions {
+    pub fn new(config: &ChainConfig) -> Result<Self, Error> {
+        let non_critical_extension_options = config
+            .non_critical_extension_options
+            .iter()
+            .map(extension_option)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            chain_id: config.id.clone(),
+            memo_template: config.memo_template.clone(),
+            memo_overrides: config.memo_overrides.clone(),
+            non_critical_extension_options,
+        })
+    }
+
+    /// The memo of a transaction with the messages `msgs`, relaying from the chain
+    /// `counterparty_chain_id`, if known.
+    ///
+    /// The template is the override of the first message which has one, or else the memo
+    /// template of the chain. The memo is empty if there is no template.
+    pub fn memo(&self, msgs: &[Any], counterparty_chain_id: Option<&ChainId>) -> String {
+        let template = msgs
+            .iter()
+            .find_map(|msg| self.memo_overrides.get(&msg.type_url))
+            .or_else(|| self.memo_template.as_ref());
+
+        match template {
+            Some(template) => template
+                .replace("{version}", RELAYER_VERSION)
+                .replace("{chain_id}", self.chain_id.as_str())
+                .replace(
+                    "{counterparty_chain_id}",
+                    counterparty_chain_id.map_or("", ChainId::as_str),
+                ),
+            None => String::new(),
+        }
+    }
+
+    pub fn non_critical_extension_options(&self) -> &[Any] {
+        &self.non_critical_extension_options
+    }
+}
+
+fn extension_option(option: &ExtensionOption) -> Result<Any, Error> {
+    let value = hex::decode(&option.value).map_err(|e| {
+        Kind::Config.context(format!(
+            "invalid value of the extension option {}: {}",
+            option.type_url, e
+        ))
+    })?;
+
+    Ok(Any {
+        type_url: option.type_url.clone(),
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use prost_types::Any;
+    use test_env_log::test;
+
+    use ibc::ics24_host::identifier::ChainId;
+
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::config::ExtensionOption;
+
+    use super::{TxOptions, RELAYER_VERSION};
+
+    fn msg(type_url: &str) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        }
+    }
+
+    #[test]
+    fn memo_from_template_or_override() {
+        let mut config = get_basic_chain_config("chain_a");
+        let options = TxOptions::new(&config).unwrap();
+        assert_eq!(
+            options.memo(&[msg("/ibc.core.client.v1.MsgUpdateClient")], None),
+            ""
+        );
+
+        config.memo_template = Some("hermes {version} {counterparty_chain_id}->{chain_id}".into());
+        config.memo_overrides.insert(
+            "/ibc.core.channel.v1.MsgRecvPacket".into(),
+            "recv by relayer-1".into(),
+        );
+        let options = TxOptions::new(&config).unwrap();
+
+        let counterparty = ChainId::new("chain_b".to_string(), 0);
+        assert_eq!(
+            options.memo(
+                &[msg("/ibc.core.client.v1.MsgUpdateClient")],
+                Some(&counterparty)
+            ),
+            format!("hermes {} chain_b-0->chain_a", RELAYER_VERSION)
+        );
+
+        let msgs = [
+            msg("/ibc.core.client.v1.MsgUpdateClient"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+        ];
+        assert_eq!(options.memo(&msgs, None), "recv by relayer-1");
+    }
+
+    #[test]
+    fn hex_encoded_extension_options() {
+        let mut config = get_basic_chain_config("chain_a");
+        config.non_critical_extension_options = vec![ExtensionOption {
+            type_url: "/ethermint.types.v1.ExtensionOptionsWeb3Tx".into(),
+            value: "0a0131".into(),
+        }];
+
+        let options = TxOptions::new(&config).unwrap();
+        assert_eq!(
+            options.non_critical_extension_options()[0].value,
+            vec![10, 1, 49]
+        );
+
+        config.non_critical_extension_options[0].value = "not hex".into();
+        assert!(TxOptions::new(&config).is_err());
+    }
+}
@@ -187,18 +187,19 @@ fn fetch_channel_on_destination(
 ) -> Result<Option<ChannelEnd>, Error> {
     let req = QueryConnectionChannelsRequest {
         connection: remote_connection_id.to_string(),
-        pagination: ibc_proto::cosmos::base::query::pagination::all(),
+        pagination: None,
     };
 
-    let counterparty_channels = counterparty_chain
-        .query_connection_channels(req)
-        .map_err(|e| Error::QueryFailed(format!("{}", e)))?;
+    // Stop at the first page which has the channel
+    for page in counterparty_chain.query_connection_channels_pages(req) {
+        let counterparty_channels = page.map_err(|e| Error::QueryFailed(format!("{}", e)))?;
 
-    for counterparty_channel in counterparty_channels.into_iter() {
-        let local_channel_end = &counterparty_channel.channel_end.remote;
-        if let Some(local_channel_id) = local_channel_end.channel_id() {
-            if local_channel_id == channel_id && local_channel_end.port_id() == port_id {
-                return Ok(Some(counterparty_channel.channel_end));
+        for counterparty_channel in counterparty_channels.items.into_iter() {
+            let local_channel_end = &counterparty_channel.channel_end.remote;
+            if let Some(local_channel_id) = local_channel_end.channel_id() {
+                if local_channel_id == channel_id && local_channel_end.port_id() == port_id {
+                    return Ok(Some(counterparty_channel.channel_end));
+                }
             }
         }
     }
@@ -0,0 +1,309 @@
+//! The endpoints of the nodes of a chain, in order of preference, whose health is tracked so
+//! that requests fail over to another endpoint when the active endpoint fails.
+
+use std::fmt;
+use std::future::Future;
+use std::iter;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use tendermint_rpc::Url;
+use tracing::{info, warn};
+
+use ibc::ics24_host::identifier::ChainId;
+
+use crate::telemetry;
+
+/// The kind of an endpoint, which labels its logs and metrics.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EndpointKind {
+    Rpc,
+    Grpc,
+    WebSocket,
+}
+
+impl fmt::Display for EndpointKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc => write!(f, "rpc"),
+            Self::Grpc => write!(f, "grpc"),
+            Self::WebSocket => write!(f, "websocket"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint<T> {
+    address: Url,
+    client: T,
+    healthy: AtomicBool,
+}
+
+/// The endpoints of one kind of a chain, in order of preference, with the client of each.
+/// Requests are sent to the active endpoint first, which changes when it fails.
+#[derive(Debug)]
+pub struct Endpoints<T> {
+    chain_id: ChainId,
+    kind: EndpointKind,
+    endpoints: Vec<Endpoint<T>>,
+    /// The index of the active endpoint
+    active: AtomicUsize,
+}
+
+impl<T> Endpoints<T> {
+    /// Creates the endpoints at the given addresses, in order of preference, whose clients are
+    /// created by `client`. The first endpoint is active, and all are presumed healthy.
+    ///
+    /// ## Panics
+    /// If there are no addresses.
+    pub fn new<E>(
+        chain_id: ChainId,
+        kind: EndpointKind,
+        addresses: Vec<Url>,
+        mut client: impl FnMut(&Url) -> Result<T, E>,
+    ) -> Result<Self, E> {
+        assert!(!addresses.is_empty(), "a chain needs at least one endpoint");
+
+        let endpoints = addresses
+            .into_iter()
+            .map(|address| {
+                Ok(Endpoint {
+                    client: client(&address)?,
+                    address,
+                    healthy: AtomicBool::new(true),
+                })
+            })
+            .collect::<Result<Vec<_>, E>>()?;
+
+        let endpoints = Self {
+            chain_id,
+            kind,
+            endpoints,
+            active: AtomicUsize::new(0),
+        };
+
+        telemetry!(endpoints.report_active(0, 1));
+
+        Ok(endpoints)
+    }
+
+    pub fn kind(&self) -> EndpointKind {
+        self.kind
+    }
+
+    /// The number of endpoints.
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Whether there are no endpoints, which cannot happen once created.
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// The address of the active endpoint.
+    pub fn active_address(&self) -> &Url {
+        &self.endpoints[self.active()].address
+    }
+
+    /// The client of the active endpoint.
+    pub fn active_client(&self) -> &T {
+        &self.endpoints[self.active()].client
+    }
+
+    /// The addresses and clients of the endpoints, in order of preference.
+    pub fn iter(&self) -> impl Iterator<Item = (&Url, &T)> {
+        self.endpoints.iter().map(|e| (&e.address, &e.client))
+    }
+
+    pub fn is_healthy(&self, index: usize) -> bool {
+        self.endpoints[index].healthy.load(Ordering::SeqCst)
+    }
+
+    pub fn set_healthy(&self, index: usize, healthy: bool) {
+        self.endpoints[index]
+            .healthy
+            .store(healthy, Ordering::SeqCst);
+    }
+
+    /// Makes the preferred healthy endpoint the active endpoint.
+    /// Returns `false`, leaving the active endpoint unchanged, if no endpoint is healthy.
+    pub fn activate_healthy(&self) -> bool {
+        match (0..self.len()).find(|&index| self.is_healthy(index)) {
+            Some(index) => {
+                self.activate(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sends a request, built by `request` for the client of an endpoint, to the active
+    /// endpoint and, if it fails, to the other endpoints in turn: the healthy endpoints, then
+    /// the unhealthy ones. The first endpoint which succeeds becomes the active endpoint.
+    ///
+    /// Returns the error of the last endpoint if the request fails on all of them.
+    pub async fn failover<'a, R, E, F, Fut>(&'a self, mut request: F) -> Result<R, E>
+    where
+        F: FnMut(&'a T) -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+        E: fmt::Display,
+    {
+        let mut last_error = None;
+
+        for index in self.failover_order() {
+            let endpoint = &self.endpoints[index];
+
+            match request(&endpoint.client).await {
+                Ok(response) => {
+                    self.set_healthy(index, true);
+                    self.activate(index);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    if self.len() > 1 {
+                        warn!(
+                            chain.id = %self.chain_id,
+                            "request to {} endpoint {} failed: {}", self.kind, endpoint.address, e
+                        );
+                    }
+
+                    self.set_healthy(index, false);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.expect("there is at least one endpoint"))
+    }
+
+    fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    fn activate(&self, index: usize) {
+        let previous = self.active.swap(index, Ordering::SeqCst);
+        if previous == index {
+            return;
+        }
+
+        info!(
+            chain.id = %self.chain_id,
+            "failing over from {} endpoint {} to {}",
+            self.kind,
+            self.endpoints[previous].address,
+            self.endpoints[index].address
+        );
+
+        telemetry!({
+            self.report_active(previous, -1);
+            self.report_active(index, 1);
+            crate::telemetry::global().endpoint_failover(&self.chain_id, &self.kind.to_string(), 1);
+        });
+    }
+
+    /// The indices of the endpoints in the order in which a request is sent to them.
+    fn failover_order(&self) -> Vec<usize> {
+        let active = self.active();
+
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) = (0..self.len())
+            .filter(|&index| index != active)
+            .partition(|&index| self.is_healthy(index));
+
+        iter::once(active).chain(healthy).chain(unhealthy).collect()
+    }
+
+    #[cfg(feature = "telemetry")]
+    fn report_active(&self, index: usize, count: i64) {
+        crate::telemetry::global().active_endpoint(
+            &self.chain_id,
+            &self.kind.to_string(),
+            &self.endpoints[index].address.to_string(),
+            count,
+        );
+    }
+}
+
+impl<T> Drop for Endpoints<T> {
+    fn drop(&mut self) {
+        telemetry!(self.report_active(self.active(), -1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use test_env_log::test;
+
+    use ibc::ics24_host::identifier::ChainId;
+
+    use super::{EndpointKind, Endpoints};
+
+    /// Endpoints whose clients are stand-in servers, which are up or down
+    fn endpoints(up: &[bool]) -> Endpoints<RefCell<bool>> {
+        let addresses = (0..up.len())
+            .map(|i| format!("http://127.0.0.1:{}", 26657 + i).parse().unwrap())
+            .collect();
+
+        let mut up = up.iter();
+        Endpoints::new(
+            ChainId::new("ibc".to_string(), 0),
+            EndpointKind::Rpc,
+            addresses,
+            |_| Ok::<_, String>(RefCell::new(*up.next().unwrap())),
+        )
+        .unwrap()
+    }
+
+    /// A request to a stand-in server, which returns its address
+    fn request(endpoints: &Endpoints<RefCell<bool>>) -> Result<String, String> {
+        futures::executor::block_on(endpoints.failover(|up| async move {
+            if *up.borrow() {
+                Ok(())
+            } else {
+                Err("connection refused".to_string())
+            }
+        }))
+        .map(|()| endpoints.active_address().to_string())
+    }
+
+    fn set_up(endpoints: &Endpoints<RefCell<bool>>, index: usize, up: bool) {
+        *endpoints.iter().nth(index).unwrap().1.borrow_mut() = up;
+    }
+
+    #[test]
+    fn fails_over_to_next_healthy_endpoint() {
+        let endpoints = endpoints(&[true, true, true]);
+        assert_eq!(request(&endpoints).unwrap(), "http://127.0.0.1:26657/");
+
+        // The active endpoint goes down
+        set_up(&endpoints, 0, false);
+        assert_eq!(request(&endpoints).unwrap(), "http://127.0.0.1:26658/");
+        assert!(!endpoints.is_healthy(0));
+
+        // The new active endpoint is kept even once the preferred one recovers...
+        set_up(&endpoints, 0, true);
+        assert_eq!(request(&endpoints).unwrap(), "http://127.0.0.1:26658/");
+
+        // ...until a health checkup finds it healthy again
+        endpoints.set_healthy(0, true);
+        assert!(endpoints.activate_healthy());
+        assert_eq!(request(&endpoints).unwrap(), "http://127.0.0.1:26657/");
+    }
+
+    #[test]
+    fn tries_unhealthy_endpoints_last() {
+        // The second endpoint is up, but unhealthy as of the last health checkup
+        let endpoints = endpoints(&[false, true, true]);
+        endpoints.set_healthy(1, false);
+        assert_eq!(request(&endpoints).unwrap(), "http://127.0.0.1:26659/");
+
+        set_up(&endpoints, 2, false);
+        assert_eq!(request(&endpoints).unwrap(), "http://127.0.0.1:26658/");
+
+        // The error of the last endpoint is returned if they all fail
+        set_up(&endpoints, 1, false);
+        assert_eq!(request(&endpoints).unwrap_err(), "connection refused");
+        assert!(!endpoints.activate_healthy());
+    }
+}
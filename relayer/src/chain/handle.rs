@@ -46,6 +46,8 @@ use ibc_proto::ibc::core::{
 pub use prod::ProdChainHandle;
 
 use crate::{
+    chain::pagination::{self, Page, Pages},
+    chain::{SentMsgs, TxEstimate},
     connection::ConnectionMsgType,
     error::Error,
     event::monitor::{EventBatch, Result as MonitorResult},
@@ -103,14 +105,25 @@ pub enum ChainRequest {
 
     SendMsgs {
         proto_msgs: Vec<prost_types::Any>,
-        reply_to: ReplyTo<Vec<IbcEvent>>,
+        key_name: Option<String>,
+        counterparty_chain_id: Option<ChainId>,
+        reply_to: ReplyTo<SentMsgs>,
+    },
+
+    SimulateMsgs {
+        proto_msgs: Vec<prost_types::Any>,
+        key_name: Option<String>,
+        counterparty_chain_id: Option<ChainId>,
+        reply_to: ReplyTo<Vec<TxEstimate>>,
     },
 
     Signer {
+        key_name: Option<String>,
         reply_to: ReplyTo<Signer>,
     },
 
     Key {
+        key_name: Option<String>,
         reply_to: ReplyTo<KeyEntry>,
     },
 
@@ -125,7 +138,7 @@ pub enum ChainRequest {
 
     QueryClients {
         request: QueryClientStatesRequest,
-        reply_to: ReplyTo<Vec<IdentifiedAnyClientState>>,
+        reply_to: ReplyTo<Page<IdentifiedAnyClientState>>,
     },
 
     BuildHeader {
@@ -174,7 +187,7 @@ pub enum ChainRequest {
 
     QueryConsensusStates {
         request: QueryConsensusStatesRequest,
-        reply_to: ReplyTo<Vec<AnyConsensusStateWithHeight>>,
+        reply_to: ReplyTo<Page<AnyConsensusStateWithHeight>>,
     },
 
     QueryConsensusState {
@@ -210,17 +223,17 @@ pub enum ChainRequest {
 
     QueryConnections {
         request: QueryConnectionsRequest,
-        reply_to: ReplyTo<Vec<IdentifiedConnectionEnd>>,
+        reply_to: ReplyTo<Page<IdentifiedConnectionEnd>>,
     },
 
     QueryConnectionChannels {
         request: QueryConnectionChannelsRequest,
-        reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
+        reply_to: ReplyTo<Page<IdentifiedChannelEnd>>,
     },
 
     QueryChannels {
         request: QueryChannelsRequest,
-        reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
+        reply_to: ReplyTo<Page<IdentifiedChannelEnd>>,
     },
 
     QueryChannel {
@@ -277,7 +290,8 @@ pub enum ChainRequest {
 
     QueryPacketCommitments {
         request: QueryPacketCommitmentsRequest,
-        reply_to: ReplyTo<(Vec<PacketState>, Height)>,
+        height: Option<Height>,
+        reply_to: ReplyTo<(Page<PacketState>, Height)>,
     },
 
     QueryUnreceivedPackets {
@@ -287,7 +301,8 @@ pub enum ChainRequest {
 
     QueryPacketAcknowledgement {
         request: QueryPacketAcknowledgementsRequest,
-        reply_to: ReplyTo<(Vec<PacketState>, Height)>,
+        height: Option<Height>,
+        reply_to: ReplyTo<(Page<PacketState>, Height)>,
     },
 
     QueryUnreceivedAcknowledgement {
@@ -316,20 +331,63 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
 
     /// Send the given `msgs` to the chain, packaged as one or more transactions,
     /// and return the list of events emitted by the chain after the transaction was committed.
-    fn send_msgs(&self, proto_msgs: Vec<prost_types::Any>) -> Result<Vec<IbcEvent>, Error>;
+    fn send_msgs(&self, proto_msgs: Vec<prost_types::Any>) -> Result<Vec<IbcEvent>, Error> {
+        self.send_msgs_with_receipts(proto_msgs)
+            .map(|sent| sent.events)
+    }
+
+    /// Send the given `msgs` to the chain, packaged as one or more transactions,
+    /// and return the events emitted by the chain with the receipts of the transactions.
+    fn send_msgs_with_receipts(&self, proto_msgs: Vec<prost_types::Any>)
+        -> Result<SentMsgs, Error>;
+
+    /// Simulate the transactions with the given `msgs` which `send_msgs` would send to the chain,
+    /// without signing or broadcasting them, and return their estimated gas and fee.
+    fn simulate_msgs(&self, proto_msgs: Vec<prost_types::Any>) -> Result<Vec<TxEstimate>, Error>;
 
     fn get_signer(&self) -> Result<Signer, Error>;
 
     fn get_key(&self) -> Result<KeyEntry, Error>;
 
+    /// Returns a handle to the same chain which signs the transactions it sends, and gets the
+    /// signer and key, with `key_name`, one of the signing keys configured for the chain.
+    fn with_key(&self, key_name: String) -> Box<dyn ChainHandle>;
+
+    /// Returns a handle to the same chain whose transactions relay from the chain
+    /// `counterparty_chain_id`, which their memo can name.
+    fn with_counterparty(&self, counterparty_chain_id: ChainId) -> Box<dyn ChainHandle>;
+
     fn module_version(&self, port_id: &PortId) -> Result<String, Error>;
 
     fn query_latest_height(&self) -> Result<Height, Error>;
 
+    /// Queries one page of the client states hosted by the chain.
+    fn query_clients_page(
+        &self,
+        request: QueryClientStatesRequest,
+    ) -> Result<Page<IdentifiedAnyClientState>, Error>;
+
+    /// Iterates over the pages of the client states hosted by the chain.
+    fn query_clients_pages(
+        &self,
+        request: QueryClientStatesRequest,
+    ) -> Pages<'_, QueryClientStatesRequest, Page<IdentifiedAnyClientState>> {
+        Pages::new(
+            request,
+            Box::new(move |request| self.query_clients_page(request)),
+        )
+    }
+
+    /// Queries all the client states hosted by the chain, page by page,
+    /// sorted by the counter of their identifier.
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
-    ) -> Result<Vec<IdentifiedAnyClientState>, Error>;
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        let mut clients = self.query_clients_pages(request).collect_items()?;
+        pagination::sort_clients(&mut clients);
+        Ok(clients)
+    }
 
     fn query_client_state(
         &self,
@@ -342,10 +400,33 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
         request: QueryClientConnectionsRequest,
     ) -> Result<Vec<ConnectionId>, Error>;
 
+    /// Queries one page of the consensus states of a client.
+    fn query_consensus_states_page(
+        &self,
+        request: QueryConsensusStatesRequest,
+    ) -> Result<Page<AnyConsensusStateWithHeight>, Error>;
+
+    /// Iterates over the pages of the consensus states of a client.
+    fn query_consensus_states_pages(
+        &self,
+        request: QueryConsensusStatesRequest,
+    ) -> Pages<'_, QueryConsensusStatesRequest, Page<AnyConsensusStateWithHeight>> {
+        Pages::new(
+            request,
+            Box::new(move |request| self.query_consensus_states_page(request)),
+        )
+    }
+
+    /// Queries all the consensus states of a client, page by page,
+    /// from the latest to the earliest height.
     fn query_consensus_states(
         &self,
         request: QueryConsensusStatesRequest,
-    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error>;
+    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
+        let mut consensus_states = self.query_consensus_states_pages(request).collect_items()?;
+        pagination::sort_consensus_states(&mut consensus_states);
+        Ok(consensus_states)
+    }
 
     fn query_consensus_state(
         &self,
@@ -374,25 +455,86 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
         height: Height,
     ) -> Result<ConnectionEnd, Error>;
 
+    /// Queries one page of the connections of the chain.
+    fn query_connections_page(
+        &self,
+        request: QueryConnectionsRequest,
+    ) -> Result<Page<IdentifiedConnectionEnd>, Error>;
+
+    /// Iterates over the pages of the connections of the chain.
+    fn query_connections_pages(
+        &self,
+        request: QueryConnectionsRequest,
+    ) -> Pages<'_, QueryConnectionsRequest, Page<IdentifiedConnectionEnd>> {
+        Pages::new(
+            request,
+            Box::new(move |request| self.query_connections_page(request)),
+        )
+    }
+
+    /// Queries all the connections of the chain, page by page.
     fn query_connections(
         &self,
         request: QueryConnectionsRequest,
-    ) -> Result<Vec<IdentifiedConnectionEnd>, Error>;
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.query_connections_pages(request).collect_items()
+    }
 
+    /// Queries one page of the channels of a connection.
+    fn query_connection_channels_page(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Page<IdentifiedChannelEnd>, Error>;
+
+    /// Iterates over the pages of the channels of a connection.
+    fn query_connection_channels_pages(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Pages<'_, QueryConnectionChannelsRequest, Page<IdentifiedChannelEnd>> {
+        Pages::new(
+            request,
+            Box::new(move |request| self.query_connection_channels_page(request)),
+        )
+    }
+
+    /// Queries all the channels of a connection, page by page.
     fn query_connection_channels(
         &self,
         request: QueryConnectionChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error>;
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.query_connection_channels_pages(request)
+            .collect_items()
+    }
 
     fn query_next_sequence_receive(
         &self,
         request: QueryNextSequenceReceiveRequest,
     ) -> Result<Sequence, Error>;
 
+    /// Queries one page of the channels of the chain.
+    fn query_channels_page(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Result<Page<IdentifiedChannelEnd>, Error>;
+
+    /// Iterates over the pages of the channels of the chain.
+    fn query_channels_pages(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Pages<'_, QueryChannelsRequest, Page<IdentifiedChannelEnd>> {
+        Pages::new(
+            request,
+            Box::new(move |request| self.query_channels_page(request)),
+        )
+    }
+
+    /// Queries all the channels of the chain, page by page.
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error>;
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.query_channels_pages(request).collect_items()
+    }
 
     fn query_channel(
         &self,
@@ -473,20 +615,73 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
         height: Height,
     ) -> Result<(Vec<u8>, Proofs), Error>;
 
+    /// Queries one page of the packet commitments of a channel at `height`, or at the latest
+    /// height if none, with the height of the query.
+    fn query_packet_commitments_page(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+        height: Option<Height>,
+    ) -> Result<(Page<PacketState>, Height), Error>;
+
+    /// Iterates over the pages of the packet commitments of a channel, all queried at the
+    /// height of the first page.
+    fn query_packet_commitments_pages(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+    ) -> Pages<'_, QueryPacketCommitmentsRequest, (Page<PacketState>, Height)> {
+        Pages::new(
+            request,
+            pagination::at_first_height(move |request, height| {
+                self.query_packet_commitments_page(request, height)
+            }),
+        )
+    }
+
+    /// Queries all the packet commitments of a channel, page by page,
+    /// with the height at which all the pages were queried.
     fn query_packet_commitments(
         &self,
         request: QueryPacketCommitmentsRequest,
-    ) -> Result<(Vec<PacketState>, Height), Error>;
+    ) -> Result<(Vec<PacketState>, Height), Error> {
+        self.query_packet_commitments_pages(request).collect_items()
+    }
 
     fn query_unreceived_packets(
         &self,
         request: QueryUnreceivedPacketsRequest,
     ) -> Result<Vec<u64>, Error>;
 
+    /// Queries one page of the packet acknowledgements of a channel at `height`, or at the latest
+    /// height if none, with the height of the query.
+    fn query_packet_acknowledgements_page(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+        height: Option<Height>,
+    ) -> Result<(Page<PacketState>, Height), Error>;
+
+    /// Iterates over the pages of the packet acknowledgements of a channel, all queried at the
+    /// height of the first page.
+    fn query_packet_acknowledgements_pages(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Pages<'_, QueryPacketAcknowledgementsRequest, (Page<PacketState>, Height)> {
+        Pages::new(
+            request,
+            pagination::at_first_height(move |request, height| {
+                self.query_packet_acknowledgements_page(request, height)
+            }),
+        )
+    }
+
+    /// Queries all the packet acknowledgements of a channel, page by page,
+    /// with the height at which all the pages were queried.
     fn query_packet_acknowledgements(
         &self,
         request: QueryPacketAcknowledgementsRequest,
-    ) -> Result<(Vec<PacketState>, Height), Error>;
+    ) -> Result<(Vec<PacketState>, Height), Error> {
+        self.query_packet_acknowledgements_pages(request)
+            .collect_items()
+    }
 
     fn query_unreceived_acknowledgement(
         &self,
@@ -36,6 +36,8 @@ use ibc_proto::ibc::core::connection::v1::QueryClientConnectionsRequest;
 use ibc_proto::ibc::core::connection::v1::QueryConnectionsRequest;
 
 use crate::{
+    chain::pagination::Page,
+    chain::{SentMsgs, TxEstimate},
     connection::ConnectionMsgType,
     error::{Error, Kind},
     keyring::KeyEntry,
@@ -50,6 +52,12 @@ pub struct ProdChainHandle {
 
     /// The handle's channel for sending requests to the runtime
     runtime_sender: channel::Sender<ChainRequest>,
+
+    /// The signing key of the handle, or `None` for the chain's default key
+    key_name: Option<String>,
+
+    /// The chain from which the transactions sent through the handle relay, if known
+    counterparty_chain_id: Option<ChainId>,
 }
 
 impl ProdChainHandle {
@@ -57,6 +65,8 @@ impl ProdChainHandle {
         Self {
             chain_id,
             runtime_sender: sender,
+            key_name: None,
+            counterparty_chain_id: None,
         }
     }
 
@@ -89,19 +99,53 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::Subscribe { reply_to })
     }
 
-    fn send_msgs(&self, proto_msgs: Vec<prost_types::Any>) -> Result<Vec<IbcEvent>, Error> {
+    fn send_msgs_with_receipts(
+        &self,
+        proto_msgs: Vec<prost_types::Any>,
+    ) -> Result<SentMsgs, Error> {
         self.send(|reply_to| ChainRequest::SendMsgs {
             proto_msgs,
+            key_name: self.key_name.clone(),
+            counterparty_chain_id: self.counterparty_chain_id.clone(),
+            reply_to,
+        })
+    }
+
+    fn simulate_msgs(&self, proto_msgs: Vec<prost_types::Any>) -> Result<Vec<TxEstimate>, Error> {
+        self.send(|reply_to| ChainRequest::SimulateMsgs {
+            proto_msgs,
+            key_name: self.key_name.clone(),
+            counterparty_chain_id: self.counterparty_chain_id.clone(),
             reply_to,
         })
     }
 
     fn get_signer(&self) -> Result<Signer, Error> {
-        self.send(|reply_to| ChainRequest::Signer { reply_to })
+        self.send(|reply_to| ChainRequest::Signer {
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
     }
 
     fn get_key(&self) -> Result<KeyEntry, Error> {
-        self.send(|reply_to| ChainRequest::Key { reply_to })
+        self.send(|reply_to| ChainRequest::Key {
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
+    }
+
+    fn with_key(&self, key_name: String) -> Box<dyn ChainHandle> {
+        Box::new(Self {
+            key_name: Some(key_name),
+            ..self.clone()
+        })
+    }
+
+    fn with_counterparty(&self, counterparty_chain_id: ChainId) -> Box<dyn ChainHandle> {
+        Box::new(Self {
+            counterparty_chain_id: Some(counterparty_chain_id),
+            ..self.clone()
+        })
     }
 
     fn module_version(&self, port_id: &PortId) -> Result<String, Error> {
@@ -115,10 +159,10 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::QueryLatestHeight { reply_to })
     }
 
-    fn query_clients(
+    fn query_clients_page(
         &self,
         request: QueryClientStatesRequest,
-    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+    ) -> Result<Page<IdentifiedAnyClientState>, Error> {
         self.send(|reply_to| ChainRequest::QueryClients { request, reply_to })
     }
 
@@ -141,10 +185,10 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::QueryClientConnections { request, reply_to })
     }
 
-    fn query_consensus_states(
+    fn query_consensus_states_page(
         &self,
         request: QueryConsensusStatesRequest,
-    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
+    ) -> Result<Page<AnyConsensusStateWithHeight>, Error> {
         self.send(|reply_to| ChainRequest::QueryConsensusStates { request, reply_to })
     }
 
@@ -196,17 +240,17 @@ impl ChainHandle for ProdChainHandle {
         })
     }
 
-    fn query_connections(
+    fn query_connections_page(
         &self,
         request: QueryConnectionsRequest,
-    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+    ) -> Result<Page<IdentifiedConnectionEnd>, Error> {
         self.send(|reply_to| ChainRequest::QueryConnections { request, reply_to })
     }
 
-    fn query_connection_channels(
+    fn query_connection_channels_page(
         &self,
         request: QueryConnectionChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+    ) -> Result<Page<IdentifiedChannelEnd>, Error> {
         self.send(|reply_to| ChainRequest::QueryConnectionChannels { request, reply_to })
     }
 
@@ -217,10 +261,10 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::QueryNextSequenceReceive { request, reply_to })
     }
 
-    fn query_channels(
+    fn query_channels_page(
         &self,
         request: QueryChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+    ) -> Result<Page<IdentifiedChannelEnd>, Error> {
         self.send(|reply_to| ChainRequest::QueryChannels { request, reply_to })
     }
 
@@ -377,11 +421,16 @@ impl ChainHandle for ProdChainHandle {
         })
     }
 
-    fn query_packet_commitments(
+    fn query_packet_commitments_page(
         &self,
         request: QueryPacketCommitmentsRequest,
-    ) -> Result<(Vec<PacketState>, Height), Error> {
-        self.send(|reply_to| ChainRequest::QueryPacketCommitments { request, reply_to })
+        height: Option<Height>,
+    ) -> Result<(Page<PacketState>, Height), Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketCommitments {
+            request,
+            height,
+            reply_to,
+        })
     }
 
     fn query_unreceived_packets(
@@ -391,11 +440,16 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::QueryUnreceivedPackets { request, reply_to })
     }
 
-    fn query_packet_acknowledgements(
+    fn query_packet_acknowledgements_page(
         &self,
         request: QueryPacketAcknowledgementsRequest,
-    ) -> Result<(Vec<PacketState>, Height), Error> {
-        self.send(|reply_to| ChainRequest::QueryPacketAcknowledgement { request, reply_to })
+        height: Option<Height>,
+    ) -> Result<(Page<PacketState>, Height), Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketAcknowledgement {
+            request,
+            height,
+            reply_to,
+        })
     }
 
     fn query_unreceived_acknowledgement(
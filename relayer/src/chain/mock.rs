@@ -38,7 +38,8 @@ use ibc_proto::ibc::core::connection::v1::{
     QueryClientConnectionsRequest, QueryConnectionsRequest,
 };
 
-use crate::chain::Chain;
+use crate::chain::pagination::Page;
+use crate::chain::{Chain, TxEstimate};
 use crate::config::ChainConfig;
 use crate::error::{Error, Kind};
 use crate::event::monitor::{EventReceiver, EventSender, TxMonitorCmd};
@@ -118,6 +119,17 @@ impl Chain for MockChain {
         Ok(events)
     }
 
+    fn simulate_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<TxEstimate>, Error> {
+        // The mock chain does not meter gas, all the messages fit in one free transaction
+        Ok(vec![TxEstimate {
+            msg_count: proto_msgs.len(),
+            gas_estimate: 0,
+            gas_wanted: 0,
+            fee: String::new(),
+            error: None,
+        }])
+    }
+
     fn get_signer(&mut self) -> Result<Signer, Error> {
         Ok(get_dummy_account_id())
     }
@@ -126,6 +138,12 @@ impl Chain for MockChain {
         unimplemented!()
     }
 
+    fn select_key(&mut self, _key_name: Option<&str>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn select_counterparty(&mut self, _counterparty_chain_id: Option<&ChainId>) {}
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         unimplemented!()
     }
@@ -137,7 +155,7 @@ impl Chain for MockChain {
     fn query_clients(
         &self,
         _request: QueryClientStatesRequest,
-    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+    ) -> Result<Page<IdentifiedAnyClientState>, Error> {
         unimplemented!()
     }
 
@@ -182,21 +200,21 @@ impl Chain for MockChain {
     fn query_connections(
         &self,
         _request: QueryConnectionsRequest,
-    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+    ) -> Result<Page<IdentifiedConnectionEnd>, Error> {
         unimplemented!()
     }
 
     fn query_connection_channels(
         &self,
         _request: QueryConnectionChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+    ) -> Result<Page<IdentifiedChannelEnd>, Error> {
         unimplemented!()
     }
 
     fn query_channels(
         &self,
         _request: QueryChannelsRequest,
-    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+    ) -> Result<Page<IdentifiedChannelEnd>, Error> {
         unimplemented!()
     }
 
@@ -219,7 +237,8 @@ impl Chain for MockChain {
     fn query_packet_commitments(
         &self,
         _request: QueryPacketCommitmentsRequest,
-    ) -> Result<(Vec<PacketState>, Height), Error> {
+        _height: Option<Height>,
+    ) -> Result<(Page<PacketState>, Height), Error> {
         unimplemented!()
     }
 
@@ -233,7 +252,8 @@ impl Chain for MockChain {
     fn query_packet_acknowledgements(
         &self,
         _request: QueryPacketAcknowledgementsRequest,
-    ) -> Result<(Vec<PacketState>, Height), Error> {
+        _height: Option<Height>,
+    ) -> Result<(Page<PacketState>, Height), Error> {
         unimplemented!()
     }
 
@@ -259,7 +279,7 @@ impl Chain for MockChain {
         &self,
         _client_id: &ClientId,
         _height: Height,
-    ) -> Result<(Self::ClientState, MerkleProof), Error> {
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
         unimplemented!()
     }
 
@@ -276,7 +296,7 @@ impl Chain for MockChain {
         _client_id: &ClientId,
         _consensus_height: Height,
         _height: Height,
-    ) -> Result<(Self::ConsensusState, MerkleProof), Error> {
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
         unimplemented!()
     }
 
@@ -362,10 +382,11 @@ impl Chain for MockChain {
     fn query_consensus_states(
         &self,
         request: QueryConsensusStatesRequest,
-    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
-        Ok(self
-            .context
-            .consensus_states(&request.client_id.parse().unwrap()))
+    ) -> Result<Page<AnyConsensusStateWithHeight>, Error> {
+        Ok(Page::last(
+            self.context
+                .consensus_states(&request.client_id.parse().unwrap()),
+        ))
     }
 
     /// Performs a query to retrieve the identifiers of all connections.
@@ -403,15 +424,29 @@ pub mod test_utils {
             rpc_addr: "http://127.0.0.1:26656".parse().unwrap(),
             grpc_addr: "http://127.0.0.1:9090".parse().unwrap(),
             websocket_addr: "ws://127.0.0.1:26656/websocket".parse().unwrap(),
+            extra_rpc_addrs: vec![],
+            extra_websocket_addrs: vec![],
+            extra_grpc_addrs: vec![],
             rpc_timeout: crate::config::default::rpc_timeout(),
             account_prefix: "".to_string(),
             key_name: "".to_string(),
+            extra_key_names: vec![],
+            key_store_type: Default::default(),
+            key_type: Default::default(),
+            hd_path: None,
             store_prefix: "".to_string(),
             max_gas: None,
             gas_price: GasPrice::new(0.001, "uatom".to_string()),
+            extra_gas_prices: vec![],
+            dynamic_gas_price: None,
             gas_adjustment: None,
             max_msg_num: None,
             max_tx_size: None,
+            fee_granter: None,
+            fee_payer: None,
+            memo_template: None,
+            memo_overrides: Default::default(),
+            non_critical_extension_options: vec![],
             clock_drift: Duration::from_secs(5),
             trusting_period: Duration::from_secs(14 * 24 * 60 * 60), // 14 days
             trust_threshold: Default::default(),
@@ -0,0 +1,356 @@
+//! Page-by-page iteration over the results of the paginated queries of a chain, so that the
+//! relayer does not ask a node for every client, connection, channel or packet at once.
+
+use std::fmt;
+
+use ibc::ics02_client::client_consensus::AnyConsensusStateWithHeight;
+use ibc::ics02_client::client_state::IdentifiedAnyClientState;
+use ibc::ics24_host::identifier::ClientId;
+use ibc::Height;
+use ibc_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+use ibc_proto::ibc::core::channel::v1::{
+    QueryChannelsRequest, QueryConnectionChannelsRequest, QueryPacketAcknowledgementsRequest,
+    QueryPacketCommitmentsRequest,
+};
+use ibc_proto::ibc::core::client::v1::{QueryClientStatesRequest, QueryConsensusStatesRequest};
+use ibc_proto::ibc::core::connection::v1::QueryConnectionsRequest;
+
+use crate::error::Error;
+
+/// The number of results requested per page, unless the request sets its own limit.
+pub const DEFAULT_PAGE_LIMIT: u64 = 100;
+
+/// One page of the results of a paginated query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The key at which the next page starts, or `None` if this is the last page
+    pub next_key: Option<Vec<u8>>,
+}
+
+impl<T> Page<T> {
+    /// The page of `items`, followed by the page at the `next_key` of the `pagination`
+    /// of a response, if any.
+    pub fn new(items: Vec<T>, pagination: Option<PageResponse>) -> Self {
+        let next_key = pagination
+            .map(|p| p.next_key)
+            .filter(|next_key| !next_key.is_empty());
+
+        Self { items, next_key }
+    }
+
+    /// The last page, with all the remaining `items`.
+    pub fn last(items: Vec<T>) -> Self {
+        Self {
+            items,
+            next_key: None,
+        }
+    }
+}
+
+/// The result of a query of one page, which points to the next page.
+pub trait Paged {
+    fn next_key(&self) -> Option<&[u8]>;
+}
+
+impl<T> Paged for Page<T> {
+    fn next_key(&self) -> Option<&[u8]> {
+        self.next_key.as_deref()
+    }
+}
+
+/// A page of packets, with the height at which it was queried.
+impl<T> Paged for (Page<T>, Height) {
+    fn next_key(&self) -> Option<&[u8]> {
+        self.0.next_key()
+    }
+}
+
+/// A query request whose results are paginated.
+pub trait PaginatedRequest: Clone {
+    fn pagination(&self) -> Option<&PageRequest>;
+
+    fn set_pagination(&mut self, pagination: Option<PageRequest>);
+}
+
+macro_rules! impl_paginated_request {
+    ($($request:ty),* $(,)?) => {
+        $(
+            impl PaginatedRequest for $request {
+                fn pagination(&self) -> Option<&PageRequest> {
+                    self.pagination.as_ref()
+                }
+
+                fn set_pagination(&mut self, pagination: Option<PageRequest>) {
+                    self.pagination = pagination;
+                }
+            }
+        )*
+    };
+}
+
+impl_paginated_request!(
+    QueryClientStatesRequest,
+    QueryConsensusStatesRequest,
+    QueryConnectionsRequest,
+    QueryConnectionChannelsRequest,
+    QueryChannelsRequest,
+    QueryPacketCommitmentsRequest,
+    QueryPacketAcknowledgementsRequest,
+);
+
+/// The query of one page of the results of a request.
+pub type PageQuery<'a, R, P> = Box<dyn FnMut(R) -> Result<P, Error> + 'a>;
+
+/// The query of the pages of a request at the height at which its first page is queried,
+/// so that the results of all pages are taken from the same state of the chain. `query`
+/// queries one page at the given height, or at the latest height if none, and returns the
+/// height of the query.
+pub fn at_first_height<'a, R, T>(
+    mut query: impl FnMut(R, Option<Height>) -> Result<(Page<T>, Height), Error> + 'a,
+) -> PageQuery<'a, R, (Page<T>, Height)> {
+    let mut first_height = None;
+
+    Box::new(move |request| {
+        let (page, height) = query(request, first_height)?;
+        first_height.get_or_insert(height);
+        Ok((page, height))
+    })
+}
+
+/// An iterator over the pages of the results of a request, which queries each page when
+/// it is reached. It stops after the last page or the first error.
+pub struct Pages<'a, R, P> {
+    request: R,
+    /// The key of the next page, or `None` once all pages are queried
+    next_key: Option<Vec<u8>>,
+    limit: u64,
+    query: PageQuery<'a, R, P>,
+}
+
+impl<'a, R: PaginatedRequest, P: Paged> Pages<'a, R, P> {
+    /// Iterates over the pages of the results of `request`, starting at the key of its
+    /// pagination, if any, with the limit of its pagination or else [`DEFAULT_PAGE_LIMIT`].
+    pub fn new(request: R, query: PageQuery<'a, R, P>) -> Self {
+        let (next_key, limit) = match request.pagination() {
+            // A limit which asks for all results at once is not honored
+            Some(page) if page.limit != 0 && page.limit != u64::MAX => {
+                (page.key.clone(), page.limit)
+            }
+            Some(page) => (page.key.clone(), DEFAULT_PAGE_LIMIT),
+            None => (vec![], DEFAULT_PAGE_LIMIT),
+        };
+
+        Self {
+            request,
+            next_key: Some(next_key),
+            limit,
+            query,
+        }
+    }
+}
+
+impl<'a, R: PaginatedRequest, P: Paged> Iterator for Pages<'a, R, P> {
+    type Item = Result<P, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.next_key.take()?;
+
+        let mut request = self.request.clone();
+        request.set_pagination(Some(PageRequest {
+            key,
+            limit: self.limit,
+            ..Default::default()
+        }));
+
+        let page = (self.query)(request);
+        if let Ok(page) = &page {
+            self.next_key = page.next_key().map(ToOwned::to_owned);
+        }
+
+        Some(page)
+    }
+}
+
+impl<'a, R, P> fmt::Debug for Pages<'a, R, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pages")
+            .field("next_key", &self.next_key)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<'a, R: PaginatedRequest, T> Pages<'a, R, Page<T>> {
+    /// Queries all the pages, and returns all their results.
+    pub fn collect_items(self) -> Result<Vec<T>, Error> {
+        let mut items = vec![];
+        for page in self {
+            items.extend(page?.items);
+        }
+        Ok(items)
+    }
+}
+
+impl<'a, R: PaginatedRequest, T> Pages<'a, R, (Page<T>, Height)> {
+    /// Queries all the pages, and returns all their results with the height at which the
+    /// last page was queried. If the pages are queried [`at_first_height`], it is the height
+    /// at which all the results were queried.
+    pub fn collect_items(self) -> Result<(Vec<T>, Height), Error> {
+        let mut items = vec![];
+        let mut height = Height::zero();
+        for page in self {
+            let (page, page_height) = page?;
+            items.extend(page.items);
+            height = page_height;
+        }
+        Ok((items, height))
+    }
+}
+
+/// Sorts client states by the counter of their identifier.
+///
+/// The store iterates the identifiers lexicographically, so the clients of each page,
+/// and across pages, are not ordered by their counter.
+pub fn sort_clients(clients: &mut [IdentifiedAnyClientState]) {
+    clients.sort_by(|a, b| {
+        client_id_suffix(&a.client_id)
+            .unwrap_or(0) // Fallback to `0` suffix (no sorting) if client id is malformed
+            .cmp(&client_id_suffix(&b.client_id).unwrap_or(0))
+    });
+}
+
+/// Sorts consensus states from the latest to the earliest height.
+pub fn sort_consensus_states(consensus_states: &mut [AnyConsensusStateWithHeight]) {
+    consensus_states.sort_by(|a, b| a.height.cmp(&b.height));
+    consensus_states.reverse();
+}
+
+/// Returns the suffix counter for a CosmosSDK client id.
+/// Returns `None` if the client identifier is malformed
+/// and the suffix could not be parsed.
+fn client_id_suffix(client_id: &ClientId) -> Option<u64> {
+    client_id
+        .as_str()
+        .split('-')
+        .last()
+        .map(|e| e.parse::<u64>().ok())
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use test_env_log::test;
+
+    use ibc::Height;
+    use ibc_proto::cosmos::base::query::pagination;
+    use ibc_proto::ibc::core::client::v1::QueryClientStatesRequest;
+
+    use crate::error::Kind;
+
+    use super::{at_first_height, Page, Pages, DEFAULT_PAGE_LIMIT};
+
+    /// A store of `len` numbers, which returns the page of those at and after the key
+    fn query_page(
+        len: u64,
+        requested: Arc<Mutex<Vec<(Vec<u8>, u64)>>>,
+    ) -> impl FnMut(QueryClientStatesRequest) -> Result<Page<u64>, crate::error::Error> {
+        move |request| {
+            let page = request.pagination.unwrap();
+            requested
+                .lock()
+                .unwrap()
+                .push((page.key.clone(), page.limit));
+
+            let start = u64::from(page.key.first().copied().unwrap_or(0));
+            let end = (start + page.limit).min(len);
+            let next_key = if end < len {
+                Some(vec![end as u8])
+            } else {
+                None
+            };
+
+            Ok(Page {
+                items: (start..end).collect(),
+                next_key,
+            })
+        }
+    }
+
+    #[test]
+    fn iterates_over_all_pages() {
+        let requested = Arc::new(Mutex::new(vec![]));
+        let request = QueryClientStatesRequest {
+            pagination: Some(ibc_proto::cosmos::base::query::v1beta1::PageRequest {
+                limit: 3,
+                ..Default::default()
+            }),
+        };
+
+        let pages = Pages::new(request, Box::new(query_page(7, requested.clone())));
+        let pages = pages.map(|p| p.unwrap().items).collect::<Vec<_>>();
+        assert_eq!(pages, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+
+        assert_eq!(
+            *requested.lock().unwrap(),
+            vec![(vec![], 3), (vec![3], 3), (vec![6], 3)]
+        );
+    }
+
+    #[test]
+    fn does_not_ask_for_all_results_at_once() {
+        let requested = Arc::new(Mutex::new(vec![]));
+        let request = QueryClientStatesRequest {
+            pagination: pagination::all(),
+        };
+
+        let items = Pages::new(request, Box::new(query_page(150, requested.clone())))
+            .collect_items()
+            .unwrap();
+        assert_eq!(items, (0..150).collect::<Vec<_>>());
+        assert_eq!(requested.lock().unwrap().len(), 2);
+        assert_eq!(requested.lock().unwrap()[0].1, DEFAULT_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn queries_all_pages_at_first_height() {
+        let heights = Arc::new(Mutex::new(vec![]));
+        let mut query = query_page(7, Arc::new(Mutex::new(vec![])));
+        let query = {
+            let heights = heights.clone();
+            at_first_height(move |request: QueryClientStatesRequest, height| {
+                heights.lock().unwrap().push(height);
+                // The chain advances by one block between the queries of two pages
+                let latest = Height::new(0, 10 + heights.lock().unwrap().len() as u64);
+                Ok((query(request)?, height.unwrap_or(latest)))
+            })
+        };
+
+        let request = QueryClientStatesRequest {
+            pagination: Some(ibc_proto::cosmos::base::query::v1beta1::PageRequest {
+                limit: 3,
+                ..Default::default()
+            }),
+        };
+
+        let (items, height) = Pages::new(request, query).collect_items().unwrap();
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+        assert_eq!(height, Height::new(0, 11));
+
+        let first = Some(Height::new(0, 11));
+        assert_eq!(*heights.lock().unwrap(), vec![None, first, first]);
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        let mut pages = Pages::<_, Page<u64>>::new(
+            QueryClientStatesRequest { pagination: None },
+            Box::new(|_| Err(Kind::Grpc.context("unavailable").into())),
+        );
+
+        assert!(pages.next().unwrap().is_err());
+        assert!(pages.next().is_none());
+    }
+}
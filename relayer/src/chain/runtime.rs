@@ -1,4 +1,4 @@
-use std::{sync::Arc, thread};
+use std::{collections::HashMap, sync::Arc, thread};
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
@@ -22,7 +22,7 @@ use ibc::{
         packet::{PacketMsgType, Sequence},
     },
     ics23_commitment::commitment::CommitmentPrefix,
-    ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+    ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
     proofs::Proofs,
     query::QueryTxRequest,
     signer::Signer,
@@ -53,8 +53,10 @@ use crate::{
 };
 
 use super::{
+    broadcaster::{BroadcastRequest, TxBroadcaster},
     handle::{ChainHandle, ChainRequest, ProdChainHandle, ReplyTo, Subscription},
-    Chain,
+    pagination::Page,
+    Chain, SentMsgs, TxEstimate,
 };
 
 pub struct Threads {
@@ -86,6 +88,10 @@ pub struct ChainRuntime<C: Chain> {
     /// A handle to the light client
     light_client: Box<dyn LightClient<C>>,
 
+    /// The channels to the broadcasters of the extra keys of the chain, indexed by key name.
+    /// The transactions of the default key are submitted by the runtime itself.
+    broadcasters: HashMap<String, channel::Sender<BroadcastRequest>>,
+
     #[allow(dead_code)]
     rt: Arc<TokioRuntime>, // Making this future-proof, so we keep the runtime around.
 }
@@ -97,7 +103,15 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         rt: Arc<TokioRuntime>,
     ) -> Result<Box<dyn ChainHandle>, Error> {
         // Similar to `from_config`.
-        let chain = C::bootstrap(config, rt.clone())?;
+        let chain = C::bootstrap(config.clone(), rt.clone())?;
+
+        // Start a broadcaster per extra key
+        let mut broadcasters = HashMap::new();
+        for key_name in config.key_names().into_iter().skip(1) {
+            let broadcaster =
+                TxBroadcaster::<C>::spawn(config.clone(), key_name.clone(), rt.clone())?;
+            broadcasters.insert(key_name, broadcaster);
+        }
 
         // Start the light client
         let light_client = chain.init_light_client()?;
@@ -106,7 +120,14 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         let (event_batch_rx, tx_monitor_cmd) = chain.init_event_monitor(rt.clone())?;
 
         // Instantiate & spawn the runtime
-        let (handle, _) = Self::init(chain, light_client, event_batch_rx, tx_monitor_cmd, rt);
+        let (handle, _) = Self::init(
+            chain,
+            light_client,
+            event_batch_rx,
+            tx_monitor_cmd,
+            broadcasters,
+            rt,
+        );
 
         Ok(handle)
     }
@@ -117,9 +138,17 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         light_client: Box<dyn LightClient<C>>,
         event_receiver: EventReceiver,
         tx_monitor_cmd: TxMonitorCmd,
+        broadcasters: HashMap<String, channel::Sender<BroadcastRequest>>,
         rt: Arc<TokioRuntime>,
     ) -> (Box<dyn ChainHandle>, thread::JoinHandle<()>) {
-        let chain_runtime = Self::new(chain, light_client, event_receiver, tx_monitor_cmd, rt);
+        let chain_runtime = Self::new(
+            chain,
+            light_client,
+            event_receiver,
+            tx_monitor_cmd,
+            broadcasters,
+            rt,
+        );
 
         // Get a handle to the runtime
         let handle = chain_runtime.handle();
@@ -141,6 +170,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         light_client: Box<dyn LightClient<C>>,
         event_receiver: EventReceiver,
         tx_monitor_cmd: TxMonitorCmd,
+        broadcasters: HashMap<String, channel::Sender<BroadcastRequest>>,
         rt: Arc<TokioRuntime>,
     ) -> Self {
         let (request_sender, request_receiver) = channel::unbounded::<ChainRequest>();
@@ -154,6 +184,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
             event_receiver,
             tx_monitor_cmd,
             light_client,
+            broadcasters,
         }
     }
 
@@ -184,6 +215,9 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
                         Ok(ChainRequest::Shutdown { reply_to }) => {
                             self.tx_monitor_cmd.send(MonitorCmd::Shutdown).map_err(Kind::channel)?;
 
+                            // Dropping the channels stops the broadcasters
+                            self.broadcasters.clear();
+
                             let res = self.chain.shutdown();
                             reply_to.send(res).map_err(Kind::channel)?;
 
@@ -194,16 +228,20 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
                             self.subscribe(reply_to)?
                         },
 
-                        Ok(ChainRequest::SendMsgs { proto_msgs, reply_to }) => {
-                            self.send_msgs(proto_msgs, reply_to)?
+                        Ok(ChainRequest::SendMsgs { proto_msgs, key_name, counterparty_chain_id, reply_to }) => {
+                            self.send_msgs(proto_msgs, key_name, counterparty_chain_id, reply_to)?
+                        },
+
+                        Ok(ChainRequest::SimulateMsgs { proto_msgs, key_name, counterparty_chain_id, reply_to }) => {
+                            self.simulate_msgs(proto_msgs, key_name, counterparty_chain_id, reply_to)?
                         },
 
-                        Ok(ChainRequest::Signer { reply_to }) => {
-                            self.get_signer(reply_to)?
+                        Ok(ChainRequest::Signer { key_name, reply_to }) => {
+                            self.get_signer(key_name, reply_to)?
                         }
 
-                        Ok(ChainRequest::Key { reply_to }) => {
-                            self.get_key(reply_to)?
+                        Ok(ChainRequest::Key { key_name, reply_to }) => {
+                            self.get_key(key_name, reply_to)?
                         }
 
                         Ok(ChainRequest::ModuleVersion { port_id, reply_to }) => {
@@ -314,16 +352,16 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
                             self.build_packet_proofs(packet_type, port_id, channel_id, sequence, height, reply_to)?
                         },
 
-                        Ok(ChainRequest::QueryPacketCommitments { request, reply_to }) => {
-                            self.query_packet_commitments(request, reply_to)?
+                        Ok(ChainRequest::QueryPacketCommitments { request, height, reply_to }) => {
+                            self.query_packet_commitments(request, height, reply_to)?
                         },
 
                         Ok(ChainRequest::QueryUnreceivedPackets { request, reply_to }) => {
                             self.query_unreceived_packets(request, reply_to)?
                         },
 
-                        Ok(ChainRequest::QueryPacketAcknowledgement { request, reply_to }) => {
-                            self.query_packet_acknowledgements(request, reply_to)?
+                        Ok(ChainRequest::QueryPacketAcknowledgement { request, height, reply_to }) => {
+                            self.query_packet_acknowledgements(request, height, reply_to)?
                         },
 
                         Ok(ChainRequest::QueryUnreceivedAcknowledgement { request, reply_to }) => {
@@ -358,13 +396,64 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn send_msgs(
         &mut self,
         proto_msgs: Vec<prost_types::Any>,
-        reply_to: ReplyTo<Vec<IbcEvent>>,
+        key_name: Option<String>,
+        counterparty_chain_id: Option<ChainId>,
+        reply_to: ReplyTo<SentMsgs>,
     ) -> Result<(), Error> {
-        let result = self.chain.send_msgs(proto_msgs);
+        let request = BroadcastRequest::SendMsgs {
+            proto_msgs,
+            counterparty_chain_id,
+            reply_to,
+        };
 
-        reply_to.send(result).map_err(Kind::channel)?;
+        self.broadcast(key_name, request)
+    }
 
-        Ok(())
+    fn simulate_msgs(
+        &mut self,
+        proto_msgs: Vec<prost_types::Any>,
+        key_name: Option<String>,
+        counterparty_chain_id: Option<ChainId>,
+        reply_to: ReplyTo<Vec<TxEstimate>>,
+    ) -> Result<(), Error> {
+        let request = BroadcastRequest::SimulateMsgs {
+            proto_msgs,
+            counterparty_chain_id,
+            reply_to,
+        };
+
+        self.broadcast(key_name, request)
+    }
+
+    /// Forwards the request to the broadcaster of the key `key_name`, if it has one, so that it
+    /// is processed concurrently with the requests of the other keys. Otherwise, e.g. for the
+    /// default key, the request is processed by the runtime.
+    fn broadcast(
+        &mut self,
+        key_name: Option<String>,
+        request: BroadcastRequest,
+    ) -> Result<(), Error> {
+        let request = match key_name.as_ref().and_then(|k| self.broadcasters.get(k)) {
+            None => request,
+            Some(broadcaster) => match broadcaster.send(request) {
+                Ok(()) => return Ok(()),
+                Err(channel::SendError(request)) => {
+                    let key_name = key_name.as_deref().unwrap_or_default();
+                    error!(
+                        "[{}] broadcaster of key '{}' stopped, submitting from the runtime",
+                        self.chain.id(),
+                        key_name
+                    );
+                    self.broadcasters.remove(key_name);
+                    request
+                }
+            },
+        };
+
+        match self.chain.select_key(key_name.as_deref()) {
+            Ok(()) => request.process(&mut self.chain),
+            Err(e) => request.fail(e),
+        }
     }
 
     fn query_latest_height(&self, reply_to: ReplyTo<Height>) -> Result<(), Error> {
@@ -375,16 +464,30 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
-    fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
-        let result = self.chain.get_signer();
+    fn get_signer(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<Signer>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .select_key(key_name.as_deref())
+            .and_then(|_| self.chain.get_signer());
 
         reply_to.send(result).map_err(Kind::channel)?;
 
         Ok(())
     }
 
-    fn get_key(&mut self, reply_to: ReplyTo<KeyEntry>) -> Result<(), Error> {
-        let result = self.chain.get_key();
+    fn get_key(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<KeyEntry>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .select_key(key_name.as_deref())
+            .and_then(|_| self.chain.get_key());
 
         reply_to.send(result).map_err(Kind::channel)?;
 
@@ -492,9 +595,6 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
             height,
         );
 
-        let result = result
-            .map(|(opt_client_state, proofs)| (opt_client_state.map(|cs| cs.wrap_any()), proofs));
-
         reply_to.send(result).map_err(Kind::channel)?;
 
         Ok(())
@@ -503,7 +603,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
-        reply_to: ReplyTo<Vec<IdentifiedAnyClientState>>,
+        reply_to: ReplyTo<Page<IdentifiedAnyClientState>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_clients(request);
 
@@ -558,7 +658,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn query_consensus_states(
         &self,
         request: QueryConsensusStatesRequest,
-        reply_to: ReplyTo<Vec<AnyConsensusStateWithHeight>>,
+        reply_to: ReplyTo<Page<AnyConsensusStateWithHeight>>,
     ) -> Result<(), Error> {
         let consensus_states = self.chain.query_consensus_states(request);
 
@@ -630,7 +730,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn query_connections(
         &self,
         request: QueryConnectionsRequest,
-        reply_to: ReplyTo<Vec<IdentifiedConnectionEnd>>,
+        reply_to: ReplyTo<Page<IdentifiedConnectionEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connections(request);
 
@@ -642,7 +742,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn query_connection_channels(
         &self,
         request: QueryConnectionChannelsRequest,
-        reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
+        reply_to: ReplyTo<Page<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connection_channels(request);
 
@@ -656,7 +756,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
-        reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
+        reply_to: ReplyTo<Page<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channels(request);
 
@@ -697,10 +797,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         height: Height,
         reply_to: ReplyTo<(AnyClientState, MerkleProof)>,
     ) -> Result<(), Error> {
-        let result = self
-            .chain
-            .proven_client_state(&client_id, height)
-            .map(|(cs, mp)| (cs.wrap_any(), mp));
+        let result = self.chain.proven_client_state(&client_id, height);
 
         reply_to.send(result).map_err(Kind::channel)?;
 
@@ -729,8 +826,7 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     ) -> Result<(), Error> {
         let result = self
             .chain
-            .proven_client_consensus(&client_id, consensus_height, height)
-            .map(|(cs, mp)| (cs.wrap_any(), mp));
+            .proven_client_consensus(&client_id, consensus_height, height);
 
         reply_to.send(result).map_err(Kind::channel)?;
 
@@ -774,9 +870,10 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn query_packet_commitments(
         &self,
         request: QueryPacketCommitmentsRequest,
-        reply_to: ReplyTo<(Vec<PacketState>, Height)>,
+        height: Option<Height>,
+        reply_to: ReplyTo<(Page<PacketState>, Height)>,
     ) -> Result<(), Error> {
-        let result = self.chain.query_packet_commitments(request);
+        let result = self.chain.query_packet_commitments(request, height);
 
         reply_to.send(result).map_err(Kind::channel)?;
 
@@ -798,9 +895,10 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn query_packet_acknowledgements(
         &self,
         request: QueryPacketAcknowledgementsRequest,
-        reply_to: ReplyTo<(Vec<PacketState>, Height)>,
+        height: Option<Height>,
+        reply_to: ReplyTo<(Page<PacketState>, Height)>,
     ) -> Result<(), Error> {
-        let result = self.chain.query_packet_acknowledgements(request);
+        let result = self.chain.query_packet_acknowledgements(request, height);
 
         reply_to.send(result).map_err(Kind::channel)?;
 
@@ -259,7 +259,7 @@ impl Channel {
         if a_channel.state_matches(&State::Init) && a_channel.remote.channel_id.is_none() {
             let req = QueryConnectionChannelsRequest {
                 connection: b_connection_id.to_string(),
-                pagination: ibc_proto::cosmos::base::query::pagination::all(),
+                pagination: None,
             };
 
             let channels: Vec<IdentifiedChannelEnd> =
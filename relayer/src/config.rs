@@ -1,8 +1,9 @@
 //! Relayer configuration
 
+pub mod filter;
 pub mod reload;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap};
 use std::{fmt, fs, fs::File, io::Write, path::Path, time::Duration};
 
 use serde_derive::{Deserialize, Serialize};
@@ -12,6 +13,9 @@ use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::timestamp::ZERO_DURATION;
 
 use crate::error;
+use crate::keyring::{self, HDPath, KeyType, Store};
+
+pub use filter::{ChannelsSpec, PacketFilter};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -31,46 +35,24 @@ impl fmt::Display for GasPrice {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(
-    rename_all = "lowercase",
-    tag = "policy",
-    content = "list",
-    deny_unknown_fields
-)]
-pub enum PacketFilter {
-    Allow(ChannelsSpec),
-    Deny(ChannelsSpec),
-    AllowAll,
-}
-
-impl Default for PacketFilter {
-    /// By default, allows all channels & ports.
-    fn default() -> Self {
-        Self::AllowAll
-    }
-}
-
-impl PacketFilter {
-    /// Returns true if the packets can be relayed on the channel with [`PortId`] and [`ChannelId`],
-    /// false otherwise.
-    pub fn is_allowed(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
-        match self {
-            PacketFilter::Allow(spec) => spec.contains(&(port_id.clone(), channel_id.clone())),
-            PacketFilter::Deny(spec) => !spec.contains(&(port_id.clone(), channel_id.clone())),
-            PacketFilter::AllowAll => true,
-        }
-    }
+/// A non-critical extension option of the relayer's transactions, which the chain may ignore.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExtensionOption {
+    pub type_url: String,
+    /// The protobuf encoding of the option, in hexadecimal
+    #[serde(default)]
+    pub value: String,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+/// Raising of the gas price when a transaction is rejected for insufficient fees.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct ChannelsSpec(HashSet<(PortId, ChannelId)>);
-
-impl ChannelsSpec {
-    pub fn contains(&self, channel_port: &(PortId, ChannelId)) -> bool {
-        self.0.contains(channel_port)
-    }
+pub struct DynamicGasPrice {
+    /// The ratio of the configured gas price added on each rejection, e.g., 0.1 for 10%
+    pub step: f64,
+    /// The maximum ratio of the configured gas price paid, e.g., 2.0 for twice the price
+    pub max_multiplier: f64,
 }
 
 /// Defaults for various fields
@@ -247,15 +229,49 @@ pub struct ChainConfig {
     pub rpc_addr: tendermint_rpc::Url,
     pub websocket_addr: tendermint_rpc::Url,
     pub grpc_addr: tendermint_rpc::Url,
+    /// Fallback RPC endpoints, in order of preference, to fail over to when `rpc_addr` fails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_rpc_addrs: Vec<tendermint_rpc::Url>,
+    /// Fallback WebSocket endpoints, in order of preference
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_websocket_addrs: Vec<tendermint_rpc::Url>,
+    /// Fallback gRPC endpoints, in order of preference
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_grpc_addrs: Vec<tendermint_rpc::Url>,
     #[serde(default = "default::rpc_timeout", with = "humantime_serde")]
     pub rpc_timeout: Duration,
     pub account_prefix: String,
     pub key_name: String,
+    /// Additional signing keys, which the packet workers relaying to this chain are assigned
+    /// round-robin so that they can submit transactions in parallel, each from its own account.
+    #[serde(default)]
+    pub extra_key_names: Vec<String>,
+    /// The backend of the key store holding the keys, `test` (plaintext) or `encrypted`
+    #[serde(default)]
+    pub key_store_type: Store,
+    /// The algorithm of the keys, `secp256k1` or `ethsecp256k1` for EVM-compatible chains
+    #[serde(default)]
+    pub key_type: KeyType,
+    /// The HD path from which the keys are derived, by default `m/44'/<coin type>'/0'/0/0`
+    /// with the coin type of `key_type`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hd_path: Option<String>,
     pub store_prefix: String,
     pub max_gas: Option<u64>,
     pub gas_adjustment: Option<f64>,
     pub max_msg_num: Option<usize>,
     pub max_tx_size: Option<usize>,
+    /// The account whose fee allowance, granted to the relayer's keys, pays the fees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_granter: Option<String>,
+    /// The account which pays the fees, and which must therefore also sign the transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<String>,
+    /// The template of the memo of the relayer's transactions, in which `{version}`,
+    /// `{chain_id}` and `{counterparty_chain_id}` are replaced by the version of the relayer,
+    /// the chain and the chain from which it relays, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo_template: Option<String>,
     #[serde(default = "default::clock_drift", with = "humantime_serde")]
     pub clock_drift: Duration,
     #[serde(default = "default::trusting_period", with = "humantime_serde")]
@@ -265,10 +281,73 @@ pub struct ChainConfig {
     #[serde(default)]
     pub trust_threshold: TrustThreshold,
     pub gas_price: GasPrice,
+    /// Additional fee denominations accepted by the chain, used in order when the account
+    /// balance does not cover the fee in the denomination of `gas_price`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_gas_prices: Vec<GasPrice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_gas_price: Option<DynamicGasPrice>,
+    /// Memo templates overriding `memo_template` for the transactions with a message of a
+    /// type, by the type URL of the message, e.g. `/ibc.core.channel.v1.MsgRecvPacket`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub memo_overrides: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub non_critical_extension_options: Vec<ExtensionOption>,
     #[serde(default)]
     pub packet_filter: PacketFilter,
 }
 
+impl ChainConfig {
+    /// The names of all the signing keys of the chain, starting with the default `key_name`.
+    pub fn key_names(&self) -> Vec<String> {
+        let mut key_names = vec![self.key_name.clone()];
+        for key_name in &self.extra_key_names {
+            if !key_names.contains(key_name) {
+                key_names.push(key_name.clone());
+            }
+        }
+        key_names
+    }
+
+    /// The RPC endpoints of the chain, starting with the preferred `rpc_addr`.
+    pub fn rpc_addrs(&self) -> Vec<tendermint_rpc::Url> {
+        endpoint_addrs(&self.rpc_addr, &self.extra_rpc_addrs)
+    }
+
+    /// The WebSocket endpoints of the chain, starting with the preferred `websocket_addr`.
+    pub fn websocket_addrs(&self) -> Vec<tendermint_rpc::Url> {
+        endpoint_addrs(&self.websocket_addr, &self.extra_websocket_addrs)
+    }
+
+    /// The gRPC endpoints of the chain, starting with the preferred `grpc_addr`.
+    pub fn grpc_addrs(&self) -> Vec<tendermint_rpc::Url> {
+        endpoint_addrs(&self.grpc_addr, &self.extra_grpc_addrs)
+    }
+
+    /// The HD path from which the keys of the chain are derived.
+    pub fn key_hd_path(&self) -> Result<HDPath, keyring::errors::Error> {
+        match &self.hd_path {
+            Some(hd_path) => hd_path
+                .parse()
+                .map_err(|_| keyring::errors::Kind::InvalidHdPath(hd_path.clone()).into()),
+            None => Ok(self.key_type.default_hd_path()),
+        }
+    }
+}
+
+fn endpoint_addrs(
+    addr: &tendermint_rpc::Url,
+    extra_addrs: &[tendermint_rpc::Url],
+) -> Vec<tendermint_rpc::Url> {
+    let mut addrs = vec![addr.clone()];
+    for extra_addr in extra_addrs {
+        if !addrs.contains(extra_addr) {
+            addrs.push(extra_addr.clone());
+        }
+    }
+    addrs
+}
+
 /// Attempt to load and parse the TOML config file as a `Config`.
 pub fn load(path: impl AsRef<Path>) -> Result<Config, error::Error> {
     let config_toml =
@@ -304,7 +383,7 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 
 #[cfg(test)]
 mod tests {
-    use super::{load, store_writer};
+    use super::{load, store_writer, Config};
     use test_env_log::test;
 
     #[test]
@@ -331,4 +410,30 @@ mod tests {
         let mut buffer = Vec::new();
         store_writer(&config, &mut buffer).unwrap();
     }
+
+    #[test]
+    fn fee_granter_and_payer_round_trip() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config");
+        config.chains[0].fee_granter = Some("cosmos1granter".to_string());
+        config.chains[0].fee_payer = Some("cosmos1payer".to_string());
+
+        let mut buffer = Vec::new();
+        store_writer(&config, &mut buffer).unwrap();
+        let stored: Config = toml::from_slice(&buffer).unwrap();
+
+        let fees = |config: &Config| -> Vec<_> {
+            config
+                .chains
+                .iter()
+                .map(|chain| (chain.fee_granter.clone(), chain.fee_payer.clone()))
+                .collect()
+        };
+        assert_eq!(fees(&stored), fees(&config));
+        assert_eq!(stored.chains[1].fee_granter, None);
+    }
 }
@@ -0,0 +1,309 @@
+//! The packet filter of a chain, which restricts the channels on which the relayer relays
+//! packets. The channels are specified by pairs of port and channel identifiers, either of
+//! which may be a wildcard pattern, e.g., `['ica*', '*']` or `['transfer', 'channel-1*']`.
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use regex::Regex;
+use serde_derive::{Deserialize, Serialize};
+
+use ibc::ics24_host::identifier::{ChannelId, PortId};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(
+    rename_all = "lowercase",
+    tag = "policy",
+    content = "list",
+    deny_unknown_fields
+)]
+pub enum PacketFilter {
+    Allow(ChannelsSpec),
+    Deny(ChannelsSpec),
+    /// Permits relaying on the channels matched by the `allow` list, except for those
+    /// which are also matched by the `deny` list.
+    #[serde(rename = "allow_deny")]
+    AllowDeny {
+        allow: ChannelsSpec,
+        deny: ChannelsSpec,
+    },
+    AllowAll,
+}
+
+impl Default for PacketFilter {
+    /// By default, allows all channels & ports.
+    fn default() -> Self {
+        Self::AllowAll
+    }
+}
+
+impl PacketFilter {
+    /// Returns true if the packets can be relayed on the channel with [`PortId`] and [`ChannelId`],
+    /// false otherwise.
+    pub fn is_allowed(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        match self {
+            PacketFilter::Allow(spec) => spec.matches(port_id, channel_id),
+            PacketFilter::Deny(spec) => !spec.matches(port_id, channel_id),
+            PacketFilter::AllowDeny { allow, deny } => {
+                allow.matches(port_id, channel_id) && !deny.matches(port_id, channel_id)
+            }
+            PacketFilter::AllowAll => true,
+        }
+    }
+}
+
+/// A pattern against which an identifier is matched: either an exact identifier, or a
+/// wildcard pattern in which `*` matches any sequence of characters.
+#[derive(Clone, Debug)]
+pub enum FilterPattern<T> {
+    Exact(T),
+    Wildcard(Wildcard),
+}
+
+impl<T: FromStr> FromStr for FilterPattern<T>
+where
+    T::Err: fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('*') {
+            Ok(FilterPattern::Wildcard(s.parse()?))
+        } else {
+            s.parse()
+                .map(FilterPattern::Exact)
+                .map_err(|e| format!("invalid identifier '{}': {}", s, e))
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for FilterPattern<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterPattern::Exact(id) => write!(f, "{}", id),
+            FilterPattern::Wildcard(wildcard) => write!(f, "{}", wildcard),
+        }
+    }
+}
+
+impl FilterPattern<PortId> {
+    fn matches(&self, port_id: &PortId) -> bool {
+        match self {
+            FilterPattern::Exact(exact) => exact == port_id,
+            FilterPattern::Wildcard(wildcard) => wildcard.matches(port_id.as_str()),
+        }
+    }
+}
+
+impl FilterPattern<ChannelId> {
+    fn matches(&self, channel_id: &ChannelId) -> bool {
+        match self {
+            FilterPattern::Exact(exact) => exact == channel_id,
+            FilterPattern::Wildcard(wildcard) => wildcard.matches(channel_id.as_str()),
+        }
+    }
+}
+
+/// A wildcard pattern, in which `*` matches any (possibly empty) sequence of characters,
+/// compiled to a regular expression when the configuration is loaded.
+#[derive(Clone, Debug)]
+pub struct Wildcard {
+    pattern: String,
+    regex: Regex,
+}
+
+impl Wildcard {
+    pub fn matches(&self, id: &str) -> bool {
+        self.regex.is_match(id)
+    }
+}
+
+impl FromStr for Wildcard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let regex = format!("^{}$", s.split('*').map(regex::escape).join(".*"));
+        let regex =
+            Regex::new(&regex).map_err(|e| format!("invalid wildcard pattern '{}': {}", s, e))?;
+
+        Ok(Self {
+            pattern: s.to_string(),
+            regex,
+        })
+    }
+}
+
+impl fmt::Display for Wildcard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
+
+/// A list of channels, each specified by a pair of patterns matching its port and channel
+/// identifiers.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(try_from = "Vec<(String, String)>", into = "Vec<(String, String)>")]
+pub struct ChannelsSpec {
+    /// The patterns, in the order in which they are configured
+    patterns: Vec<(FilterPattern<PortId>, FilterPattern<ChannelId>)>,
+    /// The channels specified by exact port and channel identifiers, looked up before
+    /// the patterns with a wildcard are evaluated
+    exact: HashMap<PortId, HashSet<ChannelId>>,
+    /// The pairs of patterns of which at least one has a wildcard
+    wildcards: Vec<(FilterPattern<PortId>, FilterPattern<ChannelId>)>,
+}
+
+impl ChannelsSpec {
+    /// Returns true if the channel with [`PortId`] and [`ChannelId`] is matched by
+    /// one of the pairs of patterns, false otherwise.
+    pub fn matches(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        let exact_match = self
+            .exact
+            .get(port_id)
+            .map_or(false, |channel_ids| channel_ids.contains(channel_id));
+
+        exact_match
+            || self
+                .wildcards
+                .iter()
+                .any(|(port, channel)| port.matches(port_id) && channel.matches(channel_id))
+    }
+
+    /// Returns true if the channel with the given port and channel identifiers is matched by
+    /// one of the pairs of patterns, false otherwise.
+    pub fn contains(&self, (port_id, channel_id): &(PortId, ChannelId)) -> bool {
+        self.matches(port_id, channel_id)
+    }
+}
+
+impl TryFrom<Vec<(String, String)>> for ChannelsSpec {
+    type Error = String;
+
+    fn try_from(list: Vec<(String, String)>) -> Result<Self, Self::Error> {
+        let mut spec = ChannelsSpec::default();
+
+        for (port, channel) in list {
+            let port: FilterPattern<PortId> = port.parse()?;
+            let channel: FilterPattern<ChannelId> = channel.parse()?;
+
+            if let (FilterPattern::Exact(port_id), FilterPattern::Exact(channel_id)) =
+                (&port, &channel)
+            {
+                spec.exact
+                    .entry(port_id.clone())
+                    .or_default()
+                    .insert(channel_id.clone());
+            } else {
+                spec.wildcards.push((port.clone(), channel.clone()));
+            }
+
+            spec.patterns.push((port, channel));
+        }
+
+        Ok(spec)
+    }
+}
+
+impl From<ChannelsSpec> for Vec<(String, String)> {
+    fn from(spec: ChannelsSpec) -> Self {
+        spec.patterns
+            .iter()
+            .map(|(port, channel)| (port.to_string(), channel.to_string()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    use test_env_log::test;
+
+    use ibc::ics24_host::identifier::{ChannelId, PortId};
+
+    use super::{ChannelsSpec, PacketFilter};
+
+    fn allowed(filter: &PacketFilter, port_id: &str, channel_id: &str) -> bool {
+        filter.is_allowed(
+            &PortId::from_str(port_id).unwrap(),
+            &ChannelId::from_str(channel_id).unwrap(),
+        )
+    }
+
+    #[test]
+    fn wildcard_and_exact_patterns() {
+        let filter: PacketFilter = toml::from_str(
+            r#"
+            policy = 'allow'
+            list = [
+              ['ica*', '*'],
+              ['transfer', 'channel-1*'],
+              ['transfer', 'channel-0'],
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert!(allowed(&filter, "icacontroller-cosmos1abc", "channel-7"));
+        assert!(allowed(&filter, "transfer", "channel-0"));
+        assert!(allowed(&filter, "transfer", "channel-1"));
+        assert!(allowed(&filter, "transfer", "channel-12"));
+        assert!(!allowed(&filter, "transfer", "channel-2"));
+        assert!(!allowed(&filter, "oracle", "channel-0"));
+
+        // The patterns are serialized as they are configured
+        let serialized = toml::to_string(&filter).unwrap();
+        let filter: PacketFilter = toml::from_str(&serialized).unwrap();
+        assert!(allowed(&filter, "icahost", "channel-3"));
+        assert!(allowed(&filter, "transfer", "channel-12"));
+        assert!(!allowed(&filter, "transfer", "channel-2"));
+    }
+
+    #[test]
+    fn channels_spec_contains() {
+        let spec = ChannelsSpec::try_from(vec![
+            ("transfer".to_string(), "channel-0".to_string()),
+            ("ica*".to_string(), "*".to_string()),
+        ])
+        .unwrap();
+
+        let channel = |port_id: &str, channel_id: &str| {
+            (
+                PortId::from_str(port_id).unwrap(),
+                ChannelId::from_str(channel_id).unwrap(),
+            )
+        };
+        assert!(spec.contains(&channel("transfer", "channel-0")));
+        assert!(spec.contains(&channel("icahost", "channel-4")));
+        assert!(!spec.contains(&channel("transfer", "channel-1")));
+    }
+
+    #[test]
+    fn combined_allow_and_deny_lists() {
+        let filter: PacketFilter = toml::from_str(
+            r#"
+            policy = 'allow_deny'
+            list = { allow = [['transfer', '*']], deny = [['transfer', 'channel-1*']] }
+            "#,
+        )
+        .unwrap();
+
+        assert!(allowed(&filter, "transfer", "channel-0"));
+        assert!(allowed(&filter, "transfer", "channel-2"));
+        assert!(!allowed(&filter, "transfer", "channel-15"));
+        assert!(!allowed(&filter, "icahost", "channel-0"));
+    }
+
+    #[test]
+    fn invalid_identifiers_are_rejected_at_load() {
+        let invalid = r#"
+            policy = 'deny'
+            list = [['transfer', 'not/a/channel']]
+            "#;
+        assert!(toml::from_str::<PacketFilter>(invalid).is_err());
+    }
+}
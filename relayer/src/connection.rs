@@ -212,9 +212,7 @@ impl Connection {
         };
 
         if a_connection.state_matches(&State::Init) && counterparty_connection_id.is_none() {
-            let req = QueryConnectionsRequest {
-                pagination: ibc_proto::cosmos::base::query::pagination::all(),
-            };
+            let req = QueryConnectionsRequest { pagination: None };
             let connections: Vec<IdentifiedConnectionEnd> =
                 counterparty_chain.query_connections(req)?;
 
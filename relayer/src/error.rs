@@ -192,6 +192,10 @@ pub enum Kind {
     #[error("invalid key address: {0}")]
     InvalidKeyAddress(String),
 
+    /// The key is not among the signing keys configured for the chain
+    #[error("key '{0}' is not configured for chain {1}")]
+    UnknownKey(String, ChainId),
+
     #[error("bech32 encoding failed")]
     Bech32Encoding(#[from] bech32::Error),
 
@@ -20,6 +20,7 @@ use tendermint_rpc::{
 
 use ibc::{events::IbcEvent, ics02_client::height::Height, ics24_host::identifier::ChainId};
 
+use crate::chain::endpoints::{EndpointKind, Endpoints};
 use crate::util::{
     retry::{retry_count, retry_with_index, RetryResult},
     stream::group_while,
@@ -123,8 +124,8 @@ pub struct EventMonitor {
     tx_err: mpsc::UnboundedSender<tendermint_rpc::Error>,
     /// Channel where to receive commands
     rx_cmd: channel::Receiver<MonitorCmd>,
-    /// Node Address
-    node_addr: tendermint_rpc::Url,
+    /// The WebSocket endpoints of the nodes, which the monitor fails over between
+    endpoints: Endpoints<tendermint_rpc::Url>,
     /// Queries
     event_queries: Vec<Query>,
     /// All subscriptions combined in a single stream
@@ -134,18 +135,25 @@ pub struct EventMonitor {
 }
 
 impl EventMonitor {
-    /// Create an event monitor, and connect to a node
+    /// Create an event monitor, and connect to the first node which is reachable
+    /// at one of the WebSocket addresses `node_addrs`, in order of preference
     pub fn new(
         chain_id: ChainId,
-        node_addr: tendermint_rpc::Url,
+        node_addrs: Vec<tendermint_rpc::Url>,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, EventReceiver, TxMonitorCmd)> {
         let (tx_batch, rx_batch) = channel::unbounded();
         let (tx_cmd, rx_cmd) = channel::unbounded();
 
-        let ws_addr = node_addr.clone();
+        let endpoints = Endpoints::new(
+            chain_id.clone(),
+            EndpointKind::WebSocket,
+            node_addrs,
+            |address| Ok::<_, Error>(address.clone()),
+        )?;
+
         let (client, driver) = rt
-            .block_on(async move { WebSocketClient::new(ws_addr).await })
+            .block_on(endpoints.failover(|address| WebSocketClient::new(address.clone())))
             .map_err(Error::ClientCreationFailed)?;
 
         let (tx_err, rx_err) = mpsc::unbounded_channel();
@@ -164,7 +172,7 @@ impl EventMonitor {
             rx_err,
             tx_err,
             rx_cmd,
-            node_addr,
+            endpoints,
             subscriptions: Box::new(futures::stream::empty()),
         };
 
@@ -212,13 +220,16 @@ impl EventMonitor {
     fn try_reconnect(&mut self) -> Result<()> {
         trace!(chain.id = %self.chain_id,
             "trying to reconnect to WebSocket endpoint {}",
-            self.node_addr
+            self.endpoints.active_address()
         );
 
-        // Try to reconnect
+        // Try to reconnect, failing over to the other endpoints if needed
         let (mut client, driver) = self
             .rt
-            .block_on(WebSocketClient::new(self.node_addr.clone()))
+            .block_on(
+                self.endpoints
+                    .failover(|address| WebSocketClient::new(address.clone())),
+            )
             .map_err(Error::ClientCreationFailed)?;
 
         let mut driver_handle = self.rt.spawn(run_driver(driver, self.tx_err.clone()));
@@ -231,7 +242,7 @@ impl EventMonitor {
         trace!(
             chain.id = %self.chain_id,
             "reconnected to WebSocket endpoint {}",
-            self.node_addr,
+            self.endpoints.active_address(),
         );
 
         // Shut down previous client
@@ -278,12 +289,12 @@ impl EventMonitor {
             Ok(()) => info!(
                 chain.id = %self.chain_id,
                 "successfully reconnected to WebSocket endpoint {}",
-                self.node_addr
+                self.endpoints.active_address()
             ),
             Err(retries) => error!(
                 chain.id = %self.chain_id,
                 "failed to reconnect to {} after {} retries",
-                self.node_addr, retry_count(&retries)
+                self.endpoints.active_address(), retry_count(&retries)
             ),
         }
     }
@@ -11,14 +11,20 @@ use ibc::events::{IbcEvent, IbcEventType};
 use ibc::ics02_client::client_consensus::{
     AnyConsensusState, AnyConsensusStateWithHeight, ConsensusState, QueryClientEventRequest,
 };
-use ibc::ics02_client::client_state::ClientState;
+use ibc::ics02_client::client_state::{AnyClientState, ClientState};
+use ibc::ics02_client::client_type::ClientType;
 use ibc::ics02_client::events::UpdateClient;
-use ibc::ics02_client::header::Header;
-use ibc::ics02_client::misbehaviour::MisbehaviourEvidence;
+use ibc::ics02_client::header::{AnyHeader, Header};
+use ibc::ics02_client::misbehaviour::{AnyMisbehaviour, MisbehaviourEvidence};
 use ibc::ics02_client::msgs::create_client::MsgCreateAnyClient;
 use ibc::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
 use ibc::ics02_client::msgs::update_client::MsgUpdateAnyClient;
 use ibc::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
+use ibc::ics07_tendermint::client_def::verify_misbehaviour;
+use ibc::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+use ibc::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use ibc::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
+use ibc::ics09_localhost::header::Header as LocalhostHeader;
 use ibc::ics24_host::identifier::{ChainId, ClientId};
 use ibc::query::QueryTxRequest;
 use ibc::timestamp::Timestamp;
@@ -125,6 +131,20 @@ impl ForeignClient {
         Ok(client)
     }
 
+    /// Creates a new localhost client on `chain`, i.e., a client of `chain` hosted on `chain`
+    /// itself. Blocks until the client is created, or an error occurs.
+    pub fn new_localhost(chain: Box<dyn ChainHandle>) -> Result<ForeignClient, ForeignClientError> {
+        let mut client = ForeignClient {
+            id: ClientId::default(),
+            dst_chain: chain.clone(),
+            src_chain: chain,
+        };
+
+        client.create_localhost()?;
+
+        Ok(client)
+    }
+
     pub fn restore(
         id: ClientId,
         dst_chain: Box<dyn ChainHandle>,
@@ -320,10 +340,64 @@ impl ForeignClient {
         Ok(msg)
     }
 
+    /// Lower-level interface for preparing a message to create a localhost client, i.e., a client
+    /// of the destination chain hosted on the destination chain itself. The source and
+    /// destination chains must be the same.
+    pub fn build_create_localhost_client(&self) -> Result<MsgCreateAnyClient, ForeignClientError> {
+        if self.src_chain.id() != self.dst_chain.id() {
+            return Err(ForeignClientError::ClientCreate(format!(
+                "a localhost client must track its host chain, but the source ({}) and destination ({}) chains differ",
+                self.src_chain.id(),
+                self.dst_chain.id(),
+            )));
+        }
+
+        let signer = self.dst_chain.get_signer().map_err(|e| {
+            ForeignClientError::ClientCreate(format!(
+                "failed while fetching the destination chain ({}) signer: {}",
+                self.dst_chain.id(),
+                e
+            ))
+        })?;
+
+        let latest_height = self.dst_chain.query_latest_height().map_err(|e| {
+            ForeignClientError::ClientCreate(format!(
+                "failed while querying dst chain ({}) for latest height: {}",
+                self.dst_chain.id(),
+                e
+            ))
+        })?;
+
+        let client_state = LocalhostClientState::new(self.dst_chain.id(), latest_height);
+        MsgCreateAnyClient::new(
+            client_state.wrap_any(),
+            LocalhostConsensusState::default().wrap_any(),
+            signer,
+        )
+        .map_err(|e| {
+            ForeignClientError::ClientCreate(format!(
+                "failed while building the create client message: {}",
+                e
+            ))
+        })
+    }
+
     /// Returns the identifier of the newly created client.
     pub fn build_create_client_and_send(&self) -> Result<IbcEvent, ForeignClientError> {
         let new_msg = self.build_create_client()?;
+        self.send_create_client(new_msg)
+    }
 
+    /// Returns the identifier of the newly created localhost client.
+    pub fn build_create_localhost_client_and_send(&self) -> Result<IbcEvent, ForeignClientError> {
+        let new_msg = self.build_create_localhost_client()?;
+        self.send_create_client(new_msg)
+    }
+
+    fn send_create_client(
+        &self,
+        new_msg: MsgCreateAnyClient,
+    ) -> Result<IbcEvent, ForeignClientError> {
         let res = self
             .dst_chain
             .send_msgs(vec![new_msg.to_any()])
@@ -341,7 +415,22 @@ impl ForeignClient {
 
     /// Sends the client creation transaction & subsequently sets the id of this ForeignClient
     fn create(&mut self) -> Result<(), ForeignClientError> {
-        match self.build_create_client_and_send() {
+        let res = self.build_create_client_and_send();
+        self.set_created_id(res)
+    }
+
+    /// Sends the localhost client creation transaction & subsequently sets the id of this
+    /// ForeignClient
+    fn create_localhost(&mut self) -> Result<(), ForeignClientError> {
+        let res = self.build_create_localhost_client_and_send();
+        self.set_created_id(res)
+    }
+
+    fn set_created_id(
+        &mut self,
+        res: Result<IbcEvent, ForeignClientError>,
+    ) -> Result<(), ForeignClientError> {
+        match res {
             Err(e) => {
                 error!("[{}]  failed CreateClient: {}", self, e);
                 return Err(ForeignClientError::ClientCreate(format!(
@@ -433,6 +522,11 @@ impl ForeignClient {
                 ))
             })?;
 
+        // A localhost client tracks its host chain directly, there is no header to build.
+        if client_state.client_type() == ClientType::Localhost {
+            return self.build_localhost_update_client(client_state.latest_height(), target_height);
+        }
+
         // If not specified, set trusted state to the highest height smaller than target height.
         // Otherwise ensure that a consensus state at trusted height exists on-chain.
         let cs_heights = self.consensus_state_heights()?;
@@ -525,6 +619,38 @@ impl ForeignClient {
         Ok(msgs)
     }
 
+    /// Returns a vector with a message for updating the localhost client to `target_height`, or
+    /// an empty vector if the client is already at `target_height` or higher.
+    fn build_localhost_update_client(
+        &self,
+        client_height: Height,
+        target_height: Height,
+    ) -> Result<Vec<Any>, ForeignClientError> {
+        if client_height >= target_height {
+            return Ok(vec![]);
+        }
+
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ForeignClientError::ClientUpdate(format!(
+                "failed getting signer for dst chain ({}) with error: {}",
+                self.dst_chain.id(),
+                e
+            ))
+        })?;
+
+        debug!(
+            "[{}] MsgUpdateAnyClient for localhost client to height {}",
+            self, target_height,
+        );
+
+        Ok(vec![MsgUpdateAnyClient {
+            header: LocalhostHeader::new(target_height).wrap_any(),
+            signer,
+            client_id: self.id.clone(),
+        }
+        .to_any()])
+    }
+
     pub fn build_latest_update_client_and_send(&self) -> Result<Vec<IbcEvent>, ForeignClientError> {
         self.build_update_client_and_send(Height::zero(), Height::zero())
     }
@@ -650,7 +776,7 @@ impl ForeignClient {
             .dst_chain
             .query_consensus_states(QueryConsensusStatesRequest {
                 client_id: self.id.to_string(),
-                pagination: ibc_proto::cosmos::base::query::pagination::all(),
+                pagination: None,
             })
             .map_err(|e| {
                 ForeignClientError::ClientQuery(
@@ -825,8 +951,9 @@ impl ForeignClient {
                     ))
                 })?;
 
-            if misbehavior.is_some() {
-                return Ok(misbehavior);
+            if let Some(evidence) = misbehavior {
+                self.verify_misbehaviour_evidence(&client_state, &evidence)?;
+                return Ok(Some(evidence));
             }
 
             // Exit the loop if the check was for a single update or if more than
@@ -850,6 +977,73 @@ impl ForeignClient {
         Ok(None)
     }
 
+    /// Checks locally that the `evidence` of misbehaviour would be accepted by the client on the
+    /// destination chain, in state `client_state`, so as not to submit evidence bound to fail.
+    /// The trusted consensus state of each misbehaviour header is either already installed on
+    /// the destination chain, or to be installed by one of the supporting headers.
+    fn verify_misbehaviour_evidence(
+        &self,
+        client_state: &AnyClientState,
+        evidence: &MisbehaviourEvidence,
+    ) -> Result<(), ForeignClientError> {
+        let (client_state, misbehaviour) = match (client_state, &evidence.misbehaviour) {
+            (AnyClientState::Tendermint(cs), AnyMisbehaviour::Tendermint(m)) => (cs, m),
+            // Only the evidence against Tendermint clients is verified locally.
+            _ => return Ok(()),
+        };
+
+        let trusted_consensus_state = |trusted_height: Height| {
+            let from_headers = evidence.supporting_headers.iter().find_map(|h| match h {
+                AnyHeader::Tendermint(h) if h.height() == trusted_height => {
+                    Some(TmConsensusState::from(h.clone()))
+                }
+                _ => None,
+            });
+            if let Some(cs) = from_headers {
+                return Ok(cs);
+            }
+
+            match self.dst_chain().query_consensus_state(
+                self.id.clone(),
+                trusted_height,
+                Height::zero(),
+            ) {
+                Ok(AnyConsensusState::Tendermint(cs)) => Ok(cs),
+                Ok(cs) => Err(ForeignClientError::Misbehaviour(format!(
+                    "unexpected consensus state type {} for client {} at height {}",
+                    cs.client_type(),
+                    self.id,
+                    trusted_height
+                ))),
+                Err(e) => Err(ForeignClientError::Misbehaviour(format!(
+                    "failed querying consensus state for client {} at height {}: {}",
+                    self.id, trusted_height, e
+                ))),
+            }
+        };
+
+        let trusted_consensus_state1 =
+            trusted_consensus_state(misbehaviour.header1.trusted_height)?;
+        let trusted_consensus_state2 =
+            trusted_consensus_state(misbehaviour.header2.trusted_height)?;
+
+        verify_misbehaviour(
+            client_state,
+            misbehaviour,
+            &trusted_consensus_state1,
+            &trusted_consensus_state2,
+            Timestamp::now(),
+        )
+        .map_err(|e| {
+            ForeignClientError::Misbehaviour(format!(
+                "invalid evidence of misbehaviour {} for client {}: {}",
+                evidence.misbehaviour, self.id, e
+            ))
+        })?;
+
+        Ok(())
+    }
+
     fn submit_evidence(
         &self,
         evidence: MisbehaviourEvidence,
@@ -1176,6 +1370,30 @@ mod test {
         );
     }
 
+    /// Localhost clients are only created explicitly, and only on the chain they track.
+    #[test]
+    fn foreign_client_create_localhost_is_explicit() {
+        let a_cfg = get_basic_chain_config("chain_a");
+        let b_cfg = get_basic_chain_config("chain_b");
+
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let a_chain = ChainRuntime::<MockChain>::spawn(a_cfg, rt.clone()).unwrap();
+        let b_chain = ChainRuntime::<MockChain>::spawn(b_cfg, rt).unwrap();
+
+        let res = ForeignClient::new(a_chain.clone(), a_chain.clone());
+        assert!(
+            res.is_err(),
+            "Client creation with the same source and destination chains was supposed to fail"
+        );
+
+        let a_client = ForeignClient::restore(ClientId::default(), a_chain, b_chain);
+        let res = a_client.build_create_localhost_client();
+        assert!(
+            res.is_err(),
+            "Localhost client creation with different chains was supposed to fail"
+        );
+    }
+
     /// Tests for `ForeignClient::update()`.
     #[test]
     fn foreign_client_update() {
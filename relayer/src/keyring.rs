@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::ffi::OsStr;
+use std::fmt;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use bech32::{ToBase32, Variant};
 use bip39::{Language, Mnemonic, Seed};
@@ -10,12 +12,21 @@ use bitcoin::{
     secp256k1::Secp256k1,
     util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
 };
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use hdpath::StandardHDPath;
 use ibc::ics24_host::identifier::ChainId;
-use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use k256::ecdsa::{
+    signature::{DigestSigner, Signer},
+    Signature, SigningKey,
+};
+use once_cell::sync::OnceCell;
+use rand::{rngs::OsRng, RngCore};
 use ripemd160::Ripemd160;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use zeroize::Zeroize;
 
 use errors::{Error, Kind};
 pub use pub_key::EncodedPubKey;
@@ -27,8 +38,20 @@ pub type HDPath = StandardHDPath;
 
 pub const KEYSTORE_DEFAULT_FOLDER: &str = ".hermes/keys/";
 pub const KEYSTORE_DISK_BACKEND: &str = "keyring-test";
+pub const KEYSTORE_ENCRYPTED_BACKEND: &str = "keyring-encrypted";
 pub const KEYSTORE_FILE_EXTENSION: &str = "json";
 
+/// Type URL of the public keys of the Cosmos SDK secp256k1 keys
+pub const SECP256K1_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+/// Type URL of the public keys of the Ethermint ethsecp256k1 keys
+pub const ETHSECP256K1_PUB_KEY_TYPE_URL: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+/// Environment variable holding the passphrase of the encrypted key store
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "HERMES_KEYRING_PASSPHRASE";
+/// Environment variable holding the path of a file containing the passphrase of the encrypted
+/// key store
+pub const KEYSTORE_PASSPHRASE_FILE_ENV: &str = "HERMES_KEYRING_PASSPHRASE_FILE";
+
 // /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\
 // WARNING: Changing this struct in backward incompatible way
 //          will force users to re-import their keys.
@@ -98,9 +121,53 @@ impl KeyEntry {
     }
 }
 
+/// The algorithm of the keys of a chain, which determines the encoding of their public keys,
+/// the derivation of their addresses and how they sign messages.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyType {
+    /// Cosmos SDK keys, with SHA256 + RIPEMD160 addresses and SHA256 message digests
+    Secp256k1,
+    /// Ethermint keys of EVM-compatible chains, with Ethereum (Keccak256) addresses and
+    /// Keccak256 message digests
+    EthSecp256k1,
+}
+
+impl KeyType {
+    /// The type URL with which the public keys are encoded in transactions.
+    pub fn pub_key_type_url(&self) -> &'static str {
+        match self {
+            KeyType::Secp256k1 => SECP256K1_PUB_KEY_TYPE_URL,
+            KeyType::EthSecp256k1 => ETHSECP256K1_PUB_KEY_TYPE_URL,
+        }
+    }
+
+    /// The BIP-44 coin type of the keys: 118 (ATOM) or 60 (ETH).
+    pub fn coin_type(&self) -> u32 {
+        match self {
+            KeyType::Secp256k1 => 118,
+            KeyType::EthSecp256k1 => 60,
+        }
+    }
+
+    /// The default HD path of the keys, `m/44'/<coin type>'/0'/0/0`.
+    pub fn default_hd_path(&self) -> HDPath {
+        format!("m/44'/{}'/0'/0/0", self.coin_type())
+            .parse()
+            .expect("the default HD path is valid")
+    }
+}
+
+impl Default for KeyType {
+    fn default() -> Self {
+        Self::Secp256k1
+    }
+}
+
 pub trait KeyStore {
     fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error>;
     fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error>;
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error>;
     fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error>;
 }
 
@@ -137,6 +204,13 @@ impl KeyStore for Memory {
         Ok(())
     }
 
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        self.keys
+            .remove(key_name)
+            .map(|_| ())
+            .ok_or_else(|| Kind::KeyNotFound.into())
+    }
+
     fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
         Ok(self
             .keys
@@ -191,42 +265,337 @@ impl KeyStore for Test {
         let mut filename = self.store.join(key_name);
         filename.set_extension(KEYSTORE_FILE_EXTENSION);
 
-        let file = File::create(filename)
-            .map_err(|_| Kind::KeyStore.context("error creating the key file"))?;
-
-        serde_json::to_writer_pretty(file, &key_entry)
-            .map_err(|_| Kind::KeyStore.context("error writing the key file"))?;
+        write_key_file(&filename, &key_entry)
+    }
 
-        Ok(())
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        remove_key_file(&self.store, key_name)
     }
 
     fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
-        let dir = fs::read_dir(&self.store)
-            .map_err(|e| Kind::KeyStore.context(format!("cannot list keys: {}", e)))?;
+        key_file_names(&self.store)?
+            .into_iter()
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+/// The passphrase of the encrypted key store, which is read once by a process for all its key
+/// rings, e.g., those of each chain and of the broadcasters of a chain
+static PASSPHRASE: OnceCell<Passphrase> = OnceCell::new();
+
+/// The passphrase of the encrypted key store, which is never printed, and is erased from
+/// memory once dropped.
+#[derive(Clone, Default)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    pub fn new(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+
+    /// Reads the passphrase from the `HERMES_KEYRING_PASSPHRASE` environment variable if it is
+    /// set, else from the file at the path in `HERMES_KEYRING_PASSPHRASE_FILE` if it is set,
+    /// else prompts for it on the terminal.
+    pub fn read() -> Result<Self, Error> {
+        let passphrase = if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+            Self(passphrase)
+        } else if let Ok(path) = std::env::var(KEYSTORE_PASSPHRASE_FILE_ENV) {
+            let contents = Self(fs::read_to_string(&path).map_err(|e| {
+                Kind::KeyStore.context(format!("cannot read the passphrase file '{}': {}", path, e))
+            })?);
+            Self(contents.0.trim_end_matches(&['\r', '\n'][..]).to_string())
+        } else {
+            Self(
+                rpassword::read_password_from_tty(Some("Key store passphrase: ")).map_err(|e| {
+                    Kind::KeyStore.context(format!("cannot read the passphrase: {}", e))
+                })?,
+            )
+        };
+
+        if passphrase.0.is_empty() {
+            return Err(Kind::KeyStore
+                .context("the passphrase of the encrypted key store cannot be empty")
+                .into());
+        }
+
+        Ok(passphrase)
+    }
+
+    /// The passphrase read by this process, which is only read the first time.
+    pub fn read_once() -> Result<Self, Error> {
+        PASSPHRASE.get_or_try_init(Self::read).map(Clone::clone)
+    }
+}
+
+impl Drop for Passphrase {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(..)")
+    }
+}
+
+/// Parameters of the scrypt derivation of the encryption key of a key file from the passphrase
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A key file of the encrypted key store: the JSON-encoded [`KeyEntry`], encrypted with
+/// ChaCha20-Poly1305 under a key derived from the passphrase with scrypt
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EncryptedKeyFile {
+    kdf: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// A disk-backed key store, whose key files are encrypted with a passphrase.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Encrypted {
+    account_prefix: String,
+    store: PathBuf,
+    kdf: KdfParams,
+    #[serde(skip)]
+    passphrase: Passphrase,
+    /// The keys decrypted so far, to only derive their encryption key once
+    #[serde(skip)]
+    decrypted: Arc<Mutex<HashMap<String, KeyEntry>>>,
+}
+
+impl Encrypted {
+    pub fn new(account_prefix: String, store: PathBuf, passphrase: Passphrase) -> Self {
+        Self {
+            account_prefix,
+            store,
+            kdf: KdfParams::default(),
+            passphrase,
+            decrypted: Default::default(),
+        }
+    }
+
+    fn encryption_key(&self, kdf: &KdfParams, salt: &[u8]) -> Result<[u8; 32], Error> {
+        let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p)
+            .map_err(|_| Kind::KeyStore.context("invalid scrypt parameters"))?;
+
+        let mut key = [0; 32];
+        scrypt::scrypt(self.passphrase.0.as_bytes(), salt, &params, &mut key)
+            .map_err(|_| Kind::KeyStore.context("cannot derive the encryption key"))?;
+
+        Ok(key)
+    }
+
+    fn encrypt(&self, key_entry: &KeyEntry) -> Result<EncryptedKeyFile, Error> {
+        let mut salt = [0; 16];
+        let mut nonce = [0; 12];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let key = self.encryption_key(&self.kdf, &salt)?;
+        let plaintext = serde_json::to_vec(key_entry).map_err(|e| Kind::KeyStore.context(e))?;
+
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_ref())
+            .map_err(|_| Kind::KeyStore.context("cannot encrypt the key"))?;
+
+        Ok(EncryptedKeyFile {
+            kdf: self.kdf,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    fn decrypt(&self, key_file: &EncryptedKeyFile) -> Result<KeyEntry, Error> {
+        let decode = |field: &str| hex::decode(field).map_err(|e| Kind::KeyStore.context(e));
+        let salt = decode(&key_file.salt)?;
+        let nonce = decode(&key_file.nonce)?;
+        let ciphertext = decode(&key_file.ciphertext)?;
+
+        if nonce.len() != 12 {
+            return Err(Kind::KeyStore.context("invalid nonce length").into());
+        }
+
+        let key = self.encryption_key(&key_file.kdf, &salt)?;
+
+        let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| {
+                Kind::KeyStore.context("cannot decrypt the key: wrong passphrase or corrupted file")
+            })?;
+
+        Ok(serde_json::from_slice(&plaintext).map_err(|e| Kind::KeyStore.context(e))?)
+    }
+}
+
+impl fmt::Debug for Encrypted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Encrypted")
+            .field("account_prefix", &self.account_prefix)
+            .field("store", &self.store)
+            .finish()
+    }
+}
 
-        let ext = OsStr::new(KEYSTORE_FILE_EXTENSION);
+impl KeyStore for Encrypted {
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        if let Some(key_entry) = self.decrypted.lock().unwrap().get(key_name) {
+            return Ok(key_entry.clone());
+        }
+
+        let mut key_file = self.store.join(key_name);
+        key_file.set_extension(KEYSTORE_FILE_EXTENSION);
 
-        dir.into_iter()
-            .flatten()
-            .map(|entry| entry.path())
-            .filter(|path| path.extension() == Some(ext))
-            .flat_map(|path| path.file_stem().map(OsStr::to_owned))
-            .flat_map(|stem| stem.to_str().map(ToString::to_string))
+        if !key_file.as_path().exists() {
+            return Err(Kind::KeyStore
+                .context(format!("cannot find key file at '{}'", key_file.display()))
+                .into());
+        }
+
+        let file = File::open(&key_file).map_err(|_| {
+            Kind::KeyStore.context(format!("cannot open key file at '{}'", key_file.display()))
+        })?;
+
+        let encrypted: EncryptedKeyFile = serde_json::from_reader(file).map_err(|e| {
+            Kind::KeyStore.context(format!(
+                "invalid key file at '{}': {}",
+                key_file.display(),
+                e
+            ))
+        })?;
+
+        let key_entry = self.decrypt(&encrypted)?;
+
+        self.decrypted
+            .lock()
+            .unwrap()
+            .insert(key_name.to_string(), key_entry.clone());
+
+        Ok(key_entry)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error> {
+        let mut filename = self.store.join(key_name);
+        filename.set_extension(KEYSTORE_FILE_EXTENSION);
+
+        let encrypted = self.encrypt(&key_entry)?;
+        write_key_file(&filename, &encrypted)?;
+
+        self.decrypted
+            .lock()
+            .unwrap()
+            .insert(key_name.to_string(), key_entry);
+
+        Ok(())
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        self.decrypted.lock().unwrap().remove(key_name);
+        remove_key_file(&self.store, key_name)
+    }
+
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        key_file_names(&self.store)?
+            .into_iter()
             .map(|name| self.get_key(&name).map(|key| (name, key)))
             .collect()
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// Writes the key file at `path`, only readable and writable by its owner, and flushes it to
+/// disk, so that the key is persisted before any copy of it is deleted.
+fn write_key_file(path: &Path, content: &impl Serialize) -> Result<(), Error> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let file = options
+        .open(path)
+        .map_err(|_| Kind::KeyStore.context("error creating the key file"))?;
+
+    serde_json::to_writer_pretty(&file, content)
+        .map_err(|_| Kind::KeyStore.context("error writing the key file"))?;
+
+    file.sync_all()
+        .map_err(|_| Kind::KeyStore.context("error flushing the key file"))?;
+
+    Ok(())
+}
+
+/// The names of the keys in the key files of the disk store at `store`.
+fn key_file_names(store: &Path) -> Result<Vec<String>, Error> {
+    let dir = fs::read_dir(store)
+        .map_err(|e| Kind::KeyStore.context(format!("cannot list keys: {}", e)))?;
+
+    let ext = OsStr::new(KEYSTORE_FILE_EXTENSION);
+
+    Ok(dir
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension() == Some(ext))
+        .flat_map(|path| path.file_stem().map(OsStr::to_owned))
+        .flat_map(|stem| stem.to_str().map(ToString::to_string))
+        .collect())
+}
+
+fn remove_key_file(store: &Path, key_name: &str) -> Result<(), Error> {
+    let mut key_file = store.join(key_name);
+    key_file.set_extension(KEYSTORE_FILE_EXTENSION);
+
+    fs::remove_file(&key_file).map_err(|e| {
+        Kind::KeyStore
+            .context(format!(
+                "cannot remove key file at '{}': {}",
+                key_file.display(),
+                e
+            ))
+            .into()
+    })
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Store {
     Memory,
     Test,
+    Encrypted,
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        Self::Test
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum KeyRing {
     Memory(Memory),
     Test(Test),
+    Encrypted(Encrypted),
 }
 
 impl KeyRing {
@@ -235,20 +604,35 @@ impl KeyRing {
             Store::Memory => Ok(Self::Memory(Memory::new(account_prefix.to_string()))),
 
             Store::Test => {
-                let keys_folder = disk_store_path(chain_id.as_str()).map_err(|e| {
-                    Kind::KeyStore.context(format!("failed to compute keys folder path: {:?}", e))
-                })?;
-
-                // Create keys folder if it does not exist
-                fs::create_dir_all(&keys_folder).map_err(|e| {
-                    Kind::KeyStore.context(format!("failed to create keys folder: {:?}", e))
-                })?;
+                let keys_folder = keys_folder(chain_id, KEYSTORE_DISK_BACKEND)?;
 
                 Ok(Self::Test(Test::new(
                     account_prefix.to_string(),
                     keys_folder,
                 )))
             }
+
+            Store::Encrypted => {
+                let keys_folder = keys_folder(chain_id, KEYSTORE_ENCRYPTED_BACKEND)?;
+
+                Ok(Self::Encrypted(Encrypted::new(
+                    account_prefix.to_string(),
+                    keys_folder,
+                    Passphrase::read_once()?,
+                )))
+            }
+        }
+    }
+
+    /// Returns a copy of this key ring which reads the keys from its store again, rather than
+    /// returning the keys it already decrypted.
+    pub fn reloaded(&self) -> Self {
+        match self {
+            KeyRing::Encrypted(e) => KeyRing::Encrypted(Encrypted {
+                decrypted: Default::default(),
+                ..e.clone()
+            }),
+            _ => self.clone(),
         }
     }
 
@@ -256,6 +640,7 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.get_key(key_name),
             KeyRing::Test(d) => d.get_key(key_name),
+            KeyRing::Encrypted(e) => e.get_key(key_name),
         }
     }
 
@@ -263,6 +648,15 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.add_key(key_name, key_entry),
             KeyRing::Test(d) => d.add_key(key_name, key_entry),
+            KeyRing::Encrypted(e) => e.add_key(key_name, key_entry),
+        }
+    }
+
+    pub fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        match self {
+            KeyRing::Memory(m) => m.remove_key(key_name),
+            KeyRing::Test(d) => d.remove_key(key_name),
+            KeyRing::Encrypted(e) => e.remove_key(key_name),
         }
     }
 
@@ -270,6 +664,7 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.keys(),
             KeyRing::Test(d) => d.keys(),
+            KeyRing::Encrypted(e) => e.keys(),
         }
     }
 
@@ -290,6 +685,7 @@ impl KeyRing {
         &self,
         mnemonic_words: &str,
         hd_path: &HDPath,
+        key_type: KeyType,
     ) -> Result<KeyEntry, Error> {
         // Get the private key from the mnemonic
         let private_key = private_key_from_mnemonic(mnemonic_words, hd_path)?;
@@ -298,7 +694,7 @@ impl KeyRing {
         let public_key = ExtendedPubKey::from_private(&Secp256k1::new(), &private_key);
 
         // Get address from the public Key
-        let address = get_address(public_key);
+        let address = get_address(public_key, key_type);
 
         // Compute Bech32 account
         let account = bech32::encode(self.account_prefix(), address.to_base32(), Variant::Bech32)
@@ -312,8 +708,13 @@ impl KeyRing {
         })
     }
 
-    /// Sign a message
-    pub fn sign_msg(&self, key_name: &str, msg: Vec<u8>) -> Result<Vec<u8>, Error> {
+    /// Sign a message, with the algorithm of the keys of type `key_type`
+    pub fn sign_msg(
+        &self,
+        key_name: &str,
+        msg: Vec<u8>,
+        key_type: KeyType,
+    ) -> Result<Vec<u8>, Error> {
         let key = self.get_key(key_name)?;
 
         let private_key_bytes = key.private_key.private_key.to_bytes();
@@ -321,7 +722,10 @@ impl KeyRing {
             Kind::InvalidKey.context("could not build signing key from private key bytes")
         })?;
 
-        let signature: Signature = signing_key.sign(&msg);
+        let signature: Signature = match key_type {
+            KeyType::Secp256k1 => signing_key.sign(&msg),
+            KeyType::EthSecp256k1 => signing_key.sign_digest(Keccak256::new().chain(&msg)),
+        };
         Ok(signature.as_ref().to_vec())
     }
 
@@ -329,8 +733,41 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => &m.account_prefix,
             KeyRing::Test(d) => &d.account_prefix,
+            KeyRing::Encrypted(e) => &e.account_prefix,
+        }
+    }
+}
+
+/// Copies the keys of the key ring `from` which are not in the key ring `to` into it, e.g., to
+/// encrypt the keys of the test store, and returns the names of the copied keys. Each copied key
+/// is read back from the store of `to`, and decrypted again if encrypted, so that the keys in
+/// `from` can safely be removed afterwards.
+pub fn migrate_keys(from: &KeyRing, to: &mut KeyRing) -> Result<Vec<String>, Error> {
+    let existing = to
+        .keys()?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+
+    let mut migrated = vec![];
+
+    for (name, key_entry) in from.keys()? {
+        if existing.contains(&name) {
+            continue;
+        }
+
+        to.add_key(&name, key_entry.clone())?;
+
+        if to.reloaded().get_key(&name)? != key_entry {
+            return Err(Kind::KeyStore
+                .context(format!("key '{}' was not migrated faithfully", name))
+                .into());
         }
+
+        migrated.push(name);
     }
+
+    Ok(migrated)
 }
 
 /// Decode an extended private key from a mnemonic
@@ -351,7 +788,15 @@ fn private_key_from_mnemonic(
 }
 
 /// Return an address from a Public Key
-fn get_address(pk: ExtendedPubKey) -> Vec<u8> {
+fn get_address(pk: ExtendedPubKey, key_type: KeyType) -> Vec<u8> {
+    if key_type == KeyType::EthSecp256k1 {
+        // The last 20 bytes of the Keccak256 hash of the uncompressed public key,
+        // without its 0x04 prefix
+        let uncompressed = pk.public_key.key.serialize_uncompressed();
+        let hash = Keccak256::digest(&uncompressed[1..]);
+        return hash[12..].to_vec();
+    }
+
     let mut hasher = Sha256::new();
     hasher.update(pk.public_key.to_bytes().as_slice());
 
@@ -376,14 +821,177 @@ fn decode_bech32(input: &str) -> Result<Vec<u8>, Error> {
     Ok(bytes)
 }
 
-fn disk_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+fn disk_store_path(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
     let home = dirs_next::home_dir()
         .ok_or_else(|| Kind::KeyStore.context("cannot retrieve home folder location"))?;
 
     let folder = Path::new(home.as_path())
         .join(KEYSTORE_DEFAULT_FOLDER)
         .join(folder_name)
-        .join(KEYSTORE_DISK_BACKEND);
+        .join(backend);
 
     Ok(folder)
 }
+
+/// Returns the folder of the disk store `backend` of the keys of `chain_id`, creating it if it
+/// does not exist.
+fn keys_folder(chain_id: &ChainId, backend: &str) -> Result<PathBuf, Error> {
+    let keys_folder = disk_store_path(chain_id.as_str(), backend).map_err(|e| {
+        Kind::KeyStore.context(format!("failed to compute keys folder path: {:?}", e))
+    })?;
+
+    // Create keys folder if it does not exist
+    fs::create_dir_all(&keys_folder)
+        .map_err(|e| Kind::KeyStore.context(format!("failed to create keys folder: {:?}", e)))?;
+
+    Ok(keys_folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use test_env_log::test;
+
+    use super::{
+        migrate_keys, Encrypted, HDPath, KdfParams, KeyEntry, KeyRing, KeyType, Memory, Passphrase,
+        Test,
+    };
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn temp_store(name: &str) -> PathBuf {
+        let store =
+            std::env::temp_dir().join(format!("hermes-keyring-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&store).unwrap();
+        store
+    }
+
+    fn key_entry() -> KeyEntry {
+        let hd_path = HDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        KeyRing::Memory(Memory::new("cosmos".to_string()))
+            .key_from_mnemonic(MNEMONIC, &hd_path, KeyType::Secp256k1)
+            .unwrap()
+    }
+
+    fn encrypted_keyring(store: PathBuf, passphrase: &str) -> KeyRing {
+        let mut encrypted = Encrypted::new(
+            "cosmos".to_string(),
+            store,
+            Passphrase::new(passphrase.to_string()),
+        );
+        // A cheap key derivation, to keep the tests fast
+        encrypted.kdf = KdfParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        };
+        KeyRing::Encrypted(encrypted)
+    }
+
+    #[test]
+    fn encrypted_store() {
+        let store = temp_store("encrypted");
+        let key = key_entry();
+
+        let mut keyring = encrypted_keyring(store.clone(), "correct horse");
+        keyring.add_key("relayer", key.clone()).unwrap();
+
+        let key_file = fs::read_to_string(store.join("relayer.json")).unwrap();
+        assert!(!key_file.contains(&key.account));
+
+        // Fresh key rings, which have not decrypted the key yet
+        let keyring = encrypted_keyring(store.clone(), "correct horse");
+        assert_eq!(keyring.get_key("relayer").unwrap(), key);
+        let keyring = encrypted_keyring(store.clone(), "battery staple");
+        assert!(keyring.get_key("relayer").is_err());
+
+        fs::remove_dir_all(store).unwrap();
+    }
+
+    #[test]
+    fn reloaded_encrypted_store() {
+        let store = temp_store("reloaded");
+        let key = key_entry();
+
+        let mut keyring = encrypted_keyring(store.clone(), "correct horse");
+        keyring.add_key("relayer", key.clone()).unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = fs::metadata(store.join("relayer.json")).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        }
+
+        // The key ring returns the key it encrypted, but a reloaded one reads the corrupted file
+        fs::write(store.join("relayer.json"), "{}").unwrap();
+        assert_eq!(keyring.get_key("relayer").unwrap(), key);
+        assert!(keyring.reloaded().get_key("relayer").is_err());
+
+        fs::remove_dir_all(store).unwrap();
+    }
+
+    #[test]
+    fn migrate_test_keys() {
+        let (test_store, encrypted_store) = (temp_store("migrate-test"), temp_store("migrate"));
+        let key = key_entry();
+
+        let mut test = KeyRing::Test(Test::new("cosmos".to_string(), test_store.clone()));
+        test.add_key("relayer", key.clone()).unwrap();
+
+        let mut encrypted = encrypted_keyring(encrypted_store.clone(), "correct horse");
+        assert_eq!(
+            migrate_keys(&test, &mut encrypted).unwrap(),
+            vec!["relayer"]
+        );
+        assert!(migrate_keys(&test, &mut encrypted).unwrap().is_empty());
+
+        test.remove_key("relayer").unwrap();
+        assert!(test.keys().unwrap().is_empty());
+
+        let encrypted = encrypted_keyring(encrypted_store.clone(), "correct horse");
+        assert_eq!(
+            encrypted.keys().unwrap(),
+            vec![("relayer".to_string(), key)]
+        );
+
+        fs::remove_dir_all(test_store).unwrap();
+        fs::remove_dir_all(encrypted_store).unwrap();
+    }
+
+    #[test]
+    fn ethsecp256k1_key() {
+        use std::convert::TryFrom;
+
+        use k256::ecdsa::{signature::DigestVerifier, Signature, VerifyingKey};
+        use sha2::Digest;
+        use sha3::Keccak256;
+
+        let key_type = KeyType::EthSecp256k1;
+        let mut keyring = KeyRing::Memory(Memory::new("evmos".to_string()));
+        let key = keyring
+            .key_from_mnemonic(MNEMONIC, &key_type.default_hd_path(), key_type)
+            .unwrap();
+
+        // The well-known first Ethereum account of this mnemonic
+        assert_eq!(
+            hex::encode(&key.address),
+            "9858effd232b4033e47d90003d41ec34ecaeda94"
+        );
+        assert!(key.account.starts_with("evmos1"));
+
+        keyring.add_key("relayer", key.clone()).unwrap();
+        let msg = b"sign doc".to_vec();
+        let signature = keyring.sign_msg("relayer", msg.clone(), key_type).unwrap();
+
+        let verifying_key =
+            VerifyingKey::from_sec1_bytes(&key.public_key.public_key.to_bytes()).unwrap();
+        let signature = Signature::try_from(signature.as_slice()).unwrap();
+        assert!(verifying_key
+            .verify_digest(Keccak256::new().chain(&msg), &signature)
+            .is_ok());
+    }
+}
@@ -4,8 +4,8 @@ use serde::{Deserialize, Deserializer};
 use subtle_encoding::base64;
 use tracing::{error, trace};
 
-use super::decode_bech32;
 use super::errors::{Error, Kind};
+use super::{decode_bech32, ETHSECP256K1_PUB_KEY_TYPE_URL, SECP256K1_PUB_KEY_TYPE_URL};
 
 #[derive(Debug)]
 pub enum EncodedPubKey {
@@ -63,10 +63,13 @@ impl FromStr for EncodedPubKey {
                     proto.tpe
                 );
 
-                if proto.tpe != "/cosmos.crypto.secp256k1.PubKey" {
+                if proto.tpe != SECP256K1_PUB_KEY_TYPE_URL
+                    && proto.tpe != ETHSECP256K1_PUB_KEY_TYPE_URL
+                {
                     return Err(Kind::EncodedPublicKey(
                         s.to_string(),
-                        "only secp256k1 pub keys are currently supported".to_string(),
+                        "only secp256k1 and ethsecp256k1 pub keys are currently supported"
+                            .to_string(),
                     )
                     .into());
                 }
@@ -0,0 +1,251 @@
+//! A persistent store of the light blocks verified by the relayer's light clients. Verified
+//! blocks are kept on disk, keyed by chain identifier and height, so that the verification of
+//! later headers, including after a restart, can start from the closest trusted block rather
+//! than bisecting again from the trusted height of the IBC client.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tendermint::Time;
+use tendermint_light_client::types::LightBlock;
+
+use ibc::ics24_host::identifier::ChainId;
+
+use crate::error::{self, Error};
+use crate::util::sled::{self as sled_util, KeyValueDb};
+
+pub const LIGHT_STORE_DEFAULT_FOLDER: &str = ".hermes/light_store/";
+
+/// The databases opened by this process, by path. sled locks the database it opens for the
+/// process, so the light clients of a chain, e.g., those of its transaction broadcasters,
+/// all share the database opened by the first one.
+static OPEN_DATABASES: Lazy<Mutex<HashMap<PathBuf, sled::Db>>> = Lazy::new(Default::default);
+
+/// A disk-backed store of the verified light blocks of a chain, indexed by height.
+#[derive(Clone, Debug)]
+pub struct LightBlockStore {
+    db: sled::Db,
+    blocks: KeyValueDb<u64, LightBlock>,
+}
+
+impl LightBlockStore {
+    /// Opens (or creates) the store of the chain `chain_id` in the default folder.
+    pub fn open_default(chain_id: &ChainId) -> Result<Self, Error> {
+        Self::open(default_store_path(chain_id)?, chain_id)
+    }
+
+    /// Opens (or creates) the store of the chain `chain_id` at `path`, or reuses the database
+    /// at `path` if this process already opened it.
+    pub fn open(path: impl AsRef<Path>, chain_id: &ChainId) -> Result<Self, Error> {
+        let mut databases = OPEN_DATABASES
+            .lock()
+            .map_err(|e| error::Kind::Store.context(e.to_string()))?;
+
+        let db = match databases.get(path.as_ref()) {
+            Some(db) => db.clone(),
+            None => {
+                let db = sled::open(&path).map_err(|e| error::Kind::Store.context(e))?;
+                databases.insert(path.as_ref().to_path_buf(), db.clone());
+                db
+            }
+        };
+
+        Ok(Self::new(db, chain_id))
+    }
+
+    /// Creates a store of the blocks of the chain `chain_id` in the given database.
+    pub fn new(db: sled::Db, chain_id: &ChainId) -> Self {
+        Self {
+            db,
+            blocks: sled_util::key_value(format!("light_blocks/{}/", chain_id)),
+        }
+    }
+
+    /// Returns the block at `height`, if it is in the store.
+    pub fn get(&self, height: u64) -> Result<Option<LightBlock>, Error> {
+        self.blocks.fetch(&self.db, &height)
+    }
+
+    /// Stores the given verified block, and prunes the lower blocks which are outside the
+    /// `trusting_period`, so that the store only holds blocks which can serve as a root of
+    /// trust. The store is flushed to disk in the background.
+    pub fn insert(&self, block: &LightBlock, trusting_period: Duration) -> Result<(), Error> {
+        let height = block.height().value();
+        self.blocks.insert(&self.db, &height, block)?;
+
+        self.prune_expired(height, Time::now(), trusting_period)
+    }
+
+    /// Removes the block at `height` from the store.
+    pub fn remove(&self, height: u64) -> Result<(), Error> {
+        self.blocks.remove(&self.db, &height)
+    }
+
+    /// Removes all the blocks up to `height` (inclusive) from the store.
+    pub fn prune(&self, height: u64) -> Result<(), Error> {
+        for entry in self.blocks.range(&self.db, ..=height)? {
+            let (block_height, _) = entry?;
+            self.remove(block_height)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the block of highest height between `min_height` and `max_height` (inclusive) in
+    /// the store, if any.
+    pub fn closest(&self, min_height: u64, max_height: u64) -> Result<Option<LightBlock>, Error> {
+        let closest = self
+            .blocks
+            .range(&self.db, min_height..=max_height)?
+            .next_back()
+            .transpose()?;
+
+        Ok(closest.map(|(_, block)| block))
+    }
+
+    /// Removes the blocks below `height` which are outside the trusting period at `now`. Blocks
+    /// are scanned by ascending height, hence time, up to the first one still trusted.
+    fn prune_expired(
+        &self,
+        height: u64,
+        now: Time,
+        trusting_period: Duration,
+    ) -> Result<(), Error> {
+        for entry in self.blocks.range(&self.db, ..height)? {
+            let (block_height, block) = entry?;
+            if within_trusting_period(&block, now, trusting_period) {
+                break;
+            }
+            self.remove(block_height)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if the header of `block` is less than `trusting_period` old at `now`.
+pub fn within_trusting_period(block: &LightBlock, now: Time, trusting_period: Duration) -> bool {
+    now.duration_since(block.signed_header.header.time)
+        .map_or(false, |elapsed| elapsed < trusting_period)
+}
+
+fn default_store_path(chain_id: &ChainId) -> Result<PathBuf, Error> {
+    let home = dirs_next::home_dir()
+        .ok_or_else(|| error::Kind::Store.context("cannot retrieve home folder location"))?;
+
+    Ok(home
+        .join(LIGHT_STORE_DEFAULT_FOLDER)
+        .join(chain_id.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use tendermint_light_client::types::LightBlock;
+    use test_env_log::test;
+
+    use ibc::ics24_host::identifier::ChainId;
+    use ibc::mock::host::HostBlock;
+
+    use super::LightBlockStore;
+
+    /// The blocks generated by `light_block` date back to the Unix epoch.
+    const NO_EXPIRY: Duration = Duration::from_secs(u64::MAX);
+
+    fn light_block(chain_id: &ChainId, height: u64) -> LightBlock {
+        light_block_at(chain_id, height, height)
+    }
+
+    fn light_block_at(chain_id: &ChainId, height: u64, time: u64) -> LightBlock {
+        let block = HostBlock::generate_tm_block_with_time(chain_id.clone(), height, time);
+        LightBlock::new(
+            block.signed_header,
+            block.validators,
+            block.next_validators,
+            block.provider,
+        )
+    }
+
+    #[test]
+    fn closest_trusted_block() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let chain_a = ChainId::new("chain_a".to_string(), 0);
+        let chain_b = ChainId::new("chain_b".to_string(), 0);
+        let (store_a, store_b) = (
+            LightBlockStore::new(db.clone(), &chain_a),
+            LightBlockStore::new(db, &chain_b),
+        );
+
+        for height in [10, 20, 30].iter() {
+            store_a
+                .insert(&light_block(&chain_a, *height), NO_EXPIRY)
+                .unwrap();
+        }
+        store_b
+            .insert(&light_block(&chain_b, 25), NO_EXPIRY)
+            .unwrap();
+
+        let height_of = |block: Option<LightBlock>| block.map(|b| b.height().value());
+        assert_eq!(height_of(store_a.get(20).unwrap()), Some(20));
+        assert_eq!(height_of(store_a.closest(10, 29).unwrap()), Some(20));
+        assert_eq!(height_of(store_a.closest(10, 30).unwrap()), Some(30));
+        assert_eq!(height_of(store_a.closest(21, 29).unwrap()), None);
+        assert_eq!(height_of(store_b.closest(0, 100).unwrap()), Some(25));
+
+        store_a.remove(30).unwrap();
+        assert_eq!(height_of(store_a.closest(10, 30).unwrap()), Some(20));
+        store_a.prune(20).unwrap();
+        assert_eq!(height_of(store_a.closest(0, 100).unwrap()), None);
+        assert_eq!(height_of(store_b.get(25).unwrap()), Some(25));
+    }
+
+    #[test]
+    fn stores_share_the_database_of_a_path() {
+        let path = std::env::temp_dir().join(format!("light_store_{}", std::process::id()));
+        let chain_id = ChainId::new("chain".to_string(), 0);
+        let store = LightBlockStore::open(&path, &chain_id).unwrap();
+        let other = LightBlockStore::open(&path, &chain_id).unwrap();
+
+        store
+            .insert(&light_block(&chain_id, 10), NO_EXPIRY)
+            .unwrap();
+        assert!(other.get(10).unwrap().is_some());
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn insert_prunes_expired_blocks() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let chain_id = ChainId::new("chain".to_string(), 0);
+        let store = LightBlockStore::new(db, &chain_id);
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let trusting_period = Duration::from_secs(3600);
+
+        // The first block is expired, but is only pruned once a higher block is inserted.
+        store
+            .insert(&light_block_at(&chain_id, 10, now - 7200), trusting_period)
+            .unwrap();
+        assert!(store.get(10).unwrap().is_some());
+
+        store
+            .insert(&light_block_at(&chain_id, 20, now - 60), trusting_period)
+            .unwrap();
+        store
+            .insert(&light_block_at(&chain_id, 30, now), trusting_period)
+            .unwrap();
+
+        let heights = [10, 20, 30]
+            .iter()
+            .filter(|height| store.get(**height).unwrap().is_some())
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(heights, vec![20, 30]);
+    }
+}
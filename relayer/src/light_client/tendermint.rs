@@ -1,6 +1,7 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, sync::Arc, time::Duration};
 
 use itertools::Itertools;
+use tokio::runtime::Runtime as TokioRuntime;
 
 use tendermint_light_client::{
     components::{self, io::AtHeight},
@@ -11,7 +12,7 @@ use tendermint_light_client::{
     types::Height as TMHeight,
     types::{LightBlock, PeerId, Status},
 };
-use tendermint_rpc as rpc;
+use tendermint_rpc::{self as rpc, Client};
 
 use ibc::{
     downcast,
@@ -28,7 +29,7 @@ use ibc::{
     },
     ics24_host::identifier::ChainId,
 };
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::error::Kind;
 use crate::{
@@ -37,12 +38,21 @@ use crate::{
     error::{self, Error},
 };
 
+use super::store::{within_trusting_period, LightBlockStore};
 use super::Verified;
 
 pub struct LightClient {
     chain_id: ChainId,
+    /// The RPC endpoints of the chain, in order of preference
+    rpc_addrs: Vec<rpc::Url>,
+    /// The index in `rpc_addrs` of the node whose headers are verified
+    active: usize,
     peer_id: PeerId,
     io: components::io::ProdIo,
+    rpc_timeout: Duration,
+    rt: Arc<TokioRuntime>,
+    /// The store of the blocks verified so far, if it could be opened.
+    store: Option<LightBlockStore>,
 }
 
 impl super::LightClient<CosmosSdkChain> for LightClient {
@@ -63,31 +73,7 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
         target: ibc::Height,
         client_state: &AnyClientState,
     ) -> Result<Verified<LightBlock>, Error> {
-        trace!(%trusted, %target, "light client verification");
-
-        let target_height = TMHeight::try_from(target.revision_height)
-            .map_err(|e| error::Kind::InvalidHeight.context(e))?;
-
-        let client = self.prepare_client(client_state)?;
-        let mut state = self.prepare_state(trusted)?;
-
-        // Verify the target header
-        let target = client
-            .verify_to_target(target_height, &mut state)
-            .map_err(|e| error::Kind::LightClient(self.chain_id.to_string()).context(e))?;
-
-        // Collect the verification trace for the target block
-        let target_trace = state.get_trace(target.height());
-
-        // Compute the minimal supporting set, sorted by ascending height
-        let supporting = target_trace
-            .into_iter()
-            .filter(|lb| lb.height() != target.height())
-            .unique_by(LightBlock::height)
-            .sorted_by_key(LightBlock::height)
-            .collect_vec();
-
-        Ok(Verified { target, supporting })
+        self.failover(|light_client| light_client.verify_with_active(trusted, target, client_state))
     }
 
     fn fetch(&mut self, height: ibc::Height) -> Result<LightBlock, Error> {
@@ -96,7 +82,7 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
         let height = TMHeight::try_from(height.revision_height)
             .map_err(|e| error::Kind::InvalidHeight.context(e))?;
 
-        self.fetch_light_block(AtHeight::At(height))
+        self.failover(|light_client| light_client.fetch_light_block(AtHeight::At(height)))
     }
 
     /// Given a client update event that includes the header used in a client update,
@@ -125,7 +111,8 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
             ))
         })?;
 
-        let latest_chain_block = self.fetch_light_block(AtHeight::Highest)?;
+        let latest_chain_block =
+            self.failover(|light_client| light_client.fetch_light_block(AtHeight::Highest))?;
         let latest_chain_height =
             ibc::Height::new(self.chain_id.version(), latest_chain_block.height().into());
 
@@ -170,25 +157,138 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
 }
 
 impl LightClient {
-    pub fn from_config(config: &ChainConfig, peer_id: PeerId) -> Result<Self, Error> {
-        let rpc_client = rpc::HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| error::Kind::LightClient(config.rpc_addr.to_string()).context(e))?;
+    /// Creates a light client of the chain which verifies the headers of the full node at
+    /// `rpc_addr`, one of the RPC endpoints of the chain, whose peer id is `peer_id`. If that
+    /// node fails, the light client fails over to the nodes of the other RPC endpoints.
+    pub fn from_config(
+        config: &ChainConfig,
+        rt: Arc<TokioRuntime>,
+        rpc_addr: &rpc::Url,
+        peer_id: PeerId,
+    ) -> Result<Self, Error> {
+        let rpc_client = rpc::HttpClient::new(rpc_addr.clone())
+            .map_err(|e| error::Kind::LightClient(rpc_addr.to_string()).context(e))?;
 
         let io = components::io::ProdIo::new(peer_id, rpc_client, Some(config.rpc_timeout));
 
+        let rpc_addrs = config.rpc_addrs();
+        let active = rpc_addrs
+            .iter()
+            .position(|addr| addr == rpc_addr)
+            .unwrap_or_default();
+
+        let store = LightBlockStore::open_default(&config.id)
+            .map_err(|e| {
+                warn!(
+                    "[{}] failed to open the light block store, which another process may be using; verified blocks will not be persisted: {}",
+                    config.id, e
+                )
+            })
+            .ok();
+
         Ok(Self {
             chain_id: config.id.clone(),
+            rpc_addrs,
+            active,
             peer_id,
             io,
+            rpc_timeout: config.rpc_timeout,
+            rt,
+            store,
         })
     }
 
-    fn prepare_client(&self, client_state: &AnyClientState) -> Result<TmLightClient, Error> {
-        let clock = components::clock::SystemClock;
-        let hasher = operations::hasher::ProdHasher;
-        let verifier = components::verifier::ProdVerifier::default();
-        let scheduler = components::scheduler::basic_bisecting_schedule;
+    /// Runs `f` with the node of the active RPC endpoint and, if it fails, with the nodes of the
+    /// other RPC endpoints in turn. The first node with which it succeeds becomes the active one.
+    ///
+    /// Returns the error of the last node if `f` fails with all of them.
+    fn failover<R>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let first = self.active;
+        let mut result = f(self);
+
+        for offset in 1..self.rpc_addrs.len() {
+            let e = match result {
+                Ok(r) => return Ok(r),
+                Err(e) => e,
+            };
+
+            let next = (first + offset) % self.rpc_addrs.len();
+            warn!(
+                "[{}] light client failed with the node at {}, failing over to {}: {}",
+                self.chain_id, self.rpc_addrs[self.active], self.rpc_addrs[next], e
+            );
+
+            result = self.activate(next).and_then(|_| f(self));
+        }
+
+        result
+    }
+
+    /// Makes the node of the RPC endpoint at `index` the active one, after querying its peer id.
+    fn activate(&mut self, index: usize) -> Result<(), Error> {
+        let rpc_addr = &self.rpc_addrs[index];
+
+        let rpc_client = rpc::HttpClient::new(rpc_addr.clone())
+            .map_err(|e| error::Kind::LightClient(rpc_addr.to_string()).context(e))?;
+
+        let peer_id = self
+            .rt
+            .block_on(rpc_client.status())
+            .map_err(|e| error::Kind::LightClient(rpc_addr.to_string()).context(e))?
+            .node_info
+            .id;
+
+        self.io = components::io::ProdIo::new(peer_id, rpc_client, Some(self.rpc_timeout));
+        self.peer_id = peer_id;
+        self.active = index;
+
+        Ok(())
+    }
+
+    fn verify_with_active(
+        &mut self,
+        trusted: ibc::Height,
+        target: ibc::Height,
+        client_state: &AnyClientState,
+    ) -> Result<Verified<LightBlock>, Error> {
+        trace!(%trusted, %target, "light client verification");
+
+        let target_height = TMHeight::try_from(target.revision_height)
+            .map_err(|e| error::Kind::InvalidHeight.context(e))?;
+
+        let options = self.prepare_options(client_state)?;
+        let client = self.prepare_client(options);
+        let (mut state, root) = self.prepare_state(trusted, target, &options)?;
+
+        // Verify the target header
+        let target = client
+            .verify_to_target(target_height, &mut state)
+            .map_err(|e| error::Kind::LightClient(self.chain_id.to_string()).context(e))?;
+
+        // Persist the blocks verified along the way, to start from them next time
+        self.store_blocks(state.light_store.all(Status::Verified), &options);
+
+        // Collect the verification trace for the target block
+        let target_trace = state.get_trace(target.height());
 
+        // Compute the minimal supporting set, sorted by ascending height. If the bisection
+        // started from a stored block above the trusted height, the client must first be
+        // updated with that block.
+        let supporting = target_trace
+            .into_iter()
+            .chain(root)
+            .filter(|lb| lb.height() != target.height())
+            .unique_by(LightBlock::height)
+            .sorted_by_key(LightBlock::height)
+            .collect_vec();
+
+        Ok(Verified { target, supporting })
+    }
+
+    fn prepare_options(&self, client_state: &AnyClientState) -> Result<TmOptions, Error> {
         let client_state =
             downcast!(client_state => AnyClientState::Tendermint).ok_or_else(|| {
                 error::Kind::ClientTypeMismatch {
@@ -197,13 +297,20 @@ impl LightClient {
                 }
             })?;
 
-        let params = TmOptions {
+        Ok(TmOptions {
             trust_threshold: client_state.trust_level,
             trusting_period: client_state.trusting_period,
             clock_drift: client_state.max_clock_drift,
-        };
+        })
+    }
 
-        Ok(TmLightClient::new(
+    fn prepare_client(&self, params: TmOptions) -> TmLightClient {
+        let clock = components::clock::SystemClock;
+        let hasher = operations::hasher::ProdHasher;
+        let verifier = components::verifier::ProdVerifier::default();
+        let scheduler = components::scheduler::basic_bisecting_schedule;
+
+        TmLightClient::new(
             self.peer_id,
             params,
             clock,
@@ -211,19 +318,108 @@ impl LightClient {
             verifier,
             hasher,
             self.io.clone(),
-        ))
+        )
     }
 
-    fn prepare_state(&self, trusted: ibc::Height) -> Result<LightClientState, Error> {
+    /// Prepares the light client state for the verification of the block at height `target`,
+    /// from the block at height `trusted`. If a block between these heights was verified before,
+    /// and can be verified from the trusted block in a single step, the bisection starts from
+    /// the closest such block, which is then returned along with the state.
+    fn prepare_state(
+        &self,
+        trusted: ibc::Height,
+        target: ibc::Height,
+        options: &TmOptions,
+    ) -> Result<(LightClientState, Option<LightBlock>), Error> {
         let trusted_height = TMHeight::try_from(trusted.revision_height)
             .map_err(|e| error::Kind::InvalidHeight.context(e))?;
 
-        let trusted_block = self.fetch_light_block(AtHeight::At(trusted_height))?;
+        let trusted_block = match self.stored_block(trusted.revision_height) {
+            Some(block) => block,
+            None => {
+                let block = self.fetch_light_block(AtHeight::At(trusted_height))?;
+                self.store_blocks(std::iter::once(block.clone()), options);
+                block
+            }
+        };
+
+        let root = self
+            .closest_stored_block(trusted.revision_height + 1, target.revision_height, options)
+            .filter(|block| verifies(block, &trusted_block, options));
 
         let mut store = MemoryStore::new();
         store.insert(trusted_block, Status::Trusted);
+        if let Some(block) = &root {
+            trace!(height = %block.height(), "starting bisection from stored block");
+            store.insert(block.clone(), Status::Trusted);
+        }
+
+        Ok((LightClientState::new(store), root))
+    }
+
+    fn stored_block(&self, height: u64) -> Option<LightBlock> {
+        let store = self.store.as_ref()?;
+
+        store.get(height).unwrap_or_else(|e| {
+            warn!(
+                "[{}] failed to read the light block store: {}",
+                self.chain_id, e
+            );
+            None
+        })
+    }
+
+    /// Returns the stored block of highest height between `min_height` and `max_height`, if it
+    /// is still within the trusting period. Otherwise, it is pruned along with the lower blocks.
+    fn closest_stored_block(
+        &self,
+        min_height: u64,
+        max_height: u64,
+        options: &TmOptions,
+    ) -> Option<LightBlock> {
+        use tendermint_light_client::components::clock::Clock;
+
+        let store = self.store.as_ref()?;
+
+        let block = match store.closest(min_height, max_height) {
+            Ok(block) => block?,
+            Err(e) => {
+                warn!(
+                    "[{}] failed to read the light block store: {}",
+                    self.chain_id, e
+                );
+                return None;
+            }
+        };
+
+        let now = components::clock::SystemClock.now();
+        if within_trusting_period(&block, now, options.trusting_period) {
+            return Some(block);
+        }
 
-        Ok(LightClientState::new(store))
+        // The lower blocks are older, hence expired as well.
+        if let Err(e) = store.prune(block.height().value()) {
+            warn!(
+                "[{}] failed to prune the light block store: {}",
+                self.chain_id, e
+            );
+        }
+        None
+    }
+
+    /// Persists the given verified blocks, pruning the stored blocks which are outside the
+    /// trusting period along the way.
+    fn store_blocks(&self, blocks: impl Iterator<Item = LightBlock>, options: &TmOptions) {
+        if let Some(store) = &self.store {
+            for block in blocks {
+                if let Err(e) = store.insert(&block, options.trusting_period) {
+                    warn!(
+                        "[{}] failed to write to the light block store: {}",
+                        self.chain_id, e
+                    );
+                }
+            }
+        }
     }
 
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
@@ -301,3 +497,16 @@ impl LightClient {
         Ok((target_header, supporting_headers))
     }
 }
+
+/// Returns true if the `untrusted` block can be verified from the `trusted` one in a single step.
+fn verifies(untrusted: &LightBlock, trusted: &LightBlock, options: &TmOptions) -> bool {
+    use tendermint_light_client::components::{clock::Clock, verifier::Verifier};
+
+    let verifier = components::verifier::ProdVerifier::default();
+    let now = components::clock::SystemClock.now();
+
+    matches!(
+        verifier.verify(untrusted, trusted, options, now),
+        components::verifier::Verdict::Success
+    )
+}
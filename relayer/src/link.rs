@@ -1,5 +1,4 @@
 use ibc::{
-    events::IbcEvent,
     ics03_connection::connection::State as ConnectionState,
     ics04_channel::channel::State as ChannelState,
     ics24_host::identifier::{ChannelId, PortChannelId, PortId},
@@ -13,12 +12,16 @@ use crate::link::error::LinkError;
 use crate::link::relay_path::RelayPath;
 
 mod error;
+mod journal;
 mod operational_data;
 mod relay_path;
 mod relay_summary;
+mod sequences;
 
+pub use journal::RelayJournal;
 // Re-export the telemetries summary
-pub use relay_summary::RelaySummary;
+pub use relay_summary::{BatchSummary, BatchTimings, FailedMsg, PacketOutcome, RelaySummary};
+pub use sequences::SequenceRanges;
 
 #[derive(Clone, Debug)]
 pub struct LinkParameters {
@@ -146,6 +149,12 @@ impl Link {
             )));
         }
 
+        // The transactions of the link to each chain relay from the other chain
+        let (a_chain, b_chain) = (
+            a_chain.with_counterparty(b_chain.id()),
+            b_chain.with_counterparty(a_chain.id()),
+        );
+
         let channel = Channel {
             ordering: Default::default(),
             a_side: ChannelSide::new(
@@ -169,31 +178,34 @@ impl Link {
         Ok(Link::new(channel))
     }
 
-    pub fn build_and_send_recv_packet_messages(&mut self) -> Result<Vec<IbcEvent>, LinkError> {
+    /// Returns the link relaying in the other direction, from the counterparty channel.
+    pub fn reversed(&self) -> Link {
+        Link::new(self.a_to_b.channel().flipped())
+    }
+
+    pub fn build_and_send_recv_packet_messages(&mut self) -> Result<RelaySummary, LinkError> {
         self.a_to_b.build_recv_packet_and_timeout_msgs(None)?;
 
-        let mut results = vec![];
+        let mut summary = RelaySummary::empty();
 
         // Block waiting for all of the scheduled data (until `None` is returned)
         while let Some(odata) = self.a_to_b.fetch_scheduled_operational_data() {
-            let mut last_res = self.a_to_b.relay_from_operational_data(odata)?;
-            results.append(&mut last_res.events);
+            summary.extend(self.a_to_b.relay_from_operational_data(odata)?);
         }
 
-        Ok(results)
+        Ok(summary)
     }
 
-    pub fn build_and_send_ack_packet_messages(&mut self) -> Result<Vec<IbcEvent>, LinkError> {
+    pub fn build_and_send_ack_packet_messages(&mut self) -> Result<RelaySummary, LinkError> {
         self.a_to_b.build_packet_ack_msgs(None)?;
 
-        let mut results = vec![];
+        let mut summary = RelaySummary::empty();
 
         // Block waiting for all of the scheduled data
         while let Some(odata) = self.a_to_b.fetch_scheduled_operational_data() {
-            let mut last_res = self.a_to_b.relay_from_operational_data(odata)?;
-            results.append(&mut last_res.events);
+            summary.extend(self.a_to_b.relay_from_operational_data(odata)?);
         }
 
-        Ok(results)
+        Ok(summary)
     }
 }
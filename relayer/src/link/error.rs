@@ -46,4 +46,7 @@ pub enum LinkError {
 
     #[error("chain error when sending messages: {0}")]
     SendError(Box<IbcEvent>),
+
+    #[error("invalid packet sequences '{0}': {1}")]
+    InvalidSequences(String, String),
 }
@@ -0,0 +1,298 @@
+//! A persistent journal of the operational data scheduled on a relaying path, and of the height
+//! of the last batch of events processed on it. The journal is kept on disk, so that a relaying
+//! path which restarts, e.g., after a crash, resumes from the data it had scheduled instead of
+//! losing it.
+
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+
+use prost_types::Any;
+use serde_derive::{Deserialize, Serialize};
+
+use ibc::events::IbcEvent;
+use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::Height;
+
+use crate::error::{self, Error};
+use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+use crate::util::sled::{self as sled_util, KeyValueDb, SingleDb};
+
+pub const RELAY_JOURNAL_DEFAULT_FOLDER: &str = ".hermes/relay_journal/";
+
+/// An operational data, as it is recorded in the journal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    proofs_height: Height,
+    target: OperationalDataTarget,
+    batch: Vec<JournalMessage>,
+    /// The time at which the operational data was scheduled.
+    /// Unlike an `Instant`, it is still meaningful after a restart.
+    scheduled_at: SystemTime,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct JournalMessage {
+    event: IbcEvent,
+    type_url: String,
+    value: Vec<u8>,
+}
+
+impl JournalEntry {
+    fn new(od: &OperationalData) -> Self {
+        let batch = od
+            .batch
+            .iter()
+            .map(|tm| JournalMessage {
+                event: tm.event.clone(),
+                type_url: tm.msg.type_url.clone(),
+                value: tm.msg.value.clone(),
+            })
+            .collect();
+
+        Self {
+            proofs_height: od.proofs_height,
+            target: od.target,
+            batch,
+            scheduled_at: SystemTime::now() - od.scheduled_time.elapsed(),
+        }
+    }
+
+    fn into_operational_data(self, journal_id: u64) -> OperationalData {
+        let mut od = OperationalData::new(self.proofs_height, self.target);
+
+        od.batch = self
+            .batch
+            .into_iter()
+            .map(|m| TransitMessage {
+                event: m.event,
+                msg: Any {
+                    type_url: m.type_url,
+                    value: m.value,
+                },
+            })
+            .collect();
+
+        // The delay of the data elapses from the time at which it was scheduled,
+        // including the time during which the relayer was not running
+        let elapsed = SystemTime::now()
+            .duration_since(self.scheduled_at)
+            .unwrap_or_default();
+        od.scheduled_time = Instant::now()
+            .checked_sub(elapsed)
+            .unwrap_or_else(Instant::now);
+        od.journal_id = Some(journal_id);
+
+        od
+    }
+}
+
+/// A disk-backed journal of the operational data scheduled on the relaying path from a channel,
+/// indexed by the order in which it was scheduled.
+#[derive(Clone, Debug)]
+pub struct RelayJournal {
+    db: sled::Db,
+    operational_data: KeyValueDb<u64, JournalEntry>,
+    last_height: SingleDb<Height>,
+}
+
+impl RelayJournal {
+    /// Opens (or creates) the journal of the relaying path from the channel `channel_id` on
+    /// port `port_id` of chain `chain_id` in the default folder.
+    pub fn open_default(
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Self, Error> {
+        Self::open(
+            default_journal_path(chain_id, port_id, channel_id)?,
+            chain_id,
+            port_id,
+            channel_id,
+        )
+    }
+
+    /// Opens (or creates) the journal of the relaying path from the channel `channel_id` on
+    /// port `port_id` of chain `chain_id` at `path`.
+    pub fn open(
+        path: impl AsRef<Path>,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Self, Error> {
+        let db = sled::open(path).map_err(|e| error::Kind::Store.context(e))?;
+        Ok(Self::new(db, chain_id, port_id, channel_id))
+    }
+
+    /// Creates the journal of the relaying path from the channel `channel_id` on
+    /// port `port_id` of chain `chain_id` in the given database.
+    pub fn new(db: sled::Db, chain_id: &ChainId, port_id: &PortId, channel_id: &ChannelId) -> Self {
+        let prefix = format!("relay_journal/{}/{}/{}/", chain_id, port_id, channel_id);
+
+        Self {
+            db,
+            operational_data: sled_util::key_value(format!("{}operational_data/", prefix)),
+            last_height: sled_util::single(format!("{}last_height", prefix)),
+        }
+    }
+
+    /// Records a newly scheduled operational data, and returns its key in the journal.
+    ///
+    /// The keys are generated by the database, so that they keep increasing across restarts
+    /// and are never reused, even once the newest data is removed.
+    pub fn record(&self, od: &OperationalData) -> Result<u64, Error> {
+        let journal_id = self
+            .db
+            .generate_id()
+            .map_err(|e| error::Kind::Store.context(e))?;
+
+        self.operational_data
+            .insert(&self.db, &journal_id, &JournalEntry::new(od))?;
+        self.flush()?;
+
+        Ok(journal_id)
+    }
+
+    /// Replaces the operational data recorded at `journal_id`, e.g., once some of its messages
+    /// are no longer relevant.
+    pub fn update(&self, journal_id: u64, od: &OperationalData) -> Result<(), Error> {
+        self.operational_data
+            .insert(&self.db, &journal_id, &JournalEntry::new(od))?;
+        self.flush()
+    }
+
+    /// Removes the operational data recorded at `journal_id`, once it is relayed or discarded.
+    pub fn remove(&self, journal_id: u64) -> Result<(), Error> {
+        self.operational_data.remove(&self.db, &journal_id)?;
+        self.flush()
+    }
+
+    /// Returns all the recorded operational data, from the oldest to the newest.
+    pub fn operational_data(&self) -> Result<Vec<OperationalData>, Error> {
+        let mut journal_ids = self.operational_data.keys(&self.db)?;
+        journal_ids.sort_unstable();
+
+        let mut ods = vec![];
+        for journal_id in journal_ids {
+            if let Some(entry) = self.operational_data.fetch(&self.db, &journal_id)? {
+                ods.push(entry.into_operational_data(journal_id));
+            }
+        }
+        Ok(ods)
+    }
+
+    /// Returns the height of the last batch of events processed on the path, if any.
+    pub fn last_height(&self) -> Result<Option<Height>, Error> {
+        self.last_height.get(&self.db)
+    }
+
+    /// Records the height of the last batch of events processed on the path.
+    pub fn set_last_height(&self, height: Height) -> Result<(), Error> {
+        self.last_height.set(&self.db, &height)?;
+        self.flush()
+    }
+
+    fn flush(&self) -> Result<(), Error> {
+        self.db
+            .flush()
+            .map(|_| ())
+            .map_err(|e| error::Kind::Store.context(e).into())
+    }
+}
+
+fn default_journal_path(
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<PathBuf, Error> {
+    let home = dirs_next::home_dir()
+        .ok_or_else(|| error::Kind::Store.context("cannot retrieve home folder location"))?;
+
+    Ok(home
+        .join(RELAY_JOURNAL_DEFAULT_FOLDER)
+        .join(chain_id.as_str())
+        .join(port_id.as_str())
+        .join(channel_id.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::{Duration, Instant};
+
+    use prost_types::Any;
+    use test_env_log::test;
+
+    use ibc::events::IbcEvent;
+    use ibc::ics02_client::events::NewBlock;
+    use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc::Height;
+
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+
+    use super::RelayJournal;
+
+    fn operational_data(height: u64, target: OperationalDataTarget) -> OperationalData {
+        let mut od = OperationalData::new(Height::new(0, height), target);
+        od.batch.push(TransitMessage {
+            event: IbcEvent::NewBlock(NewBlock::new(Height::new(0, height))),
+            msg: Any {
+                type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+                value: vec![height as u8],
+            },
+        });
+        od
+    }
+
+    #[test]
+    fn resume_scheduled_operational_data() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let chain_id = ChainId::new("chain_a".to_string(), 0);
+        let port_id = PortId::from_str("transfer").unwrap();
+        let (channel_0, channel_1) = (
+            ChannelId::from_str("channel-0").unwrap(),
+            ChannelId::from_str("channel-1").unwrap(),
+        );
+        let journal = RelayJournal::new(db.clone(), &chain_id, &port_id, &channel_0);
+        let other_journal = RelayJournal::new(db.clone(), &chain_id, &port_id, &channel_1);
+
+        let mut od = operational_data(10, OperationalDataTarget::Destination);
+        od.scheduled_time = Instant::now() - Duration::from_secs(60);
+        let first = journal.record(&od).unwrap();
+        let second = journal
+            .record(&operational_data(11, OperationalDataTarget::Source))
+            .unwrap();
+        journal.set_last_height(Height::new(0, 12)).unwrap();
+        other_journal
+            .record(&operational_data(20, OperationalDataTarget::Destination))
+            .unwrap();
+
+        // A journal opened again on the same database resumes the recorded data
+        let journal = RelayJournal::new(db, &chain_id, &port_id, &channel_0);
+        let ods = journal.operational_data().unwrap();
+        assert_eq!(ods.len(), 2);
+        assert_eq!(ods[0].journal_id, Some(first));
+        assert_eq!(ods[0].proofs_height, Height::new(0, 10));
+        assert!(ods[0].target == OperationalDataTarget::Destination);
+        assert_eq!(ods[0].batch[0].msg.value, vec![10]);
+        assert!(ods[0].scheduled_time.elapsed() >= Duration::from_secs(60));
+        assert!(ods[1].target == OperationalDataTarget::Source);
+        assert_eq!(journal.last_height().unwrap(), Some(Height::new(0, 12)));
+        assert_eq!(other_journal.last_height().unwrap(), None);
+
+        journal.remove(first).unwrap();
+        let mut od = journal.operational_data().unwrap().remove(0);
+        od.batch.clear();
+        journal.update(second, &od).unwrap();
+        let ods = journal.operational_data().unwrap();
+        assert_eq!(ods.len(), 1);
+        assert!(ods[0].batch.is_empty());
+        assert_eq!(other_journal.operational_data().unwrap().len(), 1);
+
+        // The key of removed data is not reused, so newer data is still resumed last
+        journal.remove(second).unwrap();
+        let third = journal
+            .record(&operational_data(13, OperationalDataTarget::Source))
+            .unwrap();
+        assert!(third > second);
+    }
+}
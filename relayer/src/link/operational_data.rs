@@ -1,16 +1,18 @@
 use std::fmt;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use prost_types::Any;
+use serde_derive::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use ibc::events::IbcEvent;
+use ibc::ics04_channel::packet::Sequence;
 use ibc::Height;
 
 use crate::link::error::LinkError;
 use crate::link::RelayPath;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum OperationalDataTarget {
     Source,
     Destination,
@@ -33,6 +35,18 @@ pub struct TransitMessage {
     pub msg: Any,
 }
 
+impl TransitMessage {
+    /// The sequence of the packet of the send packet or write acknowledgement event
+    /// which generated the message.
+    pub fn packet_sequence(&self) -> Option<Sequence> {
+        match &self.event {
+            IbcEvent::SendPacket(e) => Some(e.packet.sequence),
+            IbcEvent::WriteAcknowledgement(e) => Some(e.packet.sequence),
+            _ => None,
+        }
+    }
+}
+
 /// Holds all the necessary information for handling a set of in-transit messages.
 ///
 /// Each `OperationalData` item is uniquely identified by the combination of two attributes:
@@ -48,6 +62,10 @@ pub struct OperationalData {
     /// Stores the time when the clients on the target chain has been updated, i.e., when this data
     /// was scheduled. Necessary for packet delays.
     pub scheduled_time: Instant,
+    /// Time spent querying the proofs and building the messages of the batch
+    pub query_time: Duration,
+    /// The key of this data in the journal of the relaying path, once it is recorded there.
+    pub journal_id: Option<u64>,
 }
 
 impl OperationalData {
@@ -57,6 +75,8 @@ impl OperationalData {
             batch: vec![],
             target,
             scheduled_time: Instant::now(),
+            query_time: Duration::default(),
+            journal_id: None,
         }
     }
 
@@ -64,6 +84,14 @@ impl OperationalData {
         self.batch.iter().map(|gm| gm.event.clone()).collect()
     }
 
+    /// The sequences of the packets of the messages in this operational data.
+    pub fn packet_sequences(&self) -> Vec<Sequence> {
+        self.batch
+            .iter()
+            .filter_map(TransitMessage::packet_sequence)
+            .collect()
+    }
+
     /// Returns all the messages in this operational data, plus prepending the client update message
     /// if necessary.
     pub fn assemble_msgs(&self, relay_path: &RelayPath) -> Result<Vec<Any>, LinkError> {
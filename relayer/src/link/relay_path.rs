@@ -4,7 +4,7 @@ use std::{fmt, thread};
 
 use itertools::Itertools;
 use prost_types::Any;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use ibc::{
     downcast,
@@ -35,8 +35,10 @@ use crate::channel::{Channel, ChannelError};
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
 use crate::link::error::LinkError;
+use crate::link::journal::RelayJournal;
 use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
-use crate::link::relay_summary::RelaySummary;
+use crate::link::relay_summary::{BatchSummary, RelaySummary};
+use crate::link::sequences::SequenceRanges;
 
 const MAX_RETRIES: usize = 5;
 
@@ -53,6 +55,16 @@ pub struct RelayPath {
     src_operational_data: Vec<OperationalData>,
     // The operational data targeting the destination chain comprises mostly RecvPacket and Ack msgs.
     dst_operational_data: Vec<OperationalData>,
+    // The journal in which the scheduled operational data is recorded, if any, so that
+    // it survives a restart of the relayer.
+    journal: Option<RelayJournal>,
+    // The height of the last batch of events processed on this path, if known.
+    last_processed_height: Option<Height>,
+    // Marks whether the transactions of this path are only simulated, to estimate their gas
+    // and fee, instead of being signed and broadcast.
+    dry_run: bool,
+    // The sequences to which the relaying of pending packets is restricted, if any.
+    sequence_filter: Option<SequenceRanges>,
 }
 
 impl RelayPath {
@@ -62,9 +74,78 @@ impl RelayPath {
             clear_packets: true,
             src_operational_data: vec![],
             dst_operational_data: vec![],
+            journal: None,
+            last_processed_height: None,
+            dry_run: false,
+            sequence_filter: None,
         }
     }
 
+    /// Resumes the operational data scheduled on this path before a restart from the `journal`,
+    /// and records the data scheduled from now on in it.
+    ///
+    /// The messages whose packets were handled in the meantime are dropped, so that they are
+    /// not submitted again. So are the messages which were not generated by a packet event,
+    /// e.g., client updates, since whether they were handled cannot be told; they are derived
+    /// again from the events of the chains, if still needed.
+    pub fn restore_from_journal(&mut self, journal: RelayJournal) -> Result<(), LinkError> {
+        self.last_processed_height = journal.last_height()?;
+
+        for mut od in journal.operational_data()? {
+            let journal_id = od
+                .journal_id
+                .expect("operational data from the journal has a key");
+
+            let initial_len = od.batch.len();
+            let mut batch = vec![];
+            for tm in std::mem::take(&mut od.batch) {
+                if tm.packet_sequence().is_none() {
+                    debug!(
+                        "[{}] restoring schedule: dropping {}, not generated by a packet",
+                        self, tm.event
+                    );
+                } else if self.transit_message_handled(&tm)? {
+                    debug!(
+                        "[{}] restoring schedule: already handled {}",
+                        self, tm.event
+                    );
+                } else {
+                    batch.push(tm);
+                }
+            }
+
+            od.batch = batch;
+
+            if od.batch.is_empty() {
+                journal.remove(journal_id)?;
+                continue;
+            } else if od.batch.len() != initial_len {
+                journal.update(journal_id, &od)?;
+            }
+
+            info!(
+                "[{}] resuming op. data with {} msg(s) for {} (height {}) from the journal",
+                self,
+                od.batch.len(),
+                od.target,
+                od.proofs_height.increment(),
+            );
+
+            match od.target {
+                OperationalDataTarget::Source => self.src_operational_data.push(od),
+                OperationalDataTarget::Destination => self.dst_operational_data.push(od),
+            };
+        }
+
+        if let Some(height) = self.last_processed_height {
+            info!("[{}] resuming after events at height {}", self, height);
+        }
+
+        self.journal = Some(journal);
+
+        Ok(())
+    }
+
     #[allow(clippy::borrowed_box)]
     pub fn src_chain(&self) -> &Box<dyn ChainHandle> {
         self.channel.src_chain()
@@ -301,11 +382,29 @@ impl RelayPath {
             self.clear_packets = false;
         }
 
+        // Skip the events which were processed before a restart.
+        if let Some(last_height) = self.last_processed_height {
+            if batch.height <= last_height {
+                debug!(
+                    "[{}] ignoring events at height {}, already processed",
+                    self, batch.height
+                );
+                return Ok(());
+            }
+        }
+
         // Collect relevant events from the incoming batch & adjust their height.
         let events = self.filter_events(&batch.events);
 
         // Transform the events into operational data items
-        self.events_to_operational_data(events)
+        self.events_to_operational_data(events)?;
+
+        if let Some(journal) = &self.journal {
+            journal.set_last_height(batch.height)?;
+        }
+        self.last_processed_height = Some(batch.height);
+
+        Ok(())
     }
 
     /// Produces and schedules operational data for this relaying path based on the input events.
@@ -356,6 +455,7 @@ impl RelayPath {
 
         for event in input {
             debug!("[{}] {} => {}", self, self.src_chain().id(), event);
+            let start = Instant::now();
             let (dst_msg, src_msg) = match event {
                 IbcEvent::CloseInitChannel(_) => (
                     Some(self.build_chan_close_confirm_from_event(&event)?),
@@ -406,6 +506,7 @@ impl RelayPath {
                 }
                 _ => (None, None),
             };
+            let query_time = start.elapsed();
 
             // Collect messages to be sent to the destination chain (e.g., RecvPacket)
             if let Some(msg) = dst_msg {
@@ -420,6 +521,7 @@ impl RelayPath {
                     event: event.clone(),
                     msg,
                 });
+                dst_od.query_time += query_time;
             }
 
             // Collect timeout messages, to be sent to the source chain
@@ -435,6 +537,7 @@ impl RelayPath {
                         event
                     );
                     src_od.batch.push(TransitMessage { event, msg });
+                    src_od.query_time += query_time;
                 }
             }
         }
@@ -458,9 +561,28 @@ impl RelayPath {
     pub(crate) fn relay_from_operational_data(
         &mut self,
         initial_od: OperationalData,
+    ) -> Result<RelaySummary, LinkError> {
+        let journal_id = initial_od.journal_id;
+
+        let summary = self.try_relay_from_operational_data(initial_od)?;
+
+        // The data is either relayed or discarded, so it is no longer scheduled. Upon an error,
+        // it stays in the journal, and is resumed (or dropped if handled) after a restart.
+        if let (Some(journal), Some(journal_id)) = (&self.journal, journal_id) {
+            journal.remove(journal_id)?;
+        }
+
+        Ok(summary)
+    }
+
+    fn try_relay_from_operational_data(
+        &mut self,
+        initial_od: OperationalData,
     ) -> Result<RelaySummary, LinkError> {
         // We will operate on potentially different operational data if the initial one fails.
         let mut odata = initial_od;
+        // The summary of all the attempts
+        let mut summary = RelaySummary::empty();
 
         for i in 0..MAX_RETRIES {
             info!(
@@ -475,8 +597,8 @@ impl RelayPath {
             );
 
             // Consume the operational data by attempting to send its messages
-            match self.send_from_operational_data(odata.clone()) {
-                Ok(summary) => {
+            match self.send_from_operational_data(odata.clone(), &mut summary) {
+                Ok(()) => {
                     // Done with this op. data
                     info!("[{}] success", self);
 
@@ -495,7 +617,7 @@ impl RelayPath {
                     } else {
                         // If we haven't exhausted all retries, regenerate the op. data & retry
                         match self.regenerate_operational_data(odata.clone()) {
-                            None => return Ok(RelaySummary::empty()), // Nothing to retry
+                            None => return Ok(summary), // Nothing to retry
                             Some(new_od) => odata = new_od,
                         }
                     }
@@ -507,10 +629,10 @@ impl RelayPath {
             }
         }
 
-        Ok(RelaySummary::empty())
+        Ok(summary)
     }
 
-    /// Helper for managing retries of the `relay_from_operational_data` method.
+    /// Helper for managing retries of the `try_relay_from_operational_data` method.
     /// Expects as input the initial operational data that failed to send.
     ///
     /// Return value:
@@ -590,14 +712,18 @@ impl RelayPath {
     /// Sends a transaction to the chain targeted by the operational data `odata`.
     /// If the transaction generates an error, returns the error as well as  `LinkError::SendError`
     /// if  input events if a sending failure occurs.
-    /// Returns the events generated by the target chain upon success.
+    /// Records the summary of the batch in `summary`, with the events generated by the target
+    /// chain for the transactions which succeeded. If the messages cannot be built or sent at
+    /// all, the batch is recorded with all its messages failed, before the error is returned.
+    /// In a dry run, only simulates the transaction, and records its estimates in `summary`.
     fn send_from_operational_data(
         &mut self,
         odata: OperationalData,
-    ) -> Result<RelaySummary, LinkError> {
+        summary: &mut RelaySummary,
+    ) -> Result<(), LinkError> {
         if odata.batch.is_empty() {
             error!("[{}] ignoring empty operational data!", self);
-            return Ok(RelaySummary::empty());
+            return Ok(());
         }
 
         let target = match odata.target {
@@ -605,19 +731,76 @@ impl RelayPath {
             OperationalDataTarget::Destination => self.dst_chain(),
         };
 
-        let msgs = odata.assemble_msgs(self)?;
+        let mut batch = BatchSummary::new(target.id(), &odata);
 
-        let tx_events = target.send_msgs(msgs)?;
-        info!("[{}] result {}\n", self, PrettyEvents(&tx_events));
+        let start = Instant::now();
+        let msgs = odata.assemble_msgs(self);
+        batch.timings.query_proofs += start.elapsed();
 
-        let ev = tx_events
-            .clone()
+        let msgs = match msgs {
+            Ok(msgs) => msgs,
+            Err(e) => {
+                let msgs = odata.batch.iter().map(|tm| tm.msg.clone()).collect_vec();
+                batch.record_error(&msgs, &e.to_string());
+                summary.batches.push(batch);
+                return Err(e);
+            }
+        };
+
+        if self.dry_run {
+            let estimates = match target.simulate_msgs(msgs.clone()) {
+                Ok(estimates) => estimates,
+                Err(e) => {
+                    batch.record_error(&msgs, &e.to_string());
+                    summary.batches.push(batch);
+                    return Err(e.into());
+                }
+            };
+
+            info!(
+                "[{}] dry run: would relay packets [{}] to {} in {} tx(s), estimated gas {}, fee {}",
+                self,
+                batch.sequences.iter().join(", "),
+                target.id(),
+                estimates.len(),
+                estimates.iter().map(|tx| tx.gas_estimate).sum::<u64>(),
+                estimates.iter().map(|tx| tx.fee.as_str()).join(" + "),
+            );
+
+            for error in estimates.iter().filter_map(|tx| tx.error.as_ref()) {
+                warn!("[{}] dry run: simulation failed: {}", self, error);
+            }
+
+            batch.record_simulated(&msgs, &estimates);
+            summary.batches.push(batch);
+
+            return Ok(());
+        }
+
+        let sent = match target.send_msgs_with_receipts(msgs.clone()) {
+            Ok(sent) => sent,
+            Err(e) => {
+                batch.record_error(&msgs, &e.to_string());
+                summary.batches.push(batch);
+                return Err(e.into());
+            }
+        };
+        info!("[{}] result {}\n", self, PrettyEvents(&sent.events));
+
+        batch.record_sent(&msgs, &sent);
+        summary.batches.push(batch);
+
+        // A batch may be split in several transactions: the messages of those which succeeded
+        // are relayed, even if another one failed, so their events are kept along with the error
+        let (errors, events): (Vec<IbcEvent>, Vec<IbcEvent>) = sent
+            .events
             .into_iter()
-            .find(|event| matches!(event, IbcEvent::ChainError(_)));
+            .partition(|event| matches!(event, IbcEvent::ChainError(_)));
+        summary.events.extend(events);
 
-        match ev {
+        match errors.into_iter().next() {
             Some(ev) => Err(LinkError::SendError(Box::new(ev))),
-            None => Ok(RelaySummary::from_events(tx_events)),
+            None => Ok(()),
         }
     }
 
@@ -674,6 +857,39 @@ impl RelayPath {
         self.recv_packet_acknowledged_on_src(&rp.packet)
     }
 
+    /// Checks if the packet of the event which generated a message has already been handled.
+    fn transit_message_handled(&self, tm: &TransitMessage) -> Result<bool, LinkError> {
+        match &tm.event {
+            IbcEvent::SendPacket(e) => self.send_packet_event_handled(e),
+            IbcEvent::WriteAcknowledgement(e) => self.write_ack_event_handled(e),
+            _ => Ok(false),
+        }
+    }
+
+    /// Checks if the same message for the packet of the event which generated a message is
+    /// already scheduled for the `target` chain, e.g., if the clearing of pending packets
+    /// finds a packet whose operational data was resumed from the journal.
+    fn transit_message_scheduled(
+        &self,
+        target: OperationalDataTarget,
+        tm: &TransitMessage,
+    ) -> bool {
+        let sequence = match tm.packet_sequence() {
+            Some(sequence) => sequence,
+            None => return false,
+        };
+
+        let scheduled = match target {
+            OperationalDataTarget::Source => &self.src_operational_data,
+            OperationalDataTarget::Destination => &self.dst_operational_data,
+        };
+
+        scheduled
+            .iter()
+            .flat_map(|od| od.batch.iter())
+            .any(|s| s.msg.type_url == tm.msg.type_url && s.packet_sequence() == Some(sequence))
+    }
+
     /// Returns `true` if the delay for this relaying path is zero.
     /// Conversely, returns `false` if the delay is non-zero.
     pub fn zero_delay(&self) -> bool {
@@ -775,35 +991,57 @@ impl RelayPath {
 
         let src_channel_id = self.src_channel_id()?;
 
-        // Query packet commitments on source chain that have not been acknowledged
+        // Query packet commitments on source chain that have not been acknowledged, page by page,
+        // all at the height of the first page
         let pc_request = QueryPacketCommitmentsRequest {
             port_id: self.src_port_id().to_string(),
             channel_id: src_channel_id.to_string(),
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            pagination: None,
         };
-        let (packet_commitments, src_response_height) =
-            self.src_chain().query_packet_commitments(pc_request)?;
 
-        let query_height = opt_query_height.unwrap_or(src_response_height);
+        let mut commit_sequences: Vec<u64> = vec![];
+        let mut sequences: Vec<Sequence> = vec![];
+        let mut src_response_height = Height::zero();
 
-        if packet_commitments.is_empty() {
-            return Ok((events_result, query_height));
+        for page in self.src_chain().query_packet_commitments_pages(pc_request) {
+            let (packet_commitments, page_height) = page?;
+            src_response_height = page_height;
+
+            let page_sequences: Vec<u64> = packet_commitments
+                .items
+                .iter()
+                .map(|p| p.sequence)
+                .filter(|sequence| self.sequence_selected(*sequence))
+                .collect();
+
+            if page_sequences.is_empty() {
+                continue;
+            }
+
+            // Get the packets of the page that have not been received on destination chain
+            let request = QueryUnreceivedPacketsRequest {
+                port_id: self.dst_port_id().to_string(),
+                channel_id: self.dst_channel_id()?.to_string(),
+                packet_commitment_sequences: page_sequences.clone(),
+            };
+
+            sequences.extend(
+                self.dst_chain()
+                    .query_unreceived_packets(request)?
+                    .into_iter()
+                    .map(Sequence::from),
+            );
+            commit_sequences.extend(page_sequences);
         }
-        let commit_sequences: Vec<u64> = packet_commitments.iter().map(|p| p.sequence).collect();
 
-        // Get the packets that have not been received on destination chain
-        let request = QueryUnreceivedPacketsRequest {
-            port_id: self.dst_port_id().to_string(),
-            channel_id: self.dst_channel_id()?.to_string(),
-            packet_commitment_sequences: commit_sequences.clone(),
-        };
+        // The pages are ordered by the store keys of the commitments, not by their sequence
+        sequences.sort_unstable();
 
-        let sequences: Vec<Sequence> = self
-            .dst_chain()
-            .query_unreceived_packets(request)?
-            .into_iter()
-            .map(From::from)
-            .collect();
+        let query_height = opt_query_height.unwrap_or(src_response_height);
+
+        if commit_sequences.is_empty() {
+            return Ok((events_result, query_height));
+        }
 
         if sequences.is_empty() {
             return Ok((events_result, query_height));
@@ -868,39 +1106,64 @@ impl RelayPath {
         let src_channel_id = self.src_channel_id()?;
         let dst_channel_id = self.dst_channel_id()?;
 
-        // Get the sequences of packets that have been acknowledged on source
+        // Get the sequences of packets that have been acknowledged on source, page by page,
+        // all at the height of the first page
         let pc_request = QueryPacketAcknowledgementsRequest {
             port_id: self.src_port_id().to_string(),
             channel_id: src_channel_id.to_string(),
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            pagination: None,
         };
-        let (acks_on_source, src_response_height) = self
+
+        let mut acked_sequences: Vec<u64> = vec![];
+        let mut sequences: Vec<Sequence> = vec![];
+        let mut src_response_height = Height::zero();
+
+        for page in self
             .src_chain()
-            .query_packet_acknowledgements(pc_request)
-            .map_err(|e| LinkError::QueryError(self.src_chain().id(), e))?;
+            .query_packet_acknowledgements_pages(pc_request)
+        {
+            let (acks_on_source, page_height) =
+                page.map_err(|e| LinkError::QueryError(self.src_chain().id(), e))?;
+            src_response_height = page_height;
+
+            let page_sequences: Vec<u64> = acks_on_source
+                .items
+                .iter()
+                .map(|p| p.sequence)
+                .filter(|sequence| self.sequence_selected(*sequence))
+                .collect();
+
+            if page_sequences.is_empty() {
+                continue;
+            }
 
-        let query_height = opt_query_height.unwrap_or(src_response_height);
+            // Get the packets of the page whose acknowledgements have not been received on
+            // destination chain
+            let request = QueryUnreceivedAcksRequest {
+                port_id: self.dst_port_id().to_string(),
+                channel_id: dst_channel_id.to_string(),
+                packet_ack_sequences: page_sequences.clone(),
+            };
 
-        if acks_on_source.is_empty() {
-            return Ok((events_result, query_height));
+            sequences.extend(
+                self.dst_chain()
+                    .query_unreceived_acknowledgement(request)
+                    .map_err(|e| LinkError::QueryError(self.dst_chain().id(), e))?
+                    .into_iter()
+                    .map(Sequence::from),
+            );
+            acked_sequences.extend(page_sequences);
         }
 
-        let mut acked_sequences: Vec<u64> = acks_on_source.iter().map(|p| p.sequence).collect();
+        // The pages are ordered by the store keys of the acknowledgements, not by their sequence
         acked_sequences.sort_unstable();
+        sequences.sort_unstable();
 
-        let request = QueryUnreceivedAcksRequest {
-            port_id: self.dst_port_id().to_string(),
-            channel_id: dst_channel_id.to_string(),
-            packet_ack_sequences: acked_sequences.clone(),
-        };
+        let query_height = opt_query_height.unwrap_or(src_response_height);
 
-        let sequences: Vec<Sequence> = self
-            .dst_chain()
-            .query_unreceived_acknowledgement(request)
-            .map_err(|e| LinkError::QueryError(self.dst_chain().id(), e))?
-            .into_iter()
-            .map(From::from)
-            .collect();
+        if acked_sequences.is_empty() {
+            return Ok((events_result, query_height));
+        }
 
         if sequences.is_empty() {
             return Ok((events_result, query_height));
@@ -1254,6 +1517,19 @@ impl RelayPath {
             odata.batch = retain_batch;
         }
 
+        // Record the updated operational data, dropping the data left with no messages
+        if let Some(journal) = &self.journal {
+            for (initial_od, od) in self.dst_operational_data.iter().zip(all_dst_odata.iter()) {
+                match od.journal_id {
+                    Some(journal_id) if od.batch.is_empty() => journal.remove(journal_id)?,
+                    Some(journal_id) if od.batch.len() != initial_od.batch.len() => {
+                        journal.update(journal_id, od)?
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         // Replace the original operational data with the updated one
         self.dst_operational_data = all_dst_odata;
         // Possibly some op. data became empty (if no events were kept).
@@ -1289,6 +1565,16 @@ impl RelayPath {
     /// If the relaying path has non-zero packet delays, this method also updates the client on the
     /// target chain with the appropriate headers.
     fn schedule_operational_data(&mut self, mut od: OperationalData) -> Result<(), LinkError> {
+        let mut batch = std::mem::take(&mut od.batch);
+        batch.retain(|tm| {
+            let scheduled = self.transit_message_scheduled(od.target, tm);
+            if scheduled {
+                debug!("[{}] ignoring already scheduled msg for {}", self, tm.event);
+            }
+            !scheduled
+        });
+        od.batch = batch;
+
         if od.batch.is_empty() {
             info!(
                 "[{}] ignoring operational data for {} because it has no messages",
@@ -1306,7 +1592,8 @@ impl RelayPath {
         );
 
         // Update clients ahead of scheduling the operational data, if the delays are non-zero.
-        if !self.zero_delay() {
+        // A dry run does not send the client updates.
+        if !self.zero_delay() && !self.dry_run {
             let target_height = od.proofs_height.increment();
             match od.target {
                 OperationalDataTarget::Source => self.update_client_src(target_height)?,
@@ -1316,6 +1603,10 @@ impl RelayPath {
 
         od.scheduled_time = Instant::now();
 
+        if let Some(journal) = &self.journal {
+            od.journal_id = Some(journal.record(&od)?);
+        }
+
         match od.target {
             OperationalDataTarget::Source => self.src_operational_data.push(od),
             OperationalDataTarget::Destination => self.dst_operational_data.push(od),
@@ -1377,6 +1668,13 @@ impl RelayPath {
                     odata.batch.len(),
                     odata.target,
                 ),
+                Some(delay_left) if self.dry_run => info!(
+                    "[{}] dry run: not waiting ({:?} left) for a scheduled op. data with batch of size {} targeting {}",
+                    self,
+                    delay_left,
+                    odata.batch.len(),
+                    odata.target,
+                ),
                 Some(delay_left) => {
                     info!(
                         "[{}] waiting ({:?} left) for a scheduled op. data with batch of size {} targeting {}",
@@ -1407,6 +1705,26 @@ impl RelayPath {
         self.clear_packets = clear_packets;
     }
 
+    /// Restricts the pending packets which the relay path finds, e.g., when clearing packets,
+    /// to those with the `sequences`, or lifts the restriction if `None`.
+    /// The packets relayed upon new events are not restricted.
+    pub fn set_sequence_filter(&mut self, sequences: Option<SequenceRanges>) {
+        self.sequence_filter = sequences;
+    }
+
+    fn sequence_selected(&self, sequence: u64) -> bool {
+        self.sequence_filter.as_ref().map_or(true, |sequences| {
+            sequences.contains(Sequence::from(sequence))
+        })
+    }
+
+    /// Set the relay path's dry run flag. In a dry run, the transactions which would relay the
+    /// scheduled operational data are simulated to estimate their gas and fee, but are neither
+    /// signed nor broadcast, and no client is updated ahead of the packet delays.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
     fn restore_src_client(&self) -> ForeignClient {
         ForeignClient::restore(
             self.src_client_id().clone(),
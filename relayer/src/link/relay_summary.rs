@@ -1,22 +1,499 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use prost_types::Any;
+use serde::Serialize;
+
 use ibc::events::IbcEvent;
+use ibc::ics04_channel::packet::Sequence;
+use ibc::ics24_host::identifier::ChainId;
+use ibc::Height;
+
+use crate::chain::{SentMsgs, TxEstimate, TxReceipt};
+use crate::link::operational_data::{OperationalData, TransitMessage};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct RelaySummary {
+    /// The events of the messages which were relayed
     pub events: Vec<IbcEvent>,
-    // errors: todo!(),
-    // timings: todo!(),
+    /// The batches of messages sent to a chain, including the failed attempts
+    pub batches: Vec<BatchSummary>,
 }
 
 impl RelaySummary {
     pub fn empty() -> Self {
-        Self { events: vec![] }
+        Self {
+            events: vec![],
+            batches: vec![],
+        }
     }
 
     pub fn from_events(events: Vec<IbcEvent>) -> Self {
-        Self { events }
+        Self {
+            events,
+            batches: vec![],
+        }
     }
 
     pub fn extend(&mut self, other: RelaySummary) {
-        self.events.extend(other.events)
+        self.events.extend(other.events);
+        self.batches.extend(other.batches);
+    }
+
+    /// The outcome of the relaying of each packet of the batches, by sequence.
+    /// A packet whose messages generated no event failed, in the last batch which included it,
+    /// with the error of its own message or, if it did not fail, with the error of the client
+    /// update of the batch.
+    pub fn packet_outcomes(&self) -> BTreeMap<Sequence, PacketOutcome> {
+        let mut outcomes = BTreeMap::new();
+
+        for batch in self.batches.iter() {
+            let batch_error = batch
+                .failed_msgs
+                .iter()
+                .find(|msg| msg.sequence.is_none())
+                .map(|msg| format!("batch failed: {}", msg.error));
+
+            for sequence in batch.sequences.iter() {
+                let error = batch
+                    .failed_msgs
+                    .iter()
+                    .find(|msg| msg.sequence == Some(*sequence))
+                    .map(|msg| msg.error.clone())
+                    .or_else(|| batch_error.clone())
+                    .unwrap_or_else(|| "not relayed".to_string());
+
+                outcomes.insert(*sequence, PacketOutcome::Failed(error));
+            }
+        }
+
+        for event in self.events.iter() {
+            let (sequence, outcome) = match event {
+                IbcEvent::ReceivePacket(e) => (e.packet.sequence, PacketOutcome::Received),
+                IbcEvent::AcknowledgePacket(e) => (e.packet.sequence, PacketOutcome::Acknowledged),
+                IbcEvent::TimeoutPacket(e) => (e.packet.sequence, PacketOutcome::TimedOut),
+                IbcEvent::TimeoutOnClosePacket(e) => (e.packet.sequence, PacketOutcome::TimedOut),
+                _ => continue,
+            };
+            outcomes.insert(sequence, outcome);
+        }
+
+        outcomes
+    }
+}
+
+/// The outcome of the relaying of a packet.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "outcome", content = "error", rename_all = "snake_case")]
+pub enum PacketOutcome {
+    Received,
+    Acknowledged,
+    TimedOut,
+    Failed(String),
+}
+
+/// The summary of an attempt to send the messages of an operational data to its target chain.
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchSummary {
+    pub target_chain: ChainId,
+    pub proofs_height: Height,
+    /// The sequences of the packets relayed by the batch
+    pub sequences: Vec<Sequence>,
+    /// The number of messages, including client updates, by type URL
+    pub msg_counts: BTreeMap<String, usize>,
+    pub txs: Vec<TxReceipt>,
+    /// The estimates of the transactions which would be sent, in a dry run
+    pub estimated_txs: Vec<TxEstimate>,
+    pub timings: BatchTimings,
+    pub failed_msgs: Vec<FailedMsg>,
+    /// The sequence of the packet of each message of the operational data, if any
+    #[serde(skip)]
+    msg_sequences: Vec<Option<Sequence>>,
+}
+
+/// The time spent on each step of the relaying of a batch.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BatchTimings {
+    /// Querying the proofs and headers, and building the messages
+    #[serde(with = "humantime_serde")]
+    pub query_proofs: Duration,
+    /// Signing and broadcasting the transactions
+    #[serde(with = "humantime_serde")]
+    pub broadcast: Duration,
+    /// Waiting for the transactions to be committed
+    #[serde(with = "humantime_serde")]
+    pub confirm: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FailedMsg {
+    pub type_url: String,
+    /// The sequence of the packet of the message, if any
+    pub sequence: Option<Sequence>,
+    pub error: String,
+}
+
+impl BatchSummary {
+    pub fn new(target_chain: ChainId, od: &OperationalData) -> Self {
+        Self {
+            target_chain,
+            proofs_height: od.proofs_height,
+            sequences: od.packet_sequences(),
+            msg_counts: BTreeMap::new(),
+            txs: vec![],
+            estimated_txs: vec![],
+            timings: BatchTimings {
+                query_proofs: od.query_time,
+                ..BatchTimings::default()
+            },
+            failed_msgs: vec![],
+            msg_sequences: od
+                .batch
+                .iter()
+                .map(TransitMessage::packet_sequence)
+                .collect(),
+        }
+    }
+
+    /// Records the outcome of sending the messages `msgs`, which are packaged in transactions
+    /// in order. The messages of a transaction which failed are recorded as failed with its error.
+    pub fn record_sent(&mut self, msgs: &[Any], sent: &SentMsgs) {
+        self.count_msgs(msgs);
+
+        self.timings.broadcast += sent.broadcast_time;
+        self.timings.confirm += sent.confirm_time;
+        self.txs.extend(sent.txs.iter().cloned());
+
+        if sent.txs.is_empty() {
+            // Without receipts, an error cannot be attributed to the messages of a transaction
+            if let Some(error) = chain_error(&sent.events) {
+                self.record_failed(msgs, &self.sequences_of(msgs), error);
+            }
+            return;
+        }
+
+        self.record_failed_txs(
+            msgs,
+            sent.txs
+                .iter()
+                .map(|tx| (tx.msg_count, tx.error.as_deref())),
+        );
+    }
+
+    /// Records the estimates of the simulated transactions which would send the messages `msgs`
+    /// in order. The messages of a transaction whose simulation failed are recorded as failed
+    /// with its error.
+    pub fn record_simulated(&mut self, msgs: &[Any], estimates: &[TxEstimate]) {
+        self.count_msgs(msgs);
+
+        self.estimated_txs.extend(estimates.iter().cloned());
+
+        self.record_failed_txs(
+            msgs,
+            estimates
+                .iter()
+                .map(|tx| (tx.msg_count, tx.error.as_deref())),
+        );
+    }
+
+    /// Records the messages `msgs` as failed with `error`, when they could not be sent at all,
+    /// e.g., because their proofs could not be queried or the chain could not be reached.
+    pub fn record_error(&mut self, msgs: &[Any], error: &str) {
+        self.count_msgs(msgs);
+        self.record_failed(msgs, &self.sequences_of(msgs), error);
+    }
+
+    fn count_msgs(&mut self, msgs: &[Any]) {
+        for msg in msgs {
+            *self.msg_counts.entry(msg.type_url.clone()).or_insert(0) += 1;
+        }
+    }
+
+    /// Records the messages of the transactions, given by their number of messages and
+    /// their error, which failed.
+    fn record_failed_txs<'a>(
+        &mut self,
+        msgs: &[Any],
+        txs: impl Iterator<Item = (usize, Option<&'a str>)>,
+    ) {
+        let sequences = self.sequences_of(msgs);
+
+        let mut start = 0;
+        for (msg_count, error) in txs {
+            let end = (start + msg_count).min(msgs.len());
+            if let Some(error) = error {
+                self.record_failed(&msgs[start..end], &sequences[start..end], error);
+            }
+            start = end;
+        }
+    }
+
+    /// Records the messages `msgs`, whose packets have the given `sequences`, as failed.
+    fn record_failed(&mut self, msgs: &[Any], sequences: &[Option<Sequence>], error: &str) {
+        self.failed_msgs
+            .extend(msgs.iter().zip(sequences).map(|(msg, sequence)| FailedMsg {
+                type_url: msg.type_url.clone(),
+                sequence: *sequence,
+                error: error.to_string(),
+            }));
+    }
+
+    /// The sequence of the packet of each of the messages `msgs`, which are the messages of the
+    /// operational data, preceded by the client updates prepended to them, if any.
+    fn sequences_of(&self, msgs: &[Any]) -> Vec<Option<Sequence>> {
+        let client_updates = msgs.len().saturating_sub(self.msg_sequences.len());
+
+        let mut sequences = vec![None; client_updates];
+        sequences.extend(self.msg_sequences.iter().copied());
+        sequences
+    }
+
+    /// The total gas used by the transactions of the batch
+    pub fn gas_used(&self) -> u64 {
+        self.txs.iter().filter_map(|tx| tx.gas_used).sum()
+    }
+
+    /// The total estimated gas of the simulated transactions of the batch
+    pub fn gas_estimate(&self) -> u64 {
+        self.estimated_txs.iter().map(|tx| tx.gas_estimate).sum()
+    }
+}
+
+fn chain_error(events: &[IbcEvent]) -> Option<&str> {
+    events.iter().find_map(|event| match event {
+        IbcEvent::ChainError(e) => Some(e.as_str()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use prost_types::Any;
+    use test_env_log::test;
+
+    use ibc::events::IbcEvent;
+    use ibc::ics04_channel::events::{ReceivePacket, SendPacket};
+    use ibc::ics04_channel::packet::{Packet, Sequence};
+    use ibc::ics24_host::identifier::ChainId;
+    use ibc::Height;
+
+    use crate::chain::{SentMsgs, TxEstimate, TxReceipt};
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+
+    use super::{BatchSummary, FailedMsg, PacketOutcome, RelaySummary};
+
+    fn msg(type_url: &str) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        }
+    }
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: Sequence::from(sequence),
+            ..Packet::default()
+        }
+    }
+
+    /// An operational data with the receive messages of the packets with the `sequences`
+    fn recv_operational_data(sequences: &[u64]) -> OperationalData {
+        let mut od = OperationalData::new(Height::new(0, 10), OperationalDataTarget::Destination);
+        for sequence in sequences {
+            od.batch.push(TransitMessage {
+                event: IbcEvent::SendPacket(SendPacket {
+                    height: Height::new(0, 10),
+                    packet: packet(*sequence),
+                }),
+                msg: msg("/ibc.core.channel.v1.MsgRecvPacket"),
+            });
+        }
+        od
+    }
+
+    fn receipt(msg_count: usize, gas_used: u64, error: Option<&str>) -> TxReceipt {
+        TxReceipt {
+            hash: "ABCD".to_string(),
+            msg_count,
+            gas_wanted: 200_000,
+            gas_used: Some(gas_used),
+            fee: "5000stake".to_string(),
+            error: error.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn failed_msgs_of_failed_txs() {
+        let od = recv_operational_data(&[7, 8]);
+        let mut batch = BatchSummary::new(ChainId::new("chain_b".to_string(), 0), &od);
+
+        let msgs = [
+            msg("/ibc.core.client.v1.MsgUpdateClient"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+        ];
+        let sent = SentMsgs {
+            txs: vec![
+                receipt(2, 120_000, None),
+                receipt(1, 30_000, Some("out of gas")),
+            ],
+            ..SentMsgs::default()
+        };
+        batch.record_sent(&msgs, &sent);
+
+        assert_eq!(batch.msg_counts["/ibc.core.channel.v1.MsgRecvPacket"], 2);
+        assert_eq!(batch.msg_counts["/ibc.core.client.v1.MsgUpdateClient"], 1);
+        assert_eq!(batch.gas_used(), 150_000);
+        assert_eq!(
+            batch.failed_msgs,
+            vec![FailedMsg {
+                type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+                sequence: Some(Sequence::from(8)),
+                error: "out of gas".to_string(),
+            }]
+        );
+
+        // Without receipts, all the messages failed with the error of the chain
+        let mut batch = BatchSummary::new(ChainId::new("chain_b".to_string(), 0), &od);
+        let sent = SentMsgs {
+            events: vec![IbcEvent::ChainError("rejected".to_string())],
+            ..SentMsgs::default()
+        };
+        batch.record_sent(&msgs, &sent);
+        assert_eq!(batch.failed_msgs.len(), 3);
+
+        // Messages which could not be sent at all failed with the error which prevented it
+        let mut batch = BatchSummary::new(ChainId::new("chain_b".to_string(), 0), &od);
+        batch.record_error(&msgs, "connection refused");
+        assert!(batch.txs.is_empty());
+        assert_eq!(batch.msg_counts["/ibc.core.channel.v1.MsgRecvPacket"], 2);
+        assert_eq!(batch.failed_msgs.len(), 3);
+        assert_eq!(batch.failed_msgs[0].error, "connection refused");
+        assert_eq!(batch.failed_msgs[0].sequence, None);
+        assert_eq!(batch.failed_msgs[1].sequence, Some(Sequence::from(7)));
+    }
+
+    #[test]
+    fn estimates_of_simulated_txs() {
+        let od = recv_operational_data(&[7, 8]);
+        let mut batch = BatchSummary::new(ChainId::new("chain_b".to_string(), 0), &od);
+        assert_eq!(batch.sequences, vec![Sequence::from(7), Sequence::from(8)]);
+
+        let msgs = [
+            msg("/ibc.core.client.v1.MsgUpdateClient"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+        ];
+        let estimate = |msg_count, gas_estimate, error: Option<&str>| TxEstimate {
+            msg_count,
+            gas_estimate,
+            gas_wanted: gas_estimate * 11 / 10,
+            fee: "5000stake".to_string(),
+            error: error.map(ToString::to_string),
+        };
+        let estimates = [
+            estimate(1, 80_000, None),
+            estimate(2, 400_000, Some("client state height < proof height")),
+        ];
+        batch.record_simulated(&msgs, &estimates);
+
+        assert!(batch.txs.is_empty());
+        assert_eq!(batch.estimated_txs.len(), 2);
+        assert_eq!(batch.gas_estimate(), 480_000);
+        assert_eq!(batch.msg_counts["/ibc.core.channel.v1.MsgRecvPacket"], 2);
+        assert_eq!(batch.failed_msgs.len(), 2);
+        assert_eq!(
+            batch.failed_msgs[0].type_url,
+            "/ibc.core.channel.v1.MsgRecvPacket"
+        );
+    }
+
+    #[test]
+    fn outcomes_of_relayed_and_failed_packets() {
+        let chain_id = ChainId::new("chain_b".to_string(), 0);
+        let msgs = [
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+        ];
+
+        // The first attempt fails for both packets, the retry only relays the first one
+        let mut first = BatchSummary::new(chain_id.clone(), &recv_operational_data(&[7, 8]));
+        first.record_sent(
+            &msgs,
+            &SentMsgs {
+                txs: vec![receipt(2, 30_000, Some("out of gas"))],
+                ..SentMsgs::default()
+            },
+        );
+        let mut retry = BatchSummary::new(chain_id, &recv_operational_data(&[7, 8]));
+        retry.record_sent(
+            &msgs,
+            &SentMsgs {
+                txs: vec![
+                    receipt(1, 90_000, None),
+                    receipt(1, 30_000, Some("timeout")),
+                ],
+                ..SentMsgs::default()
+            },
+        );
+
+        let summary = RelaySummary {
+            events: vec![IbcEvent::ReceivePacket(ReceivePacket {
+                height: Height::new(0, 12),
+                packet: packet(7),
+            })],
+            batches: vec![first, retry],
+        };
+
+        let outcomes = summary.packet_outcomes();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[&Sequence::from(7)], PacketOutcome::Received);
+        assert_eq!(
+            outcomes[&Sequence::from(8)],
+            PacketOutcome::Failed("timeout".to_string())
+        );
+    }
+
+    #[test]
+    fn outcomes_of_failed_msgs_and_client_updates() {
+        let chain_id = ChainId::new("chain_b".to_string(), 0);
+        let msgs = [
+            msg("/ibc.core.client.v1.MsgUpdateClient"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+        ];
+
+        // Each packet failed with the error of its own message, if it failed, and otherwise
+        // with the error of the client update
+        let mut batch = BatchSummary::new(chain_id, &recv_operational_data(&[7, 8, 9]));
+        batch.record_sent(
+            &msgs,
+            &SentMsgs {
+                txs: vec![
+                    receipt(1, 30_000, Some("invalid header")),
+                    receipt(1, 30_000, Some("packet already received")),
+                    receipt(2, 90_000, None),
+                ],
+                ..SentMsgs::default()
+            },
+        );
+
+        let summary = RelaySummary {
+            events: vec![],
+            batches: vec![batch],
+        };
+
+        let outcomes = summary.packet_outcomes();
+        assert_eq!(
+            outcomes[&Sequence::from(7)],
+            PacketOutcome::Failed("packet already received".to_string())
+        );
+        for sequence in [8, 9].iter() {
+            assert_eq!(
+                outcomes[&Sequence::from(*sequence)],
+                PacketOutcome::Failed("batch failed: invalid header".to_string())
+            );
+        }
     }
 }
@@ -0,0 +1,152 @@
+//! Selections of packet sequences, such as `10..20,35`, to which the relaying of the pending
+//! packets of a path can be restricted.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use ibc::ics04_channel::packet::Sequence;
+
+use crate::link::error::LinkError;
+
+/// A selection of packet sequences, given as a comma-separated list of sequences and of
+/// inclusive ranges of sequences, e.g., `10..20,35`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SequenceRanges(Vec<RangeInclusive<Sequence>>);
+
+impl SequenceRanges {
+    pub fn contains(&self, sequence: Sequence) -> bool {
+        self.0.iter().any(|range| range.contains(&sequence))
+    }
+
+    /// The sequences of this selection which are not `excluded`, if any.
+    pub fn excluding(&self, excluded: &BTreeSet<Sequence>) -> Option<Self> {
+        let mut ranges = vec![];
+
+        for range in self.0.iter() {
+            // The start of the part of the range above the excluded sequences seen so far
+            let mut start = Some(u64::from(*range.start()));
+
+            for sequence in excluded.range(range.clone()) {
+                let sequence = u64::from(*sequence);
+                if let Some(start) = start.filter(|start| *start < sequence) {
+                    ranges.push(Sequence::from(start)..=Sequence::from(sequence - 1));
+                }
+                start = sequence.checked_add(1);
+            }
+
+            if let Some(start) = start.filter(|start| *start <= u64::from(*range.end())) {
+                ranges.push(Sequence::from(start)..=*range.end());
+            }
+        }
+
+        if ranges.is_empty() {
+            None
+        } else {
+            Some(Self(ranges))
+        }
+    }
+}
+
+impl FromStr for SequenceRanges {
+    type Err = LinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| LinkError::InvalidSequences(s.to_string(), reason.to_string());
+
+        let parse = |sequence: &str| {
+            sequence
+                .trim()
+                .parse::<u64>()
+                .map(Sequence::from)
+                .map_err(|e| invalid(&e.to_string()))
+        };
+
+        let ranges = s
+            .split(',')
+            .map(|item| match item.split_once("..") {
+                Some((start, end)) => {
+                    let (start, end) = (parse(start)?, parse(end)?);
+                    if start > end {
+                        return Err(invalid("the start of a range is above its end"));
+                    }
+                    Ok(start..=end)
+                }
+                None => parse(item).map(|sequence| sequence..=sequence),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self(ranges))
+    }
+}
+
+impl fmt::Display for SequenceRanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges = self.0.iter().format_with(",", |range, f| {
+            if range.start() == range.end() {
+                f(range.start())
+            } else {
+                f(&format_args!("{}..{}", range.start(), range.end()))
+            }
+        });
+
+        write!(f, "{}", ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::str::FromStr;
+
+    use test_env_log::test;
+
+    use ibc::ics04_channel::packet::Sequence;
+
+    use super::SequenceRanges;
+
+    #[test]
+    fn parse_sequences_and_ranges() {
+        let sequences = SequenceRanges::from_str("10..20, 35").unwrap();
+
+        for selected in [10, 15, 20, 35].iter() {
+            assert!(sequences.contains(Sequence::from(*selected)));
+        }
+        for other in [1, 9, 21, 34, 36].iter() {
+            assert!(!sequences.contains(Sequence::from(*other)));
+        }
+        assert_eq!(sequences.to_string(), "10..20,35");
+
+        assert!(SequenceRanges::from_str("").is_err());
+        assert!(SequenceRanges::from_str("20..10").is_err());
+        assert!(SequenceRanges::from_str("10..").is_err());
+        assert!(SequenceRanges::from_str("ten").is_err());
+    }
+
+    #[test]
+    fn sequences_excluding_others() {
+        let sequences = SequenceRanges::from_str("10..20,35").unwrap();
+        let excluded = |sequences: &[u64]| {
+            sequences
+                .iter()
+                .copied()
+                .map(Sequence::from)
+                .collect::<BTreeSet<_>>()
+        };
+
+        let remaining = sequences
+            .excluding(&excluded(&[5, 10, 14, 15, 20]))
+            .unwrap();
+        assert_eq!(remaining.to_string(), "11..13,16..19,35");
+
+        assert_eq!(sequences.excluding(&excluded(&[])), Some(sequences.clone()));
+        let all = (10..=20).chain(Some(35)).collect::<Vec<_>>();
+        assert_eq!(sequences.excluding(&excluded(&all)), None);
+
+        let last = SequenceRanges::from_str(&u64::MAX.to_string()).unwrap();
+        assert_eq!(last.excluding(&excluded(&[u64::MAX])), None);
+    }
+}
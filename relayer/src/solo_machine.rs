@@ -0,0 +1,305 @@
+//! Creation and update of ICS06 solo machine clients. The relayer acts on behalf of a solo
+//! machine whose secp256k1 key is held in the keyring of the chain hosting the client: it builds
+//! the client and consensus states from the public key of the solo machine, and signs the headers
+//! which rotate its key and diversifier.
+
+use tracing::info;
+
+use ibc::events::IbcEvent;
+use ibc::ics02_client::client_consensus::AnyConsensusState;
+use ibc::ics02_client::client_state::AnyClientState;
+use ibc::ics02_client::header::AnyHeader;
+use ibc::ics02_client::msgs::create_client::MsgCreateAnyClient;
+use ibc::ics02_client::msgs::update_client::MsgUpdateAnyClient;
+use ibc::ics06_solomachine::client_state::ClientState;
+use ibc::ics06_solomachine::consensus_state::{ConsensusState, PublicKey};
+use ibc::ics06_solomachine::header::Header;
+use ibc::ics06_solomachine::signing::{encode_message, sign_bytes};
+use ibc::ics24_host::identifier::ClientId;
+use ibc::timestamp::Timestamp;
+use ibc::tx_msg::Msg;
+use ibc::Height;
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::{Single, Sum};
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data as SignatureData;
+use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+use ibc_proto::ibc::lightclients::solomachine::v1::DataType;
+
+use crate::chain::handle::ChainHandle;
+use crate::error::{Error, Kind};
+use crate::foreign_client::ForeignClientError;
+use crate::keyring::{KeyRing, KeyType};
+
+/// A solo machine, identified by the name of its key in a keyring and by its diversifier.
+#[derive(Clone, Debug)]
+pub struct SoloMachine {
+    keyring: KeyRing,
+    key_name: String,
+    diversifier: String,
+}
+
+impl SoloMachine {
+    pub fn new(keyring: KeyRing, key_name: String, diversifier: String) -> Self {
+        Self {
+            keyring,
+            key_name,
+            diversifier,
+        }
+    }
+
+    pub fn diversifier(&self) -> &str {
+        &self.diversifier
+    }
+
+    /// Returns the public key of the solo machine, as tracked by its clients.
+    pub fn public_key(&self) -> Result<PublicKey, Error> {
+        let key = self
+            .keyring
+            .get_key(&self.key_name)
+            .map_err(|e| Kind::KeyBase.context(e))?;
+
+        PublicKey::from_bytes(&key.public_key.public_key.to_bytes())
+            .map_err(|e| Kind::KeyBase.context(e).into())
+    }
+
+    /// Builds the state of a new client of this solo machine, at the initial sequence.
+    pub fn build_client_state(&self, timestamp: Timestamp) -> Result<ClientState, Error> {
+        let consensus_state = ConsensusState::new(
+            self.public_key()?,
+            self.diversifier.clone(),
+            timestamp.as_nanoseconds(),
+        );
+
+        Ok(ClientState::new(1, consensus_state))
+    }
+
+    /// Builds a header for the client at `client_state`, signed by this solo machine, which
+    /// rotates the key and diversifier of the client to the ones of `next`.
+    pub fn build_header(
+        &self,
+        client_state: &ClientState,
+        timestamp: Timestamp,
+        next: &SoloMachine,
+    ) -> Result<Header, Error> {
+        let mut header = Header {
+            sequence: client_state.sequence,
+            // The timestamp of a header cannot be earlier than the one of the client.
+            timestamp: timestamp
+                .as_nanoseconds()
+                .max(client_state.consensus_state.timestamp),
+            signature: vec![],
+            new_public_key: next.public_key()?,
+            new_diversifier: next.diversifier.clone(),
+        };
+
+        let bytes = sign_bytes(
+            header.sequence,
+            header.timestamp,
+            &client_state.consensus_state.diversifier,
+            DataType::Header,
+            header.data(),
+        );
+        header.signature = self.sign(bytes)?;
+
+        Ok(header)
+    }
+
+    /// Signs `sign_bytes` with the key of the solo machine, and encodes the signature as a
+    /// single-signature `SignatureDescriptor.Data`, as expected by the solo machine clients.
+    fn sign(&self, sign_bytes: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let signature = self
+            .keyring
+            .sign_msg(&self.key_name, sign_bytes, KeyType::Secp256k1)
+            .map_err(|e| Kind::KeyBase.context(e))?;
+
+        Ok(encode_message(&SignatureData {
+            sum: Some(Sum::Single(Single {
+                mode: SignMode::Unspecified as i32,
+                signature,
+            })),
+        }))
+    }
+}
+
+/// Creates a client of `solo_machine` on `dst_chain`, and returns the resulting event.
+pub fn create_client(
+    dst_chain: &dyn ChainHandle,
+    solo_machine: &SoloMachine,
+) -> Result<IbcEvent, ForeignClientError> {
+    let signer = dst_chain.get_signer().map_err(|e| {
+        ForeignClientError::ClientCreate(format!(
+            "failed while fetching the destination chain ({}) signer: {}",
+            dst_chain.id(),
+            e
+        ))
+    })?;
+
+    let client_state = solo_machine
+        .build_client_state(Timestamp::now())
+        .map_err(|e| {
+            ForeignClientError::ClientCreate(format!(
+                "failed while building the solo machine client state: {}",
+                e
+            ))
+        })?;
+    let consensus_state = client_state.consensus_state.clone();
+
+    let msg = MsgCreateAnyClient::new(
+        AnyClientState::SoloMachine(client_state),
+        AnyConsensusState::SoloMachine(consensus_state),
+        signer,
+    )
+    .map_err(|e| {
+        ForeignClientError::ClientCreate(format!(
+            "failed while building the create client message: {}",
+            e
+        ))
+    })?;
+
+    let events = dst_chain.send_msgs(vec![msg.to_any()]).map_err(|e| {
+        ForeignClientError::ClientCreate(format!(
+            "failed sending message to dst chain ({}) with err: {}",
+            dst_chain.id(),
+            e
+        ))
+    })?;
+
+    events.into_iter().next().ok_or_else(|| {
+        ForeignClientError::ClientCreate(format!(
+            "no event was emitted by dst chain ({}) for the client creation",
+            dst_chain.id()
+        ))
+    })
+}
+
+/// Updates the client `client_id` of `solo_machine` on `dst_chain`, rotating its key and
+/// diversifier to the ones of `next`, and returns the resulting event.
+pub fn update_client(
+    dst_chain: &dyn ChainHandle,
+    client_id: &ClientId,
+    solo_machine: &SoloMachine,
+    next: &SoloMachine,
+) -> Result<IbcEvent, ForeignClientError> {
+    let client_state = match dst_chain.query_client_state(client_id, Height::zero()) {
+        Ok(AnyClientState::SoloMachine(client_state)) => client_state,
+        Ok(client_state) => {
+            return Err(ForeignClientError::ClientUpdate(format!(
+                "client {} on chain {} is a {} client, not a solo machine client",
+                client_id,
+                dst_chain.id(),
+                client_state.client_type()
+            )))
+        }
+        Err(e) => {
+            return Err(ForeignClientError::ClientQuery(
+                client_id.clone(),
+                dst_chain.id(),
+                e.to_string(),
+            ))
+        }
+    };
+
+    let header = solo_machine
+        .build_header(&client_state, Timestamp::now(), next)
+        .map_err(|e| {
+            ForeignClientError::ClientUpdate(format!(
+                "failed while building the solo machine header: {}",
+                e
+            ))
+        })?;
+
+    let signer = dst_chain.get_signer().map_err(|e| {
+        ForeignClientError::ClientUpdate(format!(
+            "failed while fetching the destination chain ({}) signer: {}",
+            dst_chain.id(),
+            e
+        ))
+    })?;
+
+    info!(
+        "updating solo machine client {} on chain {} at sequence {}",
+        client_id,
+        dst_chain.id(),
+        header.sequence
+    );
+
+    let msg = MsgUpdateAnyClient::new(client_id.clone(), AnyHeader::SoloMachine(header), signer);
+
+    let events = dst_chain.send_msgs(vec![msg.to_any()]).map_err(|e| {
+        ForeignClientError::ClientUpdate(format!(
+            "failed sending message to dst chain ({}) with err: {}",
+            dst_chain.id(),
+            e
+        ))
+    })?;
+
+    events.into_iter().next().ok_or_else(|| {
+        ForeignClientError::ClientUpdate(format!(
+            "no event was emitted by dst chain ({}) for the update of client {}",
+            dst_chain.id(),
+            client_id
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use ibc::ics02_client::client_def::ClientDef;
+    use ibc::ics06_solomachine::client_def::SoloMachineClient;
+    use ibc::ics24_host::identifier::ChainId;
+    use ibc::mock::context::MockContext;
+    use ibc::timestamp::Timestamp;
+
+    use crate::keyring::{HDPath, KeyRing, KeyType, Store};
+
+    use super::SoloMachine;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn solo_machine(key_name: &str, diversifier: &str) -> SoloMachine {
+        let mut keyring = KeyRing::new(
+            Store::Memory,
+            "cosmos",
+            &ChainId::new("solo".to_string(), 0),
+        )
+        .unwrap();
+        let hd_path: HDPath = "m/44'/118'/0'/0/0".parse().unwrap();
+        let key = keyring
+            .key_from_mnemonic(MNEMONIC, &hd_path, KeyType::Secp256k1)
+            .unwrap();
+        keyring.add_key(key_name, key).unwrap();
+
+        SoloMachine::new(keyring, key_name.to_string(), diversifier.to_string())
+    }
+
+    #[test]
+    fn signed_header_updates_client() {
+        let solo_machine = solo_machine("solo", "diversifier");
+        let client_state = solo_machine
+            .build_client_state(Timestamp::from_nanoseconds(10).unwrap())
+            .unwrap();
+
+        let next = solo_machine("solo", "new diversifier");
+        let header = solo_machine
+            .build_header(
+                &client_state,
+                Timestamp::from_nanoseconds(20).unwrap(),
+                &next,
+            )
+            .unwrap();
+
+        let (client_state, consensus_state) = SoloMachineClient
+            .check_header_and_update_state(
+                &MockContext::default(),
+                Default::default(),
+                client_state,
+                header,
+            )
+            .unwrap();
+
+        assert_eq!(client_state.sequence, 2);
+        assert_eq!(consensus_state.diversifier, "new diversifier");
+        assert_eq!(consensus_state.timestamp, 20);
+    }
+}
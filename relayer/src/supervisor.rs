@@ -90,6 +90,13 @@ impl Supervisor {
         (supervisor, cmd_tx)
     }
 
+    /// Only simulate the transactions which would relay packets, to estimate their gas and fee,
+    /// without signing or broadcasting them. In a dry run, the supervisor only spawns
+    /// [`Object::Packet`] workers.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.workers.set_dry_run(dry_run);
+    }
+
     /// Returns `true` if the relayer should filter based on
     /// client state attributes, e.g., trust threshold.
     /// Returns `false` otherwise.
@@ -123,6 +130,11 @@ impl Supervisor {
     }
 
     fn relay_on_object(&mut self, chain_id: &ChainId, object: &Object) -> bool {
+        // A dry run only relays packets
+        if self.workers.dry_run() && !matches!(object, Object::Packet(_)) {
+            return false;
+        }
+
         // No filter is enabled, bail fast.
         if !self.channel_filter_enabled() && !self.client_filter_enabled() {
             return true;
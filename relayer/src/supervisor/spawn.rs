@@ -87,9 +87,7 @@ impl<'a> SpawnContext<'a> {
         from_chain_id: &ChainId,
         to_chain_id: &ChainId,
     ) {
-        let clients_req = QueryClientStatesRequest {
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
-        };
+        let clients_req = QueryClientStatesRequest { pagination: None };
 
         let chain = match self.registry.get_or_spawn(from_chain_id) {
             Ok(chain_handle) => chain_handle,
@@ -103,29 +101,30 @@ impl<'a> SpawnContext<'a> {
             }
         };
 
-        let clients = match chain.query_clients(clients_req) {
-            Ok(clients) => clients,
-            Err(e) => {
-                error!(
-                    "skipping workers for chain {}, reason: failed to query clients with error: {}",
-                    from_chain_id, e
-                );
+        // Spawn the workers of each page of clients before querying the next page
+        for page in chain.query_clients_pages(clients_req) {
+            let clients = match page {
+                Ok(page) => page.items,
+                Err(e) => {
+                    error!(
+                        "skipping workers for chain {}, reason: failed to query clients with error: {}",
+                        from_chain_id, e
+                    );
 
-                return;
-            }
-        };
+                    return;
+                }
+            };
 
-        for client in clients {
-            if &client.client_state.chain_id() == to_chain_id {
-                self.spawn_workers_for_client(chain.clone(), client);
+            for client in clients {
+                if &client.client_state.chain_id() == to_chain_id {
+                    self.spawn_workers_for_client(chain.clone(), client);
+                }
             }
         }
     }
 
     pub fn spawn_workers_for_chain(&mut self, chain_id: &ChainId) {
-        let clients_req = QueryClientStatesRequest {
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
-        };
+        let clients_req = QueryClientStatesRequest { pagination: None };
 
         let chain = match self.registry.get_or_spawn(chain_id) {
             Ok(chain_handle) => chain_handle,
@@ -139,20 +138,23 @@ impl<'a> SpawnContext<'a> {
             }
         };
 
-        let clients = match chain.query_clients(clients_req) {
-            Ok(clients) => clients,
-            Err(e) => {
-                error!(
-                    "skipping workers for chain {}, reason: failed to query clients with error: {}",
-                    chain_id, e
-                );
+        // Spawn the workers of each page of clients before querying the next page
+        for page in chain.query_clients_pages(clients_req) {
+            let clients = match page {
+                Ok(page) => page.items,
+                Err(e) => {
+                    error!(
+                        "skipping workers for chain {}, reason: failed to query clients with error: {}",
+                        chain_id, e
+                    );
 
-                return;
-            }
-        };
+                    return;
+                }
+            };
 
-        for client in clients {
-            self.spawn_workers_for_client(chain.clone(), client);
+            for client in clients {
+                self.spawn_workers_for_client(chain.clone(), client);
+            }
         }
 
         if self.mode != SpawnMode::Reload {
@@ -343,38 +345,40 @@ impl<'a> SpawnContext<'a> {
 
         let chans_req = QueryConnectionChannelsRequest {
             connection: connection_id.to_string(),
-            pagination: ibc_proto::cosmos::base::query::pagination::all(),
-        };
-
-        let connection_channels = match chain.query_connection_channels(chans_req) {
-            Ok(channels) => channels,
-            Err(e) => {
-                error!(
-                    "skipping workers for chain {} and connection {}, reason: failed to query its channels: {}",
-                    chain.id(), connection_id, e
-                );
-
-                return;
-            }
+            pagination: None,
         };
 
         let connection = IdentifiedConnectionEnd::new(connection_id, connection_end);
 
-        for channel in connection_channels {
-            let channel_id = channel.channel_id.clone();
+        for page in chain.query_connection_channels_pages(chans_req) {
+            let connection_channels = match page {
+                Ok(page) => page.items,
+                Err(e) => {
+                    error!(
+                        "skipping workers for chain {} and connection {}, reason: failed to query its channels: {}",
+                        chain.id(), connection.connection_id, e
+                    );
 
-            match self.spawn_workers_for_channel(chain.clone(), &client, &connection, channel) {
-                Ok(()) => debug!(
-                    "done spawning workers for chain {} and channel {}",
-                    chain.id(),
-                    channel_id,
-                ),
-                Err(e) => error!(
-                    "skipped workers for chain {} and channel {} due to error {}",
-                    chain.id(),
-                    channel_id,
-                    e
-                ),
+                    return;
+                }
+            };
+
+            for channel in connection_channels {
+                let channel_id = channel.channel_id.clone();
+
+                match self.spawn_workers_for_channel(chain.clone(), &client, &connection, channel) {
+                    Ok(()) => debug!(
+                        "done spawning workers for chain {} and channel {}",
+                        chain.id(),
+                        channel_id,
+                    ),
+                    Err(e) => error!(
+                        "skipped workers for chain {} and channel {} due to error {}",
+                        chain.id(),
+                        channel_id,
+                        e
+                    ),
+                }
             }
         }
     }
@@ -2,6 +2,13 @@
 #[cfg(feature = "telemetry")]
 pub type Telemetry = std::sync::Arc<ibc_telemetry::TelemetryState>;
 
+/// The telemetry state shared by the whole process, for the components which are not
+/// given a telemetry handle, e.g., the chain runtimes.
+#[cfg(feature = "telemetry")]
+pub fn global() -> &'static Telemetry {
+    ibc_telemetry::global()
+}
+
 // Otherwise, define and export a dummy type.
 #[cfg(not(feature = "telemetry"))]
 #[derive(Clone, Debug)]
@@ -1,5 +1,6 @@
 use serde::{de::DeserializeOwned, Serialize};
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 
 use crate::error;
 
@@ -95,4 +96,84 @@ where
 
         Ok(())
     }
+
+    pub fn remove(&self, db: &sled::Db, key: &K) -> Result<(), error::Error> {
+        let key_bytes = serde_cbor::to_vec(&key).map_err(|e| error::Kind::Store.context(e))?;
+
+        let prefixed_key_bytes = self.prefixed_key(key_bytes);
+
+        db.remove(prefixed_key_bytes)
+            .map(|_| ())
+            .map_err(|e| error::Kind::Store.context(e))?;
+
+        Ok(())
+    }
+}
+
+impl<K, V> KeyValueDb<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// Returns all the keys stored under the prefix of this database.
+    pub fn keys(&self, db: &sled::Db) -> Result<Vec<K>, error::Error> {
+        db.scan_prefix(&self.prefix)
+            .keys()
+            .map(|key_bytes| -> Result<K, error::Error> {
+                let key_bytes = key_bytes.map_err(|e| error::Kind::Store.context(e))?;
+                let key = serde_cbor::from_slice(&key_bytes[self.prefix.len()..])
+                    .map_err(|e| error::Kind::Store.context(e))?;
+                Ok(key)
+            })
+            .collect()
+    }
+
+    /// Returns the entries whose keys are within `keys`, ordered by their encoded keys.
+    /// For unsigned integer keys, whose encoding preserves their order, these are ordered
+    /// by key.
+    pub fn range(
+        &self,
+        db: &sled::Db,
+        keys: impl RangeBounds<K>,
+    ) -> Result<impl DoubleEndedIterator<Item = Result<(K, V), error::Error>>, error::Error> {
+        let start = match keys.start_bound() {
+            Bound::Included(key) => Bound::Included(self.encode_key(key)?),
+            Bound::Excluded(key) => Bound::Excluded(self.encode_key(key)?),
+            Bound::Unbounded => Bound::Included(self.prefix.clone()),
+        };
+        let end = match keys.end_bound() {
+            Bound::Included(key) => Bound::Included(self.encode_key(key)?),
+            Bound::Excluded(key) => Bound::Excluded(self.encode_key(key)?),
+            Bound::Unbounded => prefix_end(&self.prefix),
+        };
+
+        let prefix_len = self.prefix.len();
+        let entries = db.range((start, end)).map(move |entry| {
+            let (key_bytes, value_bytes) = entry.map_err(|e| error::Kind::Store.context(e))?;
+            let key = serde_cbor::from_slice(&key_bytes[prefix_len..])
+                .map_err(|e| error::Kind::Store.context(e))?;
+            let value =
+                serde_cbor::from_slice(&value_bytes).map_err(|e| error::Kind::Store.context(e))?;
+            Ok((key, value))
+        });
+
+        Ok(entries)
+    }
+
+    fn encode_key(&self, key: &K) -> Result<Vec<u8>, error::Error> {
+        let key_bytes = serde_cbor::to_vec(key).map_err(|e| error::Kind::Store.context(e))?;
+        Ok(self.prefixed_key(key_bytes))
+    }
+}
+
+/// The (exclusive) upper bound of the keys starting with `prefix`.
+fn prefix_end(prefix: &[u8]) -> Bound<Vec<u8>> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < u8::MAX {
+            end.push(last + 1);
+            return Bound::Excluded(end);
+        }
+    }
+    Bound::Unbounded
 }
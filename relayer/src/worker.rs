@@ -17,6 +17,9 @@ pub use cmd::WorkerCmd;
 mod map;
 pub use map::WorkerMap;
 
+mod key_pool;
+pub use key_pool::KeyPool;
+
 mod client;
 pub use client::ClientWorker;
 
@@ -77,6 +80,7 @@ impl Worker {
         msg_tx: Sender<WorkerMsg>,
         telemetry: Telemetry,
         config: &Config,
+        dry_run: bool,
     ) -> WorkerHandle {
         let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
 
@@ -103,6 +107,7 @@ impl Worker {
                     cmd_rx,
                     telemetry,
                     config.global.clear_packets_interval,
+                    dry_run,
                 ),
             ),
         };
@@ -0,0 +1,75 @@
+/// Assigns the signing keys configured for a chain to the workers which submit transactions
+/// to it, so that each worker can use its own account and sequence number.
+#[derive(Clone, Debug)]
+pub struct KeyPool {
+    key_names: Vec<String>,
+    /// The number of workers which are assigned each key
+    assigned: Vec<usize>,
+    /// The index at which to resume the round-robin search for a key
+    next: usize,
+}
+
+impl KeyPool {
+    pub fn new(key_names: Vec<String>) -> Self {
+        Self {
+            assigned: vec![0; key_names.len()],
+            key_names,
+            next: 0,
+        }
+    }
+
+    /// Returns the number of keys in the pool.
+    pub fn len(&self) -> usize {
+        self.key_names.len()
+    }
+
+    /// Returns whether the pool has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.key_names.is_empty()
+    }
+
+    /// Assigns a key to a new worker: the next unused key in round-robin order or,
+    /// once all keys are in use, the next of the least used keys.
+    pub fn acquire(&mut self) -> Option<String> {
+        let least_assigned = *self.assigned.iter().min()?;
+        let len = self.len();
+
+        let index = (0..len)
+            .map(|offset| (self.next + offset) % len)
+            .find(|&index| self.assigned[index] == least_assigned)?;
+
+        self.assigned[index] += 1;
+        self.next = (index + 1) % len;
+
+        Some(self.key_names[index].clone())
+    }
+
+    /// Releases the key of a worker which stopped.
+    pub fn release(&mut self, key_name: &str) {
+        if let Some(index) = self.key_names.iter().position(|k| k == key_name) {
+            self.assigned[index] = self.assigned[index].saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_env_log::test;
+
+    use super::KeyPool;
+
+    #[test]
+    fn round_robin_assignment() {
+        let mut pool = KeyPool::new(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+
+        let mut acquired = (0..4).map(|_| pool.acquire().unwrap()).collect::<Vec<_>>();
+        assert_eq!(acquired, vec!["a", "b", "c", "a"]);
+
+        // `b` is unused again, so it is assigned before the other, still used, keys
+        pool.release("b");
+        acquired = (0..3).map(|_| pool.acquire().unwrap()).collect();
+        assert_eq!(acquired, vec!["b", "c", "b"]);
+
+        assert_eq!(KeyPool::new(vec![]).acquire(), None);
+    }
+}
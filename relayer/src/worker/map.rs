@@ -13,7 +13,7 @@ use crate::{
     telemetry::Telemetry,
 };
 
-use super::{Worker, WorkerHandle, WorkerId, WorkerMsg};
+use super::{KeyPool, Worker, WorkerHandle, WorkerId, WorkerMsg};
 
 /// Manage the lifecycle of [`Worker`]s associated with [`Object`]s.
 #[derive(Debug)]
@@ -22,6 +22,9 @@ pub struct WorkerMap {
     latest_worker_id: WorkerId,
     msg_tx: Sender<WorkerMsg>,
     telemetry: Telemetry,
+    key_pools: HashMap<ChainId, KeyPool>,
+    assigned_keys: HashMap<Object, Vec<(ChainId, String)>>,
+    dry_run: bool,
 }
 
 impl WorkerMap {
@@ -33,9 +36,22 @@ impl WorkerMap {
             latest_worker_id: WorkerId::new(0),
             msg_tx,
             telemetry,
+            key_pools: HashMap::new(),
+            assigned_keys: HashMap::new(),
+            dry_run: false,
         }
     }
 
+    /// Set whether the spawned workers only simulate their transactions. In a dry run, only
+    /// [`Object::Packet`] workers are spawned, as the other workers need to submit transactions.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
     /// Returns `true` if there is a spawned [`Worker`] associated with the given [`Object`].
     pub fn contains(&self, object: &Object) -> bool {
         self.workers.contains_key(object)
@@ -47,6 +63,7 @@ impl WorkerMap {
         match self.workers.remove(&object) {
             Some(handle) if handle.id() == id => {
                 telemetry!(self.telemetry.worker(metric_type(&object), -1));
+                self.release_keys(&object);
 
                 let id = handle.id();
 
@@ -131,6 +148,10 @@ impl WorkerMap {
         object: &Object,
         config: &Config,
     ) -> bool {
+        if self.dry_run && !matches!(object, Object::Packet(_)) {
+            return false;
+        }
+
         if !self.workers.contains_key(object) {
             let worker = self.spawn_worker(src, dst, object, config);
             self.workers.entry(object.clone()).or_insert(worker);
@@ -150,6 +171,22 @@ impl WorkerMap {
     ) -> WorkerHandle {
         telemetry!(self.telemetry.worker(metric_type(object), 1));
 
+        // Packet workers submit transactions concurrently, each from its own account if possible
+        let (src, dst) = if let Object::Packet(_) = object {
+            (
+                self.assign_key(src, object, config),
+                self.assign_key(dst, object, config),
+            )
+        } else {
+            (src, dst)
+        };
+
+        // The transactions of the worker to each chain relay from the other chain
+        let (src, dst) = (
+            src.with_counterparty(dst.id()),
+            dst.with_counterparty(src.id()),
+        );
+
         Worker::spawn(
             ChainHandlePair { a: src, b: dst },
             self.next_worker_id(),
@@ -157,9 +194,59 @@ impl WorkerMap {
             self.msg_tx.clone(),
             self.telemetry.clone(),
             config,
+            self.dry_run,
         )
     }
 
+    /// Assigns one of the signing keys of the chain of `handle`, picked round-robin, to the
+    /// worker for `object`, if more than one key is configured for that chain.
+    fn assign_key(
+        &mut self,
+        handle: Box<dyn ChainHandle>,
+        object: &Object,
+        config: &Config,
+    ) -> Box<dyn ChainHandle> {
+        let chain_id = handle.id();
+
+        let pool = self.key_pools.entry(chain_id.clone()).or_insert_with(|| {
+            KeyPool::new(
+                config
+                    .find_chain(&chain_id)
+                    .map_or_else(Vec::new, |chain_config| chain_config.key_names()),
+            )
+        });
+
+        if pool.len() < 2 {
+            return handle;
+        }
+
+        match pool.acquire() {
+            Some(key_name) => {
+                debug!(
+                    worker.object = %object.short_name(), chain.id = %chain_id,
+                    "assigning key '{}' to worker", key_name
+                );
+
+                self.assigned_keys
+                    .entry(object.clone())
+                    .or_default()
+                    .push((chain_id, key_name.clone()));
+
+                handle.with_key(key_name)
+            }
+            None => handle,
+        }
+    }
+
+    /// Releases the signing keys assigned to the worker for `object`.
+    fn release_keys(&mut self, object: &Object) {
+        for (chain_id, key_name) in self.assigned_keys.remove(object).unwrap_or_default() {
+            if let Some(pool) = self.key_pools.get_mut(&chain_id) {
+                pool.release(&key_name);
+            }
+        }
+    }
+
     fn next_worker_id(&mut self) -> WorkerId {
         let id = self.latest_worker_id.next();
         self.latest_worker_id = id;
@@ -180,6 +267,7 @@ impl WorkerMap {
     pub fn shutdown_worker(&mut self, object: &Object) {
         if let Some(handle) = self.workers.remove(object) {
             telemetry!(self.telemetry.worker(metric_type(object), -1));
+            self.release_keys(object);
 
             match handle.shutdown() {
                 Ok(()) => {
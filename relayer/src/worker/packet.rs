@@ -6,7 +6,7 @@ use tracing::{error, info, warn};
 
 use crate::{
     chain::handle::ChainHandlePair,
-    link::{Link, LinkParameters, RelaySummary},
+    link::{Link, LinkParameters, RelayJournal, RelaySummary},
     object::Packet,
     telemetry,
     telemetry::Telemetry,
@@ -28,6 +28,7 @@ pub struct PacketWorker {
     cmd_rx: Receiver<WorkerCmd>,
     telemetry: Telemetry,
     clear_packets_interval: u64,
+    dry_run: bool,
 }
 
 impl PacketWorker {
@@ -37,6 +38,7 @@ impl PacketWorker {
         cmd_rx: Receiver<WorkerCmd>,
         telemetry: Telemetry,
         clear_packets_interval: u64,
+        dry_run: bool,
     ) -> Self {
         Self {
             path,
@@ -44,6 +46,7 @@ impl PacketWorker {
             cmd_rx,
             telemetry,
             clear_packets_interval,
+            dry_run,
         }
     }
 
@@ -64,6 +67,25 @@ impl PacketWorker {
             return Ok(());
         }
 
+        link.a_to_b.set_dry_run(self.dry_run);
+
+        // Resume the operational data scheduled before a restart, if any. A dry run neither
+        // resumes nor records the data, which it does not relay.
+        if !self.dry_run {
+            let journal = RelayJournal::open_default(
+                &self.path.src_chain_id,
+                &self.path.src_port_id,
+                &self.path.src_channel_id,
+            );
+            match journal {
+                Ok(journal) => link.a_to_b.restore_from_journal(journal)?,
+                Err(e) => warn!(
+                    path = %self.path.short_name(),
+                    "relaying without a journal, which could not be opened: {}", e
+                ),
+            }
+        }
+
         loop {
             const BACKOFF: Duration = Duration::from_millis(200);
 
@@ -80,7 +102,10 @@ impl PacketWorker {
 
             match result {
                 Ok(Step::Success(_summary)) => {
-                    telemetry!(self.packet_metrics(&_summary));
+                    // Nothing is relayed in a dry run
+                    if !self.dry_run {
+                        telemetry!(self.packet_metrics(&_summary));
+                    }
                 }
 
                 Ok(Step::Shutdown) => {
@@ -170,6 +195,7 @@ impl PacketWorker {
         self.receive_packet_metrics(summary);
         self.acknowledgment_metrics(summary);
         self.timeout_metrics(summary);
+        self.batch_metrics(summary);
     }
 
     #[cfg(feature = "telemetry")]
@@ -224,4 +250,34 @@ impl PacketWorker {
             count as u64,
         )
     }
+
+    #[cfg(feature = "telemetry")]
+    fn batch_metrics(&self, summary: &RelaySummary) {
+        for batch in summary.batches.iter() {
+            let chain = &batch.target_chain;
+
+            self.telemetry.tx_gas_used(chain, batch.gas_used());
+
+            if !batch.failed_msgs.is_empty() {
+                self.telemetry.relay_failed_msgs(
+                    chain,
+                    &self.path.src_channel_id,
+                    &self.path.src_port_id,
+                    batch.failed_msgs.len() as u64,
+                );
+            }
+
+            let timings = &batch.timings;
+            for (step, time) in [
+                ("query_proofs", timings.query_proofs),
+                ("broadcast", timings.broadcast),
+                ("confirm", timings.confirm),
+            ]
+            .iter()
+            {
+                self.telemetry
+                    .relay_batch_time(chain, step, time.as_millis() as u64);
+            }
+        }
+    }
 }
@@ -8,10 +8,20 @@ use std::{
     thread::JoinHandle,
 };
 
+use once_cell::sync::Lazy;
+
 pub use crate::state::TelemetryState;
 
+static GLOBAL_STATE: Lazy<Arc<TelemetryState>> = Lazy::new(|| Arc::new(TelemetryState::default()));
+
+/// The telemetry state shared by the whole process, to which the chain runtimes,
+/// which are not given a telemetry handle, also report their metrics.
+pub fn global() -> &'static Arc<TelemetryState> {
+    &GLOBAL_STATE
+}
+
 pub fn new_state() -> Arc<TelemetryState> {
-    Arc::new(TelemetryState::default())
+    global().clone()
 }
 
 pub fn spawn<A>(
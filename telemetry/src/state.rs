@@ -2,7 +2,7 @@ use std::fmt;
 
 use opentelemetry::{
     global,
-    metrics::{Counter, UpDownCounter},
+    metrics::{Counter, UpDownCounter, ValueRecorder},
     KeyValue,
 };
 use opentelemetry_prometheus::PrometheusExporter;
@@ -50,6 +50,21 @@ pub struct TelemetryState {
 
     /// Number of timeout packets relayed, per channel
     timeout_packets: Counter<u64>,
+
+    /// The active endpoint of each kind per chain, which counts one
+    active_endpoints: UpDownCounter<i64>,
+
+    /// Number of failovers from an endpoint to another, per chain and kind of endpoint
+    endpoint_failovers: Counter<u64>,
+
+    /// Gas used by the relayer's transactions, per chain
+    tx_gas_used: Counter<u64>,
+
+    /// Number of messages which failed to be relayed, per chain and channel
+    relay_failed_msgs: Counter<u64>,
+
+    /// Time spent relaying a batch of messages, per chain and step
+    relay_batch_time_ms: ValueRecorder<u64>,
 }
 
 impl TelemetryState {
@@ -132,6 +147,62 @@ impl TelemetryState {
 
         self.timeout_packets.add(count, labels);
     }
+
+    /// Update the active endpoint of the given kind of a chain
+    pub fn active_endpoint(&self, chain: &ChainId, kind: &str, address: &str, count: i64) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("kind", kind.to_string()),
+            KeyValue::new("address", address.to_string()),
+        ];
+
+        self.active_endpoints.add(count, labels);
+    }
+
+    /// Number of failovers from an endpoint to another, per chain and kind of endpoint
+    pub fn endpoint_failover(&self, chain: &ChainId, kind: &str, count: u64) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("kind", kind.to_string()),
+        ];
+
+        self.endpoint_failovers.add(count, labels);
+    }
+
+    /// Gas used by the relayer's transactions, per chain
+    pub fn tx_gas_used(&self, chain: &ChainId, gas: u64) {
+        let labels = &[KeyValue::new("chain", chain.to_string())];
+
+        self.tx_gas_used.add(gas, labels);
+    }
+
+    /// Number of messages to `chain` which failed to be relayed, per channel
+    pub fn relay_failed_msgs(
+        &self,
+        chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.relay_failed_msgs.add(count, labels);
+    }
+
+    /// Record the time spent on a step of the relaying of a batch of messages to `chain`,
+    /// e.g., `query_proofs`, `broadcast` or `confirm`
+    pub fn relay_batch_time(&self, chain: &ChainId, step: &str, millis: u64) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("step", step.to_string()),
+        ];
+
+        self.relay_batch_time_ms.record(millis, labels);
+    }
 }
 
 impl Default for TelemetryState {
@@ -171,6 +242,31 @@ impl Default for TelemetryState {
                 .u64_counter("ibc_timeout_packets")
                 .with_description("Number of timeout packets relayed per channel")
                 .init(),
+
+            active_endpoints: meter
+                .i64_up_down_counter("active_endpoints")
+                .with_description("The active RPC, gRPC and WebSocket endpoints per chain")
+                .init(),
+
+            endpoint_failovers: meter
+                .u64_counter("endpoint_failovers")
+                .with_description("Number of failovers from an endpoint to another per chain")
+                .init(),
+
+            tx_gas_used: meter
+                .u64_counter("tx_gas_used")
+                .with_description("Gas used by the relayer's transactions per chain")
+                .init(),
+
+            relay_failed_msgs: meter
+                .u64_counter("relay_failed_msgs")
+                .with_description("Number of messages which failed to be relayed per chain")
+                .init(),
+
+            relay_batch_time_ms: meter
+                .u64_value_recorder("relay_batch_time_ms")
+                .with_description("Time spent querying proofs, broadcasting and confirming batches")
+                .init(),
         }
     }
 }